@@ -1,5 +1,12 @@
-use crate::commands::{IdeaCommands, ProjectCommands, TaskCommands, VersionArgs};
-use clap::{Args, Parser};
+use crate::commands::{
+    BackupCommands, CaptureArgs, CompactArgs, ConfigCommands, DoctorArgs, ExportCommands,
+    GitCommands, HistoryArgs, IdeaCommands, ImportArgs, InboxCommands, MigrateArgs, PathArgs,
+    PickArgs, ProjectCommands, RecentArgs, ReviewArgs, SyncArgs, TaskCommands, TemplateCommands,
+    TrashCommands, UndoArgs, VaultCommands, VersionArgs,
+};
+use crate::models::config::OutputFormat;
+use clap::{ArgAction, ArgMatches, Args, Parser};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "ideavault")]
@@ -10,8 +17,69 @@ pub struct Cli {
     #[arg(long, hide = true)]
     pub markdown_help: bool,
 
+    /// Use this vault instead of the current one
+    #[arg(long, global = true, value_name = "NAME")]
+    pub vault: Option<String>,
+
+    /// Keep data in this directory instead of a vault
+    #[arg(long, global = true, value_name = "PATH", env = "IDEAVAULT_DATA_DIR")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Print results as table, json, or yaml (defaults to the default_format setting).
+    /// Goes before the command, since `export --format` picks the export's file format
+    #[arg(long, value_name = "FORMAT")]
+    pub format: Option<OutputFormat>,
+
+    /// Print without colors (also set by the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Print plain ASCII labels instead of emoji
+    #[arg(long, global = true)]
+    pub no_emoji: bool,
+
+    /// Print only what scripts need, such as the ID of a new item or the IDs
+    /// a list finds, without emojis or messages
+    #[arg(short, long, global = true, visible_alias = "porcelain")]
+    pub quiet: bool,
+
+    /// Log which files are read and written and how long storage takes, to
+    /// standard error; repeat for more detail (RUST_LOG overrides)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
+}
+
+/// The subcommand path that was invoked, e.g. "task status".
+pub fn command_path(matches: &ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub_matches)) = current.subcommand() {
+        names.push(name);
+        current = sub_matches;
+    }
+    names.join(" ")
+}
+
+/// Whether the invoked command was given `--json`.
+pub fn wants_json(matches: &ArgMatches) -> bool {
+    leaf_flag(matches, "json")
+}
+
+/// Whether the invoked list command was given `--ids`.
+pub fn wants_ids(matches: &ArgMatches) -> bool {
+    leaf_flag(matches, "ids")
+}
+
+/// Whether the innermost subcommand has the flag `id` and it was given.
+fn leaf_flag(matches: &ArgMatches, id: &str) -> bool {
+    let mut current = matches;
+    while let Some((_, sub_matches)) = current.subcommand() {
+        current = sub_matches;
+    }
+    matches!(current.try_get_one::<bool>(id), Ok(Some(true)))
 }
 
 #[derive(Parser)]
@@ -22,8 +90,50 @@ pub enum Commands {
     Project(ProjectCommands),
     /// Manage tasks
     Task(TaskCommands),
+    /// Save projects as templates for starting new ones
+    Template(TemplateCommands),
+    /// Quickly jot something down in the inbox to sort out later
+    Capture(CaptureArgs),
+    /// List and process captured items
+    Inbox(InboxCommands),
     /// Search across ideas, projects, and tags
     Search(SearchArgs),
+    /// Choose an idea, project, or task from a list filtered as you type, and print its ID
+    Pick(PickArgs),
+    /// List the ideas, projects, and tasks created or changed lately, newest first
+    Recent(RecentArgs),
+    /// Go through stale ideas, overdue and blocked tasks, and projects with no next action
+    Review(ReviewArgs),
+    /// Create, list, restore, and prune backups
+    Backup(BackupCommands),
+    /// Export ideas, projects, and tasks to JSON, CSV, YAML, or Markdown
+    Export(ExportCommands),
+    /// Import ideas, projects, or tasks from a JSON or CSV file
+    Import(ImportArgs),
+    /// Check the vault for broken links and corrupt data
+    Doctor(DoctorArgs),
+    /// Convert the vault to another on-disk layout
+    Migrate(MigrateArgs),
+    /// Fold the event log of an events-layout vault into a snapshot
+    Compact(CompactArgs),
+    /// List, restore, and empty deleted items
+    Trash(TrashCommands),
+    /// Show the recorded changes to an idea, project, task, or tag
+    History(HistoryArgs),
+    /// Revert the most recent change
+    Undo(UndoArgs),
+    /// Create, list, and switch between vaults
+    Vault(VaultCommands),
+    /// View and change settings
+    Config(ConfigCommands),
+    /// Record every change as a git commit in the data directory
+    Git(GitCommands),
+    /// Pull from and push to the vault's git remote
+    Sync(SyncArgs),
+    /// Print where the vault's data, the config file, and backups are
+    Path(PathArgs),
+    /// Open the vault's data directory in the file manager
+    OpenDataDir,
     /// Show version information
     Version(VersionArgs),
 }
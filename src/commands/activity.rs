@@ -0,0 +1,291 @@
+//! `project activity`: what happened to a project and its ideas and tasks,
+//! oldest first, read from the history log.
+
+use super::id::IdArg;
+use super::table::truncate;
+use crate::dates;
+use crate::output;
+use crate::storage::history::{Action, EntityKind, FieldChange, HistoryEntry};
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols::{self, Symbol};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Field order of events printed as JSON or YAML.
+const EVENT_FIELDS: &[&str] = &[
+    "timestamp",
+    "entity",
+    "entity_id",
+    "title",
+    "kind",
+    "detail",
+];
+
+/// Fields left out of "updated" events: timestamps and the status log that
+/// change along with other fields, and status and notes, which get events of
+/// their own.
+const QUIET_FIELDS: &[&str] = &[
+    "updated_at",
+    "status",
+    "status_history",
+    "completed_at",
+    "notes",
+];
+
+/// How much of a note an event shows.
+const NOTE_PREVIEW: usize = 60;
+
+#[derive(Args)]
+pub struct ActivityArgs {
+    /// The ID or title of the project
+    pub id: IdArg,
+
+    /// Only events within this span, such as 12h, 7d, or 2w
+    #[arg(long, value_name = "SPAN", value_parser = dates::parse_span)]
+    pub since: Option<Duration>,
+
+    /// Show at most this many events, the latest ones
+    #[arg(short = 'n', long, default_value_t = 30)]
+    pub limit: usize,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Something that happened to a project, or to one of its ideas or tasks.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityEvent {
+    pub timestamp: DateTime<Utc>,
+    pub entity: EntityKind,
+    pub entity_id: String,
+    pub title: String,
+    pub kind: ActivityKind,
+    /// The status change, the start of the note, or the fields updated
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityKind {
+    Created,
+    Status,
+    Note,
+    Updated,
+    Deleted,
+}
+
+pub fn show_activity(storage: &Storage, args: &ActivityArgs) -> Result<()> {
+    let id = args.id.project(storage)?;
+    let project = storage.get_project(id)?;
+    let mut events = project_activity(storage, id)?;
+    if let Some(span) = args.since {
+        let since = Utc::now() - span;
+        events.retain(|event| event.timestamp >= since);
+    }
+    let skipped = events.len().saturating_sub(args.limit);
+    events.drain(..skipped);
+    if !output::is_table() {
+        return output::print_list(&events, EVENT_FIELDS);
+    }
+    if events.is_empty() {
+        println!(
+            "{} No recorded activity for project {}",
+            symbols::RECENT,
+            project.title
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} Activity for project {} ({} event(s)):",
+        symbols::RECENT,
+        style::bold(&project.title),
+        events.len()
+    );
+    if skipped > 0 {
+        println!(
+            "   {}",
+            style::dim(format!("{} earlier event(s) not shown", skipped))
+        );
+    }
+    println!();
+    for event in &events {
+        println!(
+            "   {} {} {}",
+            marker(event.kind),
+            describe(event),
+            style::dim(dates::moment(event.timestamp, dates::DATE_TIME))
+        );
+    }
+    Ok(())
+}
+
+/// Everything the history log has on the project `id`, the ideas that are
+/// or were linked to it, and the tasks that are or were in it, oldest first. Updates that only
+/// touch `updated_at` are left out. Items are named by their current title,
+/// or the last one the history log has for those since deleted.
+pub fn project_activity(storage: &Storage, id: Uuid) -> Result<Vec<ActivityEvent>> {
+    let project = storage.get_project(id)?;
+    let tasks = storage.load_tasks().context("Failed to load tasks")?;
+    let history = storage.load_history().context("Failed to load history")?;
+
+    let project_id = Value::from(id.to_string());
+    let mut related: Vec<String> = std::iter::once(id)
+        .chain(project.idea_ids.iter().copied())
+        .chain(
+            tasks
+                .iter()
+                .filter(|task| task.project_id == Some(id))
+                .map(|task| task.id),
+        )
+        .map(|id| id.to_string())
+        .collect();
+    // Ideas unlinked from the project since
+    let project_entries = history
+        .iter()
+        .filter(|entry| entry.entity == EntityKind::Project && entry.entity_id == id.to_string());
+    for change in project_entries
+        .flat_map(|entry| &entry.changes)
+        .filter(|change| change.field == "idea_ids")
+    {
+        let linked = [&change.before, &change.after]
+            .into_iter()
+            .filter_map(Value::as_array)
+            .flatten()
+            .filter_map(Value::as_str);
+        for idea_id in linked {
+            if !related.iter().any(|related| related == idea_id) {
+                related.push(idea_id.to_string());
+            }
+        }
+    }
+    // Tasks deleted or moved out of the project since
+    for entry in &history {
+        let was_in_project = entry.entity == EntityKind::Task
+            && entry.changes.iter().any(|change| {
+                change.field == "project_id"
+                    && (change.before == project_id || change.after == project_id)
+            });
+        if was_in_project && !related.contains(&entry.entity_id) {
+            related.push(entry.entity_id.clone());
+        }
+    }
+
+    let ideas = storage.load_ideas().context("Failed to load ideas")?;
+    let mut titles = titles(&history);
+    let current = std::iter::once((project.id, &project.title))
+        .chain(ideas.iter().map(|idea| (idea.id, &idea.title)))
+        .chain(tasks.iter().map(|task| (task.id, &task.title)));
+    for (id, title) in current {
+        titles.insert(id.to_string(), title.clone());
+    }
+    let mut events = Vec::new();
+    for entry in history
+        .iter()
+        .filter(|entry| related.contains(&entry.entity_id))
+    {
+        let title = titles
+            .get(&entry.entity_id)
+            .cloned()
+            .unwrap_or_else(|| entry.entity_id.clone());
+        let event = |kind: ActivityKind, detail: Option<String>| ActivityEvent {
+            timestamp: entry.timestamp,
+            entity: entry.entity,
+            entity_id: entry.entity_id.clone(),
+            title: title.clone(),
+            kind,
+            detail,
+        };
+        match entry.action {
+            Action::Create => events.push(event(ActivityKind::Created, None)),
+            Action::Delete => events.push(event(ActivityKind::Deleted, None)),
+            Action::Update => {
+                if let Some(change) = changed(entry, "status") {
+                    let detail = format!("{} → {}", text(&change.before), text(&change.after));
+                    events.push(event(ActivityKind::Status, Some(detail)));
+                }
+                if let Some(note) = changed(entry, "notes").and_then(added_note) {
+                    events.push(event(ActivityKind::Note, Some(note)));
+                }
+                let fields: Vec<&str> = entry
+                    .changes
+                    .iter()
+                    .map(|change| change.field.as_str())
+                    .filter(|field| !QUIET_FIELDS.contains(field))
+                    .collect();
+                if !fields.is_empty() {
+                    events.push(event(ActivityKind::Updated, Some(fields.join(", "))));
+                }
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// The latest title the history log has for each entity.
+fn titles(history: &[HistoryEntry]) -> HashMap<String, String> {
+    let mut titles = HashMap::new();
+    for entry in history {
+        if let Some(change) = changed(entry, "title") {
+            let title = match (&change.after, &change.before) {
+                (Value::String(title), _) | (Value::Null, Value::String(title)) => title,
+                _ => continue,
+            };
+            titles.insert(entry.entity_id.clone(), title.clone());
+        }
+    }
+    titles
+}
+
+fn changed<'a>(entry: &'a HistoryEntry, field: &str) -> Option<&'a FieldChange> {
+    entry.changes.iter().find(|change| change.field == field)
+}
+
+/// The start of the note added in `change` to an idea's notes, if one was.
+fn added_note(change: &FieldChange) -> Option<String> {
+    let count = |value: &Value| value.as_array().map_or(0, Vec::len);
+    if count(&change.after) <= count(&change.before) {
+        return None;
+    }
+    let body = change.after.as_array()?.last()?.get("body")?.as_str()?;
+    Some(truncate(body, NOTE_PREVIEW))
+}
+
+/// A status or other plain value as text.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => "(none)".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn marker(kind: ActivityKind) -> Symbol {
+    match kind {
+        ActivityKind::Created => symbols::NEW,
+        ActivityKind::Status => symbols::STATUS,
+        ActivityKind::Note => symbols::NOTES,
+        ActivityKind::Updated => symbols::EDITED,
+        ActivityKind::Deleted => symbols::TRASH,
+    }
+}
+
+/// `event` as a line of the feed, such as "task 'Ship it': Todo → Done".
+fn describe(event: &ActivityEvent) -> String {
+    let subject = format!("{} '{}'", event.entity, event.title);
+    let detail = event.detail.as_deref().unwrap_or_default();
+    match event.kind {
+        ActivityKind::Created => format!("Created {}", subject),
+        ActivityKind::Deleted => format!("Deleted {}", subject),
+        ActivityKind::Status => format!("{}: {}", subject, detail),
+        ActivityKind::Note => format!("Note on {}: \"{}\"", subject, detail),
+        ActivityKind::Updated => format!("Updated {}: {}", subject, detail),
+    }
+}
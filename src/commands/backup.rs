@@ -0,0 +1,246 @@
+use crate::models::config::BackupConfig;
+use crate::output;
+use crate::say;
+use crate::storage::backup::{self, BackupInfo};
+use crate::storage::json::write_atomic;
+use crate::storage::Storage;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "backup")]
+#[command(about = "Manage vault backups")]
+pub struct BackupCommands {
+    #[command(subcommand)]
+    pub command: BackupSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum BackupSubcommand {
+    /// Take a snapshot of the vault now
+    Create,
+    /// List available snapshots
+    List,
+    /// Restore the data files from a snapshot
+    Restore(RestoreBackupArgs),
+    /// Delete old snapshots
+    Prune(PruneBackupArgs),
+}
+
+#[derive(Args)]
+pub struct RestoreBackupArgs {
+    /// Snapshot name (or a unique prefix), or "latest"
+    pub name: String,
+
+    /// Skip confirmation prompt
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct PruneBackupArgs {
+    /// Number of snapshots to keep (defaults to the configured max_backups)
+    #[arg(short, long)]
+    pub keep: Option<usize>,
+}
+
+impl BackupCommands {
+    pub fn execute(&self) -> Result<()> {
+        let storage = Storage::new().context("Failed to initialize storage")?;
+
+        match &self.command {
+            BackupSubcommand::Create => Self::create_backup(&storage).map(|_| ()),
+            BackupSubcommand::List => Self::list_backups(&storage),
+            BackupSubcommand::Restore(args) => Self::restore_backup(&storage, args),
+            BackupSubcommand::Prune(args) => Self::prune_backups(&storage, args),
+        }
+    }
+
+    pub fn create_backup(storage: &Storage) -> Result<BackupInfo> {
+        let info = storage.create_backup().context("Failed to create backup")?;
+
+        if output::is_quiet() {
+            println!("{}", info.name);
+            return Ok(info);
+        }
+        println!("{} Created backup {}", symbols::OK, info.name);
+        println!(
+            "   {} file(s), {}",
+            info.files.len(),
+            format_size(info.size_bytes)
+        );
+        Ok(info)
+    }
+
+    fn list_backups(storage: &Storage) -> Result<()> {
+        let backups = backup::list_backups(&storage.backup_dir())?;
+
+        if backups.is_empty() {
+            println!(
+                "{} No backups found in {:?}",
+                symbols::BACKUP,
+                storage.backup_dir()
+            );
+            return Ok(());
+        }
+
+        println!("{} Found {} backup(s):", symbols::BACKUP, backups.len());
+        println!();
+
+        for info in backups.iter().rev() {
+            println!("{}", info.name);
+            println!(
+                "   {} {}  {} {}  ({})",
+                symbols::DATE,
+                info.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                symbols::SIZE,
+                format_size(info.size_bytes),
+                describe_files(&info.files)
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn restore_backup(storage: &Storage, args: &RestoreBackupArgs) -> Result<()> {
+        let backups = backup::list_backups(&storage.backup_dir())?;
+        let chosen = find_backup(&backups, &args.name)?;
+
+        if !args.force {
+            say!(
+                "This will replace the vault's data files with backup {} ({}).",
+                chosen.name,
+                chosen.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+            print!("Are you sure you want to restore this backup? [y/N]: ");
+            io::stdout().flush().context("Failed to flush output")?;
+
+            let mut input = String::new();
+            io::stdin()
+                .read_line(&mut input)
+                .context("Failed to read input")?;
+
+            let response = input.trim().to_lowercase();
+            if !matches!(response.as_str(), "y" | "yes") {
+                say!("{} Restore cancelled", symbols::ERROR);
+                return Ok(());
+            }
+        }
+
+        // Keep the current state around in case the restore was a mistake
+        let safety = storage
+            .create_backup()
+            .context("Failed to back up current data before restoring")?;
+
+        // Data files the snapshot doesn't have (e.g. entities created since) go away
+        let restored: Vec<PathBuf> = chosen
+            .files
+            .iter()
+            .map(|file| storage.data_dir().join(file))
+            .collect();
+        for file in storage.data_files() {
+            if file.is_file() && !restored.contains(&file) {
+                fs::remove_file(&file).with_context(|| format!("Failed to remove {:?}", file))?;
+            }
+        }
+
+        for (file, target) in chosen.files.iter().zip(&restored) {
+            let content = fs::read(chosen.path.join(file))
+                .with_context(|| format!("Failed to read {} from backup", file))?;
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_atomic(target, &content)
+                .with_context(|| format!("Failed to restore {}", file))?;
+        }
+
+        say!("{} Restored backup {}", symbols::OK, chosen.name);
+        say!("   Previous data saved as {}", safety.name);
+        Ok(())
+    }
+
+    pub fn prune_backups(storage: &Storage, args: &PruneBackupArgs) -> Result<()> {
+        let keep = args
+            .keep
+            .unwrap_or_else(|| BackupConfig::default().max_backups);
+        let removed = backup::prune_backups(&storage.backup_dir(), keep)?;
+
+        if removed.is_empty() {
+            say!(
+                "{} Nothing to prune (keeping up to {})",
+                symbols::BACKUP,
+                keep
+            );
+            return Ok(());
+        }
+
+        let freed: u64 = removed.iter().map(|b| b.size_bytes).sum();
+        say!(
+            "{} Pruned {} backup(s), freed {}",
+            symbols::OK,
+            removed.len(),
+            format_size(freed)
+        );
+        for info in &removed {
+            say!("   - {}", info.name);
+        }
+        Ok(())
+    }
+}
+
+fn describe_files(files: &[String]) -> String {
+    if files.len() <= 5 {
+        files.join(", ")
+    } else {
+        format!("{} files", files.len())
+    }
+}
+
+fn find_backup<'a>(backups: &'a [BackupInfo], name: &str) -> Result<&'a BackupInfo> {
+    if name == "latest" {
+        return backups
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("No backups available"));
+    }
+
+    if let Some(exact) = backups.iter().find(|b| b.name == name) {
+        return Ok(exact);
+    }
+
+    let matches: Vec<&BackupInfo> = backups
+        .iter()
+        .filter(|b| b.name.starts_with(name))
+        .collect();
+    match matches.as_slice() {
+        [single] => Ok(single),
+        [] => Err(anyhow::anyhow!("Backup '{}' not found", name)),
+        _ => Err(anyhow::anyhow!(
+            "Backup name '{}' is ambiguous: {}",
+            name,
+            matches
+                .iter()
+                .map(|b| b.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
@@ -0,0 +1,231 @@
+//! `project board`: a project's tasks as a kanban board, one column per task
+//! status, with cards that can be moved between columns.
+
+use super::id::IdArg;
+use super::table::{short_id, truncate};
+use crate::models::task::{statuses, Task, TaskStatus};
+use crate::storage::Storage;
+use crate::style::{self, Color};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use dialoguer::{FuzzySelect, Select};
+use std::io::{self, IsTerminal};
+use unicode_width::UnicodeWidthStr;
+
+/// Space between columns.
+const GAP: &str = "  ";
+
+#[derive(Args)]
+pub struct BoardArgs {
+    /// The ID or title of the project
+    pub id: IdArg,
+
+    /// How wide each column is, in characters
+    #[arg(short, long, default_value_t = 24, value_parser = clap::value_parser!(u16).range(8..))]
+    pub width: u16,
+
+    /// Leave out columns with no cards
+    #[arg(long)]
+    pub hide_empty: bool,
+
+    /// Move cards between columns, picking a card and then where it goes
+    #[arg(short, long)]
+    pub interactive: bool,
+}
+
+pub fn show_board(storage: &Storage, args: &BoardArgs) -> Result<()> {
+    if args.interactive && (!io::stdin().is_terminal() || !io::stderr().is_terminal()) {
+        anyhow::bail!("Moving cards needs a terminal");
+    }
+    let id = args.id.project(storage)?;
+    let project = storage.get_project(id)?;
+    let width = usize::from(args.width);
+    let tasks_of = || -> Result<Vec<Task>> {
+        Ok(storage
+            .load_tasks()
+            .context("Failed to load tasks")?
+            .into_iter()
+            .filter(|task| task.project_id == Some(id))
+            .collect())
+    };
+
+    println!(
+        "{} {}",
+        symbols::project_status(&project.status),
+        style::bold(&project.title)
+    );
+    println!();
+    let mut tasks = tasks_of()?;
+    print!("{}", render_board(&tasks, width, args.hide_empty));
+    if !args.interactive {
+        return Ok(());
+    }
+
+    // The plain theme draws with ASCII and no colors
+    let colorful = ColorfulTheme::default();
+    let theme: &dyn Theme = if style::enabled() && !symbols::is_plain() {
+        &colorful
+    } else {
+        &SimpleTheme
+    };
+    let columns: Vec<TaskStatus> = board_columns(&tasks);
+    loop {
+        if tasks.is_empty() {
+            println!("{} No cards to move", symbols::LIST);
+            return Ok(());
+        }
+        let cards = sorted(&tasks);
+        let labels: Vec<String> = cards
+            .iter()
+            .map(|task| {
+                format!(
+                    "{}  ({})  [{}]",
+                    task.title,
+                    task.status,
+                    short_id(&task.id)
+                )
+            })
+            .collect();
+        println!();
+        let Some(card) = FuzzySelect::with_theme(theme)
+            .with_prompt("Move which card? (type to filter, Esc to finish)")
+            .items(&labels)
+            .max_length(15)
+            .interact_opt()
+            .context("Failed to read the choice")?
+        else {
+            return Ok(());
+        };
+        let task = cards[card];
+        let names: Vec<String> = columns.iter().map(ToString::to_string).collect();
+        let current = columns
+            .iter()
+            .position(|status| *status == task.status)
+            .unwrap_or(0);
+        let Some(column) = Select::with_theme(theme)
+            .with_prompt(format!("Move '{}' to", task.title))
+            .items(&names)
+            .default(current)
+            .interact_opt()
+            .context("Failed to read the choice")?
+        else {
+            continue;
+        };
+
+        let status = columns[column].clone();
+        if status != task.status {
+            storage.update_task(task.id, |task| task.set_status(status))?;
+        }
+        tasks = tasks_of()?;
+        println!();
+        print!("{}", render_board(&tasks, width, args.hide_empty));
+    }
+}
+
+/// `tasks` laid out as a board: a column `width` characters wide for each
+/// configured task status, then any other status a task has, with the most
+/// urgent cards at the top. Each card is the task's title, cut to fit, and a
+/// line with its short ID and priority.
+pub fn render_board(tasks: &[Task], width: usize, hide_empty: bool) -> String {
+    let mut columns: Vec<(TaskStatus, Vec<&Task>)> = board_columns(tasks)
+        .into_iter()
+        .map(|status| {
+            let cards = sorted(tasks)
+                .into_iter()
+                .filter(|task| task.status == status)
+                .collect();
+            (status, cards)
+        })
+        .collect();
+    if hide_empty {
+        columns.retain(|(_, cards)| !cards.is_empty());
+    }
+    if columns.is_empty() {
+        return format!("{} No tasks in this project\n", symbols::LIST);
+    }
+
+    let rule = if symbols::is_plain() { "-" } else { "─" };
+    let mut rows: Vec<Vec<(String, Option<Color>)>> = vec![
+        columns
+            .iter()
+            .map(|(status, cards)| {
+                let heading = format!("{} ({})", status, cards.len());
+                (
+                    truncate(&heading, width),
+                    Some(style::task_status_color(status)),
+                )
+            })
+            .collect(),
+        columns.iter().map(|_| (rule.repeat(width), None)).collect(),
+    ];
+    let depth = columns
+        .iter()
+        .map(|(_, cards)| cards.len())
+        .max()
+        .unwrap_or(0);
+    for index in 0..depth {
+        let card = |line: usize| {
+            columns
+                .iter()
+                .map(|(_, cards)| match cards.get(index) {
+                    Some(task) if line == 0 => (truncate(&task.title, width), None),
+                    Some(task) => (
+                        truncate(
+                            &format!("{} · {}", short_id(&task.id), task.priority),
+                            width,
+                        ),
+                        Some(Color::Gray),
+                    ),
+                    None => (String::new(), None),
+                })
+                .collect()
+        };
+        rows.push(card(0));
+        rows.push(card(1));
+    }
+
+    let mut out = String::new();
+    for row in rows {
+        let mut line = String::new();
+        for (index, (text, color)) in row.iter().enumerate() {
+            if index > 0 {
+                line.push_str(GAP);
+            }
+            match color {
+                Some(color) => line.push_str(&style::paint(text, *color)),
+                None => line.push_str(text),
+            }
+            line.push_str(&" ".repeat(width.saturating_sub(text.width())));
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// The configured task statuses, then any others `tasks` have.
+fn board_columns(tasks: &[Task]) -> Vec<TaskStatus> {
+    let mut columns: Vec<TaskStatus> = statuses()
+        .iter()
+        .map(|status| TaskStatus::named(&status.name))
+        .collect();
+    for task in tasks {
+        if !columns.contains(&task.status) {
+            columns.push(task.status.clone());
+        }
+    }
+    columns
+}
+
+/// `tasks`, most urgent first, then oldest first.
+fn sorted(tasks: &[Task]) -> Vec<&Task> {
+    let mut sorted: Vec<&Task> = tasks.iter().collect();
+    sorted.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    });
+    sorted
+}
@@ -0,0 +1,207 @@
+//! `edit-many`: the same change made to every idea or task a filter matches,
+//! saved as one operation so a single `undo` reverts it.
+
+use super::history::print_changes;
+use super::sort::Sortable;
+use crate::models::Identifiable;
+use crate::output;
+use crate::say;
+use crate::storage::history::{self, EntityKind, HistoryEntry};
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols;
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// The changes `edit-many` makes, shared by ideas and tasks.
+#[derive(Args, Clone, Debug, Default)]
+pub struct BulkArgs {
+    /// Set a field on every match, e.g. priority=high (repeatable)
+    #[arg(long = "set", value_name = "FIELD=VALUE")]
+    pub set: Vec<Assignment>,
+
+    /// Add a tag to every match (repeatable)
+    #[arg(long = "add-tag", value_name = "TAG")]
+    pub add_tags: Vec<String>,
+
+    /// Remove a tag from every match (repeatable)
+    #[arg(long = "remove-tag", value_name = "TAG")]
+    pub remove_tags: Vec<String>,
+
+    /// Change every item, when no filter is given
+    #[arg(long)]
+    pub all: bool,
+
+    /// Show what would change without saving anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// A `--set` flag: a field and the value to give it, as typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    pub field: String,
+    pub value: String,
+}
+
+/// Items `edit-many` can change.
+pub trait BulkEditable: Sortable + Serialize + Clone + Identifiable {
+    /// What this kind of item is in the history.
+    const ENTITY: EntityKind;
+    /// The fields `--set` accepts.
+    const FIELDS: &'static [&'static str];
+
+    /// A `--set` value, checked and resolved once for all matches.
+    type Change;
+
+    /// Check `assignment`, whose field is one of [`Self::FIELDS`].
+    fn parse_change(storage: &Storage, assignment: &Assignment) -> Result<Self::Change>;
+
+    fn apply(&mut self, change: &Self::Change);
+
+    fn tags_mut(&mut self) -> &mut Vec<String>;
+}
+
+/// What an `edit-many` did, or would do with `--dry-run`.
+#[derive(Debug, Default)]
+pub struct BulkEdit {
+    /// How many items the filter matched
+    pub matched: usize,
+    /// What changed in each item that changed
+    pub changes: Vec<HistoryEntry>,
+}
+
+impl BulkArgs {
+    /// Fail unless there is something to change, or if the filter is empty
+    /// without `--all`.
+    pub fn check(&self, filtered: bool) -> Result<()> {
+        if self.set.is_empty() && self.add_tags.is_empty() && self.remove_tags.is_empty() {
+            anyhow::bail!("Nothing to change. Use --set, --add-tag, or --remove-tag");
+        }
+        if !filtered && !self.all {
+            anyhow::bail!("No filter given. Add a filter, or --all to change every item");
+        }
+        Ok(())
+    }
+}
+
+/// Make the changes in `args` to the `items` that `matches` accepts. Changed
+/// items are touched; nothing is saved.
+pub fn apply<T: BulkEditable>(
+    storage: &Storage,
+    items: &mut [T],
+    matches: impl Fn(&T) -> bool,
+    args: &BulkArgs,
+) -> Result<BulkEdit> {
+    let mut changes = Vec::new();
+    for assignment in &args.set {
+        if !T::FIELDS.contains(&assignment.field.as_str()) {
+            anyhow::bail!(
+                "Can't set '{}' on {}s. Set one of: {}",
+                assignment.field,
+                T::KIND,
+                T::FIELDS.join(", ")
+            );
+        }
+        let change = T::parse_change(storage, assignment)
+            .map_err(|err| anyhow::anyhow!("{}: {}", assignment.field, err))?;
+        changes.push(change);
+    }
+
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    for item in items.iter_mut().filter(|item| matches(item)) {
+        let original = item.clone();
+        for change in &changes {
+            item.apply(change);
+        }
+        let tags = item.tags_mut();
+        for tag in &args.add_tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.retain(|tag| !args.remove_tags.contains(tag));
+
+        if serde_json::to_value(&*item)? != serde_json::to_value(&original)? {
+            item.touch();
+        }
+        before.push(original);
+        after.push(item.clone());
+    }
+
+    let changes = history::diff(
+        T::ENTITY,
+        &before,
+        &after,
+        |item| item.id().to_string(),
+        Uuid::nil(),
+    )?;
+    Ok(BulkEdit {
+        matched: before.len(),
+        changes,
+    })
+}
+
+/// Print what `edit` changed in `items`, or would change with `dry_run`.
+pub fn report<T: BulkEditable>(items: &[T], edit: &BulkEdit, dry_run: bool) {
+    if output::is_quiet() && !dry_run {
+        output::print_ids(
+            edit.changes
+                .iter()
+                .filter_map(|entry| entry.entity_id.parse().ok()),
+        );
+        return;
+    }
+
+    let (icon, verb) = if dry_run {
+        (symbols::PREVIEW, "Would change")
+    } else {
+        (symbols::OK, "Changed")
+    };
+    println!(
+        "{} {} {} of {} matching {}(s)",
+        icon,
+        verb,
+        edit.changes.len(),
+        edit.matched,
+        T::KIND
+    );
+    for entry in &edit.changes {
+        let title = items
+            .iter()
+            .find(|item| item.id().to_string() == entry.entity_id)
+            .map_or("", |item| item.title());
+        println!();
+        println!("   {} [{}]", title, style::dim(&entry.entity_id));
+        print_changes(&entry.changes, "      ");
+    }
+    if dry_run {
+        println!();
+        println!("Nothing was saved. Run it again without --dry-run to make these changes.");
+    } else if !edit.changes.is_empty() {
+        say!();
+        say!("   Revert it with: ideavault undo");
+    }
+}
+
+impl FromStr for Assignment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (field, value) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected FIELD=VALUE, e.g. priority=high"))?;
+        let field = field.trim().to_lowercase();
+        if field.is_empty() {
+            anyhow::bail!("Expected FIELD=VALUE, e.g. priority=high");
+        }
+        Ok(Assignment {
+            field,
+            value: value.trim().to_string(),
+        })
+    }
+}
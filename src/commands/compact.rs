@@ -0,0 +1,35 @@
+use crate::say;
+use crate::storage::{Layout, Storage};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+
+#[derive(Args)]
+pub struct CompactArgs {}
+
+pub fn execute(_args: CompactArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    compact(&storage)
+}
+
+/// Fold the vault's event log into a fresh snapshot.
+pub fn compact(storage: &Storage) -> Result<()> {
+    let Some(events) = storage.compact().context("Failed to compact event log")? else {
+        anyhow::bail!(
+            "Only vaults using the {} layout keep an event log. Run `ideavault migrate {}` to switch.",
+            Layout::Events,
+            Layout::Events
+        );
+    };
+
+    if events == 0 {
+        say!("ℹ️  Event log is already compacted");
+    } else {
+        say!(
+            "{} Compacted {} event(s) into the snapshot",
+            symbols::OK,
+            events
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use crate::editor;
+use crate::models::config::Config;
+use crate::say;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use serde_json::Value;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+#[derive(Parser)]
+#[command(name = "config")]
+#[command(about = "View and change settings")]
+pub struct ConfigCommands {
+    #[command(subcommand)]
+    pub command: ConfigSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigSubcommand {
+    /// Walk through the main settings and write the config file
+    Init,
+    /// Show one setting
+    Get(GetConfigArgs),
+    /// Change one setting
+    Set(SetConfigArgs),
+    /// Show every setting
+    List,
+    /// Edit the config file in your editor; it is checked before being saved
+    Edit,
+}
+
+#[derive(Args)]
+pub struct GetConfigArgs {
+    /// Setting to show, e.g. data_dir or backup.enabled
+    pub key: String,
+}
+
+#[derive(Args)]
+pub struct SetConfigArgs {
+    /// Setting to change, e.g. default_format or backup.max_backups
+    pub key: String,
+
+    /// New value; use null to clear an optional setting
+    pub value: String,
+}
+
+impl ConfigCommands {
+    pub fn execute(&self) -> Result<()> {
+        let path = Config::path()?;
+
+        match &self.command {
+            ConfigSubcommand::Get(args) => Self::get_setting(&path, args).map(|_| ()),
+            ConfigSubcommand::Set(args) => Self::set_setting(&path, args).map(|_| ()),
+            ConfigSubcommand::List => Self::list_settings(&path),
+            ConfigSubcommand::Edit => Self::edit_config(&path),
+            ConfigSubcommand::Init => Self::init_config(&path, &mut io::stdin().lock()).map(|_| ()),
+        }
+    }
+
+    pub fn get_setting(path: &Path, args: &GetConfigArgs) -> Result<Value> {
+        let config = Config::load_from(path).context("Failed to load config")?;
+        let value = config.get(&args.key)?;
+        println!("{}", display(&value));
+        Ok(value)
+    }
+
+    pub fn set_setting(path: &Path, args: &SetConfigArgs) -> Result<Config> {
+        let mut config = Config::load_from(path).context("Failed to load config")?;
+        let old = config.get(&args.key)?;
+        config.set(&args.key, &args.value)?;
+        let new = config.get(&args.key)?;
+
+        if old == new {
+            say!("ℹ️  {} is already {}", args.key, display(&new));
+        } else {
+            config.save_to(path).context("Failed to save config")?;
+            say!(
+                "{} Set {} to {} (was {})",
+                symbols::OK,
+                args.key,
+                display(&new),
+                display(&old)
+            );
+        }
+        Ok(config)
+    }
+
+    fn list_settings(path: &Path) -> Result<()> {
+        let config = Config::load_from(path).context("Failed to load config")?;
+        println!("{} Settings in {}:", symbols::LIST, path.display());
+        println!();
+        for (key, value) in config.entries()? {
+            println!("   {} = {}", key, display(&value));
+        }
+
+        let overrides = config.env_overrides(|name| std::env::var(name).ok())?;
+        if !overrides.is_empty() {
+            println!();
+            println!("ℹ️  Overridden by the environment for this shell:");
+            for (name, key) in overrides {
+                let value = std::env::var(&name).unwrap_or_default();
+                println!("   {} = {} (from {})", key, value, name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Ask for the data directory, editor, colors, and backup settings, one
+    /// question per line of `input`, offering the current values as defaults.
+    pub fn init_config(path: &Path, input: &mut impl BufRead) -> Result<Config> {
+        let mut config = Config::load_from(path).context("Failed to load config")?;
+        println!(
+            "{} Setting up ideavault. Press Enter to keep the value in brackets.",
+            symbols::NEW
+        );
+        println!();
+
+        let data_dir = ask_text(
+            input,
+            "Data directory",
+            &config.data_dir.display().to_string(),
+        )?;
+        config.data_dir = data_dir.into();
+
+        let editor = config
+            .default_editor
+            .clone()
+            .unwrap_or_else(|| editor::editor_command(None));
+        let editor = ask_text(input, "Editor (e.g. vim, nano, \"code --wait\")", &editor)?;
+        editor::split_command(&editor)?;
+        config.default_editor = Some(editor);
+
+        config.use_colors = ask_yes_no(input, "Use colors", config.use_colors)?;
+        config.backup.enabled = ask_yes_no(input, "Take automatic backups", config.backup.enabled)?;
+        if config.backup.enabled {
+            config.backup.interval_hours =
+                ask_number(input, "Hours between backups", config.backup.interval_hours)?;
+            config.backup.max_backups =
+                ask_number(input, "Backups to keep", config.backup.max_backups)?;
+        }
+
+        config.save_to(path).context("Failed to save config")?;
+        println!();
+        println!("{} Saved settings to {}", symbols::OK, path.display());
+        println!("   Change them later with: ideavault config set <key> <value>");
+        Ok(config)
+    }
+
+    /// Open the config file in an editor. The edit is only saved once it
+    /// parses; otherwise it can be edited again or thrown away.
+    fn edit_config(path: &Path) -> Result<()> {
+        // Make sure there is a file to edit
+        Config::load_from(path).context("Failed to load config")?;
+        let original = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+
+        let temp_file = path.with_file_name("config.edit.json");
+        fs::write(&temp_file, &original).context("Failed to create temp file")?;
+
+        let result = loop {
+            if let Err(err) = editor::open(&temp_file) {
+                break Err(err);
+            }
+
+            let edited = fs::read_to_string(&temp_file).context("Failed to read edited config");
+            let edited = match edited {
+                Ok(edited) => edited,
+                Err(err) => break Err(err),
+            };
+            if edited == original {
+                println!("{} No changes made", symbols::LIST);
+                break Ok(());
+            }
+            match serde_json::from_str::<Config>(&edited) {
+                Ok(config) => {
+                    break config
+                        .save_to(path)
+                        .map(|_| println!("{} Saved {}", symbols::OK, path.display()))
+                }
+                Err(err) => {
+                    println!("{} The config is not valid: {}", symbols::ERROR, err);
+                    let again = if io::stdin().is_terminal() {
+                        editor::edit_again()
+                    } else {
+                        Ok(false)
+                    };
+                    match again {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            println!("{} Changes discarded", symbols::ERROR);
+                            break Ok(());
+                        }
+                        Err(err) => break Err(err),
+                    }
+                }
+            }
+        };
+
+        fs::remove_file(&temp_file).ok();
+        result
+    }
+}
+
+/// Ask `question`, showing `hint` in brackets. An empty answer is `None`.
+fn ask(input: &mut impl BufRead, question: &str, hint: &str) -> Result<Option<String>> {
+    print!("{} [{}]: ", question, hint);
+    io::stdout().flush().context("Failed to flush output")?;
+
+    let mut answer = String::new();
+    if input
+        .read_line(&mut answer)
+        .context("Failed to read input")?
+        == 0
+    {
+        anyhow::bail!("Setup cancelled");
+    }
+    let answer = answer.trim();
+    Ok((!answer.is_empty()).then(|| answer.to_string()))
+}
+
+fn ask_text(input: &mut impl BufRead, question: &str, default: &str) -> Result<String> {
+    Ok(ask(input, question, default)?.unwrap_or_else(|| default.to_string()))
+}
+
+fn ask_yes_no(input: &mut impl BufRead, question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    loop {
+        let Some(answer) = ask(input, question, hint)? else {
+            return Ok(default);
+        };
+        match answer.to_lowercase().as_str() {
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("Please answer y or n."),
+        }
+    }
+}
+
+fn ask_number<T>(input: &mut impl BufRead, question: &str, default: T) -> Result<T>
+where
+    T: std::str::FromStr + std::fmt::Display,
+{
+    loop {
+        let Some(answer) = ask(input, question, &default.to_string())? else {
+            return Ok(default);
+        };
+        match answer.parse() {
+            Ok(number) => return Ok(number),
+            Err(_) => println!("Please enter a whole number."),
+        }
+    }
+}
+
+/// A setting's value as typed on the command line: text without quotes.
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,331 @@
+//! What `ideavault` shows when run without a command: what needs attention
+//! today, and how much there is of everything. Which sections appear, and how
+//! many items each lists, follow the `dashboard` setting.
+
+use super::table::short_id;
+use crate::dates;
+use crate::models::config::{Config, DashboardConfig, DashboardSection};
+use crate::models::idea::{Idea, IdeaStatus};
+use crate::models::project::{Project, ProjectStatus};
+use crate::models::task::{Task, TaskStatus};
+use crate::output;
+use crate::say;
+use crate::storage::Storage;
+use crate::style::{self, Color};
+use crate::symbols;
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use std::fmt::Display;
+
+/// The dashboard's contents. Sections that are turned off are `None`; lists
+/// hold every item, however many are shown.
+#[derive(Debug, Default, Serialize)]
+pub struct Dashboard {
+    /// Open tasks due before today, earliest first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overdue: Option<Vec<Task>>,
+
+    /// Open tasks due today
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_today: Option<Vec<Task>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_progress: Option<InProgress>,
+
+    /// Ideas, newest first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_ideas: Option<Vec<Idea>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counts: Option<Counts>,
+}
+
+/// Work under way, most recently updated first.
+#[derive(Debug, Default, Serialize)]
+pub struct InProgress {
+    pub tasks: Vec<Task>,
+    pub projects: Vec<Project>,
+    /// Active ideas
+    pub ideas: Vec<Idea>,
+}
+
+/// How many items have each status, most common first.
+#[derive(Debug, Default, Serialize)]
+pub struct Counts {
+    pub ideas: Vec<StatusCount>,
+    pub projects: Vec<StatusCount>,
+    pub tasks: Vec<StatusCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusCount {
+    pub status: String,
+    pub count: usize,
+}
+
+pub fn execute() -> Result<()> {
+    let config = Config::load().context("Failed to load config")?;
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    let dashboard = Dashboard::build(&storage, &config.dashboard, Utc::now().date_naive())?;
+
+    if !output::is_table() {
+        return output::print_item(&dashboard, &[]);
+    }
+    dashboard.print(&config.dashboard);
+    Ok(())
+}
+
+impl Dashboard {
+    /// The sections `config` turns on, as of `today`.
+    pub fn build(storage: &Storage, config: &DashboardConfig, today: NaiveDate) -> Result<Self> {
+        let wants = |section| config.sections.contains(&section);
+        let ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let projects = storage.load_projects().context("Failed to load projects")?;
+        let tasks = storage.load_tasks().context("Failed to load tasks")?;
+
+        let due = |before: bool| {
+            let mut due: Vec<Task> = tasks
+                .iter()
+                .filter(|task| !task.status.is_closed())
+                .filter(|task| {
+                    task.due_date.is_some_and(|date| {
+                        let day = date.date_naive();
+                        if before {
+                            day < today
+                        } else {
+                            day == today
+                        }
+                    })
+                })
+                .cloned()
+                .collect();
+            due.sort_by_key(|task| task.due_date);
+            due
+        };
+
+        let mut dashboard = Dashboard::default();
+        if wants(DashboardSection::Overdue) {
+            dashboard.overdue = Some(due(true));
+        }
+        if wants(DashboardSection::DueToday) {
+            dashboard.due_today = Some(due(false));
+        }
+        if wants(DashboardSection::InProgress) {
+            let mut in_progress = InProgress {
+                tasks: tasks
+                    .iter()
+                    .filter(|task| task.status == TaskStatus::InProgress)
+                    .cloned()
+                    .collect(),
+                projects: projects
+                    .iter()
+                    .filter(|project| project.status == ProjectStatus::InProgress)
+                    .cloned()
+                    .collect(),
+                ideas: ideas
+                    .iter()
+                    .filter(|idea| idea.status == IdeaStatus::Active)
+                    .cloned()
+                    .collect(),
+            };
+            in_progress
+                .tasks
+                .sort_by_key(|task| std::cmp::Reverse(task.updated_at));
+            // Most pressing projects first, then the most recently changed
+            in_progress.projects.sort_by(|a, b| {
+                b.priority
+                    .cmp(&a.priority)
+                    .then(b.updated_at.cmp(&a.updated_at))
+            });
+            in_progress
+                .ideas
+                .sort_by_key(|idea| std::cmp::Reverse(idea.updated_at));
+            dashboard.in_progress = Some(in_progress);
+        }
+        if wants(DashboardSection::RecentIdeas) {
+            let mut recent = ideas.clone();
+            recent.sort_by_key(|idea| std::cmp::Reverse(idea.created_at));
+            dashboard.recent_ideas = Some(recent);
+        }
+        if wants(DashboardSection::Counts) {
+            dashboard.counts = Some(Counts {
+                ideas: count_by(ideas.iter().map(|idea| &idea.status)),
+                projects: count_by(projects.iter().map(|project| &project.status)),
+                tasks: count_by(tasks.iter().map(|task| &task.status)),
+            });
+        }
+        Ok(dashboard)
+    }
+
+    /// Print the sections in the order `config` lists them, with at most
+    /// `max_items` items in each.
+    pub fn print(&self, config: &DashboardConfig) {
+        let max_items = config.max_items;
+        for (number, section) in config.sections.iter().enumerate() {
+            if number > 0 {
+                say!();
+            }
+            match section {
+                DashboardSection::Overdue => {
+                    let Some(tasks) = &self.overdue else {
+                        continue;
+                    };
+                    say!("{} Overdue ({})", symbols::DUE, tasks.len());
+                    if tasks.is_empty() {
+                        say!("   {}", style::dim("Nothing overdue"));
+                    }
+                    print_tasks(tasks, max_items, "task list --overdue");
+                }
+                DashboardSection::DueToday => {
+                    let Some(tasks) = &self.due_today else {
+                        continue;
+                    };
+                    say!("{} Due today ({})", symbols::DATE, tasks.len());
+                    if tasks.is_empty() {
+                        say!("   {}", style::dim("Nothing due today"));
+                    }
+                    print_tasks(tasks, max_items, "task list --sort due");
+                }
+                DashboardSection::InProgress => {
+                    let Some(in_progress) = &self.in_progress else {
+                        continue;
+                    };
+                    let total = in_progress.tasks.len()
+                        + in_progress.projects.len()
+                        + in_progress.ideas.len();
+                    say!("{} In progress ({})", symbols::IN_PROGRESS, total);
+                    if total == 0 {
+                        say!("   {}", style::dim("Nothing in progress"));
+                    }
+                    print_tasks(
+                        &in_progress.tasks,
+                        max_items,
+                        "task list --status in-progress",
+                    );
+                    print_more(
+                        &in_progress.projects,
+                        max_items,
+                        "project list --status in-progress",
+                        |project| {
+                            format!(
+                                "{} {} [{}]",
+                                symbols::PROJECT,
+                                style::paint(
+                                    &project.title,
+                                    style::project_status_color(&project.status)
+                                ),
+                                style::dim(short_id(&project.id))
+                            )
+                        },
+                    );
+                    print_more(
+                        &in_progress.ideas,
+                        max_items,
+                        "idea list --status active",
+                        idea_line,
+                    );
+                }
+                DashboardSection::RecentIdeas => {
+                    let Some(ideas) = &self.recent_ideas else {
+                        continue;
+                    };
+                    say!("{} Recent ideas", symbols::IDEA);
+                    if ideas.is_empty() {
+                        say!("   {}", style::dim("No ideas yet"));
+                    }
+                    print_more(ideas, max_items, "idea list", |idea| {
+                        format!(
+                            "{} {}",
+                            idea_line(idea),
+                            style::dim(dates::moment(idea.created_at, dates::DATE_TIME))
+                        )
+                    });
+                }
+                DashboardSection::Counts => {
+                    let Some(counts) = &self.counts else {
+                        continue;
+                    };
+                    say!("{} Counts", symbols::COUNTS);
+                    say!("   Ideas:    {}", count_line(&counts.ideas));
+                    say!("   Projects: {}", count_line(&counts.projects));
+                    say!("   Tasks:    {}", count_line(&counts.tasks));
+                }
+            }
+        }
+    }
+}
+
+/// How many of `statuses` there are of each, most common first.
+pub fn count_by<'a, S: Display + 'a>(statuses: impl Iterator<Item = &'a S>) -> Vec<StatusCount> {
+    let mut counts: Vec<StatusCount> = Vec::new();
+    for status in statuses {
+        let status = status.to_string();
+        match counts.iter_mut().find(|count| count.status == status) {
+            Some(count) => count.count += 1,
+            None => counts.push(StatusCount { status, count: 1 }),
+        }
+    }
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.status.cmp(&b.status)));
+    counts
+}
+
+pub fn count_line(counts: &[StatusCount]) -> String {
+    if counts.is_empty() {
+        return style::dim("none");
+    }
+    counts
+        .iter()
+        .map(|count| format!("{} {}", count.count, count.status))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// List `tasks` with their due dates.
+fn print_tasks(tasks: &[Task], max_items: usize, more: &str) {
+    print_more(tasks, max_items, more, |task| {
+        let mut line = format!(
+            "{} {} {} [{}]",
+            task.status.marker(),
+            task.priority.marker(),
+            style::paint(&task.title, style::task_status_color(&task.status)),
+            style::dim(short_id(&task.id))
+        );
+        if let Some(due_date) = task.due_date {
+            let due = format!("due {}", dates::day(due_date));
+            if due_date.date_naive() < Utc::now().date_naive() && !task.status.is_closed() {
+                line = format!("{} {}", line, style::paint(due, Color::Red));
+            } else {
+                line = format!("{} {}", line, style::dim(due));
+            }
+        }
+        line
+    });
+}
+
+fn idea_line(idea: &Idea) -> String {
+    format!(
+        "{} {} [{}]",
+        symbols::IDEA,
+        style::paint(&idea.title, style::idea_status_color(&idea.status)),
+        style::dim(short_id(&idea.id))
+    )
+}
+
+/// Print the first `max_items` of `items`, then how many more there are and
+/// the command listing them all.
+fn print_more<T>(items: &[T], max_items: usize, command: &str, line: impl Fn(&T) -> String) {
+    for item in items.iter().take(max_items) {
+        say!("   {}", line(item));
+    }
+    if items.len() > max_items {
+        say!(
+            "   {}",
+            style::dim(format!(
+                "… and {} more (ideavault {})",
+                items.len() - max_items,
+                command
+            ))
+        );
+    }
+}
@@ -0,0 +1,489 @@
+//! Vault integrity checks
+//!
+//! Finds broken references, duplicate IDs, unparsable dates, and tags that are
+//! used but not registered, and repairs what can be fixed without losing data.
+
+use crate::models::tag::Tag;
+use crate::models::{Idea, Task};
+use crate::storage::compression;
+use crate::storage::json::write_atomic;
+use crate::storage::{Session, Storage};
+use crate::symbols;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use uuid::Uuid;
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Repair the problems that can be fixed safely
+    #[arg(long)]
+    pub fix: bool,
+}
+
+/// Kinds of problems the doctor knows about
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    /// A data file could not be read or parsed at all
+    Unreadable,
+    /// A timestamp field that doesn't parse as a date
+    InvalidDate,
+    /// Two entities of the same type share an ID
+    DuplicateId,
+    /// A project links an idea that doesn't exist
+    DanglingProjectIdea,
+    /// An idea is related to an idea that doesn't exist
+    DanglingRelatedIdea,
+    /// A task points to a project that doesn't exist
+    DanglingTaskProject,
+    /// A task points to an idea that doesn't exist
+    DanglingTaskIdea,
+    /// An idea or task uses a tag missing from tags.json
+    MissingTag,
+}
+
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub message: String,
+    pub fixable: bool,
+}
+
+impl Issue {
+    fn new(kind: IssueKind, message: String, fixable: bool) -> Self {
+        Self {
+            kind,
+            message,
+            fixable,
+        }
+    }
+}
+
+const DATE_FIELDS: [&str; 3] = ["created_at", "updated_at", "due_date"];
+
+pub fn execute(args: DoctorArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    run(&storage, &args)?;
+    Ok(())
+}
+
+pub fn run(storage: &Storage, args: &DoctorArgs) -> Result<Vec<Issue>> {
+    println!(
+        "{} Checking vault at {:?}",
+        symbols::CHECKUP,
+        storage.data_dir()
+    );
+
+    let issues = check_vault(storage)?;
+    if issues.is_empty() {
+        println!("{} No problems found", symbols::OK);
+        return Ok(issues);
+    }
+
+    println!("{} Found {} problem(s):", symbols::WARNING, issues.len());
+    for issue in &issues {
+        let marker = if issue.fixable { "fixable" } else { "manual" };
+        println!("   - [{}] {}", marker, issue.message);
+    }
+
+    if !args.fix {
+        if issues.iter().any(|i| i.fixable) {
+            println!();
+            println!("Run `ideavault doctor --fix` to repair fixable problems.");
+        }
+        return Ok(issues);
+    }
+
+    let fixed = fix_vault(storage)?;
+    println!();
+    println!("{} Applied {} fix(es):", symbols::FIXED, fixed.len());
+    for fix in &fixed {
+        println!("   - {}", fix);
+    }
+
+    let remaining = check_vault(storage)?;
+    if remaining.is_empty() {
+        println!("{} Vault is healthy", symbols::OK);
+    } else {
+        println!(
+            "{} {} problem(s) need manual attention",
+            symbols::WARNING,
+            remaining.len()
+        );
+    }
+    Ok(remaining)
+}
+
+/// Inspect the vault and return every problem found.
+pub fn check_vault(storage: &Storage) -> Result<Vec<Issue>> {
+    let mut issues = check_raw_dates(storage, false)?.0;
+    if !issues.is_empty() {
+        // Typed loading would fail on these, so stop here
+        return Ok(issues);
+    }
+
+    let session = Session::new(storage);
+    if let Err(err) = load_all(&session) {
+        issues.push(Issue::new(
+            IssueKind::Unreadable,
+            format!("{:#}", err),
+            false,
+        ));
+        return Ok(issues);
+    }
+    let (ideas, projects, tasks, tags) = (
+        session.ideas()?,
+        session.projects()?,
+        session.tasks()?,
+        session.tags()?,
+    );
+
+    issues.extend(duplicate_issues("idea", ideas.iter().map(|i| i.id)));
+    issues.extend(duplicate_issues("project", projects.iter().map(|p| p.id)));
+    issues.extend(duplicate_issues("task", tasks.iter().map(|t| t.id)));
+
+    let idea_ids: HashSet<Uuid> = ideas.iter().map(|i| i.id).collect();
+    let project_ids: HashSet<Uuid> = projects.iter().map(|p| p.id).collect();
+
+    for idea in ideas {
+        for related_id in idea.related_ids.iter().filter(|id| !idea_ids.contains(id)) {
+            issues.push(Issue::new(
+                IssueKind::DanglingRelatedIdea,
+                format!(
+                    "Idea '{}' [{}] is related to missing idea {}",
+                    idea.title, idea.id, related_id
+                ),
+                true,
+            ));
+        }
+    }
+
+    for project in projects {
+        for idea_id in project.idea_ids.iter().filter(|id| !idea_ids.contains(id)) {
+            issues.push(Issue::new(
+                IssueKind::DanglingProjectIdea,
+                format!(
+                    "Project '{}' [{}] links missing idea {}",
+                    project.title, project.id, idea_id
+                ),
+                true,
+            ));
+        }
+    }
+
+    for task in tasks {
+        if let Some(project_id) = task.project_id.filter(|id| !project_ids.contains(id)) {
+            issues.push(Issue::new(
+                IssueKind::DanglingTaskProject,
+                format!(
+                    "Task '{}' [{}] points to missing project {}",
+                    task.title, task.id, project_id
+                ),
+                true,
+            ));
+        }
+        if let Some(idea_id) = task.idea_id.filter(|id| !idea_ids.contains(id)) {
+            issues.push(Issue::new(
+                IssueKind::DanglingTaskIdea,
+                format!(
+                    "Task '{}' [{}] points to missing idea {}",
+                    task.title, task.id, idea_id
+                ),
+                true,
+            ));
+        }
+    }
+
+    for tag in missing_tags(ideas, tasks, tags) {
+        issues.push(Issue::new(
+            IssueKind::MissingTag,
+            format!("Tag '{}' is used but not registered in tags", tag),
+            true,
+        ));
+    }
+
+    Ok(issues)
+}
+
+/// Repair every fixable problem. Returns a description of each fix applied.
+pub fn fix_vault(storage: &Storage) -> Result<Vec<String>> {
+    let mut fixes = check_raw_dates(storage, true)?.1;
+    let mut session = Session::new(storage);
+
+    if has_duplicates(session.ideas()?.iter().map(|i| i.id)) {
+        let ideas = session.ideas_mut()?;
+        fixes.extend(fix_duplicates("idea", ideas, |i| i.id, |i, id| i.id = id));
+    }
+    if has_duplicates(session.projects()?.iter().map(|p| p.id)) {
+        let projects = session.projects_mut()?;
+        fixes.extend(fix_duplicates(
+            "project",
+            projects,
+            |p| p.id,
+            |p, id| p.id = id,
+        ));
+    }
+    if has_duplicates(session.tasks()?.iter().map(|t| t.id)) {
+        let tasks = session.tasks_mut()?;
+        fixes.extend(fix_duplicates("task", tasks, |t| t.id, |t, id| t.id = id));
+    }
+
+    let idea_ids: HashSet<Uuid> = session.ideas()?.iter().map(|i| i.id).collect();
+    let project_ids: HashSet<Uuid> = session.projects()?.iter().map(|p| p.id).collect();
+
+    let has_dangling_relations = session
+        .ideas()?
+        .iter()
+        .any(|i| i.related_ids.iter().any(|id| !idea_ids.contains(id)));
+    if has_dangling_relations {
+        for idea in session.ideas_mut()?.iter_mut() {
+            let dangling: Vec<Uuid> = idea
+                .related_ids
+                .iter()
+                .filter(|id| !idea_ids.contains(id))
+                .copied()
+                .collect();
+            for related_id in dangling {
+                idea.remove_related(&related_id);
+                fixes.push(format!(
+                    "Removed missing related idea {} from idea '{}'",
+                    related_id, idea.title
+                ));
+            }
+        }
+    }
+
+    let has_dangling_ideas = session
+        .projects()?
+        .iter()
+        .any(|p| p.idea_ids.iter().any(|id| !idea_ids.contains(id)));
+    if has_dangling_ideas {
+        for project in session.projects_mut()?.iter_mut() {
+            let dangling: Vec<Uuid> = project
+                .idea_ids
+                .iter()
+                .filter(|id| !idea_ids.contains(id))
+                .copied()
+                .collect();
+            for idea_id in dangling {
+                project.remove_idea(&idea_id);
+                fixes.push(format!(
+                    "Unlinked missing idea {} from project '{}'",
+                    idea_id, project.title
+                ));
+            }
+        }
+    }
+
+    let has_dangling_links = session.tasks()?.iter().any(|t| {
+        t.project_id.is_some_and(|id| !project_ids.contains(&id))
+            || t.idea_id.is_some_and(|id| !idea_ids.contains(&id))
+    });
+    if has_dangling_links {
+        for task in session.tasks_mut()?.iter_mut() {
+            if let Some(project_id) = task.project_id.filter(|id| !project_ids.contains(id)) {
+                task.project_id = None;
+                task.updated_at = Utc::now();
+                fixes.push(format!(
+                    "Unlinked missing project {} from task '{}'",
+                    project_id, task.title
+                ));
+            }
+            if let Some(idea_id) = task.idea_id.filter(|id| !idea_ids.contains(id)) {
+                task.idea_id = None;
+                task.updated_at = Utc::now();
+                fixes.push(format!(
+                    "Unlinked missing idea {} from task '{}'",
+                    idea_id, task.title
+                ));
+            }
+        }
+    }
+
+    let new_tags = missing_tags(session.ideas()?, session.tasks()?, session.tags()?);
+    if !new_tags.is_empty() {
+        let tags = session.tags_mut()?;
+        for name in new_tags {
+            fixes.push(format!("Registered missing tag '{}'", name));
+            tags.push(Tag::new(name));
+        }
+    }
+
+    session.flush()?;
+    Ok(fixes)
+}
+
+/// Load every collection into `session`, stopping at the first unreadable one.
+fn load_all(session: &Session) -> Result<()> {
+    session.ideas()?;
+    session.projects()?;
+    session.tasks()?;
+    session.tags()?;
+    Ok(())
+}
+
+/// Scan JSON data files for date fields that don't parse. With `fix`, required
+/// timestamps are reset to now; bad due dates are left for the user to correct.
+fn check_raw_dates(storage: &Storage, fix: bool) -> Result<(Vec<Issue>, Vec<String>)> {
+    let mut issues = Vec::new();
+    let mut fixes = Vec::new();
+
+    let json_files = storage
+        .data_files()
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json") && path.is_file());
+
+    for path in json_files {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let raw = fs::read(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        let format = compression::detect(&raw);
+        let content = compression::decode(&raw)?;
+        // Collection files hold an array, indexed vaults one object per file
+        let mut document: Value = match serde_json::from_slice(&content) {
+            Ok(document) => document,
+            Err(err) => {
+                issues.push(Issue::new(
+                    IssueKind::Unreadable,
+                    format!("{} is not valid JSON: {}", file_name, err),
+                    false,
+                ));
+                continue;
+            }
+        };
+
+        let entries = match &mut document {
+            Value::Array(entries) => entries.iter_mut().collect(),
+            entry => vec![entry],
+        };
+
+        let mut changed = false;
+        for entry in entries {
+            let Some(object) = entry.as_object_mut() else {
+                continue;
+            };
+            let id = object
+                .get("id")
+                .and_then(Value::as_str)
+                .unwrap_or("?")
+                .to_string();
+
+            for field in DATE_FIELDS {
+                let Some(Value::String(raw)) = object.get(field) else {
+                    continue;
+                };
+                if raw.parse::<DateTime<Utc>>().is_ok() {
+                    continue;
+                }
+
+                let required = field != "due_date";
+                issues.push(Issue::new(
+                    IssueKind::InvalidDate,
+                    format!(
+                        "{} entry {} has unparsable {} '{}'",
+                        file_name, id, field, raw
+                    ),
+                    required,
+                ));
+                if fix && required {
+                    let raw = raw.clone();
+                    object.insert(field.to_string(), Value::String(Utc::now().to_rfc3339()));
+                    fixes.push(format!(
+                        "Reset unparsable {} '{}' on {} entry {} to now",
+                        field, raw, file_name, id
+                    ));
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            let content = serde_json::to_vec_pretty(&document)?;
+            write_atomic(&path, &compression::encode(&content, format)?)?;
+        }
+    }
+
+    Ok((issues, fixes))
+}
+
+fn has_duplicates(ids: impl Iterator<Item = Uuid>) -> bool {
+    let mut seen = HashSet::new();
+    ids.into_iter().any(|id| !seen.insert(id))
+}
+
+fn duplicate_issues(kind: &str, ids: impl Iterator<Item = Uuid>) -> Vec<Issue> {
+    let mut counts: HashMap<Uuid, usize> = HashMap::new();
+    for id in ids {
+        *counts.entry(id).or_default() += 1;
+    }
+
+    let mut duplicates: Vec<(Uuid, usize)> = counts.into_iter().filter(|(_, n)| *n > 1).collect();
+    duplicates.sort();
+    duplicates
+        .into_iter()
+        .map(|(id, n)| {
+            Issue::new(
+                IssueKind::DuplicateId,
+                format!("{} ID {} is used by {} entries", kind, id, n),
+                true,
+            )
+        })
+        .collect()
+}
+
+/// Drop exact copies of an entity; give differing entities that share an ID a
+/// fresh ID so nothing is lost.
+fn fix_duplicates<T: serde::Serialize>(
+    kind: &str,
+    items: &mut Vec<T>,
+    id_of: impl Fn(&T) -> Uuid,
+    set_id: impl Fn(&mut T, Uuid),
+) -> Vec<String> {
+    let mut fixes = Vec::new();
+    let mut seen: HashMap<Uuid, Vec<Value>> = HashMap::new();
+    let mut keep = Vec::with_capacity(items.len());
+
+    for mut item in items.drain(..) {
+        let id = id_of(&item);
+        let value = serde_json::to_value(&item).unwrap_or(Value::Null);
+        let copies = seen.entry(id).or_default();
+
+        if copies.is_empty() {
+            copies.push(value);
+            keep.push(item);
+        } else if copies.contains(&value) {
+            fixes.push(format!("Removed identical duplicate {} {}", kind, id));
+        } else {
+            let new_id = Uuid::new_v4();
+            set_id(&mut item, new_id);
+            copies.push(value);
+            fixes.push(format!(
+                "Gave conflicting duplicate {} {} a new ID {}",
+                kind, id, new_id
+            ));
+            keep.push(item);
+        }
+    }
+
+    *items = keep;
+    fixes
+}
+
+fn missing_tags(ideas: &[Idea], tasks: &[Task], tags: &[Tag]) -> Vec<String> {
+    let known: HashSet<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+    let mut missing: Vec<String> = Vec::new();
+
+    let used = ideas
+        .iter()
+        .flat_map(|i| i.tags.iter())
+        .chain(tasks.iter().flat_map(|t| t.tags.iter()));
+    for tag in used {
+        if !known.contains(tag.as_str()) && !missing.contains(tag) {
+            missing.push(tag.clone());
+        }
+    }
+
+    missing
+}
@@ -0,0 +1,231 @@
+use crate::export::{EntityType, Export, ExportFilter, ExportFormat};
+use crate::models::config::Config;
+use crate::storage::{archive, Storage};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ExportCommands {
+    #[command(flatten)]
+    pub args: ExportArgs,
+
+    #[command(subcommand)]
+    pub target: Option<ExportTarget>,
+}
+
+#[derive(Subcommand)]
+pub enum ExportTarget {
+    /// Write every idea, project, and task as a linked note in an Obsidian vault
+    Obsidian(ObsidianArgs),
+    /// Write projects and tasks as an org-mode file for org-agenda
+    Org(OrgArgs),
+}
+
+#[derive(Args)]
+pub struct ObsidianArgs {
+    /// Obsidian vault directory to write the notes to
+    pub dir: PathBuf,
+}
+
+#[derive(Args)]
+pub struct OrgArgs {
+    /// Org file to write
+    #[arg(short, long, default_value = "ideavault.org")]
+    pub out: PathBuf,
+
+    /// Include finished (done and cancelled) tasks
+    #[arg(short, long)]
+    pub all: bool,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Output format: json, csv, yaml, or md
+    #[arg(short, long, default_value = "json")]
+    pub format: ExportFormat,
+
+    /// Directory to write the export files to
+    #[arg(short, long, default_value = ".")]
+    pub out: PathBuf,
+
+    /// Only export these entity types (idea, project, task; comma-separated)
+    #[arg(short = 't', long = "type", value_delimiter = ',')]
+    pub types: Vec<EntityType>,
+
+    /// Only export entities with this status
+    #[arg(short, long)]
+    pub status: Option<String>,
+
+    /// Only export ideas, projects, and tasks with this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+
+    /// Pack the whole vault (data, backups, history, and the config file) into this archive file instead
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["types", "status", "tag"])]
+    pub archive: Option<PathBuf>,
+
+    /// Encrypt the archive with a passphrase, read from IDEAVAULT_ARCHIVE_PASSPHRASE or prompted for
+    #[arg(long, requires = "archive")]
+    pub encrypt: bool,
+}
+
+impl ExportCommands {
+    pub fn execute(&self) -> Result<()> {
+        let storage = Storage::new().context("Failed to initialize storage")?;
+
+        match &self.target {
+            Some(ExportTarget::Obsidian(args)) => export_obsidian(&storage, args).map(|_| ()),
+            Some(ExportTarget::Org(args)) => export_org(&storage, args),
+            None => export(&storage, &self.args).map(|_| ()),
+        }
+    }
+}
+
+pub fn export(storage: &Storage, args: &ExportArgs) -> Result<Vec<PathBuf>> {
+    if let Some(out) = &args.archive {
+        export_archive(storage, out, args.encrypt)?;
+        return Ok(vec![out.clone()]);
+    }
+
+    let ideas = storage.load_ideas().context("Failed to load ideas")?;
+    let projects = storage.load_projects().context("Failed to load projects")?;
+    let tasks = storage.load_tasks().context("Failed to load tasks")?;
+
+    let filter = ExportFilter {
+        types: args.types.clone(),
+        status: args.status.clone(),
+        tag: args.tag.clone(),
+    };
+    let export = Export::new(ideas, projects, tasks, &filter);
+    if export.is_empty() {
+        println!("{} Nothing matched; no files written", symbols::LIST);
+        return Ok(Vec::new());
+    }
+
+    let files = export
+        .write(args.format, &args.out)
+        .context("Failed to write export")?;
+
+    println!(
+        "{} Exported {} idea(s), {} project(s), {} task(s) as {} to {}",
+        symbols::OK,
+        export.ideas.len(),
+        export.projects.len(),
+        export.tasks.len(),
+        args.format,
+        args.out.display()
+    );
+    for file in &files {
+        println!("   {}", file.display());
+    }
+    Ok(files)
+}
+
+pub fn export_obsidian(storage: &Storage, args: &ObsidianArgs) -> Result<Vec<PathBuf>> {
+    let ideas = storage.load_ideas().context("Failed to load ideas")?;
+    let projects = storage.load_projects().context("Failed to load projects")?;
+    let tasks = storage.load_tasks().context("Failed to load tasks")?;
+
+    let export = Export::new(ideas, projects, tasks, &ExportFilter::default());
+    let notes = export
+        .write_obsidian(&args.dir)
+        .context("Failed to write Obsidian notes")?;
+
+    println!(
+        "{} Wrote {} note(s) to Obsidian vault {}",
+        symbols::OK,
+        notes.len(),
+        args.dir.display()
+    );
+    Ok(notes)
+}
+
+pub fn export_org(storage: &Storage, args: &OrgArgs) -> Result<()> {
+    let ideas = storage.load_ideas().context("Failed to load ideas")?;
+    let projects = storage.load_projects().context("Failed to load projects")?;
+    let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
+    if !args.all {
+        tasks.retain(|task| !task.status.is_closed());
+    }
+
+    let filter = ExportFilter {
+        types: vec![EntityType::Project, EntityType::Task],
+        ..ExportFilter::default()
+    };
+    let export = Export::new(ideas, projects, tasks, &filter);
+    export
+        .write_org(&args.out)
+        .context("Failed to write org file")?;
+
+    println!(
+        "{} Exported {} project(s) and {} task(s) to {}",
+        symbols::OK,
+        export.projects.len(),
+        export.tasks.len(),
+        args.out.display()
+    );
+    Ok(())
+}
+
+/// Pack the whole data directory and the config file into a single archive
+/// at `out`.
+pub fn export_archive(storage: &Storage, out: &Path, encrypt: bool) -> Result<Vec<String>> {
+    let passphrase = if encrypt {
+        Some(passphrase(true)?)
+    } else {
+        None
+    };
+    let config = Config::path().ok();
+    let files = archive::write(
+        storage.data_dir(),
+        config.as_deref(),
+        out,
+        passphrase.as_deref(),
+    )
+    .context("Failed to write archive")?;
+
+    println!(
+        "{} Archived {} file(s) from {} to {}{}",
+        symbols::OK,
+        files.len(),
+        storage.data_dir().display(),
+        out.display(),
+        if encrypt { " (encrypted)" } else { "" }
+    );
+    Ok(files)
+}
+
+/// The archive passphrase, from the environment or, failing that, asked for
+/// (twice when `confirm` is set, so a typo can't lock the archive).
+pub(crate) fn passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(archive::PASSPHRASE_ENV) {
+        if !passphrase.is_empty() {
+            return Ok(passphrase);
+        }
+    }
+    if !io::stdin().is_terminal() {
+        anyhow::bail!(
+            "No passphrase given. Set {} to use encrypted archives non-interactively",
+            archive::PASSPHRASE_ENV
+        );
+    }
+
+    // The prompt goes to stderr, and what's typed isn't echoed
+    let read = |prompt: &str| -> Result<String> {
+        eprint!("{}", prompt);
+        io::stderr().flush().context("Failed to flush output")?;
+        rpassword::read_password().context("Failed to read passphrase")
+    };
+    let passphrase = read("Archive passphrase: ")?;
+    if passphrase.is_empty() {
+        anyhow::bail!("Passphrase cannot be empty");
+    }
+    if confirm && read("Repeat passphrase: ")? != passphrase {
+        anyhow::bail!("Passphrases do not match");
+    }
+    Ok(passphrase)
+}
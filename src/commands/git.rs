@@ -0,0 +1,80 @@
+use crate::say;
+use crate::storage::{git, Storage};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "git")]
+#[command(about = "Keep the vault's history in git")]
+pub struct GitCommands {
+    #[command(subcommand)]
+    pub command: GitSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum GitSubcommand {
+    /// Turn the data directory into a git repository that records every change
+    Init,
+    /// Show the most recent commits
+    Log(GitLogArgs),
+}
+
+#[derive(Args)]
+pub struct GitLogArgs {
+    /// Number of commits to show
+    #[arg(short = 'n', long, default_value_t = 20)]
+    pub limit: usize,
+}
+
+impl GitCommands {
+    pub fn execute(&self) -> Result<()> {
+        let storage = Storage::new().context("Failed to initialize storage")?;
+
+        match &self.command {
+            GitSubcommand::Init => Self::init(&storage),
+            GitSubcommand::Log(args) => Self::log(&storage, args),
+        }
+    }
+
+    pub fn init(storage: &Storage) -> Result<()> {
+        let data_dir = storage.data_dir();
+        if !git::init(data_dir).context("Failed to initialize git repository")? {
+            say!(
+                "{} {} is already a git repository",
+                symbols::LIST,
+                data_dir.display()
+            );
+            return Ok(());
+        }
+
+        say!(
+            "{} Initialized git repository in {}",
+            symbols::OK,
+            data_dir.display()
+        );
+        say!("   Every change will now be committed automatically.");
+        Ok(())
+    }
+
+    pub fn log(storage: &Storage, args: &GitLogArgs) -> Result<()> {
+        let data_dir = storage.data_dir();
+        if !git::is_enabled(data_dir) {
+            println!(
+                "{} Git history is not enabled. Run `ideavault git init` to turn it on.",
+                symbols::LIST
+            );
+            return Ok(());
+        }
+
+        let log = git::log(data_dir, args.limit).context("Failed to read git log")?;
+        println!(
+            "{} Recent commits in {}:",
+            symbols::HISTORY,
+            data_dir.display()
+        );
+        println!();
+        print!("{}", log);
+        Ok(())
+    }
+}
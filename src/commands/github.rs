@@ -0,0 +1,333 @@
+//! `project github`: open issues, pull requests, and the latest release of a
+//! project's GitHub repository, with its open issues importable as tasks.
+//! Requests go to the GitHub API with ureq, as `version --check` does, and
+//! carry `GITHUB_TOKEN` when it is set.
+
+use super::id::IdArg;
+use super::project::repo_web_url;
+use super::table::short_id;
+use crate::dates;
+use crate::models::task::Task;
+use crate::output;
+use crate::say;
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const API_URL: &str = "https://api.github.com";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Issues fetched per request when importing.
+const PAGE_SIZE: usize = 100;
+
+/// Field order of repository stats printed as JSON or YAML.
+const STATS_FIELDS: &[&str] = &[
+    "repo",
+    "url",
+    "open_issues",
+    "open_pull_requests",
+    "latest_release",
+];
+
+#[derive(Args)]
+pub struct GithubArgs {
+    /// The ID or title of the project
+    pub id: IdArg,
+
+    /// Add the repository's open issues to the project as tasks, skipping
+    /// issues imported before
+    #[arg(long)]
+    pub import_issues: bool,
+
+    /// Show which issues would be imported without saving anything
+    #[arg(long, requires = "import_issues")]
+    pub dry_run: bool,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// A GitHub repository at a glance.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoStats {
+    /// `owner/name`
+    pub repo: String,
+    pub url: String,
+    /// Open issues, not counting pull requests
+    pub open_issues: u64,
+    pub open_pull_requests: u64,
+    pub latest_release: Option<Release>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    pub html_url: String,
+}
+
+/// An issue as the GitHub API lists it; pull requests are listed as issues
+/// too, with `pull_request` set.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub html_url: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    full_name: String,
+    html_url: String,
+    open_issues_count: u64,
+}
+
+#[derive(Deserialize)]
+struct SearchResults {
+    total_count: u64,
+}
+
+pub fn show_github(storage: &Storage, args: &GithubArgs) -> Result<()> {
+    let id = args.id.project(storage)?;
+    let project = storage.get_project(id)?;
+    let repo = project.repo.as_deref().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Project '{}' has no repository. Set one with: ideavault project update {} --repo <REPO>",
+            project.title,
+            project.id
+        )
+    })?;
+    let (owner, name) = parse_repo(repo).ok_or_else(|| {
+        anyhow::anyhow!(
+            "'{}' isn't a GitHub repository. Use owner/name or a github.com URL",
+            repo
+        )
+    })?;
+
+    if args.import_issues {
+        return import_issues(storage, id, &owner, &name, args.dry_run);
+    }
+    let stats = fetch_stats(&owner, &name)?;
+    if !output::is_table() {
+        return output::print_item(&stats, STATS_FIELDS);
+    }
+
+    println!("{} {}", symbols::GITHUB, style::bold(&stats.repo));
+    println!("   {}", style::dim(&stats.url));
+    println!("   Open issues: {}", stats.open_issues);
+    println!("   Open pull requests: {}", stats.open_pull_requests);
+    match &stats.latest_release {
+        Some(release) => println!(
+            "   Latest release: {}{}",
+            release.tag_name,
+            release
+                .published_at
+                .map(|at| format!(" ({})", dates::moment(at, dates::DATE)))
+                .unwrap_or_default()
+        ),
+        None => println!("   Latest release: none"),
+    }
+    Ok(())
+}
+
+fn import_issues(
+    storage: &Storage,
+    project_id: Uuid,
+    owner: &str,
+    name: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let mut issues: Vec<Issue> = Vec::new();
+    for page in 1.. {
+        let batch: Vec<Issue> = get(&format!(
+            "/repos/{}/{}/issues?state=open&per_page={}&page={}",
+            owner, name, PAGE_SIZE, page
+        ))?
+        .with_context(|| format!("Repository {}/{} not found on GitHub", owner, name))?;
+        let last = batch.len() < PAGE_SIZE;
+        issues.extend(batch);
+        if last {
+            break;
+        }
+    }
+
+    let existing = storage.load_tasks().context("Failed to load tasks")?;
+    let tasks = issue_tasks(project_id, &issues, &existing);
+    if !dry_run {
+        for task in &tasks {
+            storage.add_task(task).context("Failed to save task")?;
+        }
+    }
+    if output::is_quiet() && !dry_run {
+        output::print_ids(tasks.iter().map(|task| task.id));
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would import" } else { "Imported" };
+    say!(
+        "{} {} {} issue(s) from {}/{} as tasks",
+        symbols::GITHUB,
+        verb,
+        tasks.len(),
+        owner,
+        name
+    );
+    for task in &tasks {
+        say!("   {} [{}]", task.title, style::dim(short_id(&task.id)));
+    }
+    let open = issues
+        .iter()
+        .filter(|issue| issue.pull_request.is_none())
+        .count();
+    if open > tasks.len() {
+        say!(
+            "   {}",
+            style::dim(format!("{} issue(s) already imported", open - tasks.len()))
+        );
+    }
+    Ok(())
+}
+
+/// The repository's open issue and pull request counts and its latest
+/// release.
+pub fn fetch_stats(owner: &str, name: &str) -> Result<RepoStats> {
+    let repository: Repository =
+        get(&format!("/repos/{}/{}", owner, name))?.with_context(|| {
+            format!(
+                "Repository {}/{} not found on GitHub. Private repositories need GITHUB_TOKEN set",
+                owner, name
+            )
+        })?;
+    let pulls: SearchResults = get(&format!(
+        "/search/issues?q=repo:{}/{}+is:pr+is:open&per_page=1",
+        owner, name
+    ))?
+    .context("GitHub didn't return a pull request count")?;
+    let latest_release = get(&format!("/repos/{}/{}/releases/latest", owner, name))?;
+
+    Ok(RepoStats {
+        repo: repository.full_name,
+        url: repository.html_url,
+        // GitHub counts open pull requests among open issues
+        open_issues: repository
+            .open_issues_count
+            .saturating_sub(pulls.total_count),
+        open_pull_requests: pulls.total_count,
+        latest_release,
+    })
+}
+
+/// Tasks in the project `project_id` for the open issues among `issues`,
+/// labels becoming tags. An issue is left out when a task among `existing`
+/// was imported from it before, its description starting with the issue's
+/// [`issue_line`].
+pub fn issue_tasks(project_id: Uuid, issues: &[Issue], existing: &[Task]) -> Vec<Task> {
+    issues
+        .iter()
+        .filter(|issue| issue.pull_request.is_none())
+        .filter(|issue| {
+            let line = issue_line(issue);
+            !existing.iter().any(|task| {
+                task.description
+                    .as_deref()
+                    .and_then(|description| description.lines().next())
+                    .is_some_and(|first| first.trim_end() == line)
+            })
+        })
+        .map(|issue| {
+            let mut description = issue_line(issue);
+            if let Some(body) = issue.body.as_deref().filter(|body| !body.trim().is_empty()) {
+                description = format!("{}\n\n{}", description, body.trim_end());
+            }
+            Task::new(issue.title.clone())
+                .with_description(description)
+                .with_tags(
+                    issue
+                        .labels
+                        .iter()
+                        .map(|label| label.name.clone())
+                        .collect(),
+                )
+                .with_project(project_id)
+        })
+        .collect()
+}
+
+/// The first line of the description of a task imported from `issue`,
+/// linking back to it.
+fn issue_line(issue: &Issue) -> String {
+    format!("GitHub issue #{}: {}", issue.number, issue.html_url)
+}
+
+/// The owner and name of the GitHub repository `repo` names, as
+/// `owner/name` or as a github.com URL of either kind.
+pub fn parse_repo(repo: &str) -> Option<(String, String)> {
+    let url = repo_web_url(repo);
+    let path = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(&url);
+    let path = match path.strip_prefix("github.com/") {
+        Some(path) => path,
+        None if !path.contains('.') && !path.contains(':') => path,
+        None => return None,
+    };
+    let mut parts = path.trim_end_matches('/').split('/');
+    let owner = parts.next().filter(|owner| !owner.is_empty())?;
+    let name = parts.next().filter(|name| !name.is_empty())?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    Some((owner.to_string(), name.to_string()))
+}
+
+/// GET `path` from the GitHub API as JSON, or `None` when it isn't found.
+pub fn get<T: DeserializeOwned>(path: &str) -> Result<Option<T>> {
+    let mut request = ureq::get(&format!("{}{}", API_URL, path))
+        .set("User-Agent", &format!("IdeaVault/{VERSION}"))
+        .set("Accept", "application/vnd.github+json");
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        request = request.set("Authorization", &format!("Bearer {}", token.trim()));
+    }
+    match request.call() {
+        Ok(response) => Ok(Some(
+            response
+                .into_json()
+                .context("Failed to read GitHub's response")?,
+        )),
+        Err(ureq::Error::Status(404, _)) => Ok(None),
+        // GitHub answers 403 both when the rate limit is used up and when
+        // access is denied; only the first leaves no requests remaining
+        Err(ureq::Error::Status(status @ (403 | 429), response))
+            if status == 429 || response.header("x-ratelimit-remaining") == Some("0") =>
+        {
+            anyhow::bail!(
+                "GitHub's rate limit was reached. Set GITHUB_TOKEN to raise it, or try again later"
+            )
+        }
+        Err(ureq::Error::Status(403, _)) => anyhow::bail!(
+            "GitHub denied access. Private repositories need GITHUB_TOKEN set to a token that can read them"
+        ),
+        Err(ureq::Error::Status(401, _)) => anyhow::bail!("GitHub refused GITHUB_TOKEN"),
+        Err(err) => Err(err).context("Failed to reach GitHub"),
+    }
+}
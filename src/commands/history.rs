@@ -0,0 +1,132 @@
+use super::id::IdArg;
+use crate::storage::history::{Action, FieldChange, HistoryEntry};
+use crate::storage::Storage;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Fields that change on every update and would only add noise
+const HIDDEN_FIELDS: [&str; 1] = ["updated_at"];
+const MAX_VALUE_LEN: usize = 60;
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// The ID of an idea, project, or task (or the start of one), or the name of a tag
+    pub id: String,
+}
+
+pub fn execute(args: HistoryArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    show_history(&storage, &args)
+}
+
+pub fn show_history(storage: &Storage, args: &HistoryArgs) -> Result<()> {
+    let history = storage.load_history().context("Failed to load history")?;
+    let id = entity_id(&history, &args.id)?;
+    let entries: Vec<HistoryEntry> = history
+        .into_iter()
+        .filter(|entry| entry.entity_id == id)
+        .collect();
+
+    if entries.is_empty() {
+        println!("{} No history recorded for {}", symbols::HISTORY, id);
+        return Ok(());
+    }
+
+    println!(
+        "{} History for {} ({} change(s)):",
+        symbols::HISTORY,
+        id,
+        entries.len()
+    );
+    println!();
+
+    for entry in &entries {
+        print_entry(entry);
+        println!();
+    }
+
+    Ok(())
+}
+
+/// The entity `id` names: a tag or whole ID as given, or the ID of the one
+/// entity in the history that starts with it.
+fn entity_id(history: &[HistoryEntry], id: &str) -> Result<String> {
+    if history.iter().any(|entry| entry.entity_id == id) {
+        return Ok(id.to_string());
+    }
+    let Ok(prefix) = id.parse::<IdArg>() else {
+        return Ok(id.to_string());
+    };
+
+    let mut candidates: Vec<(Uuid, String)> = Vec::new();
+    for entry in history {
+        if let Ok(uuid) = Uuid::parse_str(&entry.entity_id) {
+            if !candidates.iter().any(|(known, _)| *known == uuid) {
+                candidates.push((uuid, entry.entity.to_string()));
+            }
+        }
+    }
+    if !candidates.iter().any(|(uuid, _)| prefix.matches(uuid)) {
+        return Ok(id.to_string());
+    }
+    Ok(prefix.resolve("item", &candidates)?.to_string())
+}
+
+fn print_entry(entry: &HistoryEntry) {
+    let (icon, verb) = match entry.action {
+        Action::Create => (symbols::NEW, "Created"),
+        Action::Update => (symbols::EDITED, "Updated"),
+        Action::Delete => (symbols::TRASH, "Deleted"),
+    };
+    let command = entry
+        .command
+        .as_ref()
+        .map(|command| format!(" via `{}`", command))
+        .unwrap_or_default();
+
+    println!(
+        "{} {} {} — {}{}",
+        icon,
+        verb,
+        entry.entity,
+        entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+        command
+    );
+
+    if entry.action != Action::Update {
+        return;
+    }
+    print_changes(&entry.changes, "   ");
+}
+
+/// Print each of `changes` as "field: before → after", after `indent`.
+pub fn print_changes(changes: &[FieldChange], indent: &str) {
+    for change in changes {
+        if HIDDEN_FIELDS.contains(&change.field.as_str()) {
+            continue;
+        }
+        println!(
+            "{}{}: {} → {}",
+            indent,
+            change.field,
+            describe(&change.before),
+            describe(&change.after)
+        );
+    }
+}
+
+fn describe(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "(none)".to_string(),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_VALUE_LEN {
+        let truncated: String = text.chars().take(MAX_VALUE_LEN - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        text
+    }
+}
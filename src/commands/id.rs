@@ -0,0 +1,212 @@
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// How many candidates an ambiguous ID or title lists before summing up the rest.
+const MAX_CANDIDATES: usize = 10;
+
+/// An ID as typed on the command line: a whole UUID, enough of its start to
+/// pick out one item (`a1b2`), or the item's title (`"Rewrite parser"`), in
+/// full or in part. It is resolved against the vault by the command, which
+/// is where the candidates are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdArg(String);
+
+impl IdArg {
+    /// The idea this names.
+    pub fn idea(&self, storage: &Storage) -> Result<Uuid> {
+        self.resolve_with("idea", || {
+            let ideas = storage.load_ideas().context("Failed to load ideas")?;
+            Ok(ideas
+                .into_iter()
+                .map(|idea| (idea.id, idea.title))
+                .collect())
+        })
+    }
+
+    /// The project this names.
+    pub fn project(&self, storage: &Storage) -> Result<Uuid> {
+        self.resolve_with("project", || {
+            let projects = storage.load_projects().context("Failed to load projects")?;
+            Ok(projects
+                .into_iter()
+                .map(|project| (project.id, project.title))
+                .collect())
+        })
+    }
+
+    /// The task this names.
+    pub fn task(&self, storage: &Storage) -> Result<Uuid> {
+        self.resolve_with("task", || {
+            let tasks = storage.load_tasks().context("Failed to load tasks")?;
+            Ok(tasks
+                .into_iter()
+                .map(|task| (task.id, task.title))
+                .collect())
+        })
+    }
+
+    /// The one ID among `candidates` (IDs and titles) that this names. Whole
+    /// UUIDs are taken as they are, so a missing item is reported by whatever
+    /// looks it up. Otherwise exact titles come first, so a title like "cafe"
+    /// isn't taken for an ID prefix; then ID prefixes, then titles containing
+    /// this, then containing each of its words, then with its letters in
+    /// order. When several titles match equally well, the user picks one if
+    /// there's a terminal to ask on.
+    pub fn resolve(&self, kind: &str, candidates: &[(Uuid, String)]) -> Result<Uuid> {
+        if let Ok(id) = Uuid::parse_str(&self.0) {
+            return Ok(id);
+        }
+
+        let wanted = self.0.to_lowercase();
+        let exact = filter(candidates, |_, title| title.to_lowercase() == wanted);
+        match exact.as_slice() {
+            [] => {}
+            [(id, _)] => return Ok(*id),
+            _ => return self.choose(kind, &exact),
+        }
+
+        let by_id = filter(candidates, |id, _| self.matches(id));
+        match by_id.as_slice() {
+            [] => {}
+            [(id, _)] => return Ok(*id),
+            _ => {
+                let heading = format!(
+                    "ID prefix '{}' matches {} {}s; type more of the ID to pick one:",
+                    self.0,
+                    by_id.len(),
+                    kind
+                );
+                anyhow::bail!(listing(heading, &by_id));
+            }
+        }
+
+        let words: Vec<&str> = wanted.split_whitespace().collect();
+        let title_rules: [&dyn Fn(&str) -> bool; 3] = [
+            &|title| title.contains(&wanted),
+            &|title| words.iter().all(|word| title.contains(word)),
+            &|title| is_subsequence(&wanted, title),
+        ];
+        for rule in title_rules {
+            let by_title = filter(candidates, |_, title| rule(&title.to_lowercase()));
+            match by_title.as_slice() {
+                [] => continue,
+                [(id, _)] => return Ok(*id),
+                _ => return self.choose(kind, &by_title),
+            }
+        }
+        anyhow::bail!(
+            "No {} found with an ID or title matching '{}'",
+            kind,
+            self.0
+        )
+    }
+
+    /// Whether `id` starts with this.
+    pub fn matches(&self, id: &Uuid) -> bool {
+        let prefix = self.0.replace('-', "").to_lowercase();
+        !prefix.is_empty()
+            && prefix.chars().all(|c| c.is_ascii_hexdigit())
+            && id.simple().to_string().starts_with(&prefix)
+    }
+
+    fn resolve_with(
+        &self,
+        kind: &str,
+        candidates: impl FnOnce() -> Result<Vec<(Uuid, String)>>,
+    ) -> Result<Uuid> {
+        match Uuid::parse_str(&self.0) {
+            Ok(id) => Ok(id),
+            Err(_) => self.resolve(kind, &candidates()?),
+        }
+    }
+
+    /// Ask which of several items with matching titles was meant.
+    fn choose(&self, kind: &str, matches: &[&(Uuid, String)]) -> Result<Uuid> {
+        let heading = format!("'{}' matches {} {}s:", self.0, matches.len(), kind);
+        if !io::stdin().is_terminal() {
+            anyhow::bail!(
+                "{}\nUse more of the title, or the ID",
+                listing(heading, matches)
+            );
+        }
+
+        // The menu goes to stderr, so `$(...)` only captures the command's output
+        eprintln!("{}", heading);
+        for (number, (id, title)) in matches.iter().enumerate() {
+            eprintln!("   {}. {} [{}]", number + 1, title, id);
+        }
+        eprint!("Which one? [1-{}]: ", matches.len());
+        io::stderr().flush().context("Failed to flush output")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read input")?;
+        match input.trim().parse::<usize>() {
+            Ok(number) if (1..=matches.len()).contains(&number) => Ok(matches[number - 1].0),
+            _ => anyhow::bail!("No {} chosen", kind),
+        }
+    }
+}
+
+fn filter(
+    candidates: &[(Uuid, String)],
+    keep: impl Fn(&Uuid, &str) -> bool,
+) -> Vec<&(Uuid, String)> {
+    candidates
+        .iter()
+        .filter(|(id, title)| keep(id, title))
+        .collect()
+}
+
+fn listing(heading: String, matches: &[&(Uuid, String)]) -> String {
+    let mut message = heading;
+    for (id, title) in matches.iter().take(MAX_CANDIDATES) {
+        message.push_str(&format!("\n   {}  {}", id, title));
+    }
+    if matches.len() > MAX_CANDIDATES {
+        message.push_str(&format!(
+            "\n   ...and {} more",
+            matches.len() - MAX_CANDIDATES
+        ));
+    }
+    message
+}
+
+/// Whether the letters of `wanted` appear in `title` in order, so "rwprs"
+/// finds "Rewrite parser". Spaces in `wanted` are ignored.
+fn is_subsequence(wanted: &str, title: &str) -> bool {
+    let mut letters = title.chars();
+    wanted
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| letters.any(|letter| letter == c))
+}
+
+impl FromStr for IdArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let id = s.trim();
+        if id.is_empty() {
+            anyhow::bail!("Expected an ID or a title");
+        }
+        Ok(Self(id.to_string()))
+    }
+}
+
+impl From<Uuid> for IdArg {
+    fn from(id: Uuid) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl fmt::Display for IdArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,12 +1,45 @@
+use super::bulk::{self, Assignment, BulkArgs, BulkEditable};
+use super::id::IdArg;
+use super::input::{ids_or_stdin, text_or_stdin};
+use super::page::{Page, PageArgs};
+use super::pick::PickKind;
+use super::sort::{sort, SortArgs};
+use super::table::{short_id, Cell, Column, Table, TableArgs};
 use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate, Utc};
 use clap::{Args, Parser, Subcommand};
-use std::env;
+use serde::{Deserialize, Serialize};
+use std::fs;
 use std::io::{self, Write};
-use std::process::Command;
-use uuid::Uuid;
-
-use crate::models::idea::{Idea, IdeaStatus};
-use crate::storage::Storage;
+use std::path::{Path, PathBuf};
+
+use crate::dates;
+use crate::editor;
+use crate::export::{markdown, Export, ExportFilter, IDEA_FIELDS};
+use crate::front_matter;
+use crate::models::idea::{Attachment, AttachmentKind, Idea, IdeaStatus, Note, Rating};
+use crate::models::task::{priorities, Task, TaskPriority};
+use crate::output;
+use crate::say;
+use crate::storage::history::EntityKind;
+use crate::storage::{Storage, Unlinked};
+use crate::style::{self, Color};
+use crate::symbols;
+
+/// Columns `idea list` shows unless `--columns` says otherwise.
+const IDEA_COLUMNS: &[&str] = &["id", "title", "status", "tags", "updated"];
+
+/// Field order of notes printed as JSON or YAML.
+const NOTE_FIELDS: &[&str] = &["created_at", "body"];
+
+/// Field order of the matrix printed as JSON or YAML.
+const MATRIX_FIELDS: &[&str] = &[
+    "quick_wins",
+    "big_bets",
+    "fill_ins",
+    "money_pits",
+    "unrated",
+];
 
 #[derive(Parser)]
 #[command(name = "idea")]
@@ -24,137 +57,543 @@ pub enum IdeaSubcommand {
     List(ListIdeaArgs),
     /// Show full details of an idea
     Show(ShowIdeaArgs),
-    /// Add or replace tags on an idea
+    /// Show ideas picked at random, to resurface forgotten ones
+    Random(RandomIdeaArgs),
+    /// Sort rated ideas into quick wins, big bets, fill-ins, and money pits
+    Matrix(MatrixIdeaArgs),
+    /// Replace the tags on an idea, or add or remove some with `tag add` and `tag remove`
     Tag(TagIdeaArgs),
+    /// Attach a file or link to an idea
+    Attach(AttachIdeaArgs),
+    /// Write an idea, with its linked projects and tasks, as a Markdown document
+    Export(ExportIdeaArgs),
+    /// Add dated notes to an idea and read them back
+    #[command(subcommand)]
+    Note(NoteIdeaSubcommand),
     /// Update the status of an idea
     Status(StatusIdeaArgs),
-    /// Edit an idea in $EDITOR
+    /// Update the priority of an idea
+    Priority(PriorityIdeaArgs),
+    /// Edit an idea in your editor
     Edit(EditIdeaArgs),
-    /// Delete an idea with confirmation
+    /// Delete an idea, or every idea matching filters, with confirmation
     Delete(DeleteIdeaArgs),
-    /// Update idea fields (title, description, status)
+    /// Update idea fields (title, description, status, priority, tags)
     Update(IdeaUpdateArgs),
+    /// Change every idea a filter matches, e.g. --tag old --set status=archived
+    EditMany(EditManyIdeaArgs),
+    /// Mark two ideas as related, so each lists the other
+    Relate(RelateIdeaArgs),
+    /// Remove the relation between two ideas
+    Unrelate(RelateIdeaArgs),
+    /// Fold a duplicate idea into another and delete the duplicate
+    Merge(MergeIdeaArgs),
+    /// Copy an idea under a new ID, to try out a variation
+    Clone(CloneIdeaArgs),
+    /// Turn an idea into a task that links back to it
+    ToTask(IdeaToTaskArgs),
+    /// Archive every idea a filter matches, e.g. --status completed --before 2024-01-01
+    Archive(ArchiveIdeaArgs),
 }
 
 #[derive(Args)]
 pub struct NewIdeaArgs {
     /// The title of the idea
-    title: String,
+    #[arg(required_unless_present = "edit")]
+    title: Option<String>,
 
-    /// Optional description for the idea
+    /// Optional description for the idea (`-` reads it from standard input)
     #[arg(short = 'd', long = "description")]
     description: Option<String>,
 
+    /// Where the idea came from, such as "book: Thinking, Fast and Slow" or a URL
+    #[arg(long = "source")]
+    source: Option<String>,
+
     /// Optional tags (comma-separated)
     #[arg(short = 't', long = "tags", value_delimiter = ',')]
     tags: Vec<String>,
+
+    /// Optional priority (low|medium|high|urgent, or a custom priority)
+    #[arg(short = 'p', long = "priority")]
+    priority: Option<TaskPriority>,
+
+    /// How much work the idea would take, from 1 to 5
+    #[arg(long = "effort")]
+    effort: Option<Rating>,
+
+    /// How much difference the idea would make, from 1 to 5
+    #[arg(long = "impact")]
+    impact: Option<Rating>,
+
+    /// Write the idea in your editor, starting from the other options
+    #[arg(short = 'e', long)]
+    edit: bool,
+
+    /// Create the idea even if one with a similar title exists
+    #[arg(long)]
+    allow_duplicate: bool,
 }
 
 #[derive(Args)]
 pub struct ListIdeaArgs {
-    /// Filter by status (Brainstorming|Active|Completed|Archived)
+    #[command(flatten)]
+    filter: IdeaFilterArgs,
+
+    #[command(flatten)]
+    sort: SortArgs,
+
+    #[command(flatten)]
+    page: PageArgs,
+
+    #[command(flatten)]
+    table: TableArgs,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Which ideas `list`, `random`, `edit-many`, `archive`, and `delete` work
+/// on, and which `project link --from-filter` links.
+#[derive(Args, Default)]
+pub struct IdeaFilterArgs {
+    /// Filter by status (Inbox|Brainstorming|Active|Completed|Archived)
     #[arg(short = 's', long = "status")]
     status: Option<IdeaStatus>,
 
-    /// Filter by tag
+    /// Filter by tag; repeat to require every tag given
     #[arg(short = 't', long = "tag")]
-    tag: Option<String>,
+    tags: Vec<String>,
+
+    /// With several --tag, match ideas with any of them rather than all
+    #[arg(long = "any-tag")]
+    any_tag: bool,
+
+    /// Only ideas with no tags
+    #[arg(long, conflicts_with = "tags")]
+    untagged: bool,
+
+    /// Only ideas with no description
+    #[arg(long)]
+    no_description: bool,
+
+    /// Filter by priority (low|medium|high|urgent, or a custom priority)
+    #[arg(short = 'p', long = "priority")]
+    priority: Option<TaskPriority>,
+
+    /// Only ideas created after this day (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_day)]
+    created_after: Option<NaiveDate>,
+
+    /// Only ideas created before this day (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_day)]
+    created_before: Option<NaiveDate>,
+
+    /// Only ideas changed within this span, such as 12h, 7d, or 2w
+    #[arg(long, value_name = "SPAN", value_parser = dates::parse_span)]
+    updated_within: Option<Duration>,
+
+    /// Only ideas whose title or description contains this text, ignoring case
+    #[arg(long, value_name = "TEXT")]
+    contains: Option<String>,
+}
+
+#[derive(Args)]
+pub struct EditManyIdeaArgs {
+    #[command(flatten)]
+    filter: IdeaFilterArgs,
+
+    #[command(flatten)]
+    changes: BulkArgs,
+}
+
+#[derive(Args)]
+pub struct RelateIdeaArgs {
+    /// The ID or title of one idea
+    pub id: IdArg,
+
+    /// The ID or title of the other idea
+    pub other_id: IdArg,
+}
+
+#[derive(Args)]
+pub struct MergeIdeaArgs {
+    /// The ID or title of the idea to keep
+    pub keep_id: IdArg,
+
+    /// The ID or title of the duplicate to merge into it and delete
+    pub duplicate_id: IdArg,
+
+    /// Skip confirmation prompt
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct CloneIdeaArgs {
+    /// The ID or title of the idea to copy
+    pub id: IdArg,
+
+    /// Title of the copy, instead of the original's with " (copy)" added
+    #[arg(short = 't', long = "title")]
+    pub title: Option<String>,
+}
+
+#[derive(Args)]
+pub struct IdeaToTaskArgs {
+    /// The ID or title of the idea to turn into a task
+    pub id: IdArg,
+
+    /// Project (ID or title) to add the task to
+    #[arg(long = "project")]
+    pub project_id: Option<IdArg>,
+
+    /// Archive the idea once the task is created
+    #[arg(long)]
+    pub archive: bool,
+}
+
+#[derive(Args)]
+pub struct ArchiveIdeaArgs {
+    #[command(flatten)]
+    filter: IdeaFilterArgs,
+
+    /// Only ideas last changed before this day (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_day)]
+    before: Option<NaiveDate>,
+
+    /// Archive every idea, when no filter is given
+    #[arg(long)]
+    all: bool,
+
+    /// Show what would be archived without saving anything
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[derive(Args)]
 pub struct ShowIdeaArgs {
-    /// The UUID of the idea to show
-    id: Uuid,
+    /// The IDs or titles of the ideas to show, or - to read IDs from standard
+    /// input; pick one from a list if left out
+    ids: Vec<IdArg>,
+
+    /// Open one of the idea's attachments, by its number or name
+    #[arg(long, value_name = "ATTACHMENT")]
+    pub open: Option<String>,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct ExportIdeaArgs {
+    /// The ID or title of the idea to export
+    pub id: IdArg,
+
+    /// File to write the document to, instead of standard output
+    #[arg(short, long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct RandomIdeaArgs {
+    #[command(flatten)]
+    filter: IdeaFilterArgs,
+
+    /// How many ideas to pick
+    #[arg(short = 'n', long, default_value_t = 1)]
+    pub count: usize,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct MatrixIdeaArgs {
+    #[command(flatten)]
+    filter: IdeaFilterArgs,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Ideas sorted by effort and impact, most impact and least effort first
+/// within each quadrant. Ratings of 3 and more count as high.
+#[derive(Debug, Default, Serialize)]
+pub struct Matrix {
+    /// High impact, low effort
+    pub quick_wins: Vec<Idea>,
+    /// High impact, high effort
+    pub big_bets: Vec<Idea>,
+    /// Low impact, low effort
+    pub fill_ins: Vec<Idea>,
+    /// Low impact, high effort
+    pub money_pits: Vec<Idea>,
+    /// Ideas missing a rating for effort or impact
+    pub unrated: Vec<Idea>,
+}
+
+#[derive(Args)]
+pub struct AttachIdeaArgs {
+    /// The ID or title of the idea
+    pub id: IdArg,
+
+    /// A file to copy into the vault, or a URL to link
+    pub target: String,
 }
 
 #[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct TagIdeaArgs {
-    /// The UUID of the idea to tag
-    id: Uuid,
+    /// The ID or title of the idea to tag
+    #[arg(required = true)]
+    id: Option<IdArg>,
+
+    /// Tags to replace the idea's tags with (space-separated)
+    tags: Vec<String>,
+
+    #[command(subcommand)]
+    command: Option<TagIdeaSubcommand>,
+}
+
+#[derive(Subcommand)]
+pub enum TagIdeaSubcommand {
+    /// Add tags to an idea, keeping the ones it has
+    Add(ChangeTagsArgs),
+    /// Remove tags from an idea, keeping the rest
+    Remove(ChangeTagsArgs),
+}
+
+#[derive(Args)]
+pub struct ChangeTagsArgs {
+    /// The ID or title of the idea
+    id: IdArg,
 
-    /// Tags to add/replace (space-separated)
+    /// Tags to add or remove (space-separated)
+    #[arg(required = true)]
     tags: Vec<String>,
 }
 
+#[derive(Subcommand)]
+pub enum NoteIdeaSubcommand {
+    /// Add a note to an idea, dated now
+    Add(AddNoteArgs),
+    /// List an idea's notes, oldest first
+    List(ListNotesArgs),
+}
+
+#[derive(Args)]
+pub struct AddNoteArgs {
+    /// The ID or title of the idea
+    pub id: IdArg,
+
+    /// The text of the note (`-` reads it from standard input)
+    pub text: String,
+}
+
+#[derive(Args)]
+pub struct ListNotesArgs {
+    /// The ID or title of the idea
+    pub id: IdArg,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
 #[derive(Args)]
 pub struct StatusIdeaArgs {
-    /// The UUID of the idea to update
-    id: Uuid,
+    /// The ID or title of the idea to update
+    id: IdArg,
 
     /// New status for the idea
     status: IdeaStatus,
 }
 
+#[derive(Args)]
+pub struct PriorityIdeaArgs {
+    /// The ID or title of the idea to update
+    id: IdArg,
+
+    /// New priority for the idea
+    priority: TaskPriority,
+}
+
 #[derive(Args)]
 pub struct EditIdeaArgs {
-    /// The UUID of the idea to edit
-    id: Uuid,
+    /// The ID or title of the idea to edit; pick one from a list if left out
+    id: Option<IdArg>,
 }
 
 #[derive(Args)]
 pub struct DeleteIdeaArgs {
-    /// The UUID of the idea to delete
-    id: Uuid,
+    /// The ID or title of the idea to delete; leave out to delete every idea
+    /// matching the filters
+    pub id: Option<IdArg>,
+
+    #[command(flatten)]
+    pub matching: DeleteMatchingArgs,
 
     /// Skip confirmation prompt
     #[arg(short, long)]
-    force: bool,
+    pub force: bool,
+
+    /// Leave projects and tasks linking to this idea pointing at it
+    #[arg(long)]
+    pub keep_links: bool,
+}
+
+/// Which ideas `delete` removes when it isn't given an ID.
+#[derive(Args, Default)]
+pub struct DeleteMatchingArgs {
+    #[command(flatten)]
+    pub filter: IdeaFilterArgs,
+
+    /// Only ideas last changed before this day (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_day)]
+    pub before: Option<NaiveDate>,
+
+    /// Show what would be deleted without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Args)]
 pub struct IdeaUpdateArgs {
-    /// Idea ID to update
-    pub id: Uuid,
+    /// Idea ID or title to update
+    pub id: IdArg,
 
     /// New title
     #[arg(short = 't', long = "title")]
     pub title: Option<String>,
 
-    /// New description
+    /// New description (`-` reads it from standard input)
     #[arg(short = 'd', long = "description")]
     pub description: Option<String>,
 
+    /// Where the idea came from
+    #[arg(long = "source")]
+    pub source: Option<String>,
+
     /// New status
     #[arg(short = 's', long = "status")]
     pub status: Option<IdeaStatus>,
 
-    /// Clear one or more optional fields (description)
+    /// New priority (low|medium|high|urgent, or a custom priority)
+    #[arg(short = 'p', long = "priority")]
+    pub priority: Option<TaskPriority>,
+
+    /// How much work the idea would take, from 1 to 5
+    #[arg(long = "effort")]
+    pub effort: Option<Rating>,
+
+    /// How much difference the idea would make, from 1 to 5
+    #[arg(long = "impact")]
+    pub impact: Option<Rating>,
+
+    /// New tags (comma-separated, replaces existing tags)
+    #[arg(short = 'g', long = "tags", value_delimiter = ',')]
+    pub tags: Option<Vec<String>>,
+
+    /// Tags to add (comma-separated)
+    #[arg(long = "add-tags", value_delimiter = ',')]
+    pub add_tags: Vec<String>,
+
+    /// Tags to remove (comma-separated)
+    #[arg(long = "remove-tags", value_delimiter = ',')]
+    pub remove_tags: Vec<String>,
+
+    /// Clear one or more optional fields (description, source, effort, impact, tags)
     #[arg(long = "clear", value_name = "FIELD")]
     pub clear: Vec<String>,
 }
 
 impl IdeaCommands {
     pub fn execute(&self) -> Result<()> {
+        // IDs piped to `show` are read before the vault is locked, as the
+        // command piping them in holds the lock until it exits
+        let shown = match &self.command {
+            IdeaSubcommand::Show(args) => ids_or_stdin(&args.ids)?,
+            _ => Vec::new(),
+        };
         let storage = Storage::new().context("Failed to initialize storage")?;
 
         match &self.command {
             IdeaSubcommand::New(args) => Self::new_idea(&storage, args),
             IdeaSubcommand::List(args) => Self::list_ideas(&storage, args),
-            IdeaSubcommand::Show(args) => Self::show_idea(&storage, args),
+            IdeaSubcommand::Random(args) => Self::random_ideas(&storage, args),
+            IdeaSubcommand::Matrix(args) => Self::matrix_ideas(&storage, args),
+            IdeaSubcommand::Show(args) => match &args.open {
+                Some(attachment) => Self::open_attachment(&storage, &shown, attachment),
+                None => Self::show_idea(&storage, &shown),
+            },
             IdeaSubcommand::Tag(args) => Self::tag_idea(&storage, args),
+            IdeaSubcommand::Attach(args) => Self::attach(&storage, args),
+            IdeaSubcommand::Export(args) => Self::export_idea(&storage, args),
+            IdeaSubcommand::Note(NoteIdeaSubcommand::Add(args)) => Self::add_note(&storage, args),
+            IdeaSubcommand::Note(NoteIdeaSubcommand::List(args)) => {
+                Self::list_notes(&storage, args)
+            }
             IdeaSubcommand::Status(args) => Self::update_status(&storage, args),
+            IdeaSubcommand::Priority(args) => Self::update_priority(&storage, args),
             IdeaSubcommand::Edit(args) => Self::edit_idea(&storage, args),
             IdeaSubcommand::Delete(args) => Self::delete_idea(&storage, args),
             IdeaSubcommand::Update(args) => Self::update_idea(&storage, args),
+            IdeaSubcommand::EditMany(args) => Self::edit_many(&storage, args),
+            IdeaSubcommand::Relate(args) => Self::relate_ideas(&storage, args),
+            IdeaSubcommand::Unrelate(args) => Self::unrelate_ideas(&storage, args),
+            IdeaSubcommand::Merge(args) => Self::merge_ideas(&storage, args),
+            IdeaSubcommand::Clone(args) => Self::clone_idea(&storage, args),
+            IdeaSubcommand::ToTask(args) => Self::idea_to_task(&storage, args),
+            IdeaSubcommand::Archive(args) => Self::archive_ideas(&storage, args),
         }
     }
 
     fn new_idea(storage: &Storage, args: &NewIdeaArgs) -> Result<()> {
-        let mut idea = Idea::new(args.title.clone());
+        let mut idea = Idea::new(args.title.clone().unwrap_or_default());
 
         if let Some(description) = &args.description {
-            idea = idea.with_description(description.clone());
+            idea = idea.with_description(text_or_stdin(description)?);
+        }
+
+        if let Some(source) = &args.source {
+            idea = idea.with_source(source.clone());
         }
 
         if !args.tags.is_empty() {
             idea = idea.with_tags(args.tags.clone());
         }
 
-        let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
-        ideas.push(idea.clone());
-        storage.save_ideas(&ideas).context("Failed to save ideas")?;
+        if let Some(priority) = &args.priority {
+            idea = idea.with_priority(priority.clone());
+        }
+
+        if args.effort.is_some() || args.impact.is_some() {
+            idea.effort = args.effort;
+            idea.impact = args.impact;
+        }
+
+        if args.edit {
+            let document = idea_document(&idea, "New idea")?;
+            idea = editor::edit_until_valid("new-idea", &document, |content| {
+                apply_document(idea.clone(), content)
+            })?;
+        }
+
+        if !args.allow_duplicate {
+            refuse_duplicate(storage, &idea.title)?;
+        }
+        storage.add_idea(&idea).context("Failed to save idea")?;
 
-        println!("✅ Created new idea:");
+        if output::is_quiet() {
+            println!("{}", idea.id);
+            return Ok(());
+        }
+        println!("{} Created new idea:", symbols::OK);
         print_idea_summary(&idea);
         Ok(())
     }
@@ -162,181 +601,664 @@ impl IdeaCommands {
     fn list_ideas(storage: &Storage, args: &ListIdeaArgs) -> Result<()> {
         let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
 
-        // Apply filters
-        if let Some(status_filter) = &args.status {
-            ideas.retain(|idea| &idea.status == status_filter);
-        }
+        ideas.retain(|idea| args.filter.matches(idea));
 
-        if let Some(tag_filter) = &args.tag {
-            ideas.retain(|idea| idea.tags.contains(tag_filter));
+        sort(&mut ideas, &args.sort)?;
+        let page = Page::from_config(ideas.len(), &args.page)?;
+        if !output::is_table() {
+            return output::print_list(&ideas[page.start..page.end], IDEA_FIELDS);
+        }
+        if output::is_quiet() {
+            output::print_ids(ideas[page.start..page.end].iter().map(|idea| idea.id));
+            return Ok(());
         }
 
         if ideas.is_empty() {
-            println!("📝 No ideas found");
+            println!("{} No ideas found", symbols::NOTES);
             return Ok(());
         }
 
-        println!("📝 Found {} idea(s):", ideas.len());
+        let table = Table::new(idea_columns(), IDEA_COLUMNS, &args.table)?;
+        println!("{} Found {} idea(s):", symbols::NOTES, ideas.len());
         println!();
-
-        for idea in &ideas {
-            print_idea_summary(idea);
+        print!("{}", table.render(&ideas[page.start..page.end]));
+        if let Some(footer) = page.footer("idea") {
             println!();
+            println!("{}", footer);
         }
 
         Ok(())
     }
 
-    fn show_idea(storage: &Storage, args: &ShowIdeaArgs) -> Result<()> {
-        let ideas = storage.load_ideas().context("Failed to load ideas")?;
+    fn show_idea(storage: &Storage, ids: &[IdArg]) -> Result<()> {
+        let ideas = PickKind::Idea
+            .resolve_all(storage, ids)?
+            .into_iter()
+            .map(|id| storage.get_idea(id))
+            .collect::<Result<Vec<_>>>()?;
+        if !output::is_table() {
+            return match ideas.as_slice() {
+                [idea] => output::print_item(idea, IDEA_FIELDS),
+                _ => output::print_list(&ideas, IDEA_FIELDS),
+            };
+        }
+
+        let all_ideas = storage.load_ideas().context("Failed to load ideas")?;
+        for (number, idea) in ideas.iter().enumerate() {
+            if number > 0 {
+                println!();
+            }
+            print_idea_full(idea, &all_ideas);
+        }
+        Ok(())
+    }
+
+    fn random_ideas(storage: &Storage, args: &RandomIdeaArgs) -> Result<()> {
+        let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+        ideas.retain(|idea| args.filter.matches(idea));
+        fastrand::shuffle(&mut ideas);
+        ideas.truncate(args.count);
 
-        let idea = ideas
-            .iter()
-            .find(|idea| idea.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", args.id))?;
+        if output::is_quiet() {
+            output::print_ids(ideas.iter().map(|idea| idea.id));
+            return Ok(());
+        }
+        if !output::is_table() {
+            return output::print_list(&ideas, IDEA_FIELDS);
+        }
+        if ideas.is_empty() {
+            println!("{} No ideas match", symbols::IDEA);
+            return Ok(());
+        }
 
-        print_idea_full(idea);
+        let all_ideas = storage.load_ideas().context("Failed to load ideas")?;
+        for (number, idea) in ideas.iter().enumerate() {
+            if number > 0 {
+                println!();
+            }
+            print_idea_full(idea, &all_ideas);
+        }
         Ok(())
     }
 
-    fn tag_idea(storage: &Storage, args: &TagIdeaArgs) -> Result<()> {
+    fn matrix_ideas(storage: &Storage, args: &MatrixIdeaArgs) -> Result<()> {
         let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+        ideas.retain(|idea| {
+            args.filter.matches(idea)
+                && (args.filter.status.is_some()
+                    || !matches!(idea.status, IdeaStatus::Completed | IdeaStatus::Archived))
+        });
+        let matrix = Matrix::of(ideas);
+        if !output::is_table() {
+            return output::print_item(&matrix, MATRIX_FIELDS);
+        }
+
+        println!("{} Effort/impact matrix", symbols::COUNTS);
+        for (heading, ideas) in [
+            ("Quick wins: high impact, low effort", &matrix.quick_wins),
+            ("Big bets: high impact, high effort", &matrix.big_bets),
+            ("Fill-ins: low impact, low effort", &matrix.fill_ins),
+            ("Money pits: low impact, high effort", &matrix.money_pits),
+        ] {
+            println!();
+            println!("{} ({})", style::bold(heading), ideas.len());
+            for idea in ideas {
+                println!(
+                    "   {} [{}] {}",
+                    idea.title,
+                    style::dim(short_id(&idea.id)),
+                    style::dim(format!(
+                        "impact {} · effort {}",
+                        rating(idea.impact),
+                        rating(idea.effort)
+                    ))
+                );
+            }
+        }
+        if !matrix.unrated.is_empty() {
+            println!();
+            println!(
+                "{} idea(s) left out for lack of ratings. Rate them with: ideavault idea update <id> --effort 2 --impact 4",
+                matrix.unrated.len()
+            );
+        }
+        Ok(())
+    }
 
-        let idea_index = ideas
-            .iter()
-            .position(|idea| idea.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", args.id))?;
+    fn open_attachment(storage: &Storage, ids: &[IdArg], which: &str) -> Result<()> {
+        let [id] = PickKind::Idea.resolve_all(storage, ids)?[..] else {
+            anyhow::bail!("--open takes one idea");
+        };
+        let idea = storage.get_idea(id)?;
+        let attachment = match which.parse::<usize>() {
+            Ok(number) => number
+                .checked_sub(1)
+                .and_then(|index| idea.attachments.get(index)),
+            Err(_) => idea.attachments.iter().find(|a| a.name == which),
+        }
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Idea '{}' has no attachment '{}'. It has {} attachment(s)",
+                idea.title,
+                which,
+                idea.attachments.len()
+            )
+        })?;
+
+        let target = match attachment.kind {
+            AttachmentKind::File => storage
+                .attachments_dir()
+                .join(&attachment.location)
+                .into_os_string(),
+            AttachmentKind::Url => attachment.location.clone().into(),
+        };
+        open::that_detached(&target)
+            .with_context(|| format!("Failed to open {}", attachment.name))?;
+        say!("{} Opened {}", symbols::OPENED, attachment.name);
+        Ok(())
+    }
 
-        // Replace all tags with the new ones
-        ideas[idea_index].tags.clear();
-        for tag in &args.tags {
-            if !ideas[idea_index].tags.contains(tag) {
-                ideas[idea_index].tags.push(tag.clone());
+    /// Link a URL to an idea, or copy a file into the vault and attach it.
+    pub fn attach(storage: &Storage, args: &AttachIdeaArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let attachment = if is_url(&args.target) {
+            Attachment::url(args.target.clone())
+        } else {
+            let source = Path::new(&args.target);
+            if !source.is_file() {
+                anyhow::bail!(
+                    "No file at {}. Attach a file or a URL such as https://example.com",
+                    args.target
+                );
             }
+            let location = storage.store_attachment(id, source)?;
+            let name = source
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Attachment::file(name, location.to_string_lossy().into_owned())
+        };
+        let number = storage.update_idea(id, |idea| {
+            idea.add_attachment(attachment.clone());
+            idea.attachments.len()
+        })?;
+
+        say!(
+            "{} Attached {} to idea {}",
+            symbols::ATTACHMENT,
+            attachment.name,
+            id
+        );
+        say!(
+            "   Open it with: ideavault idea show {} --open {}",
+            id,
+            number
+        );
+        Ok(())
+    }
+
+    /// Write one idea as a Markdown document, to standard output or a file.
+    pub fn export_idea(storage: &Storage, args: &ExportIdeaArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let idea = storage.get_idea(id)?;
+        let export = Export::new(
+            storage.load_ideas().context("Failed to load ideas")?,
+            storage.load_projects().context("Failed to load projects")?,
+            storage.load_tasks().context("Failed to load tasks")?,
+            &ExportFilter::default(),
+        );
+        let document = markdown::idea_document(&export, &idea);
+
+        let Some(path) = &args.out else {
+            print!("{}", document);
+            return Ok(());
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
         }
-        ideas[idea_index].updated_at = chrono::Utc::now();
+        fs::write(path, document)
+            .with_context(|| format!("Failed to write idea document: {:?}", path))?;
+        say!("{} Exported idea to {}", symbols::PAGE, path.display());
+        Ok(())
+    }
 
-        storage.save_ideas(&ideas).context("Failed to save ideas")?;
+    fn tag_idea(storage: &Storage, args: &TagIdeaArgs) -> Result<()> {
+        match &args.command {
+            Some(TagIdeaSubcommand::Add(args)) => return Self::add_tags(storage, args),
+            Some(TagIdeaSubcommand::Remove(args)) => return Self::remove_tags(storage, args),
+            None => {}
+        }
+        let Some(id) = &args.id else {
+            anyhow::bail!("Give the ID or title of the idea to tag");
+        };
+        let id = id.idea(storage)?;
+        // Replace all tags with the new ones
+        let tags = storage.update_idea(id, |idea| {
+            idea.tags.clear();
+            for tag in &args.tags {
+                if !idea.tags.contains(tag) {
+                    idea.tags.push(tag.clone());
+                }
+            }
+            idea.tags.clone()
+        })?;
 
-        println!("✅ Updated tags for idea {}:", args.id);
-        println!("   Tags: {}", ideas[idea_index].tags.join(", "));
+        say!("{} Updated tags for idea {}:", symbols::OK, id);
+        say!("   Tags: {}", tags.join(", "));
         Ok(())
     }
 
-    fn update_status(storage: &Storage, args: &StatusIdeaArgs) -> Result<()> {
-        let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+    fn add_tags(storage: &Storage, args: &ChangeTagsArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let tags = storage.update_idea(id, |idea| {
+            for tag in &args.tags {
+                idea.add_tag(tag.clone());
+            }
+            idea.tags.clone()
+        })?;
 
-        let idea = ideas
-            .iter_mut()
-            .find(|idea| idea.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", args.id))?;
+        say!("{} Updated tags for idea {}:", symbols::OK, id);
+        say!("   Tags: {}", tags.join(", "));
+        Ok(())
+    }
 
-        let old_status = idea.status.clone();
-        idea.set_status(args.status.clone());
+    fn remove_tags(storage: &Storage, args: &ChangeTagsArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let (tags, missing) = storage.update_idea(id, |idea| {
+            let missing: Vec<&str> = args
+                .tags
+                .iter()
+                .filter(|tag| !idea.tags.contains(tag))
+                .map(String::as_str)
+                .collect();
+            for tag in &args.tags {
+                idea.remove_tag(tag);
+            }
+            (idea.tags.clone(), missing)
+        })?;
+
+        say!("{} Updated tags for idea {}:", symbols::OK, id);
+        say!("   Tags: {}", tags.join(", "));
+        if !missing.is_empty() {
+            say!(
+                "   {} It wasn't tagged with: {}",
+                symbols::WARNING,
+                missing.join(", ")
+            );
+        }
+        Ok(())
+    }
 
-        storage.save_ideas(&ideas).context("Failed to save ideas")?;
+    pub fn add_note(storage: &Storage, args: &AddNoteArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let body = text_or_stdin(&args.text)?.trim().to_string();
+        if body.is_empty() {
+            anyhow::bail!("The note is empty");
+        }
+        let count = storage.update_idea(id, |idea| {
+            idea.add_note(Note::new(body));
+            idea.notes.len()
+        })?;
 
-        println!("✅ Updated status for idea {}:", args.id);
-        println!("   {} → {}", old_status, args.status);
+        say!("{} Added note {} to idea {}", symbols::OK, count, id);
         Ok(())
     }
 
-    fn edit_idea(storage: &Storage, args: &EditIdeaArgs) -> Result<()> {
-        let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+    fn list_notes(storage: &Storage, args: &ListNotesArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let idea = storage.get_idea(id)?;
+        if !output::is_table() {
+            return output::print_list(&idea.notes, NOTE_FIELDS);
+        }
 
-        let idea_index = ideas
-            .iter()
-            .position(|idea| idea.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", args.id))?;
-
-        // Create temporary file with current content
-        let temp_file = format!("{}.md", args.id);
-        let content = format!(
-            "# {}\n\n{}\n\nTags: {}\n\nStatus: {}\n\n",
-            ideas[idea_index].title,
-            ideas[idea_index].description.as_deref().unwrap_or(""),
-            ideas[idea_index].tags.join(", "),
-            ideas[idea_index].status
+        if idea.notes.is_empty() {
+            println!("{} No notes on '{}'", symbols::NOTES, idea.title);
+            return Ok(());
+        }
+        println!(
+            "{} {} note(s) on '{}':",
+            symbols::NOTES,
+            idea.notes.len(),
+            idea.title
         );
+        print_notes(&idea.notes);
+        Ok(())
+    }
+
+    fn update_status(storage: &Storage, args: &StatusIdeaArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let old_status = storage.update_idea(id, |idea| {
+            let old_status = idea.status.clone();
+            idea.set_status(args.status.clone());
+            old_status
+        })?;
+
+        say!("{} Updated status for idea {}:", symbols::OK, id);
+        say!("   {} → {}", old_status, args.status);
+        Ok(())
+    }
+
+    fn update_priority(storage: &Storage, args: &PriorityIdeaArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let old_priority = storage.update_idea(id, |idea| {
+            let old_priority = idea.priority.clone();
+            idea.set_priority(args.priority.clone());
+            old_priority
+        })?;
 
-        std::fs::write(&temp_file, content).context("Failed to create temp file")?;
+        say!("{} Updated priority for idea {}:", symbols::OK, id);
+        say!("   {} → {}", old_priority, args.priority);
+        Ok(())
+    }
 
-        // Open editor
-        let editor = env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
-        let status = Command::new(&editor)
-            .arg(&temp_file)
-            .status()
-            .context("Failed to open editor")?;
+    fn edit_idea(storage: &Storage, args: &EditIdeaArgs) -> Result<()> {
+        let id = PickKind::Idea.resolve(storage, args.id.as_ref())?;
+        let idea = storage.get_idea(id)?;
+
+        let document = idea_document(&idea, &format!("Idea {}", idea.id))?;
+        let edited = editor::edit_until_valid(&id.to_string(), &document, |content| {
+            apply_document(idea.clone(), content)
+        })?;
+        let idea = storage.update_idea(id, |idea| {
+            *idea = edited;
+            idea.clone()
+        })?;
+
+        say!("{} Updated idea {}:", symbols::OK, id);
+        print_idea_summary(&idea);
+        Ok(())
+    }
 
-        if !status.success() {
-            std::fs::remove_file(&temp_file)?;
-            return Err(anyhow::anyhow!("Editor exited with non-zero status"));
+    fn edit_many(storage: &Storage, args: &EditManyIdeaArgs) -> Result<()> {
+        args.changes.check(args.filter.is_set())?;
+        let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let edit = bulk::apply(
+            storage,
+            &mut ideas,
+            |idea| args.filter.matches(idea),
+            &args.changes,
+        )?;
+
+        if !args.changes.dry_run && !edit.changes.is_empty() {
+            storage.save_ideas(&ideas).context("Failed to save ideas")?;
         }
+        bulk::report(&ideas, &edit, args.changes.dry_run);
+        Ok(())
+    }
 
-        // Read updated content
-        let updated_content =
-            std::fs::read_to_string(&temp_file).context("Failed to read updated content")?;
-        std::fs::remove_file(&temp_file)?;
+    pub fn relate_ideas(storage: &Storage, args: &RelateIdeaArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let other_id = args.other_id.idea(storage)?;
+        if id == other_id {
+            anyhow::bail!("An idea can't be related to itself");
+        }
 
-        // Parse updated content (simple parsing for demo)
-        let lines: Vec<&str> = updated_content.lines().collect();
-        if let Some(title_line) = lines.iter().find(|line| line.starts_with("# ")) {
-            ideas[idea_index].title = title_line.strip_prefix("# ").unwrap().trim().to_string();
+        let related = storage.update_idea(id, |idea| {
+            if idea.related_ids.contains(&other_id) {
+                return false;
+            }
+            idea.add_related(other_id);
+            true
+        })?;
+        storage.update_idea(other_id, |idea| idea.add_related(id))?;
+
+        if !related {
+            say!(
+                "{} Ideas {} and {} are already related",
+                symbols::WARNING,
+                id,
+                other_id
+            );
+            return Ok(());
         }
+        say!("{} Related ideas {} and {}", symbols::OK, id, other_id);
+        Ok(())
+    }
 
-        // Find description (content between title and tags/status)
-        let mut description_parts = Vec::new();
-        let mut in_description = false;
+    pub fn unrelate_ideas(storage: &Storage, args: &RelateIdeaArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let other_id = args.other_id.idea(storage)?;
 
-        for line in &lines {
-            if line.starts_with("# ") {
-                in_description = true;
-                continue;
-            } else if line.starts_with("Tags:") || line.starts_with("Status:") {
-                in_description = false;
-                continue;
-            } else if in_description && !line.trim().is_empty() {
-                description_parts.push(line.trim());
+        let unrelated = storage.update_idea(id, |idea| {
+            if !idea.related_ids.contains(&other_id) {
+                return false;
             }
+            idea.remove_related(&other_id);
+            true
+        })?;
+        storage.update_idea(other_id, |idea| idea.remove_related(&id))?;
+
+        if !unrelated {
+            say!(
+                "{} Ideas {} and {} are not related",
+                symbols::WARNING,
+                id,
+                other_id
+            );
+            return Ok(());
         }
+        say!("{} Unrelated ideas {} and {}", symbols::OK, id, other_id);
+        Ok(())
+    }
+
+    /// Fold the duplicate's description, tags, notes, attachments, and
+    /// relations into the idea kept, point its links at the idea kept, and
+    /// move it to the trash.
+    pub fn merge_ideas(storage: &Storage, args: &MergeIdeaArgs) -> Result<()> {
+        let keep_id = args.keep_id.idea(storage)?;
+        let duplicate_id = args.duplicate_id.idea(storage)?;
+        if keep_id == duplicate_id {
+            anyhow::bail!("Can't merge an idea into itself");
+        }
+        let duplicate = storage.get_idea(duplicate_id)?;
+
+        if !args.force {
+            let keep = storage.get_idea(keep_id)?;
+            print!(
+                "Merge '{}' into '{}' and delete '{}'? [y/N]: ",
+                duplicate.title, keep.title, duplicate.title
+            );
+            io::stdout().flush().context("Failed to flush output")?;
+
+            let mut input = String::new();
+            io::stdin()
+                .read_line(&mut input)
+                .context("Failed to read input")?;
 
-        if !description_parts.is_empty() {
-            ideas[idea_index].description = Some(description_parts.join("\n"));
+            let response = input.trim().to_lowercase();
+            if !matches!(response.as_str(), "y" | "yes") {
+                say!("{} Merge cancelled", symbols::ERROR);
+                return Ok(());
+            }
         }
 
-        // Parse tags and status from the end of file
-        for line in lines.iter().rev() {
-            if line.starts_with("Tags:") {
-                let tags_str = line.strip_prefix("Tags:").unwrap().trim();
-                ideas[idea_index].tags.clear();
-                if !tags_str.is_empty() {
-                    ideas[idea_index].tags =
-                        tags_str.split(',').map(|t| t.trim().to_string()).collect();
+        let kept = storage.update_idea(keep_id, |idea| {
+            let description = match (idea.description.clone(), &duplicate.description) {
+                (Some(own), Some(other)) if own.trim() != other.trim() => {
+                    Some(format!("{}\n\n{}", own, other))
                 }
-            } else if line.starts_with("Status:") {
-                let status_str = line.strip_prefix("Status:").unwrap().trim();
-                if let Ok(parsed_status) = status_str.parse() {
-                    ideas[idea_index].set_status(parsed_status);
+                (own, other) => own.or_else(|| other.clone()),
+            };
+            idea.update_description(description);
+            if idea.source.is_none() {
+                idea.source = duplicate.source.clone();
+            }
+            for tag in &duplicate.tags {
+                idea.add_tag(tag.clone());
+            }
+            for related_id in &duplicate.related_ids {
+                if *related_id != keep_id {
+                    idea.add_related(*related_id);
                 }
             }
+            idea.notes.extend(duplicate.notes.iter().cloned());
+            idea.notes.sort_by_key(|note| note.created_at);
+            idea.attachments
+                .extend(duplicate.attachments.iter().cloned());
+            idea.clone()
+        })?;
+        let relinked = storage
+            .relink_idea(duplicate_id, keep_id)
+            .context("Failed to move links to the merged idea")?;
+        storage.trash_idea(duplicate_id)?;
+
+        say!(
+            "{} Merged '{}' into '{}'",
+            symbols::OK,
+            duplicate.title,
+            kept.title
+        );
+        if !kept.tags.is_empty() {
+            say!("   {} {}", symbols::TAGS, style::tags(&kept.tags));
         }
+        if !relinked.projects.is_empty() || !relinked.tasks.is_empty() {
+            say!(
+                "   Moved links from {} project(s) and {} task(s)",
+                relinked.projects.len(),
+                relinked.tasks.len()
+            );
+        }
+        say!(
+            "   Restore the duplicate with: ideavault trash restore {}",
+            duplicate_id
+        );
+        Ok(())
+    }
+
+    /// Save a copy of an idea with a new ID and timestamps, and no status
+    /// history.
+    pub fn clone_idea(storage: &Storage, args: &CloneIdeaArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let original = storage.get_idea(id)?;
+
+        let fresh = Idea::new(
+            args.title
+                .clone()
+                .unwrap_or_else(|| format!("{} (copy)", original.title)),
+        );
+        let idea = Idea {
+            id: fresh.id,
+            title: fresh.title,
+            related_ids: Vec::new(),
+            status_history: Vec::new(),
+            created_at: fresh.created_at,
+            updated_at: fresh.updated_at,
+            ..original
+        };
+        storage.add_idea(&idea).context("Failed to save idea")?;
+
+        if output::is_quiet() {
+            println!("{}", idea.id);
+            return Ok(());
+        }
+        println!("{} Cloned idea {}:", symbols::OK, id);
+        print_idea_summary(&idea);
+        Ok(())
+    }
 
-        storage.save_ideas(&ideas).context("Failed to save ideas")?;
+    /// Create a task from an idea's title, description, tags, and priority,
+    /// linked to the idea.
+    pub fn idea_to_task(storage: &Storage, args: &IdeaToTaskArgs) -> Result<()> {
+        let id = args.id.idea(storage)?;
+        let idea = storage.get_idea(id)?;
+
+        let mut task = Task::new(idea.title.clone())
+            .with_tags(idea.tags.clone())
+            .with_priority(idea.priority.clone())
+            .with_idea(idea.id);
+        if let Some(description) = &idea.description {
+            task = task.with_description(description.clone());
+        }
+        if let Some(project_id) = &args.project_id {
+            task = task.with_project(project_id.project(storage)?);
+        }
+        storage.add_task(&task).context("Failed to save task")?;
+        if args.archive {
+            storage.update_idea(id, |idea| idea.set_status(IdeaStatus::Archived))?;
+        }
 
-        println!("✅ Updated idea {}:", args.id);
-        print_idea_summary(&ideas[idea_index]);
+        if output::is_quiet() {
+            println!("{}", task.id);
+            return Ok(());
+        }
+        println!(
+            "{} Turned idea '{}' into task {}",
+            symbols::OK,
+            idea.title,
+            task.id
+        );
+        if args.archive {
+            println!(
+                "   {} Archived the idea",
+                symbols::idea_status(&IdeaStatus::Archived)
+            );
+        }
         Ok(())
     }
 
-    fn delete_idea(storage: &Storage, args: &DeleteIdeaArgs) -> Result<()> {
+    fn archive_ideas(storage: &Storage, args: &ArchiveIdeaArgs) -> Result<()> {
+        if !args.filter.is_set() && args.before.is_none() && !args.all {
+            anyhow::bail!("No filter given. Add a filter, or --all to archive every idea");
+        }
         let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let mut archived = Vec::new();
+        for idea in ideas.iter_mut() {
+            let changed_before = args
+                .before
+                .is_none_or(|day| idea.updated_at.date_naive() < day);
+            if idea.status != IdeaStatus::Archived && changed_before && args.filter.matches(idea) {
+                idea.set_status(IdeaStatus::Archived);
+                archived.push(idea.clone());
+            }
+        }
+
+        if !args.dry_run && !archived.is_empty() {
+            storage.save_ideas(&ideas).context("Failed to save ideas")?;
+        }
+        if output::is_quiet() && !args.dry_run {
+            output::print_ids(archived.iter().map(|idea| idea.id));
+            return Ok(());
+        }
 
-        let idea_index = ideas
-            .iter()
-            .position(|idea| idea.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", args.id))?;
+        let verb = if args.dry_run {
+            "Would archive"
+        } else {
+            "Archived"
+        };
+        println!(
+            "{} {} {} idea(s)",
+            symbols::idea_status(&IdeaStatus::Archived),
+            verb,
+            archived.len()
+        );
+        for idea in &archived {
+            println!("   {} [{}]", idea.title, style::dim(short_id(&idea.id)));
+        }
+        if args.dry_run {
+            println!();
+            println!("Nothing was saved. Run it again without --dry-run to archive them.");
+        } else if !archived.is_empty() {
+            say!();
+            say!("   Revert it with: ideavault undo");
+        }
+        Ok(())
+    }
 
-        let idea = &ideas[idea_index];
+    pub fn delete_idea(storage: &Storage, args: &DeleteIdeaArgs) -> Result<()> {
+        let matching = &args.matching;
+        let filtered = matching.filter.is_set() || matching.before.is_some();
+        let id = match &args.id {
+            Some(_) if filtered || matching.dry_run => {
+                anyhow::bail!("Give either an idea to delete or filters, not both")
+            }
+            Some(id) => id.idea(storage)?,
+            None if filtered => return Self::delete_matching(storage, args),
+            None => anyhow::bail!(
+                "No idea given. Pass an ID or title, or filters such as --status to delete every matching idea"
+            ),
+        };
+        let idea = storage.get_idea(id)?;
 
         if !args.force {
             print!(
@@ -352,20 +1274,139 @@ impl IdeaCommands {
 
             let response = input.trim().to_lowercase();
             if !matches!(response.as_str(), "y" | "yes") {
-                println!("❌ Deletion cancelled");
+                say!("{} Deletion cancelled", symbols::ERROR);
+                return Ok(());
+            }
+        }
+
+        let deleted_idea = storage.trash_idea(idea.id)?;
+        let unlinked = if args.keep_links {
+            Unlinked::default()
+        } else {
+            storage
+                .unlink_idea(deleted_idea.id)
+                .context("Failed to remove links to the deleted idea")?
+        };
+
+        say!(
+            "{} Moved idea to trash: {}",
+            symbols::TRASH,
+            deleted_idea.title
+        );
+        if !unlinked.ideas.is_empty() {
+            let titles: Vec<&str> = unlinked.ideas.iter().map(|i| i.title.as_str()).collect();
+            say!(
+                "   Unrelated from {} idea(s): {}",
+                titles.len(),
+                titles.join(", ")
+            );
+        }
+        if !unlinked.projects.is_empty() {
+            let titles: Vec<&str> = unlinked.projects.iter().map(|p| p.title.as_str()).collect();
+            say!(
+                "   Unlinked from {} project(s): {}",
+                titles.len(),
+                titles.join(", ")
+            );
+        }
+        if !unlinked.tasks.is_empty() {
+            let titles: Vec<&str> = unlinked.tasks.iter().map(|t| t.title.as_str()).collect();
+            say!(
+                "   Unlinked from {} task(s): {}",
+                titles.len(),
+                titles.join(", ")
+            );
+        }
+        say!(
+            "   Restore it with: ideavault trash restore {}",
+            deleted_idea.id
+        );
+        Ok(())
+    }
+
+    /// Move every idea matching the filters to the trash, after confirming
+    /// how many.
+    fn delete_matching(storage: &Storage, args: &DeleteIdeaArgs) -> Result<()> {
+        let matching = &args.matching;
+        let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+        ideas.retain(|idea| {
+            matching
+                .before
+                .is_none_or(|day| idea.updated_at.date_naive() < day)
+                && matching.filter.matches(idea)
+        });
+        if ideas.is_empty() {
+            say!("{} No ideas match", symbols::IDEA);
+            return Ok(());
+        }
+
+        if matching.dry_run {
+            println!("{} Would delete {} idea(s)", symbols::TRASH, ideas.len());
+            for idea in &ideas {
+                println!("   {} [{}]", idea.title, style::dim(short_id(&idea.id)));
+            }
+            println!();
+            println!("Nothing was deleted. Run it again without --dry-run to delete them.");
+            return Ok(());
+        }
+
+        if !args.force {
+            print!(
+                "Are you sure you want to delete {} idea(s)? [y/N]: ",
+                ideas.len()
+            );
+            io::stdout().flush().context("Failed to flush output")?;
+
+            let mut input = String::new();
+            io::stdin()
+                .read_line(&mut input)
+                .context("Failed to read input")?;
+
+            let response = input.trim().to_lowercase();
+            if !matches!(response.as_str(), "y" | "yes") {
+                say!("{} Deletion cancelled", symbols::ERROR);
                 return Ok(());
             }
         }
 
-        let deleted_idea = ideas.remove(idea_index);
-        storage.save_ideas(&ideas).context("Failed to save ideas")?;
+        let mut unlinked = Unlinked::default();
+        for idea in &ideas {
+            storage.trash_idea(idea.id)?;
+            if !args.keep_links {
+                let links = storage
+                    .unlink_idea(idea.id)
+                    .context("Failed to remove links to a deleted idea")?;
+                unlinked.ideas.extend(links.ideas);
+                unlinked.projects.extend(links.projects);
+                unlinked.tasks.extend(links.tasks);
+            }
+        }
 
-        println!("✅ Deleted idea: {}", deleted_idea.title);
+        if output::is_quiet() {
+            output::print_ids(ideas.iter().map(|idea| idea.id));
+            return Ok(());
+        }
+        println!("{} Moved {} idea(s) to trash", symbols::TRASH, ideas.len());
+        for idea in &ideas {
+            println!("   {} [{}]", idea.title, style::dim(short_id(&idea.id)));
+        }
+        if !unlinked.ideas.is_empty() || !unlinked.projects.is_empty() || !unlinked.tasks.is_empty()
+        {
+            println!(
+                "   Removed links from {} idea(s), {} project(s), and {} task(s)",
+                unlinked.ideas.len(),
+                unlinked.projects.len(),
+                unlinked.tasks.len()
+            );
+        }
+        println!();
+        println!("   Revert it with: ideavault undo");
         Ok(())
     }
 
     pub fn update_idea(storage: &Storage, args: &IdeaUpdateArgs) -> Result<()> {
-        const CLEARABLE_FIELDS: [&str; 1] = ["description"];
+        let id = args.id.idea(storage)?;
+        const CLEARABLE_FIELDS: [&str; 5] = ["description", "source", "effort", "impact", "tags"];
 
         // Validate clear fields
         for field in &args.clear {
@@ -378,104 +1419,409 @@ impl IdeaCommands {
             }
         }
 
-        let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let description = args.description.as_deref().map(text_or_stdin).transpose()?;
+        let changes = storage.update_idea(id, |idea| {
+            let mut changes: Vec<String> = Vec::new();
 
-        let idea = ideas
-            .iter_mut()
-            .find(|i| i.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", args.id))?;
+            // Update title
+            if let Some(title) = &args.title {
+                let old = idea.title.clone();
+                idea.update_title(title.clone());
+                changes.push(format!("title: \"{}\" → \"{}\"", old, title));
+            }
 
-        let mut changes: Vec<String> = Vec::new();
+            // Update description
+            if let Some(desc) = &description {
+                let old = idea.description.clone().unwrap_or_default();
+                idea.update_description(Some(desc.clone()));
+                changes.push(format!("description: \"{}\" → \"{}\"", old, desc));
+            }
 
-        // Update title
-        if let Some(title) = &args.title {
-            let old = idea.title.clone();
-            idea.update_title(title.clone());
-            changes.push(format!("title: \"{}\" → \"{}\"", old, title));
-        }
+            // Update source
+            if let Some(source) = &args.source {
+                let old = idea.source.clone().unwrap_or_default();
+                idea.set_source(Some(source.clone()));
+                changes.push(format!("source: \"{}\" → \"{}\"", old, source));
+            }
 
-        // Update description
-        if let Some(desc) = &args.description {
-            let old = idea.description.clone().unwrap_or_default();
-            idea.update_description(Some(desc.clone()));
-            changes.push(format!("description: \"{}\" → \"{}\"", old, desc));
-        }
+            // Update status
+            if let Some(status) = &args.status {
+                let old = idea.status.clone();
+                idea.set_status(status.clone());
+                changes.push(format!("status: {} → {}", old, status));
+            }
 
-        // Update status
-        if let Some(status) = &args.status {
-            let old = idea.status.clone();
-            idea.set_status(status.clone());
-            changes.push(format!("status: {} → {}", old, status));
-        }
+            // Update priority
+            if let Some(priority) = &args.priority {
+                let old = idea.priority.clone();
+                idea.set_priority(priority.clone());
+                changes.push(format!("priority: {} → {}", old, priority));
+            }
 
-        // Clear fields
-        for field in &args.clear {
-            match field.as_str() {
-                "description" => {
-                    idea.update_description(None);
-                    changes.push("description: cleared".to_string());
+            // Update ratings
+            if let Some(effort) = args.effort {
+                let old = rating(idea.effort);
+                idea.set_effort(Some(effort));
+                changes.push(format!("effort: {} → {}", old, effort));
+            }
+            if let Some(impact) = args.impact {
+                let old = rating(idea.impact);
+                idea.set_impact(Some(impact));
+                changes.push(format!("impact: {} → {}", old, impact));
+            }
+
+            // Update tags: replace them, then add and remove
+            if args.tags.is_some() || !args.add_tags.is_empty() || !args.remove_tags.is_empty() {
+                let old_tags = idea.tags.clone();
+                if let Some(tags) = &args.tags {
+                    idea.update_tags(tags.clone());
+                }
+                for tag in &args.add_tags {
+                    idea.add_tag(tag.clone());
+                }
+                for tag in &args.remove_tags {
+                    idea.remove_tag(tag);
                 }
-                _ => unreachable!(),
+                changes.push(format!("tags: {:?} → {:?}", old_tags, idea.tags));
             }
-        }
+
+            // Clear fields
+            for field in &args.clear {
+                match field.as_str() {
+                    "description" => {
+                        idea.update_description(None);
+                        changes.push("description: cleared".to_string());
+                    }
+                    "source" => {
+                        idea.set_source(None);
+                        changes.push("source: cleared".to_string());
+                    }
+                    "effort" => {
+                        idea.set_effort(None);
+                        changes.push("effort: cleared".to_string());
+                    }
+                    "impact" => {
+                        idea.set_impact(None);
+                        changes.push("impact: cleared".to_string());
+                    }
+                    "tags" => {
+                        idea.update_tags(Vec::new());
+                        changes.push("tags: cleared".to_string());
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            changes
+        })?;
 
         if changes.is_empty() {
-            println!("No changes specified for idea {}", args.id);
-            println!("Use --help to see available options.");
+            say!("No changes specified for idea {}", id);
+            say!("Use --help to see available options.");
             return Ok(());
         }
 
-        storage.save_ideas(&ideas).context("Failed to save ideas")?;
-
-        println!("✅ Updated idea {}:", args.id);
+        say!("{} Updated idea {}:", symbols::OK, id);
         for change in &changes {
-            println!("   {}", change);
+            say!("   {}", change);
         }
 
         Ok(())
     }
 }
 
-fn print_idea_summary(idea: &Idea) {
-    let status_emoji = match idea.status {
-        IdeaStatus::Brainstorming => "🧠",
-        IdeaStatus::Active => "🚀",
-        IdeaStatus::Completed => "✅",
-        IdeaStatus::Archived => "📦",
+impl Matrix {
+    /// Sort `ideas` into the quadrant their ratings put them in.
+    pub fn of(mut ideas: Vec<Idea>) -> Self {
+        ideas.sort_by_key(|idea| (std::cmp::Reverse(idea.impact), idea.effort));
+        let mut matrix = Matrix::default();
+        for idea in ideas {
+            let (Some(effort), Some(impact)) = (idea.effort, idea.impact) else {
+                matrix.unrated.push(idea);
+                continue;
+            };
+            let quadrant = match (impact.is_high(), effort.is_high()) {
+                (true, false) => &mut matrix.quick_wins,
+                (true, true) => &mut matrix.big_bets,
+                (false, false) => &mut matrix.fill_ins,
+                (false, true) => &mut matrix.money_pits,
+            };
+            quadrant.push(idea);
+        }
+        matrix
+    }
+}
+
+impl IdeaFilterArgs {
+    /// Whether any filter is given.
+    pub fn is_set(&self) -> bool {
+        self.status.is_some()
+            || !self.tags.is_empty()
+            || self.untagged
+            || self.no_description
+            || self.priority.is_some()
+            || self.created_after.is_some()
+            || self.created_before.is_some()
+            || self.updated_within.is_some()
+            || self.contains.is_some()
+    }
+
+    /// Whether `idea` passes every filter.
+    pub fn matches(&self, idea: &Idea) -> bool {
+        let created = idea.created_at.date_naive();
+        self.status
+            .as_ref()
+            .is_none_or(|status| &idea.status == status)
+            && self.matches_tags(idea)
+            && (!self.untagged || idea.tags.is_empty())
+            && (!self.no_description
+                || idea
+                    .description
+                    .as_deref()
+                    .is_none_or(|description| description.trim().is_empty()))
+            && self
+                .priority
+                .as_ref()
+                .is_none_or(|priority| &idea.priority == priority)
+            && self.created_after.is_none_or(|day| created > day)
+            && self.created_before.is_none_or(|day| created < day)
+            && self
+                .updated_within
+                .is_none_or(|span| idea.updated_at >= Utc::now() - span)
+            && self.contains.as_ref().is_none_or(|text| {
+                let text = text.to_lowercase();
+                idea.title.to_lowercase().contains(&text)
+                    || idea
+                        .description
+                        .as_ref()
+                        .is_some_and(|description| description.to_lowercase().contains(&text))
+            })
+    }
+
+    /// Whether `idea` has every tag asked for, or any of them with `--any-tag`.
+    fn matches_tags(&self, idea: &Idea) -> bool {
+        if self.tags.is_empty() {
+            return true;
+        }
+        let has = |tag: &String| idea.tags.contains(tag);
+        if self.any_tag {
+            self.tags.iter().any(has)
+        } else {
+            self.tags.iter().all(has)
+        }
+    }
+}
+
+/// Parse a `YYYY-MM-DD` day.
+fn parse_day(day: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD"))
+}
+
+/// A `--set` change `idea edit-many` makes.
+pub enum IdeaChange {
+    Status(IdeaStatus),
+    Priority(TaskPriority),
+}
+
+impl BulkEditable for Idea {
+    const ENTITY: EntityKind = EntityKind::Idea;
+    const FIELDS: &'static [&'static str] = &["status", "priority"];
+    type Change = IdeaChange;
+
+    fn parse_change(_storage: &Storage, assignment: &Assignment) -> Result<IdeaChange> {
+        Ok(match assignment.field.as_str() {
+            "status" => IdeaChange::Status(assignment.value.parse()?),
+            "priority" => IdeaChange::Priority(assignment.value.parse()?),
+            field => unreachable!("{} is not in FIELDS", field),
+        })
+    }
+
+    fn apply(&mut self, change: &IdeaChange) {
+        match change {
+            IdeaChange::Status(status) => self.set_status(status.clone()),
+            IdeaChange::Priority(priority) => self.priority = priority.clone(),
+        }
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
+
+/// The fields of an idea the editor shows as front matter. The description
+/// is the body.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdeaFields {
+    title: String,
+    status: String,
+    priority: String,
+    #[serde(default)]
+    effort: Option<u8>,
+    #[serde(default)]
+    impact: Option<u8>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// `idea` as Markdown with front matter, headed by `heading`, for editing.
+fn idea_document(idea: &Idea, heading: &str) -> Result<String> {
+    let fields = IdeaFields {
+        title: idea.title.clone(),
+        status: idea.status.to_string(),
+        priority: idea.priority.to_string(),
+        effort: idea.effort.map(Rating::value),
+        impact: idea.impact.map(Rating::value),
+        source: idea.source.clone(),
+        tags: idea.tags.clone(),
+    };
+    let comments = [
+        heading.to_string(),
+        "Status: Inbox, Brainstorming, Active, Completed, or Archived".to_string(),
+        format!(
+            "Priority: {}",
+            priorities()
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        "Effort and impact are ratings from 1 to 5".to_string(),
+        "Write the description below the closing ---".to_string(),
+    ];
+    front_matter::render(&fields, &comments, idea.description.as_deref())
+}
+
+/// `idea` with the changes in an edited [`idea_document`], or what's wrong
+/// with them.
+fn apply_document(mut idea: Idea, content: &str) -> Result<Idea> {
+    let (fields, description): (IdeaFields, _) = front_matter::parse(content)?;
+    let title = fields.title.trim();
+    if title.is_empty() {
+        anyhow::bail!("The title can't be empty");
+    }
+    idea.set_status(
+        fields
+            .status
+            .parse()
+            .map_err(|err| anyhow::anyhow!("status: {}", err))?,
+    );
+    idea.priority = fields
+        .priority
+        .parse()
+        .map_err(|err| anyhow::anyhow!("priority: {}", err))?;
+    idea.title = title.to_string();
+    idea.description = description;
+    let rated = |value: Option<u8>, field: &str| {
+        value
+            .map(|value| {
+                Rating::new(value).ok_or_else(|| anyhow::anyhow!("{}: must be from 1 to 5", field))
+            })
+            .transpose()
     };
+    idea.effort = rated(fields.effort, "effort")?;
+    idea.impact = rated(fields.impact, "impact")?;
+    idea.source = fields
+        .source
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty());
+    idea.tags = fields
+        .tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    Ok(idea)
+}
+
+fn print_idea_summary(idea: &Idea) {
+    let status_emoji = symbols::idea_status(&idea.status);
 
-    println!("{} {} [{}]", status_emoji, idea.title, idea.id);
+    say!(
+        "{} {} [{}]",
+        status_emoji,
+        style::paint(&idea.title, style::idea_status_color(&idea.status)),
+        style::dim(idea.id)
+    );
     if let Some(description) = &idea.description {
         let desc_preview = if description.len() > 50 {
             format!("{}...", &description[..50])
         } else {
             description.clone()
         };
-        println!("   {}", desc_preview);
+        say!("   {}", desc_preview);
     }
     if !idea.tags.is_empty() {
-        println!("   🏷️  {}", idea.tags.join(", "));
+        say!("   {} {}", symbols::TAGS, style::tags(&idea.tags));
     }
-    println!(
-        "   📅 Updated: {}",
-        idea.updated_at.format("%Y-%m-%d %H:%M")
+    say!(
+        "   {} Updated: {}",
+        symbols::DATE,
+        dates::moment(idea.updated_at, dates::DATE_TIME)
     );
 }
 
-fn print_idea_full(idea: &Idea) {
-    let status_emoji = match idea.status {
-        IdeaStatus::Brainstorming => "🧠",
-        IdeaStatus::Active => "🚀",
-        IdeaStatus::Completed => "✅",
-        IdeaStatus::Archived => "📦",
-    };
+/// The columns `idea list` can show.
+fn idea_columns<'a>() -> Vec<Column<'a, Idea>> {
+    vec![
+        Column::new("id", 8, |idea: &Idea| {
+            Cell::colored(short_id(&idea.id), Color::Gray)
+        }),
+        Column::new("title", 40, |idea: &Idea| Cell::new(&idea.title)),
+        Column::new("status", 14, |idea: &Idea| {
+            Cell::colored(
+                idea.status.to_string(),
+                style::idea_status_color(&idea.status),
+            )
+        }),
+        Column::new("priority", 10, |idea: &Idea| {
+            Cell::colored(
+                idea.priority.to_string(),
+                style::priority_color(&idea.priority),
+            )
+        }),
+        Column::new("tags", 30, |idea: &Idea| Cell::new(idea.tags.join(", "))),
+        Column::new("description", 40, |idea: &Idea| {
+            Cell::new(idea.description.clone().unwrap_or_default())
+        }),
+        Column::new("created", 14, |idea: &Idea| {
+            Cell::new(dates::moment(idea.created_at, dates::DATE))
+        }),
+        Column::new("updated", 14, |idea: &Idea| {
+            Cell::new(dates::moment(idea.updated_at, dates::DATE))
+        }),
+    ]
+}
 
-    println!("{} {}", status_emoji, idea.title);
+/// Print `idea` in full, naming its related ideas from `ideas`.
+fn print_idea_full(idea: &Idea, ideas: &[Idea]) {
+    let status_emoji = symbols::idea_status(&idea.status);
+
+    println!("{} {}", status_emoji, style::bold(&idea.title));
     println!("ID: {}", idea.id);
-    println!("Status: {}", idea.status);
+    println!(
+        "Status: {}",
+        style::paint(&idea.status, style::idea_status_color(&idea.status))
+    );
+    println!(
+        "Priority: {}",
+        style::paint(&idea.priority, style::priority_color(&idea.priority))
+    );
+    if idea.effort.is_some() || idea.impact.is_some() {
+        println!(
+            "Effort: {}  Impact: {}",
+            rating(idea.effort),
+            rating(idea.impact)
+        );
+    }
+    if let Some(source) = &idea.source {
+        println!("Source: {}", source);
+    }
 
     if !idea.tags.is_empty() {
-        println!("Tags: {}", idea.tags.join(", "));
+        println!("Tags: {}", style::tags(&idea.tags));
     }
 
     println!(
@@ -494,6 +1840,124 @@ fn print_idea_full(idea: &Idea) {
     } else {
         println!("No description");
     }
+
+    if !idea.notes.is_empty() {
+        println!();
+        println!("{} Notes:", symbols::NOTES);
+        print_notes(&idea.notes);
+    }
+
+    if !idea.attachments.is_empty() {
+        println!();
+        println!("{} Attachments:", symbols::ATTACHMENT);
+        for (number, attachment) in idea.attachments.iter().enumerate() {
+            let kind = match attachment.kind {
+                AttachmentKind::File => "file",
+                AttachmentKind::Url => "link",
+            };
+            println!(
+                "  {}. {} {}",
+                number + 1,
+                attachment.name,
+                style::dim(format!(
+                    "({}, added {})",
+                    kind,
+                    dates::moment(attachment.added_at, dates::DATE)
+                ))
+            );
+        }
+    }
+
+    if !idea.status_history.is_empty() {
+        println!();
+        println!("{} Status History:", symbols::HISTORY);
+        for change in &idea.status_history {
+            println!(
+                "  {}  {} → {}",
+                style::dim(change.changed_at.format(dates::DATE_TIME)),
+                change.from,
+                change.to
+            );
+        }
+        let times: Vec<String> = idea
+            .time_in_status(Utc::now())
+            .into_iter()
+            .map(|(status, time)| format!("{} {}", status, dates::length(time)))
+            .collect();
+        println!("  Time in each status: {}", times.join(", "));
+    }
+
+    if !idea.related_ids.is_empty() {
+        println!();
+        println!("{} Related Ideas:", symbols::IDEA);
+        for related_id in &idea.related_ids {
+            match ideas.iter().find(|i| i.id == *related_id) {
+                Some(related) => println!(
+                    "  {} {} [{}]",
+                    symbols::idea_status(&related.status),
+                    style::paint(&related.title, style::idea_status_color(&related.status)),
+                    style::dim(short_id(&related.id))
+                ),
+                None => println!("  - {} (not found)", related_id),
+            }
+        }
+    }
+}
+
+/// Print `notes` with their dates, lines of each note indented under it.
+fn print_notes(notes: &[Note]) {
+    for note in notes {
+        println!();
+        println!(
+            "  {}",
+            style::dim(dates::moment(note.created_at, dates::DATE_TIME))
+        );
+        for line in note.body.lines() {
+            println!("  {}", line);
+        }
+    }
+}
+
+/// An effort or impact rating as "3/5", or "unrated".
+fn rating(rating: Option<Rating>) -> String {
+    rating.map_or_else(|| "unrated".to_string(), |rating| rating.to_string())
+}
+
+/// Fail if the vault has ideas with titles like `title`, listing them, so
+/// an idea isn't captured twice by accident.
+fn refuse_duplicate(storage: &Storage, title: &str) -> Result<()> {
+    let ideas = storage.load_ideas().context("Failed to load ideas")?;
+    let similar: Vec<&Idea> = ideas
+        .iter()
+        .filter(|idea| idea.has_similar_title(title))
+        .collect();
+    if similar.is_empty() {
+        return Ok(());
+    }
+    eprintln!(
+        "{} Found {} idea(s) with a similar title:",
+        symbols::WARNING,
+        similar.len()
+    );
+    for idea in &similar {
+        eprintln!(
+            "   {} {} [{}]",
+            symbols::idea_status(&idea.status),
+            idea.title,
+            short_id(&idea.id)
+        );
+    }
+    anyhow::bail!(
+        "Not creating '{}', as it may be a duplicate. Use --allow-duplicate to create it anyway",
+        title
+    )
+}
+
+/// Whether an attachment target is a link rather than a file path.
+fn is_url(target: &str) -> bool {
+    target.split_once("://").is_some_and(|(scheme, _)| {
+        !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphabetic())
+    })
 }
 
 // Implement FromStr for IdeaStatus for CLI parsing
@@ -502,12 +1966,13 @@ impl std::str::FromStr for IdeaStatus {
 
     fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
+            "inbox" => Ok(IdeaStatus::Inbox),
             "brainstorming" => Ok(IdeaStatus::Brainstorming),
             "active" => Ok(IdeaStatus::Active),
             "completed" => Ok(IdeaStatus::Completed),
             "archived" => Ok(IdeaStatus::Archived),
             _ => Err(anyhow::anyhow!(
-                "Invalid status. Must be one of: Brainstorming, Active, Completed, Archived"
+                "Invalid status. Must be one of: Inbox, Brainstorming, Active, Completed, Archived"
             )),
         }
     }
@@ -516,6 +1981,7 @@ impl std::str::FromStr for IdeaStatus {
 impl std::fmt::Display for IdeaStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            IdeaStatus::Inbox => write!(f, "Inbox"),
             IdeaStatus::Brainstorming => write!(f, "Brainstorming"),
             IdeaStatus::Active => write!(f, "Active"),
             IdeaStatus::Completed => write!(f, "Completed"),
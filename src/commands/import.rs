@@ -0,0 +1,229 @@
+use crate::export::EntityType;
+use crate::import::{self, ColumnMap, ImportPlan, Importable};
+use crate::models::config::Config;
+use crate::say;
+use crate::storage::{archive, legacy, Storage};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// JSON or CSV file to import, or with --archive an archive made by `export --archive`
+    pub file: PathBuf,
+
+    /// What the file contains: idea, project, or task (guessed from the file name if omitted)
+    #[arg(short = 't', long = "type")]
+    pub kind: Option<EntityType>,
+
+    /// Map a column to a field, e.g. --map "Idea Name=title" (repeatable)
+    #[arg(short, long = "map", value_name = "COLUMN=FIELD")]
+    pub mappings: Vec<String>,
+
+    /// Show what would be imported without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Restore the whole vault from the archive, replacing everything in it
+    #[arg(long, conflicts_with_all = ["kind", "mappings"])]
+    pub archive: bool,
+
+    /// Restore the archive without asking for confirmation
+    #[arg(long, requires = "archive")]
+    pub force: bool,
+}
+
+/// How many rows were (or, in a dry run, would be) imported and skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub skipped: usize,
+}
+
+pub fn execute(args: ImportArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    if args.archive {
+        import_archive(&storage, &args).map(|_| ())
+    } else {
+        import(&storage, &args).map(|_| ())
+    }
+}
+
+/// Replace the vault's contents with an archive made by `export --archive`.
+/// Returns the restored paths, or nothing if cancelled.
+pub fn import_archive(storage: &Storage, args: &ImportArgs) -> Result<Vec<String>> {
+    let passphrase = if archive::is_encrypted(&args.file)? {
+        Some(super::export::passphrase(false)?)
+    } else {
+        None
+    };
+
+    if args.dry_run {
+        let files = archive::list(&args.file, passphrase.as_deref())?;
+        say!(
+            "{} Dry run: would restore {} file(s) from {} into {}",
+            symbols::LIST,
+            files.len(),
+            args.file.display(),
+            storage.data_dir().display()
+        );
+        for file in &files {
+            say!("   {}", file);
+        }
+        return Ok(files);
+    }
+
+    if !args.force {
+        say!(
+            "This will replace everything in {}, including its backups, and the config file with the contents of {}.",
+            storage.data_dir().display(),
+            args.file.display()
+        );
+        print!("Are you sure you want to restore this archive? [y/N]: ");
+        io::stdout().flush().context("Failed to flush output")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read input")?;
+
+        let response = input.trim().to_lowercase();
+        if !matches!(response.as_str(), "y" | "yes") {
+            say!("{} Restore cancelled", symbols::ERROR);
+            return Ok(Vec::new());
+        }
+    }
+
+    let config = Config::path().ok();
+    let files = archive::restore(
+        &args.file,
+        storage.data_dir(),
+        config.as_deref(),
+        passphrase.as_deref(),
+    )
+    .context("Failed to restore archive")?;
+    say!(
+        "{} Restored {} file(s) from {} into {}",
+        symbols::OK,
+        files.len(),
+        args.file.display(),
+        storage.data_dir().display()
+    );
+    Ok(files)
+}
+
+pub fn import(storage: &Storage, args: &ImportArgs) -> Result<ImportSummary> {
+    let kind = args
+        .kind
+        .or_else(|| import::detect_type(&args.file))
+        .or_else(|| {
+            let legacy = legacy::read_ideas(&args.file).ok().flatten();
+            legacy.map(|_| EntityType::Idea)
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot tell what {:?} contains. Pass --type idea, project, or task",
+                args.file
+            )
+        })?;
+    let columns = ColumnMap::new(kind).with_mappings(&args.mappings)?;
+    let rows = import::read_rows(&args.file)?;
+
+    match kind {
+        EntityType::Idea => {
+            let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+            let plan = import::plan(&rows, &columns, &ideas);
+            let summary = report(&plan, args);
+            if !args.dry_run && !plan.create.is_empty() {
+                ideas.extend(plan.create);
+                storage.save_ideas(&ideas).context("Failed to save ideas")?;
+            }
+            Ok(summary)
+        }
+        EntityType::Project => {
+            let mut projects = storage.load_projects().context("Failed to load projects")?;
+            let plan = import::plan(&rows, &columns, &projects);
+            let summary = report(&plan, args);
+            if !args.dry_run && !plan.create.is_empty() {
+                projects.extend(plan.create);
+                storage
+                    .save_projects(&projects)
+                    .context("Failed to save projects")?;
+            }
+            Ok(summary)
+        }
+        EntityType::Task => {
+            let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
+            let plan = import::plan(&rows, &columns, &tasks);
+            let summary = report(&plan, args);
+            if !args.dry_run && !plan.create.is_empty() {
+                tasks.extend(plan.create);
+                storage.save_tasks(&tasks).context("Failed to save tasks")?;
+            }
+            Ok(summary)
+        }
+    }
+}
+
+fn report<T: Importable>(plan: &ImportPlan<T>, args: &ImportArgs) -> ImportSummary {
+    if args.dry_run {
+        say!(
+            "{} Dry run: would import {} {}(s) from {}",
+            symbols::LIST,
+            plan.create.len(),
+            T::KIND,
+            args.file.display()
+        );
+    } else if plan.create.is_empty() {
+        say!(
+            "{} No new {}s to import from {}",
+            symbols::LIST,
+            T::KIND,
+            args.file.display()
+        );
+    } else {
+        say!(
+            "{} Imported {} {}(s) from {}",
+            symbols::OK,
+            plan.create.len(),
+            T::KIND,
+            args.file.display()
+        );
+    }
+    for item in &plan.create {
+        say!("   {} {} [{}]", symbols::NEW, item.title(), item.id());
+    }
+
+    if !plan.skipped.is_empty() {
+        say!();
+        say!(
+            "{} Skipped {} row(s):",
+            symbols::WARNING,
+            plan.skipped.len()
+        );
+        for skipped in &plan.skipped {
+            let title = skipped
+                .title
+                .as_ref()
+                .map(|title| format!(" \"{}\"", title))
+                .unwrap_or_default();
+            say!("   Row {}{}: {}", skipped.row, title, skipped.reason);
+        }
+    }
+
+    if !plan.ignored_columns.is_empty() {
+        say!();
+        say!(
+            "{} Ignored column(s): {}. Use --map COLUMN=FIELD to import them",
+            symbols::WARNING,
+            plan.ignored_columns.join(", ")
+        );
+    }
+
+    ImportSummary {
+        created: plan.create.len(),
+        skipped: plan.skipped.len(),
+    }
+}
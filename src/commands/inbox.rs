@@ -0,0 +1,247 @@
+use super::page::{Page, PageArgs};
+use crate::dates;
+use crate::models::idea::{Idea, IdeaStatus};
+use crate::models::task::Task;
+use crate::output;
+use crate::say;
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::io::{self, BufRead, Write};
+
+#[derive(Args)]
+pub struct CaptureArgs {
+    /// What to capture; the words don't need quotes
+    #[arg(required = true, num_args = 1..)]
+    pub text: Vec<String>,
+
+    /// Tags for the captured item (comma-separated)
+    #[arg(short = 't', long = "tags", value_delimiter = ',')]
+    pub tags: Vec<String>,
+}
+
+#[derive(Parser)]
+#[command(name = "inbox")]
+#[command(about = "Review captured items")]
+pub struct InboxCommands {
+    #[command(subcommand)]
+    pub command: InboxSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum InboxSubcommand {
+    /// List captured items waiting to be processed
+    List(ListInboxArgs),
+    /// Go through captured items one by one, turning each into an idea or a task
+    #[command(visible_alias = "triage")]
+    Process,
+}
+
+#[derive(Args)]
+pub struct ListInboxArgs {
+    #[command(flatten)]
+    pub page: PageArgs,
+
+    /// Print only the full ID of each item, one per line (short for --quiet)
+    #[arg(long)]
+    pub ids: bool,
+}
+
+/// What processing the inbox did.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Processed {
+    pub ideas: usize,
+    pub tasks: usize,
+    pub deleted: usize,
+    pub skipped: usize,
+}
+
+pub fn execute_capture(args: CaptureArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    capture(&storage, &args).map(|_| ())
+}
+
+/// Save `args.text` as an idea in the inbox.
+pub fn capture(storage: &Storage, args: &CaptureArgs) -> Result<Idea> {
+    let title = args.text.join(" ").trim().to_string();
+    if title.is_empty() {
+        anyhow::bail!("Nothing to capture");
+    }
+
+    let mut idea = Idea::new(title).with_status(IdeaStatus::Inbox);
+    if !args.tags.is_empty() {
+        idea = idea.with_tags(args.tags.clone());
+    }
+    storage.add_idea(&idea).context("Failed to save idea")?;
+
+    if output::is_quiet() {
+        println!("{}", idea.id);
+    } else {
+        println!(
+            "{} Captured: {} [{}]",
+            symbols::INBOX,
+            idea.title,
+            style::dim(idea.id)
+        );
+    }
+    Ok(idea)
+}
+
+impl InboxCommands {
+    pub fn execute(&self) -> Result<()> {
+        let storage = Storage::new().context("Failed to initialize storage")?;
+
+        match &self.command {
+            InboxSubcommand::List(args) => Self::list_inbox(&storage, args),
+            InboxSubcommand::Process => {
+                Self::process_inbox(&storage, &mut io::stdin().lock()).map(|_| ())
+            }
+        }
+    }
+
+    fn list_inbox(storage: &Storage, args: &ListInboxArgs) -> Result<()> {
+        let inbox = inbox(storage)?;
+        let page = Page::from_config(inbox.len(), &args.page)?;
+        let shown = &inbox[page.start..page.end];
+        if output::is_quiet() {
+            output::print_ids(shown.iter().map(|idea| idea.id));
+            return Ok(());
+        }
+
+        if inbox.is_empty() {
+            println!("{} Inbox is empty", symbols::INBOX);
+            return Ok(());
+        }
+        println!("{} {} item(s) in the inbox:", symbols::INBOX, inbox.len());
+        println!();
+        for idea in shown {
+            println!(
+                "   {} [{}] {}",
+                idea.title,
+                style::dim(idea.id),
+                style::dim(dates::moment(idea.created_at, dates::DATE_TIME))
+            );
+        }
+        println!();
+        if let Some(footer) = page.footer("captured item") {
+            println!("{}", footer);
+        }
+        println!("Process them with: ideavault inbox process");
+        Ok(())
+    }
+
+    /// Ask what to do with each captured item, oldest first, reading one
+    /// answer per line of `input`. Ideas leave the inbox as brainstorming
+    /// ideas, tasks replace the captured idea, and deleted items go to the
+    /// trash.
+    pub fn process_inbox(storage: &Storage, input: &mut impl BufRead) -> Result<Processed> {
+        let inbox = inbox(storage)?;
+        let mut processed = Processed::default();
+        if inbox.is_empty() {
+            say!("{} Inbox is empty", symbols::INBOX);
+            return Ok(processed);
+        }
+
+        for (number, idea) in inbox.iter().enumerate() {
+            println!();
+            println!(
+                "{} [{}/{}] {}",
+                symbols::INBOX,
+                number + 1,
+                inbox.len(),
+                idea.title
+            );
+            if let Some(description) = &idea.description {
+                println!("   {}", description);
+            }
+            if !idea.tags.is_empty() {
+                println!("   {} {}", symbols::TAGS, style::tags(&idea.tags));
+            }
+
+            let Some(action) = ask_action(input)? else {
+                break;
+            };
+            match action {
+                Triage::Idea => {
+                    storage
+                        .update_idea(idea.id, |idea| idea.set_status(IdeaStatus::Brainstorming))?;
+                    say!("   {} Kept as an idea", symbols::IDEA);
+                    processed.ideas += 1;
+                }
+                Triage::Task => {
+                    let mut task = Task::new(idea.title.clone()).with_tags(idea.tags.clone());
+                    if let Some(description) = &idea.description {
+                        task = task.with_description(description.clone());
+                    }
+                    storage.add_task(&task).context("Failed to save task")?;
+                    storage
+                        .delete_idea(idea.id)
+                        .context("Failed to delete idea")?;
+                    say!("   {} Turned into task {}", symbols::OK, task.id);
+                    processed.tasks += 1;
+                }
+                Triage::Delete => {
+                    storage.trash_idea(idea.id)?;
+                    say!("   {} Moved to trash", symbols::TRASH);
+                    processed.deleted += 1;
+                }
+                Triage::Skip => processed.skipped += 1,
+            }
+        }
+
+        say!();
+        say!(
+            "{} Processed the inbox: {} idea(s), {} task(s), {} deleted, {} left",
+            symbols::OK,
+            processed.ideas,
+            processed.tasks,
+            processed.deleted,
+            inbox.len() - processed.ideas - processed.tasks - processed.deleted
+        );
+        Ok(processed)
+    }
+}
+
+/// What to do with one captured item.
+enum Triage {
+    Idea,
+    Task,
+    Delete,
+    Skip,
+}
+
+/// Captured ideas still in the inbox, oldest first.
+fn inbox(storage: &Storage) -> Result<Vec<Idea>> {
+    let mut ideas = storage.load_ideas().context("Failed to load ideas")?;
+    ideas.retain(|idea| idea.status == IdeaStatus::Inbox);
+    ideas.sort_by_key(|idea| idea.created_at);
+    Ok(ideas)
+}
+
+/// What to do with the next item, or `None` to stop.
+fn ask_action(input: &mut impl BufRead) -> Result<Option<Triage>> {
+    loop {
+        print!("   Make it an [i]dea or a [t]ask, [d]elete it, [s]kip, or [q]uit: ");
+        io::stdout().flush().context("Failed to flush output")?;
+
+        let mut answer = String::new();
+        if input
+            .read_line(&mut answer)
+            .context("Failed to read input")?
+            == 0
+        {
+            println!();
+            return Ok(None);
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "i" | "idea" => return Ok(Some(Triage::Idea)),
+            "t" | "task" => return Ok(Some(Triage::Task)),
+            "d" | "delete" => return Ok(Some(Triage::Delete)),
+            "s" | "skip" | "" => return Ok(Some(Triage::Skip)),
+            "q" | "quit" => return Ok(None),
+            _ => println!("   Please answer i, t, d, s, or q."),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::id::IdArg;
+use anyhow::{Context, Result};
+use std::io::{self, IsTerminal, Read};
+
+/// The argument value that stands for standard input.
+pub const STDIN: &str = "-";
+
+/// `value` as given, or everything piped to standard input when it is `-`.
+pub fn text_or_stdin(value: &str) -> Result<String> {
+    if value != STDIN {
+        return Ok(value.to_string());
+    }
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        anyhow::bail!("'-' reads from standard input, but nothing was piped in");
+    }
+    read_text(stdin.lock())
+}
+
+/// `ids` as given, with `-` replaced by the IDs piped to standard input, one
+/// per line. Read these before opening storage: the command piping them in,
+/// such as `list --ids`, holds the vault's lock until it's done.
+pub fn ids_or_stdin(ids: &[IdArg]) -> Result<Vec<IdArg>> {
+    let mut expanded = Vec::new();
+    for id in ids {
+        if id.to_string() != STDIN {
+            expanded.push(id.clone());
+            continue;
+        }
+        let piped = text_or_stdin(STDIN)?;
+        let before = expanded.len();
+        for line in piped.lines().filter(|line| !line.trim().is_empty()) {
+            expanded.push(line.parse()?);
+        }
+        if expanded.len() == before {
+            anyhow::bail!("No IDs were piped in");
+        }
+    }
+    Ok(expanded)
+}
+
+/// All of `reader`, without trailing whitespace.
+pub fn read_text(mut reader: impl Read) -> Result<String> {
+    let mut text = String::new();
+    reader
+        .read_to_string(&mut text)
+        .context("Failed to read standard input")?;
+    Ok(text.trim_end().to_string())
+}
@@ -0,0 +1,38 @@
+use crate::say;
+use crate::storage::{Layout, Storage};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+
+#[derive(Args)]
+pub struct MigrateArgs {
+    /// Target layout: "json" (one file per entity type), "indexed" (one file per entity),
+    /// or "events" (a snapshot plus an append-only log of changes)
+    pub layout: Layout,
+}
+
+pub fn execute(args: MigrateArgs) -> Result<()> {
+    let mut storage = Storage::new().context("Failed to initialize storage")?;
+    migrate(&mut storage, &args)
+}
+
+pub fn migrate(storage: &mut Storage, args: &MigrateArgs) -> Result<()> {
+    if !storage.migrate(args.layout)? {
+        say!("ℹ️  Vault already uses the {} layout", args.layout);
+        return Ok(());
+    }
+
+    say!(
+        "{} Migrated vault to the {} layout:",
+        symbols::OK,
+        args.layout
+    );
+    say!(
+        "   {} idea(s), {} project(s), {} task(s), {} tag(s)",
+        storage.load_ideas()?.len(),
+        storage.load_projects()?.len(),
+        storage.load_tasks()?.len(),
+        storage.load_tags()?.len()
+    );
+    Ok(())
+}
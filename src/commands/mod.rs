@@ -1,11 +1,60 @@
+pub mod activity;
+pub mod backup;
+pub mod board;
+pub mod bulk;
+pub mod compact;
+pub mod config;
+pub mod dashboard;
+pub mod doctor;
+pub mod export;
+pub mod git;
+pub mod github;
+pub mod history;
+pub mod id;
 pub mod idea;
+pub mod import;
+pub mod inbox;
+pub mod input;
+pub mod migrate;
+pub mod page;
+pub mod path;
+pub mod pick;
 pub mod project;
+pub mod recent;
+pub mod review;
 pub mod search;
+pub mod sort;
+pub mod sync;
+pub mod table;
 pub mod task;
+pub mod template;
+pub mod timeline;
+pub mod trash;
+pub mod undo;
+pub mod vault;
 pub mod version;
 
+pub use backup::BackupCommands;
+pub use compact::CompactArgs;
+pub use config::ConfigCommands;
+pub use doctor::DoctorArgs;
+pub use export::ExportCommands;
+pub use git::GitCommands;
+pub use history::HistoryArgs;
 pub use idea::IdeaCommands;
+pub use import::ImportArgs;
+pub use inbox::{CaptureArgs, InboxCommands};
+pub use migrate::MigrateArgs;
+pub use path::PathArgs;
+pub use pick::PickArgs;
 pub use project::ProjectCommands;
+pub use recent::RecentArgs;
+pub use review::ReviewArgs;
 pub use search::execute_search;
+pub use sync::SyncArgs;
 pub use task::TaskCommands;
+pub use template::TemplateCommands;
+pub use trash::TrashCommands;
+pub use undo::UndoArgs;
+pub use vault::VaultCommands;
 pub use version::VersionArgs;
@@ -0,0 +1,147 @@
+use crate::models::config::Config;
+use crate::output;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+
+/// Paging flags shared by the list commands. The page size is the
+/// `max_list_items` setting unless `--limit` says otherwise.
+#[derive(Args, Clone, Debug, Default)]
+pub struct PageArgs {
+    /// Show every item instead of one page
+    #[arg(long, conflicts_with_all = ["page", "limit", "offset"])]
+    pub all: bool,
+
+    /// Show this page of the results (starting at 1)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub page: Option<u64>,
+
+    /// Show at most N items (instead of the max_list_items setting)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub limit: Option<u64>,
+
+    /// Skip the first N items
+    #[arg(long, value_name = "N", conflicts_with = "page")]
+    pub offset: Option<u64>,
+}
+
+/// The slice of a list to show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// 1-based page number
+    pub number: usize,
+    pub pages: usize,
+    /// Index of the first item on the page
+    pub start: usize,
+    /// Index one past the last item on the page
+    pub end: usize,
+    pub total: usize,
+    /// Whether the start was given with `--offset` rather than `--page`
+    pub by_offset: bool,
+}
+
+impl Page {
+    /// Work out which of `total` items to show, `size` per page (no limit if
+    /// `None`). `--limit` takes the place of `size`.
+    pub fn of(total: usize, size: Option<usize>, args: &PageArgs) -> Result<Self> {
+        let size = match args.limit.map(|limit| limit as usize).or(size) {
+            Some(size) if size > 0 && !args.all => size,
+            _ => total.max(1),
+        };
+        let pages = total.div_ceil(size).max(1);
+
+        if let Some(offset) = args.offset {
+            let start = offset as usize;
+            if start > total {
+                anyhow::bail!(
+                    "Offset {} is past the end; there {} {} item(s)",
+                    start,
+                    if total == 1 { "is" } else { "are" },
+                    total
+                );
+            }
+            return Ok(Self {
+                number: start / size + 1,
+                pages,
+                start,
+                end: (start + size).min(total),
+                total,
+                by_offset: true,
+            });
+        }
+
+        let number = args.page.unwrap_or(1) as usize;
+        if number > pages {
+            anyhow::bail!(
+                "Page {} is past the end; there {} {} page(s)",
+                number,
+                if pages == 1 { "is" } else { "are" },
+                pages
+            );
+        }
+
+        let start = (number - 1) * size;
+        Ok(Self {
+            number,
+            pages,
+            start,
+            end: (start + size).min(total),
+            total,
+            by_offset: false,
+        })
+    }
+
+    /// The page of `total` items to show, sized by the config. JSON, YAML,
+    /// and quiet output isn't paged unless a page or limit is asked for.
+    pub fn from_config(total: usize, args: &PageArgs) -> Result<Self> {
+        if (!output::is_table() || output::is_quiet()) && args.page.is_none() {
+            return Self::of(total, None, args);
+        }
+        let config = Config::load().context("Failed to load config")?;
+        Self::of(total, config.max_list_items, args)
+    }
+
+    /// Whether some items are left off this page.
+    pub fn is_partial(&self) -> bool {
+        self.end - self.start < self.total
+    }
+
+    /// A line saying which items are shown and how to see the rest, e.g.
+    /// "Showing 50 of 312 ideas (page 1 of 7)".
+    pub fn footer(&self, kind: &str) -> Option<String> {
+        if !self.is_partial() {
+            return None;
+        }
+        if self.by_offset {
+            let next = if self.end < self.total {
+                format!("--offset {} for the next ones", self.end)
+            } else {
+                "a smaller --offset for earlier ones".to_string()
+            };
+            return Some(format!(
+                "{} Showing {} of {} {}(s), starting at {}. Use {}, or --all for everything",
+                symbols::PAGE,
+                self.end - self.start,
+                self.total,
+                kind,
+                self.start + 1,
+                next
+            ));
+        }
+        let next = if self.number < self.pages {
+            format!("--page {} for the next page", self.number + 1)
+        } else {
+            "--page N for another page".to_string()
+        };
+        Some(format!(
+            "{} Showing {} of {} {}(s) (page {} of {}). Use {}, or --all for everything",
+            symbols::PAGE,
+            self.end - self.start,
+            self.total,
+            kind,
+            self.number,
+            self.pages,
+            next
+        ))
+    }
+}
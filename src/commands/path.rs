@@ -0,0 +1,73 @@
+//! Where ideavault keeps things: `path` prints the directories in use and
+//! `open-data-dir` shows the vault in the file manager.
+
+use crate::models::config::Config;
+use crate::say;
+use crate::storage;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct PathArgs {
+    /// Print only the data directory of the vault in use
+    #[arg(long, conflicts_with_all = ["config", "backups"])]
+    pub data: bool,
+
+    /// Print only the config file
+    #[arg(long, conflicts_with = "backups")]
+    pub config: bool,
+
+    /// Print only the backup directory
+    #[arg(long)]
+    pub backups: bool,
+}
+
+/// The directories and files ideavault is using, as `--vault`, `--data-dir`,
+/// and the config resolve them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    pub data: PathBuf,
+    pub config: PathBuf,
+    pub backups: PathBuf,
+}
+
+impl Paths {
+    pub fn resolve() -> Result<Self> {
+        let config = Config::load().context("Failed to load config")?;
+        let data = storage::active_data_dir(&config)?;
+        Ok(Self {
+            backups: storage::backup_dir(&data),
+            config: Config::path()?,
+            data,
+        })
+    }
+}
+
+pub fn execute(args: PathArgs) -> Result<()> {
+    let paths = Paths::resolve()?;
+    if args.data {
+        println!("{}", paths.data.display());
+    } else if args.config {
+        println!("{}", paths.config.display());
+    } else if args.backups {
+        println!("{}", paths.backups.display());
+    } else {
+        println!("Data:    {}", paths.data.display());
+        println!("Config:  {}", paths.config.display());
+        println!("Backups: {}", paths.backups.display());
+    }
+    Ok(())
+}
+
+/// Open the data directory in the file manager, creating it if this is a
+/// new vault.
+pub fn execute_open_data_dir() -> Result<()> {
+    let data = Paths::resolve()?.data;
+    fs::create_dir_all(&data).with_context(|| format!("Failed to create {}", data.display()))?;
+    open::that_detached(&data).with_context(|| format!("Failed to open {}", data.display()))?;
+    say!("{} Opened {}", symbols::FOLDER, data.display());
+    Ok(())
+}
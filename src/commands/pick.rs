@@ -0,0 +1,175 @@
+//! Choosing an item from a list filtered as you type, for commands run
+//! without an ID: `ideavault pick task` prints the chosen ID, and `show` and
+//! `edit` ask when no ID is given.
+
+use super::id::IdArg;
+use super::table::short_id;
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use dialoguer::FuzzySelect;
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Args)]
+pub struct PickArgs {
+    /// What to pick: idea, project, or task
+    pub kind: PickKind,
+
+    /// Start with this filter typed in
+    pub query: Option<String>,
+}
+
+/// The kinds of item that can be picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickKind {
+    Idea,
+    Project,
+    Task,
+}
+
+/// An item offered for picking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Choice {
+    pub id: Uuid,
+    /// What the filter matches and the list shows: title, status, and short ID
+    pub label: String,
+}
+
+pub fn execute(args: PickArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    let id = pick(&storage, args.kind, args.query.as_deref())?;
+    println!("{}", id);
+    Ok(())
+}
+
+impl PickKind {
+    /// The item `id` names, or the one the user picks when there's no `id`.
+    pub fn resolve(self, storage: &Storage, id: Option<&IdArg>) -> Result<Uuid> {
+        match (self, id) {
+            (PickKind::Idea, Some(id)) => id.idea(storage),
+            (PickKind::Project, Some(id)) => id.project(storage),
+            (PickKind::Task, Some(id)) => id.task(storage),
+            (_, None) => pick(storage, self, None),
+        }
+    }
+
+    /// The items `ids` name, in order, or the one the user picks when there
+    /// are no `ids`.
+    pub fn resolve_all(self, storage: &Storage, ids: &[IdArg]) -> Result<Vec<Uuid>> {
+        if ids.is_empty() {
+            return Ok(vec![pick(storage, self, None)?]);
+        }
+        ids.iter()
+            .map(|id| self.resolve(storage, Some(id)))
+            .collect()
+    }
+
+    /// Every item of this kind, most recently updated first.
+    pub fn choices(self, storage: &Storage) -> Result<Vec<Choice>> {
+        let mut choices: Vec<(chrono::DateTime<chrono::Utc>, Choice)> = match self {
+            PickKind::Idea => storage
+                .load_ideas()
+                .context("Failed to load ideas")?
+                .into_iter()
+                .map(|idea| {
+                    let label = label(&idea.title, &idea.status, &idea.id);
+                    (idea.updated_at, Choice { id: idea.id, label })
+                })
+                .collect(),
+            PickKind::Project => storage
+                .load_projects()
+                .context("Failed to load projects")?
+                .into_iter()
+                .map(|project| {
+                    let label = label(&project.title, &project.status, &project.id);
+                    (
+                        project.updated_at,
+                        Choice {
+                            id: project.id,
+                            label,
+                        },
+                    )
+                })
+                .collect(),
+            PickKind::Task => storage
+                .load_tasks()
+                .context("Failed to load tasks")?
+                .into_iter()
+                .map(|task| {
+                    let label = label(&task.title, &task.status, &task.id);
+                    (task.updated_at, Choice { id: task.id, label })
+                })
+                .collect(),
+        };
+        choices.sort_by_key(|(updated_at, _)| std::cmp::Reverse(*updated_at));
+        Ok(choices.into_iter().map(|(_, choice)| choice).collect())
+    }
+}
+
+/// Ask which item of `kind` is meant, listing them all and narrowing the
+/// list as the user types, starting from `query`. Needs a terminal.
+pub fn pick(storage: &Storage, kind: PickKind, query: Option<&str>) -> Result<Uuid> {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        anyhow::bail!(
+            "No {} ID given. Pass an ID or title, or run this in a terminal to pick one",
+            kind
+        );
+    }
+    let choices = kind.choices(storage)?;
+    if choices.is_empty() {
+        anyhow::bail!("No {}s to pick from", kind);
+    }
+
+    // The plain theme draws with ASCII and no colors
+    let colorful = ColorfulTheme::default();
+    let theme: &dyn Theme = if style::enabled() && !symbols::is_plain() {
+        &colorful
+    } else {
+        &SimpleTheme
+    };
+    let labels: Vec<&str> = choices.iter().map(|choice| choice.label.as_str()).collect();
+    let chosen = FuzzySelect::with_theme(theme)
+        .with_prompt(format!("Pick a {} (type to filter, Esc to cancel)", kind))
+        .items(&labels)
+        .with_initial_text(query.unwrap_or_default())
+        .max_length(15)
+        .interact_opt()
+        .context("Failed to read the choice")?;
+    match chosen {
+        Some(index) => Ok(choices[index].id),
+        None => anyhow::bail!("No {} chosen", kind),
+    }
+}
+
+fn label(title: &str, status: &impl fmt::Display, id: &Uuid) -> String {
+    format!("{}  ({})  [{}]", title, status, short_id(id))
+}
+
+impl FromStr for PickKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "idea" | "ideas" => Ok(PickKind::Idea),
+            "project" | "projects" => Ok(PickKind::Project),
+            "task" | "tasks" => Ok(PickKind::Task),
+            _ => anyhow::bail!("Unknown kind '{}'. Use idea, project, or task", s),
+        }
+    }
+}
+
+impl fmt::Display for PickKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickKind::Idea => write!(f, "idea"),
+            PickKind::Project => write!(f, "project"),
+            PickKind::Task => write!(f, "task"),
+        }
+    }
+}
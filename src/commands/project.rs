@@ -1,11 +1,63 @@
+use super::activity::{show_activity, ActivityArgs};
+use super::board::{show_board, BoardArgs};
+use super::dashboard::{count_by, count_line, StatusCount};
+use super::github::{show_github, GithubArgs};
+use super::id::IdArg;
+use super::idea::IdeaFilterArgs;
+use super::input::{ids_or_stdin, text_or_stdin};
+use super::page::{Page, PageArgs};
+use super::pick::PickKind;
+use super::sort::{sort, SortArgs};
+use super::table::{short_id, Cell, Column, Table, TableArgs};
+use super::task::parse_due_date;
+use super::template::find_template;
+use super::timeline::{show_timeline, TimelineArgs};
+use crate::dates;
+use crate::export::{markdown, PROJECT_FIELDS};
 use crate::models::idea::Idea;
-use crate::models::project::{Project, ProjectStatus};
-use crate::storage::Storage;
+use crate::models::project::{dependency_path, descendants, Milestone, Project, ProjectStatus};
+use crate::models::task::{Task, TaskPriority, TaskStatus};
+use crate::output;
+use crate::say;
+use crate::storage::history::{Action, EntityKind, HistoryEntry};
+use crate::storage::{Session, Storage, Unlinked};
+use crate::style::{self, Color};
+use crate::symbols;
 use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+use std::fs;
 use std::io::{self, Write};
+use std::path::PathBuf;
 use uuid::Uuid;
 
+/// Columns `project list` shows unless `--columns` says otherwise.
+const PROJECT_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "status",
+    "blocked",
+    "priority",
+    "due",
+    "milestone",
+    "ideas",
+    "updated",
+];
+
+/// How many changes `project stats` lists.
+const RECENT_ACTIVITY: usize = 5;
+
+/// Field order of milestones printed as JSON or YAML.
+const MILESTONE_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "due_date",
+    "status",
+    "completed_at",
+    "created_at",
+];
+
 #[derive(Parser)]
 #[command(name = "project")]
 #[command(about = "Manage projects")]
@@ -22,18 +74,89 @@ pub enum ProjectSubcommand {
     List(ListProjectArgs),
     /// Show full details of a project
     Show(ShowProjectArgs),
-    /// Link an idea to a project
+    /// Link ideas to a project, by ID or with --from-filter
     Link(LinkArgs),
     /// Remove idea link from project
     Unlink(UnlinkArgs),
     /// List all ideas linked to a project
     Ideas(IdeasArgs),
+    /// Sum up a project's ideas and tasks, what's overdue, and recent changes
+    Stats(StatsArgs),
+    /// Show what happened to a project and its ideas and tasks, oldest first
+    Activity(ActivityArgs),
+    /// Show a project's tasks as a kanban board, one column per status
+    Board(BoardArgs),
+    /// Write a Markdown status report: summary, milestones, completed and upcoming work, blockers
+    Report(ReportArgs),
+    /// Lay out projects and their milestones on a timeline, to spot overlapping commitments
+    Timeline(TimelineArgs),
+    /// Make a project depend on another, so it's blocked until that one is completed
+    Depend(DependArgs),
+    /// Remove a project's dependency on another
+    Undepend(DependArgs),
     /// Update the status of a project
     Status(StatusArgs),
+    /// Copy a project, and optionally its tasks, as a new project in planning
+    Clone(CloneProjectArgs),
+    /// Archive a project, hiding it from lists, and optionally cancel its open tasks
+    Archive(ArchiveProjectArgs),
     /// Delete a project with confirmation
     Delete(DeleteProjectArgs),
-    /// Update project fields (title, description, milestone, url, repo, status)
+    /// Update project fields (title, description, milestone, url, repo, status, priority, tags)
     Update(UpdateProjectArgs),
+    /// Open a project's URL (or repository) in the browser
+    Open(OpenProjectArgs),
+    /// Show the open issues, pull requests, and latest release of a project's GitHub repository
+    Github(GithubArgs),
+    /// Add milestones to a project, list them, and complete them
+    #[command(subcommand)]
+    Milestone(MilestoneSubcommand),
+}
+
+#[derive(Subcommand)]
+pub enum MilestoneSubcommand {
+    /// Add a milestone to a project, after its others unless --position says where
+    Add(AddMilestoneArgs),
+    /// List a project's milestones in order
+    List(ListMilestonesArgs),
+    /// Mark one of a project's milestones completed
+    Complete(CompleteMilestoneArgs),
+}
+
+#[derive(Args)]
+pub struct AddMilestoneArgs {
+    /// The ID or title of the project
+    pub project: IdArg,
+
+    /// The title of the milestone
+    pub title: String,
+
+    /// When the milestone is due (YYYY-MM-DD format)
+    #[arg(long)]
+    pub due: Option<String>,
+
+    /// Where the milestone goes among the others, counting from 1
+    #[arg(long)]
+    pub position: Option<usize>,
+}
+
+#[derive(Args)]
+pub struct ListMilestonesArgs {
+    /// The ID or title of the project
+    pub project: IdArg,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct CompleteMilestoneArgs {
+    /// The ID or title of the project
+    pub project: IdArg,
+
+    /// The milestone to complete, by its number or title
+    pub milestone: String,
 }
 
 #[derive(Args)]
@@ -41,7 +164,7 @@ pub struct NewProjectArgs {
     /// The title of the project
     title: String,
 
-    /// Optional description for the project
+    /// Optional description for the project (`-` reads it from standard input)
     #[arg(short = 'd', long = "description")]
     description: Option<String>,
 
@@ -49,6 +172,22 @@ pub struct NewProjectArgs {
     #[arg(short = 'm', long = "milestone")]
     milestone: Option<String>,
 
+    /// Optional tags (comma-separated)
+    #[arg(short = 't', long = "tags", value_delimiter = ',')]
+    tags: Vec<String>,
+
+    /// Optional priority (low|medium|high|urgent, or a custom priority)
+    #[arg(short = 'p', long = "priority")]
+    priority: Option<TaskPriority>,
+
+    /// Optional due date (YYYY-MM-DD format)
+    #[arg(short = 'D', long = "due")]
+    due_date: Option<String>,
+
+    /// Who the project is with
+    #[arg(long = "assignee")]
+    assignee: Option<String>,
+
     /// Optional URL for the project
     #[arg(long = "url")]
     url: Option<String>,
@@ -56,18 +195,26 @@ pub struct NewProjectArgs {
     /// Optional repository for the project
     #[arg(long = "repo")]
     repo: Option<String>,
+
+    /// Start from a saved template's description, tags, milestones, and tasks
+    #[arg(short = 'T', long = "template")]
+    template: Option<String>,
+
+    /// The ID or title of the project this one is a part of
+    #[arg(long = "parent")]
+    parent: Option<IdArg>,
 }
 
 #[derive(Args)]
 pub struct UpdateProjectArgs {
-    /// Project ID to update
-    pub id: Uuid,
+    /// Project ID or title to update
+    pub id: IdArg,
 
     /// New title
     #[arg(short = 't', long = "title")]
     pub title: Option<String>,
 
-    /// New description
+    /// New description (`-` reads it from standard input)
     #[arg(short = 'd', long = "description")]
     pub description: Option<String>,
 
@@ -87,93 +234,299 @@ pub struct UpdateProjectArgs {
     #[arg(short = 's', long = "status")]
     pub status: Option<ProjectStatus>,
 
-    /// Clear one or more optional fields (description, milestone, url, repo)
+    /// New priority (low|medium|high|urgent, or a custom priority)
+    #[arg(short = 'p', long = "priority")]
+    pub priority: Option<TaskPriority>,
+
+    /// New due date (YYYY-MM-DD format) or "clear" to remove
+    #[arg(short = 'D', long = "due")]
+    pub due_date: Option<String>,
+
+    /// Who the project is with now
+    #[arg(long = "assignee")]
+    pub assignee: Option<String>,
+
+    /// New tags (comma-separated, replaces existing tags)
+    #[arg(short = 'g', long = "tags", value_delimiter = ',')]
+    pub tags: Option<Vec<String>>,
+
+    /// Tags to add (comma-separated)
+    #[arg(long = "add-tags", value_delimiter = ',')]
+    pub add_tags: Vec<String>,
+
+    /// Tags to remove (comma-separated)
+    #[arg(long = "remove-tags", value_delimiter = ',')]
+    pub remove_tags: Vec<String>,
+
+    /// Clear one or more optional fields (description, milestone, url, repo, due_date, assignee, tags)
     #[arg(long = "clear", value_name = "FIELD")]
     pub clear: Vec<String>,
 }
 
 #[derive(Args)]
 pub struct ListProjectArgs {
-    /// Filter by status (Planning|InProgress|Completed|OnHold)
+    /// Filter by status (Planning|InProgress|Completed|OnHold|Archived)
     #[arg(short = 's', long = "status")]
     status: Option<ProjectStatus>,
+
+    /// Include archived projects, which are left out unless --status asks for them
+    #[arg(long = "archived")]
+    archived: bool,
+
+    /// Only projects with this tag; repeat to require several
+    #[arg(short = 't', long = "tag")]
+    tags: Vec<String>,
+
+    /// Show overdue projects only
+    #[arg(long = "overdue")]
+    overdue: bool,
+
+    /// Only projects assigned to this person
+    #[arg(long = "assignee")]
+    assignee: Option<String>,
+
+    /// Show sub-projects indented under their parents, with task progress
+    /// counting the tasks of sub-projects
+    #[arg(long = "tree")]
+    tree: bool,
+
+    #[command(flatten)]
+    sort: SortArgs,
+
+    #[command(flatten)]
+    page: PageArgs,
+
+    #[command(flatten)]
+    table: TableArgs,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
 }
 
 #[derive(Args)]
 pub struct ShowProjectArgs {
-    /// The UUID of the project to show
-    id: Uuid,
+    /// The IDs or titles of the projects to show, or - to read IDs from
+    /// standard input; pick one from a list if left out
+    ids: Vec<IdArg>,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
 }
 
 #[derive(Args)]
 pub struct LinkArgs {
-    /// The UUID of the project
+    /// The ID or title of the project
     #[arg(name = "project-id")]
-    project_id: Uuid,
+    project_id: IdArg,
 
-    /// The UUID of the idea to link
-    #[arg(name = "idea-id")]
-    idea_id: Uuid,
+    /// The IDs or titles of the ideas to link
+    #[arg(name = "idea-id", required_unless_present = "from_filter")]
+    idea_ids: Vec<IdArg>,
+
+    /// Link every idea the filters match instead of naming them
+    #[arg(long, conflicts_with = "idea-id")]
+    from_filter: bool,
+
+    #[command(flatten)]
+    filter: IdeaFilterArgs,
 }
 
 #[derive(Args)]
 pub struct UnlinkArgs {
-    /// The UUID of the project
+    /// The ID or title of the project
     #[arg(name = "project-id")]
-    project_id: Uuid,
+    project_id: IdArg,
 
-    /// The UUID of the idea to unlink
+    /// The ID or title of the idea to unlink
     #[arg(name = "idea-id")]
-    idea_id: Uuid,
+    idea_id: IdArg,
+}
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// The ID or title of the project
+    pub id: IdArg,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// A project's health at a glance.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectStats {
+    pub id: Uuid,
+    pub title: String,
+    pub status: ProjectStatus,
+    /// Linked ideas by status, most common first
+    pub ideas: Vec<StatusCount>,
+    /// The project's tasks by status, most common first
+    pub tasks: Vec<StatusCount>,
+    /// The project's tasks by priority, most common first
+    pub task_priorities: Vec<StatusCount>,
+    /// Open tasks past their due date
+    pub overdue_tasks: usize,
+    pub oldest_open_task: Option<Task>,
+    /// The latest changes to the project and its tasks, newest first
+    pub recent_activity: Vec<HistoryEntry>,
+}
+
+#[derive(Args)]
+pub struct DependArgs {
+    /// The ID or title of the dependent project
+    pub id: IdArg,
+
+    /// The ID or title of the project it depends on
+    pub on: IdArg,
 }
 
 #[derive(Args)]
 pub struct IdeasArgs {
-    /// The UUID of the project
-    id: Uuid,
+    /// The ID or title of the project
+    id: IdArg,
+
+    /// Print only the full ID of each idea, one per line (short for --quiet)
+    #[arg(long)]
+    pub ids: bool,
 }
 
 #[derive(Args)]
 pub struct StatusArgs {
-    /// The UUID of the project to update
-    id: Uuid,
+    /// The ID or title of the project to update
+    id: IdArg,
 
     /// New status for the project
     status: ProjectStatus,
 }
 
+#[derive(Args)]
+pub struct ReportArgs {
+    /// The ID or title of the project
+    pub id: IdArg,
+
+    /// Start of the period the report covers (YYYY-MM-DD); the last 7 days if not given
+    #[arg(long, value_name = "DATE", value_parser = parse_due_date)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Write the report to this file instead of printing it
+    #[arg(short, long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct CloneProjectArgs {
+    /// The ID or title of the project to copy
+    pub id: IdArg,
+
+    /// Title of the copy, instead of the original's with " (copy)" added
+    #[arg(short = 't', long = "title")]
+    pub title: Option<String>,
+
+    /// Also copy the project's tasks, as tasks to do
+    #[arg(long)]
+    pub with_tasks: bool,
+}
+
+#[derive(Args)]
+pub struct ArchiveProjectArgs {
+    /// The ID or title of the project to archive
+    pub id: IdArg,
+
+    /// Also cancel the project's open tasks
+    #[arg(long)]
+    pub cancel_tasks: bool,
+}
+
 #[derive(Args)]
 pub struct DeleteProjectArgs {
-    /// The UUID of the project to delete
-    id: Uuid,
+    /// The ID or title of the project to delete
+    pub id: IdArg,
 
     /// Skip confirmation prompt
     #[arg(short, long)]
-    force: bool,
+    pub force: bool,
+
+    /// Leave tasks in this project pointing at it
+    #[arg(long, conflicts_with_all = ["with_tasks", "orphan_tasks"])]
+    pub keep_links: bool,
+
+    /// Move the project's tasks to the trash too
+    #[arg(long, conflicts_with = "orphan_tasks")]
+    pub with_tasks: bool,
+
+    /// Keep the project's tasks but unlink them from it (the default)
+    #[arg(long)]
+    pub orphan_tasks: bool,
+}
+
+#[derive(Args)]
+pub struct OpenProjectArgs {
+    /// The ID or title of the project to open
+    pub id: IdArg,
+
+    /// Open the repository instead of the URL
+    #[arg(long)]
+    pub repo: bool,
 }
 
 impl ProjectCommands {
     pub fn execute(&self) -> Result<()> {
+        // IDs piped to `show` are read before the vault is locked, as the
+        // command piping them in holds the lock until it exits
+        let shown = match &self.command {
+            ProjectSubcommand::Show(args) => ids_or_stdin(&args.ids)?,
+            _ => Vec::new(),
+        };
         let storage = Storage::new().context("Failed to initialize storage")?;
 
         match &self.command {
             ProjectSubcommand::New(args) => Self::new_project(&storage, args),
             ProjectSubcommand::List(args) => Self::list_projects(&storage, args),
-            ProjectSubcommand::Show(args) => Self::show_project(&storage, args),
+            ProjectSubcommand::Show(_) => Self::show_project(&storage, &shown),
             ProjectSubcommand::Link(args) => Self::link_idea(&storage, args),
             ProjectSubcommand::Unlink(args) => Self::unlink_idea(&storage, args),
             ProjectSubcommand::Ideas(args) => Self::list_project_ideas(&storage, args),
+            ProjectSubcommand::Stats(args) => Self::show_stats(&storage, args),
+            ProjectSubcommand::Activity(args) => show_activity(&storage, args),
+            ProjectSubcommand::Board(args) => show_board(&storage, args),
+            ProjectSubcommand::Report(args) => Self::write_report(&storage, args),
+            ProjectSubcommand::Timeline(args) => show_timeline(&storage, args),
+            ProjectSubcommand::Depend(args) => Self::add_dependency(&storage, args),
+            ProjectSubcommand::Undepend(args) => Self::remove_dependency(&storage, args),
             ProjectSubcommand::Status(args) => Self::update_status(&storage, args),
+            ProjectSubcommand::Clone(args) => Self::clone_project(&storage, args),
+            ProjectSubcommand::Archive(args) => Self::archive_project(&storage, args),
             ProjectSubcommand::Delete(args) => Self::delete_project(&storage, args),
             ProjectSubcommand::Update(args) => Self::update_project(&storage, args),
+            ProjectSubcommand::Open(args) => Self::open_project(&storage, args),
+            ProjectSubcommand::Github(args) => show_github(&storage, args),
+            ProjectSubcommand::Milestone(MilestoneSubcommand::Add(args)) => {
+                Self::add_milestone(&storage, args)
+            }
+            ProjectSubcommand::Milestone(MilestoneSubcommand::List(args)) => {
+                Self::list_milestones(&storage, args)
+            }
+            ProjectSubcommand::Milestone(MilestoneSubcommand::Complete(args)) => {
+                Self::complete_milestone(&storage, args)
+            }
         }
     }
 
     fn new_project(storage: &Storage, args: &NewProjectArgs) -> Result<()> {
-        let mut project = Project::new(args.title.clone());
+        let template = args
+            .template
+            .as_deref()
+            .map(|name| find_template(storage, name))
+            .transpose()?;
+        let (mut project, tasks) = match &template {
+            Some(template) => template.instantiate(args.title.clone()),
+            None => (Project::new(args.title.clone()), Vec::new()),
+        };
 
         if let Some(description) = &args.description {
-            project = project.with_description(description.clone());
+            project = project.with_description(text_or_stdin(description)?);
         }
 
         if let Some(milestone) = &args.milestone {
@@ -186,132 +539,543 @@ impl ProjectCommands {
         if let Some(repo) = &args.repo {
             project = project.with_repo(repo.clone());
         }
+        for tag in &args.tags {
+            project.add_tag(tag.clone());
+        }
+        if let Some(priority) = &args.priority {
+            project = project.with_priority(priority.clone());
+        }
+        if let Some(due_date) = &args.due_date {
+            project = project.with_due_date(parse_due_date(due_date)?);
+        }
+        if let Some(assignee) = &args.assignee {
+            project = project.with_assignee(assignee.clone());
+        }
+        if let Some(parent) = &args.parent {
+            project = project.with_parent(parent.project(storage)?);
+        }
 
-        let mut projects = storage.load_projects().context("Failed to load projects")?;
-        projects.push(project.clone());
         storage
-            .save_projects(&projects)
-            .context("Failed to save projects")?;
+            .add_project(&project)
+            .context("Failed to save project")?;
+        for task in &tasks {
+            storage.add_task(task).context("Failed to save task")?;
+        }
 
-        println!("✅ Created new project:");
+        if output::is_quiet() {
+            println!("{}", project.id);
+            return Ok(());
+        }
+        println!("{} Created new project:", symbols::OK);
         print_project_summary(&project);
+        if let Some(template) = &template {
+            println!(
+                "   From template '{}': {} milestone(s), {} task(s)",
+                template.name,
+                project.milestones.len(),
+                tasks.len()
+            );
+        }
         Ok(())
     }
 
     fn list_projects(storage: &Storage, args: &ListProjectArgs) -> Result<()> {
-        let mut projects = storage.load_projects().context("Failed to load projects")?;
+        let all = storage.load_projects().context("Failed to load projects")?;
+        let mut projects = all.clone();
 
         // Apply filters
         if let Some(status_filter) = &args.status {
             projects.retain(|project| &project.status == status_filter);
+        } else if !args.archived {
+            projects.retain(|project| project.status != ProjectStatus::Archived);
+        }
+        projects.retain(|project| args.tags.iter().all(|tag| project.tags.contains(tag)));
+        if args.overdue {
+            let now = Utc::now();
+            projects.retain(|project| project.is_overdue(now));
+        }
+        if let Some(assignee) = &args.assignee {
+            projects.retain(|project| project.is_assigned_to(assignee));
+        }
+
+        sort(&mut projects, &args.sort)?;
+        let page = Page::from_config(projects.len(), &args.page)?;
+        if !output::is_table() {
+            return output::print_list(&projects[page.start..page.end], PROJECT_FIELDS);
+        }
+        if output::is_quiet() {
+            output::print_ids(
+                projects[page.start..page.end]
+                    .iter()
+                    .map(|project| project.id),
+            );
+            return Ok(());
         }
 
         if projects.is_empty() {
-            println!("📋 No projects found");
+            println!("{} No projects found", symbols::LIST);
             return Ok(());
         }
 
-        println!("📋 Found {} project(s):", projects.len());
+        let tasks = storage.load_tasks().context("Failed to load tasks")?;
+        if args.tree {
+            println!("{} Found {} project(s):", symbols::LIST, projects.len());
+            println!();
+            print_project_tree(&projects, &all, &tasks);
+            return Ok(());
+        }
+        let table = Table::new(project_columns(&all, &tasks), PROJECT_COLUMNS, &args.table)?;
+        println!("{} Found {} project(s):", symbols::LIST, projects.len());
         println!();
-
-        for project in &projects {
-            print_project_summary(project);
+        print!("{}", table.render(&projects[page.start..page.end]));
+        if let Some(footer) = page.footer("project") {
             println!();
+            println!("{}", footer);
         }
 
         Ok(())
     }
 
-    fn show_project(storage: &Storage, args: &ShowProjectArgs) -> Result<()> {
-        let projects = storage.load_projects().context("Failed to load projects")?;
-        let ideas = storage.load_ideas().context("Failed to load ideas")?;
+    fn show_project(storage: &Storage, ids: &[IdArg]) -> Result<()> {
+        let ids = PickKind::Project.resolve_all(storage, ids)?;
+        let session = Session::new(storage);
+
+        let projects = ids
+            .into_iter()
+            .map(|id| {
+                session
+                    .projects()?
+                    .iter()
+                    .find(|project| project.id == id)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !output::is_table() {
+            return match projects.as_slice() {
+                [project] => output::print_item(project, PROJECT_FIELDS),
+                _ => output::print_list(&projects, PROJECT_FIELDS),
+            };
+        }
 
-        let project = projects
-            .iter()
-            .find(|project| project.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", args.id))?;
+        for (number, project) in projects.iter().enumerate() {
+            if number > 0 {
+                println!();
+            }
+            print_project_full(project, &session)?;
+        }
+        Ok(())
+    }
+
+    fn open_project(storage: &Storage, args: &OpenProjectArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let project = storage
+            .load_project(id)?
+            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", id))?;
+        let link = project_link(&project, args.repo)?;
+
+        open::that_detached(&link).with_context(|| format!("Failed to open {}", link))?;
+        say!("{} Opened {}", symbols::OPENED, link);
+        Ok(())
+    }
+
+    pub fn add_milestone(storage: &Storage, args: &AddMilestoneArgs) -> Result<()> {
+        let id = args.project.project(storage)?;
+        let mut milestone = Milestone::new(args.title.clone());
+        if let Some(due) = &args.due {
+            milestone = milestone.with_due_date(parse_due_date(due)?);
+        }
+        let position = storage.update_project(id, |project| {
+            project.add_milestone(milestone, args.position)
+        })?;
+
+        say!(
+            "{} Added milestone {} to project {}: {}",
+            symbols::MILESTONE,
+            position,
+            id,
+            args.title
+        );
+        Ok(())
+    }
+
+    pub fn list_milestones(storage: &Storage, args: &ListMilestonesArgs) -> Result<()> {
+        let id = args.project.project(storage)?;
+        let project = storage.get_project(id)?;
+        if !output::is_table() {
+            return output::print_list(&project.milestones, MILESTONE_FIELDS);
+        }
+
+        if project.milestones.is_empty() {
+            println!(
+                "{} No milestones on '{}'. Add one with: ideavault project milestone add {} <TITLE>",
+                symbols::MILESTONE,
+                project.title,
+                project.id
+            );
+            return Ok(());
+        }
+        let (completed, total) = project.milestone_progress();
+        println!(
+            "{} {} of {} milestone(s) completed on '{}':",
+            symbols::MILESTONE,
+            completed,
+            total,
+            project.title
+        );
+        println!();
+        print_milestones(&project.milestones);
+        Ok(())
+    }
+
+    pub fn complete_milestone(storage: &Storage, args: &CompleteMilestoneArgs) -> Result<()> {
+        let id = args.project.project(storage)?;
+        let project = storage.get_project(id)?;
+        let Some(index) = project.find_milestone(&args.milestone) else {
+            anyhow::bail!(
+                "Project '{}' has no milestone '{}'. It has {} milestone(s)",
+                project.title,
+                args.milestone,
+                project.milestones.len()
+            );
+        };
+        let milestone = &project.milestones[index];
+        if milestone.is_completed() {
+            say!(
+                "{} Milestone '{}' is already completed",
+                symbols::WARNING,
+                milestone.title
+            );
+            return Ok(());
+        }
 
-        print_project_full(project, &ideas);
+        let (completed, total) = storage.update_project(id, |project| {
+            project.complete_milestone(index);
+            project.milestone_progress()
+        })?;
+        say!(
+            "{} Completed milestone '{}' of project {}",
+            symbols::OK,
+            milestone.title,
+            id
+        );
+        say!("   {} of {} milestone(s) completed", completed, total);
         Ok(())
     }
 
     fn link_idea(storage: &Storage, args: &LinkArgs) -> Result<()> {
-        let mut projects = storage.load_projects().context("Failed to load projects")?;
+        let project_id = args.project_id.project(storage)?;
         let ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let chosen: Vec<&Idea> = if args.from_filter {
+            if !args.filter.is_set() {
+                anyhow::bail!("No filter given. Add a filter such as --tag, or name the ideas");
+            }
+            ideas
+                .iter()
+                .filter(|idea| args.filter.matches(idea))
+                .collect()
+        } else {
+            if args.filter.is_set() {
+                anyhow::bail!("Filters only apply with --from-filter");
+            }
+            let mut chosen: Vec<&Idea> = Vec::new();
+            for id in &args.idea_ids {
+                let id = id.idea(storage)?;
+                let idea = ideas
+                    .iter()
+                    .find(|idea| idea.id == id)
+                    .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", id))?;
+                if !chosen.iter().any(|seen| seen.id == id) {
+                    chosen.push(idea);
+                }
+            }
+            chosen
+        };
+        if chosen.is_empty() {
+            say!("{} No ideas match the filters", symbols::LIST);
+            return Ok(());
+        }
 
-        // Verify idea exists
-        if !ideas.iter().any(|idea| idea.id == args.idea_id) {
-            return Err(anyhow::anyhow!("Idea with ID {} not found", args.idea_id));
+        let (linked, already): (Vec<&Idea>, Vec<&Idea>) =
+            storage.update_project(project_id, |project| {
+                chosen.iter().partition(|idea| {
+                    if project.idea_ids.contains(&idea.id) {
+                        return false;
+                    }
+                    project.add_idea(idea.id);
+                    true
+                })
+            })?;
+
+        let line = |idea: &Idea| format!("   {} [{}]", idea.title, style::dim(short_id(&idea.id)));
+        if !linked.is_empty() {
+            say!(
+                "{} Linked {} idea(s) to project {}",
+                symbols::OK,
+                linked.len(),
+                project_id
+            );
+            for idea in &linked {
+                say!("{}", line(idea));
+            }
+        }
+        if !already.is_empty() {
+            say!(
+                "{} {} idea(s) already linked to project {}",
+                symbols::WARNING,
+                already.len(),
+                project_id
+            );
+            for idea in &already {
+                say!("{}", line(idea));
+            }
         }
+        Ok(())
+    }
 
-        let project = projects
-            .iter_mut()
-            .find(|project| project.id == args.project_id)
-            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", args.project_id))?;
+    fn write_report(storage: &Storage, args: &ReportArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let now = Utc::now();
+        let since = args.since.unwrap_or(now - Duration::days(7));
+        if since > now {
+            anyhow::bail!("--since can't be in the future");
+        }
+        let report = project_report(storage, id, since, now)?;
 
-        if project.idea_ids.contains(&args.idea_id) {
-            println!(
-                "⚠️  Idea {} is already linked to project {}",
-                args.idea_id, args.project_id
-            );
+        let Some(path) = &args.out else {
+            print!("{}", report);
             return Ok(());
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
         }
+        fs::write(path, report).with_context(|| format!("Failed to write report: {:?}", path))?;
+        say!("{} Wrote report to {}", symbols::PAGE, path.display());
+        Ok(())
+    }
 
-        project.add_idea(args.idea_id);
-        storage
-            .save_projects(&projects)
-            .context("Failed to save projects")?;
+    fn show_stats(storage: &Storage, args: &StatsArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let stats = project_stats(storage, id, Utc::now())?;
+        if !output::is_table() {
+            return output::print_item(&stats, &[]);
+        }
 
+        let tasks = storage.load_tasks().context("Failed to load tasks")?;
+        println!(
+            "{} Stats for project {} [{}]",
+            symbols::COUNTS,
+            style::bold(&stats.title),
+            style::dim(short_id(&stats.id))
+        );
         println!(
-            "✅ Linked idea {} to project {}",
-            args.idea_id, args.project_id
+            "   Status: {}",
+            style::paint(&stats.status, style::project_status_color(&stats.status))
         );
+        println!("   Ideas: {}", count_line(&stats.ideas));
+        println!("   Tasks: {}", count_line(&stats.tasks));
+        println!("   Task priorities: {}", count_line(&stats.task_priorities));
+        if stats.overdue_tasks > 0 {
+            println!(
+                "   Overdue tasks: {}",
+                style::paint(stats.overdue_tasks, Color::Red)
+            );
+        } else {
+            println!("   Overdue tasks: 0");
+        }
+        if let Some(task) = &stats.oldest_open_task {
+            println!(
+                "   Oldest open task: {} [{}] {}",
+                task.title,
+                style::dim(short_id(&task.id)),
+                style::dim(format!(
+                    "created {}",
+                    dates::moment(task.created_at, dates::DATE)
+                ))
+            );
+        }
+
+        println!();
+        if stats.recent_activity.is_empty() {
+            println!("{} No recorded changes", symbols::RECENT);
+            return Ok(());
+        }
+        println!("{} Recent activity:", symbols::RECENT);
+        for entry in &stats.recent_activity {
+            let (marker, verb) = match entry.action {
+                Action::Create => (symbols::NEW, "Created"),
+                Action::Update => (symbols::EDITED, "Updated"),
+                Action::Delete => (symbols::TRASH, "Deleted"),
+            };
+            let title = match entry.entity {
+                EntityKind::Task => tasks
+                    .iter()
+                    .find(|task| task.id.to_string() == entry.entity_id)
+                    .map(|task| format!("task '{}'", task.title)),
+                _ => Some("the project".to_string()),
+            }
+            .unwrap_or_else(|| format!("{} {}", entry.entity, entry.entity_id));
+            println!(
+                "   {} {} {} {}",
+                marker,
+                verb,
+                title,
+                style::dim(dates::moment(entry.timestamp, dates::DATE_TIME))
+            );
+        }
         Ok(())
     }
 
-    fn unlink_idea(storage: &Storage, args: &UnlinkArgs) -> Result<()> {
-        let mut projects = storage.load_projects().context("Failed to load projects")?;
+    /// Make one project depend on another, refusing dependencies that would
+    /// go around in a circle.
+    pub fn add_dependency(storage: &Storage, args: &DependArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let on = args.on.project(storage)?;
+        let projects = storage.load_projects().context("Failed to load projects")?;
+        let title = |id: Uuid| {
+            projects
+                .iter()
+                .find(|project| project.id == id)
+                .map(|project| project.title.clone())
+                .unwrap_or_else(|| id.to_string())
+        };
+        if id == on {
+            anyhow::bail!("A project can't depend on itself");
+        }
+        if let Some(path) = dependency_path(&projects, on, id) {
+            let chain: Vec<String> = std::iter::once(id).chain(path).map(title).collect();
+            anyhow::bail!(
+                "'{}' can't depend on '{}', as that would make a cycle: {}",
+                title(id),
+                title(on),
+                chain.join(" → ")
+            );
+        }
 
-        let project = projects
-            .iter_mut()
-            .find(|project| project.id == args.project_id)
-            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", args.project_id))?;
+        let added = storage.update_project(id, |project| {
+            if project.depends_on.contains(&on) {
+                return false;
+            }
+            project.add_dependency(on);
+            true
+        })?;
+        if !added {
+            say!(
+                "{} '{}' already depends on '{}'",
+                symbols::WARNING,
+                title(id),
+                title(on)
+            );
+            return Ok(());
+        }
+        say!(
+            "{} '{}' now depends on '{}'",
+            symbols::OK,
+            title(id),
+            title(on)
+        );
+        Ok(())
+    }
 
-        if !project.idea_ids.contains(&args.idea_id) {
-            println!(
-                "⚠️  Idea {} is not linked to project {}",
-                args.idea_id, args.project_id
+    pub fn remove_dependency(storage: &Storage, args: &DependArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let on = args.on.project(storage)?;
+        let removed = storage.update_project(id, |project| {
+            if !project.depends_on.contains(&on) {
+                return false;
+            }
+            project.remove_dependency(&on);
+            true
+        })?;
+
+        if !removed {
+            say!(
+                "{} Project {} does not depend on project {}",
+                symbols::WARNING,
+                id,
+                on
             );
             return Ok(());
         }
+        say!(
+            "{} Project {} no longer depends on project {}",
+            symbols::OK,
+            id,
+            on
+        );
+        Ok(())
+    }
 
-        project.remove_idea(&args.idea_id);
-        storage
-            .save_projects(&projects)
-            .context("Failed to save projects")?;
+    fn unlink_idea(storage: &Storage, args: &UnlinkArgs) -> Result<()> {
+        let project_id = args.project_id.project(storage)?;
+        // The idea may be gone already, so look among the project's links
+        let project = storage.get_project(project_id)?;
+        let ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let linked: Vec<(Uuid, String)> = project
+            .idea_ids
+            .iter()
+            .map(|id| {
+                let title = ideas.iter().find(|idea| idea.id == *id);
+                (
+                    *id,
+                    title.map(|idea| idea.title.clone()).unwrap_or_default(),
+                )
+            })
+            .collect();
+        let idea_id = args.idea_id.resolve("linked idea", &linked)?;
+        let unlinked = storage.update_project(project_id, |project| {
+            if !project.idea_ids.contains(&idea_id) {
+                return false;
+            }
+            project.remove_idea(&idea_id);
+            true
+        })?;
+
+        if !unlinked {
+            say!(
+                "{} Idea {} is not linked to project {}",
+                symbols::WARNING,
+                idea_id,
+                project_id
+            );
+            return Ok(());
+        }
 
-        println!(
-            "✅ Unlinked idea {} from project {}",
-            args.idea_id, args.project_id
+        say!(
+            "{} Unlinked idea {} from project {}",
+            symbols::OK,
+            idea_id,
+            project_id
         );
         Ok(())
     }
 
     fn list_project_ideas(storage: &Storage, args: &IdeasArgs) -> Result<()> {
-        let projects = storage.load_projects().context("Failed to load projects")?;
-        let ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let id = args.id.project(storage)?;
+        let session = Session::new(storage);
 
-        let project = projects
+        let project = session
+            .projects()?
             .iter()
-            .find(|project| project.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", args.id))?;
+            .find(|project| project.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", id))?;
+        if output::is_quiet() {
+            output::print_ids(project.idea_ids.iter().copied());
+            return Ok(());
+        }
 
         if project.idea_ids.is_empty() {
-            println!("📋 No ideas linked to project {}", args.id);
+            println!("{} No ideas linked to project {}", symbols::LIST, id);
             return Ok(());
         }
 
-        println!("💡 Ideas linked to project {}:", project.title);
+        let ideas = session.ideas()?;
+        println!(
+            "{} Ideas linked to project {}:",
+            symbols::IDEA,
+            project.title
+        );
         println!("   Total: {} ideas", project.idea_ids.len());
         println!();
 
@@ -320,7 +1084,7 @@ impl ProjectCommands {
                 print_idea_in_project(idea);
                 println!();
             } else {
-                println!("⚠️  Idea {} not found in storage", idea_id);
+                println!("{} Idea {} not found in storage", symbols::WARNING, idea_id);
             }
         }
 
@@ -328,27 +1092,29 @@ impl ProjectCommands {
     }
 
     fn update_status(storage: &Storage, args: &StatusArgs) -> Result<()> {
-        let mut projects = storage.load_projects().context("Failed to load projects")?;
-
-        let project = projects
-            .iter_mut()
-            .find(|project| project.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", args.id))?;
-
-        let old_status = project.status.clone();
-        project.set_status(args.status.clone());
-
-        storage
-            .save_projects(&projects)
-            .context("Failed to save projects")?;
-
-        println!("✅ Updated status for project {}:", args.id);
-        println!("   {} → {}", old_status, args.status);
+        let id = args.id.project(storage)?;
+        let old_status = storage.update_project(id, |project| {
+            let old_status = project.status.clone();
+            project.set_status(args.status.clone());
+            old_status
+        })?;
+
+        say!("{} Updated status for project {}:", symbols::OK, id);
+        say!("   {} → {}", old_status, args.status);
         Ok(())
     }
 
     pub fn update_project(storage: &Storage, args: &UpdateProjectArgs) -> Result<()> {
-        const CLEARABLE_FIELDS: [&str; 4] = ["description", "milestone", "url", "repo"];
+        let id = args.id.project(storage)?;
+        const CLEARABLE_FIELDS: [&str; 7] = [
+            "description",
+            "milestone",
+            "url",
+            "repo",
+            "due_date",
+            "assignee",
+            "tags",
+        ];
 
         // Validate clear fields
         for field in &args.clear {
@@ -361,120 +1127,304 @@ impl ProjectCommands {
             }
         }
 
-        let mut projects = storage.load_projects().context("Failed to load projects")?;
+        let due_date = match &args.due_date {
+            Some(due_date_str) if due_date_str.to_lowercase() == "clear" => Some(None),
+            Some(due_date_str) => Some(Some(parse_due_date(due_date_str)?)),
+            None => None,
+        };
 
-        let project = projects
-            .iter_mut()
-            .find(|p| p.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", args.id))?;
+        let description = args.description.as_deref().map(text_or_stdin).transpose()?;
+        let changes = storage.update_project(id, |project| {
+            let mut changes: Vec<String> = Vec::new();
 
-        let mut changes: Vec<String> = Vec::new();
+            // Update title
+            if let Some(title) = &args.title {
+                let old = project.title.clone();
+                project.update_title(title.clone());
+                changes.push(format!("title: \"{}\" → \"{}\"", old, title));
+            }
 
-        // Update title
-        if let Some(title) = &args.title {
-            let old = project.title.clone();
-            project.update_title(title.clone());
-            changes.push(format!("title: \"{}\" → \"{}\"", old, title));
-        }
+            // Update description
+            if let Some(desc) = &description {
+                let old = project.description.clone().unwrap_or_default();
+                project.update_description(Some(desc.clone()));
+                changes.push(format!("description: \"{}\" → \"{}\"", old, desc));
+            }
 
-        // Update description
-        if let Some(desc) = &args.description {
-            let old = project.description.clone().unwrap_or_default();
-            project.update_description(Some(desc.clone()));
-            changes.push(format!("description: \"{}\" → \"{}\"", old, desc));
-        }
+            // Update milestone
+            if let Some(milestone) = &args.milestone {
+                let old = project.milestone.clone().unwrap_or_default();
+                project.update_milestone(Some(milestone.clone()));
+                changes.push(format!("milestone: \"{}\" → \"{}\"", old, milestone));
+            }
 
-        // Update milestone
-        if let Some(milestone) = &args.milestone {
-            let old = project.milestone.clone().unwrap_or_default();
-            project.update_milestone(Some(milestone.clone()));
-            changes.push(format!("milestone: \"{}\" → \"{}\"", old, milestone));
-        }
+            // Update URL
+            if let Some(url) = &args.url {
+                let old = project.url.clone().unwrap_or_default();
+                project.set_url(Some(url.clone()));
+                changes.push(format!("url: \"{}\" → \"{}\"", old, url));
+            }
 
-        // Update URL
-        if let Some(url) = &args.url {
-            let old = project.url.clone().unwrap_or_default();
-            project.set_url(Some(url.clone()));
-            changes.push(format!("url: \"{}\" → \"{}\"", old, url));
-        }
+            // Update repo
+            if let Some(repo) = &args.repo {
+                let old = project.repo.clone().unwrap_or_default();
+                project.set_repo(Some(repo.clone()));
+                changes.push(format!("repo: \"{}\" → \"{}\"", old, repo));
+            }
 
-        // Update repo
-        if let Some(repo) = &args.repo {
-            let old = project.repo.clone().unwrap_or_default();
-            project.set_repo(Some(repo.clone()));
-            changes.push(format!("repo: \"{}\" → \"{}\"", old, repo));
-        }
+            // Update status
+            if let Some(status) = &args.status {
+                let old = project.status.clone();
+                project.set_status(status.clone());
+                changes.push(format!("status: {} → {}", old, status));
+            }
 
-        // Update status
-        if let Some(status) = &args.status {
-            let old = project.status.clone();
-            project.set_status(status.clone());
-            changes.push(format!("status: {} → {}", old, status));
-        }
+            // Update priority
+            if let Some(priority) = &args.priority {
+                let old = project.priority.clone();
+                project.set_priority(priority.clone());
+                changes.push(format!("priority: {} → {}", old, priority));
+            }
 
-        // Clear fields
-        for field in &args.clear {
-            match field.as_str() {
-                "description" => {
-                    project.update_description(None);
-                    changes.push("description: cleared".to_string());
+            // Update due date
+            match due_date {
+                Some(None) => {
+                    project.set_due_date(None);
+                    changes.push("due_date: cleared".to_string());
+                }
+                Some(Some(due_date)) => {
+                    let old = project
+                        .due_date
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                        .unwrap_or_else(|| "none".to_string());
+                    project.set_due_date(Some(due_date));
+                    changes.push(format!(
+                        "due_date: {} → {}",
+                        old,
+                        due_date.format("%Y-%m-%d")
+                    ));
+                }
+                None => {}
+            }
+
+            // Update assignee
+            if let Some(assignee) = &args.assignee {
+                let old = project.assignee.clone().unwrap_or_default();
+                project.set_assignee(Some(assignee.clone()));
+                changes.push(match &project.assignee {
+                    Some(new) => format!("assignee: \"{}\" → \"{}\"", old, new),
+                    None => "assignee: cleared".to_string(),
+                });
+            }
+
+            // Update tags: replace them, then add and remove
+            if args.tags.is_some() || !args.add_tags.is_empty() || !args.remove_tags.is_empty() {
+                let old_tags = project.tags.clone();
+                if let Some(tags) = &args.tags {
+                    project.update_tags(tags.clone());
                 }
-                "milestone" => {
-                    project.update_milestone(None);
-                    changes.push("milestone: cleared".to_string());
+                for tag in &args.add_tags {
+                    project.add_tag(tag.clone());
                 }
-                "url" => {
-                    project.set_url(None);
-                    changes.push("url: cleared".to_string());
+                for tag in &args.remove_tags {
+                    project.remove_tag(tag);
                 }
-                "repo" => {
-                    project.set_repo(None);
-                    changes.push("repo: cleared".to_string());
+                changes.push(format!("tags: {:?} → {:?}", old_tags, project.tags));
+            }
+
+            // Clear fields
+            for field in &args.clear {
+                match field.as_str() {
+                    "description" => {
+                        project.update_description(None);
+                        changes.push("description: cleared".to_string());
+                    }
+                    "milestone" => {
+                        project.update_milestone(None);
+                        changes.push("milestone: cleared".to_string());
+                    }
+                    "url" => {
+                        project.set_url(None);
+                        changes.push("url: cleared".to_string());
+                    }
+                    "repo" => {
+                        project.set_repo(None);
+                        changes.push("repo: cleared".to_string());
+                    }
+                    "due_date" => {
+                        project.set_due_date(None);
+                        changes.push("due_date: cleared".to_string());
+                    }
+                    "assignee" => {
+                        project.set_assignee(None);
+                        changes.push("assignee: cleared".to_string());
+                    }
+                    "tags" => {
+                        project.update_tags(Vec::new());
+                        changes.push("tags: cleared".to_string());
+                    }
+                    _ => unreachable!(),
                 }
-                _ => unreachable!(),
             }
-        }
+            changes
+        })?;
 
         if changes.is_empty() {
-            println!("No changes specified for project {}", args.id);
-            println!("Use --help to see available options.");
+            say!("No changes specified for project {}", id);
+            say!("Use --help to see available options.");
             return Ok(());
         }
 
-        storage
-            .save_projects(&projects)
-            .context("Failed to save projects")?;
-
-        println!("✅ Updated project {}:", args.id);
+        say!("{} Updated project {}:", symbols::OK, id);
         for change in &changes {
-            println!("   {}", change);
+            say!("   {}", change);
         }
 
         Ok(())
     }
 
-    fn delete_project(storage: &Storage, args: &DeleteProjectArgs) -> Result<()> {
-        let mut projects = storage.load_projects().context("Failed to load projects")?;
+    /// Copy a project under a new ID, back in planning with its milestones
+    /// open again. With `--with-tasks`, its tasks are copied too, as to do.
+    pub fn clone_project(storage: &Storage, args: &CloneProjectArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let original = storage.get_project(id)?;
 
-        let project_index = projects
+        let fresh = Project::new(
+            args.title
+                .clone()
+                .unwrap_or_else(|| format!("{} (copy)", original.title)),
+        );
+        let milestones = original
+            .milestones
             .iter()
-            .position(|project| project.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", args.id))?;
+            .map(|milestone| Milestone {
+                due_date: milestone.due_date,
+                ..Milestone::new(milestone.title.clone())
+            })
+            .collect();
+        let project = Project {
+            id: fresh.id,
+            title: fresh.title,
+            status: fresh.status,
+            milestones,
+            created_at: fresh.created_at,
+            updated_at: fresh.updated_at,
+            ..original
+        };
+        storage
+            .add_project(&project)
+            .context("Failed to save project")?;
+
+        let mut copied = 0;
+        if args.with_tasks {
+            let tasks = storage.load_tasks().context("Failed to load tasks")?;
+            for task in tasks.into_iter().filter(|task| task.project_id == Some(id)) {
+                let fresh = Task::new(task.title.clone());
+                let task = Task {
+                    id: fresh.id,
+                    status: fresh.status,
+                    project_id: Some(project.id),
+                    created_at: fresh.created_at,
+                    updated_at: fresh.updated_at,
+                    ..task
+                };
+                storage.add_task(&task).context("Failed to save task")?;
+                copied += 1;
+            }
+        }
+
+        if output::is_quiet() {
+            println!("{}", project.id);
+            return Ok(());
+        }
+        println!("{} Cloned project {}:", symbols::OK, id);
+        print_project_summary(&project);
+        if args.with_tasks {
+            println!("   Copied {} task(s)", copied);
+        }
+        Ok(())
+    }
+
+    pub fn archive_project(storage: &Storage, args: &ArchiveProjectArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let project = storage.get_project(id)?;
+        if project.status == ProjectStatus::Archived {
+            anyhow::bail!("Project '{}' is already archived", project.title);
+        }
+        storage.update_project(id, |project| project.set_status(ProjectStatus::Archived))?;
+
+        let mut cancelled = Vec::new();
+        if args.cancel_tasks {
+            let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
+            for task in tasks.iter_mut() {
+                if task.project_id == Some(id) && !task.status.is_closed() {
+                    task.set_status(TaskStatus::Cancelled);
+                    cancelled.push(task.title.clone());
+                }
+            }
+            if !cancelled.is_empty() {
+                storage.save_tasks(&tasks).context("Failed to save tasks")?;
+            }
+        }
+
+        say!(
+            "{} Archived project: {}",
+            symbols::project_status(&ProjectStatus::Archived),
+            project.title
+        );
+        if !cancelled.is_empty() {
+            say!(
+                "   Cancelled {} open task(s): {}",
+                cancelled.len(),
+                cancelled.join(", ")
+            );
+        }
+        Ok(())
+    }
 
-        let project = &projects[project_index];
+    pub fn delete_project(storage: &Storage, args: &DeleteProjectArgs) -> Result<()> {
+        let id = args.id.project(storage)?;
+        let project = storage.get_project(id)?;
 
         if !args.force {
-            println!("📋 Project to delete:");
-            print_project_summary(project);
+            say!("{} Project to delete:", symbols::LIST);
+            print_project_summary(&project);
 
             if !project.idea_ids.is_empty() {
-                println!(
-                    "⚠️  This project has {} linked ideas. They will not be deleted.",
+                say!(
+                    "{} This project has {} linked ideas. They will not be deleted.",
+                    symbols::WARNING,
                     project.idea_ids.len()
                 );
             }
 
-            print!("Are you sure you want to delete this project? [y/N]: ");
+            let tasks = storage.load_tasks().context("Failed to load tasks")?;
+            let task_count = tasks
+                .iter()
+                .filter(|t| t.project_id == Some(project.id))
+                .count();
+            if task_count > 0 && args.with_tasks {
+                say!(
+                    "{} This project has {} task(s). They will be moved to the trash too.",
+                    symbols::WARNING,
+                    task_count
+                );
+            } else if task_count > 0 && !args.keep_links {
+                say!(
+                    "{} This project has {} task(s). They will be kept but unlinked; use --with-tasks to delete them too.",
+                    symbols::WARNING,
+                    task_count
+                );
+            }
+
+            if task_count > 0 && args.with_tasks {
+                print!(
+                    "Are you sure you want to delete this project and its {} task(s)? [y/N]: ",
+                    task_count
+                );
+            } else {
+                print!("Are you sure you want to delete this project? [y/N]: ");
+            }
             io::stdout().flush().context("Failed to flush output")?;
 
             let mut input = String::new();
@@ -484,68 +1434,241 @@ impl ProjectCommands {
 
             let response = input.trim().to_lowercase();
             if !matches!(response.as_str(), "y" | "yes") {
-                println!("❌ Deletion cancelled");
+                say!("{} Deletion cancelled", symbols::ERROR);
                 return Ok(());
             }
         }
 
-        let deleted_project = projects.remove(project_index);
-        storage
-            .save_projects(&projects)
-            .context("Failed to save projects")?;
+        let mut deleted_tasks = Vec::new();
+        if args.with_tasks {
+            for task in storage.load_tasks().context("Failed to load tasks")? {
+                if task.project_id == Some(project.id) {
+                    deleted_tasks.push(storage.trash_task(task.id)?);
+                }
+            }
+        }
+        let deleted_project = storage.trash_project(project.id)?;
+        let unlinked = if args.keep_links {
+            Unlinked::default()
+        } else {
+            storage
+                .unlink_project(deleted_project.id)
+                .context("Failed to remove links to the deleted project")?
+        };
 
-        println!("✅ Deleted project: {}", deleted_project.title);
+        say!(
+            "{} Moved project to trash: {}",
+            symbols::TRASH,
+            deleted_project.title
+        );
+        if !deleted_tasks.is_empty() {
+            let titles: Vec<&str> = deleted_tasks.iter().map(|t| t.title.as_str()).collect();
+            say!(
+                "   Moved {} task(s) to trash: {}",
+                titles.len(),
+                titles.join(", ")
+            );
+        }
+        if !unlinked.tasks.is_empty() {
+            let titles: Vec<&str> = unlinked.tasks.iter().map(|t| t.title.as_str()).collect();
+            say!(
+                "   Unlinked {} task(s): {}",
+                titles.len(),
+                titles.join(", ")
+            );
+        }
+        say!(
+            "   Restore it with: ideavault trash restore {}",
+            deleted_project.id
+        );
         Ok(())
     }
 }
 
-fn print_project_summary(project: &Project) {
-    let status_emoji = match project.status {
-        ProjectStatus::Planning => "📋",
-        ProjectStatus::InProgress => "🚀",
-        ProjectStatus::Completed => "✅",
-        ProjectStatus::OnHold => "⏸️",
+/// The link `project open` opens: the project's URL, or its repository when
+/// `repo` is set or it has no URL. Repositories given as an SSH remote such as
+/// `git@github.com:me/app.git` are opened as their web page.
+pub fn project_link(project: &Project, repo: bool) -> Result<String> {
+    let link = if repo {
+        project.repo.as_deref().map(repo_web_url).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Project '{}' has no repository. Set one with: ideavault project update {} --repo <REPO>",
+                project.title,
+                project.id
+            )
+        })?
+    } else {
+        match (&project.url, &project.repo) {
+            (Some(url), _) => url.clone(),
+            (None, Some(repo)) => repo_web_url(repo),
+            (None, None) => anyhow::bail!(
+                "Project '{}' has no URL or repository. Set one with: ideavault project update {} --url <URL>",
+                project.title,
+                project.id
+            ),
+        }
     };
+    Ok(link)
+}
 
-    println!("{} {} [{}]", status_emoji, project.title, project.id);
+/// `repo` as a page a browser can show.
+pub(crate) fn repo_web_url(repo: &str) -> String {
+    let repo = repo.trim();
+    match repo
+        .strip_prefix("git@")
+        .and_then(|rest| rest.split_once(':'))
+    {
+        Some((host, path)) => format!(
+            "https://{}/{}",
+            host,
+            path.strip_suffix(".git").unwrap_or(path)
+        ),
+        None => repo.to_string(),
+    }
+}
+
+fn print_project_summary(project: &Project) {
+    let status_emoji = symbols::project_status(&project.status);
+
+    say!(
+        "{} {} [{}]",
+        status_emoji,
+        style::paint(&project.title, style::project_status_color(&project.status)),
+        style::dim(project.id)
+    );
     if let Some(description) = &project.description {
         let desc_preview = if description.len() > 50 {
             format!("{}...", &description[..50])
         } else {
             description.clone()
         };
-        println!("   {}", desc_preview);
+        say!("   {}", desc_preview);
     }
     if let Some(milestone) = &project.milestone {
-        println!("   🎯 {}", milestone);
+        say!("   {} {}", symbols::MILESTONE, milestone);
     }
     if let Some(url) = &project.url {
-        println!("   URL: {}", url);
+        say!("   URL: {}", url);
     }
     if let Some(repo) = &project.repo {
-        println!("   Repo: {}", repo);
+        say!("   Repo: {}", repo);
+    }
+    if !project.tags.is_empty() {
+        say!("   {} {}", symbols::TAGS, style::tags(&project.tags));
+    }
+    if let Some(due_date) = project.due_date {
+        say!("   {} Due: {}", symbols::DUE, due_label(project, due_date));
+    }
+    if let Some(assignee) = &project.assignee {
+        say!("   Assignee: {}", assignee);
     }
     if !project.idea_ids.is_empty() {
-        println!("   💡 {} idea(s)", project.idea_ids.len());
+        say!("   {} {} idea(s)", symbols::IDEA, project.idea_ids.len());
     }
-    println!(
-        "   📅 Updated: {}",
-        project.updated_at.format("%Y-%m-%d %H:%M")
+    say!(
+        "   {} Updated: {}",
+        symbols::DATE,
+        dates::moment(project.updated_at, dates::DATE_TIME)
     );
 }
 
-fn print_project_full(project: &Project, ideas: &[Idea]) {
-    let status_emoji = match project.status {
-        ProjectStatus::Planning => "📋",
-        ProjectStatus::InProgress => "🚀",
-        ProjectStatus::Completed => "✅",
-        ProjectStatus::OnHold => "⏸️",
-    };
+/// The columns `project list` can show.
+fn project_columns<'a>(projects: &'a [Project], tasks: &'a [Task]) -> Vec<Column<'a, Project>> {
+    vec![
+        Column::new("id", 8, |project: &Project| {
+            Cell::colored(short_id(&project.id), Color::Gray)
+        }),
+        Column::new("title", 40, |project: &Project| Cell::new(&project.title)),
+        Column::new("status", 14, |project: &Project| {
+            Cell::colored(
+                project.status.to_string(),
+                style::project_status_color(&project.status),
+            )
+        }),
+        Column::new("blocked", 24, move |project: &Project| {
+            let titles: Vec<&str> = project
+                .blocked_by(projects)
+                .into_iter()
+                .map(|project| project.title.as_str())
+                .collect();
+            if titles.is_empty() {
+                Cell::default()
+            } else {
+                Cell::colored(titles.join(", "), Color::Red)
+            }
+        }),
+        Column::new("milestone", 24, |project: &Project| {
+            Cell::new(project.milestone.clone().unwrap_or_default())
+        }),
+        Column::new("priority", 10, |project: &Project| {
+            Cell::colored(
+                project.priority.to_string(),
+                style::priority_color(&project.priority),
+            )
+        }),
+        Column::new("due", 14, |project: &Project| match project.due_date {
+            Some(due) if project.is_overdue(Utc::now()) => {
+                Cell::colored(dates::day(due), Color::Red)
+            }
+            Some(due) => Cell::new(dates::day(due)),
+            None => Cell::default(),
+        }),
+        Column::new("assignee", 16, |project: &Project| {
+            Cell::new(project.assignee.clone().unwrap_or_default())
+        }),
+        Column::new("tags", 30, |project: &Project| {
+            Cell::new(project.tags.join(", "))
+        }),
+        Column::new("tasks", 9, move |project: &Project| {
+            let (done, total) = task_progress(project.id, projects, tasks);
+            Cell::new(format!("{}/{}", done, total))
+        }),
+        Column::new("ideas", 5, |project: &Project| {
+            Cell::new(project.idea_ids.len().to_string())
+        }),
+        Column::new("description", 40, |project: &Project| {
+            Cell::new(project.description.clone().unwrap_or_default())
+        }),
+        Column::new("url", 40, |project: &Project| {
+            Cell::new(project.url.clone().unwrap_or_default())
+        }),
+        Column::new("repo", 40, |project: &Project| {
+            Cell::new(project.repo.clone().unwrap_or_default())
+        }),
+        Column::new("created", 14, |project: &Project| {
+            Cell::new(dates::moment(project.created_at, dates::DATE))
+        }),
+        Column::new("updated", 14, |project: &Project| {
+            Cell::new(dates::moment(project.updated_at, dates::DATE))
+        }),
+    ]
+}
 
-    println!("{} {}", status_emoji, project.title);
+fn print_project_full(project: &Project, session: &Session) -> Result<()> {
+    let projects = session.projects()?;
+    let ideas = session.ideas()?;
+    let status_emoji = symbols::project_status(&project.status);
+
+    println!("{} {}", status_emoji, style::bold(&project.title));
     println!("ID: {}", project.id);
-    println!("Status: {}", project.status);
+    println!(
+        "Status: {}",
+        style::paint(
+            &project.status,
+            style::project_status_color(&project.status)
+        )
+    );
 
+    println!(
+        "Priority: {}",
+        style::paint(&project.priority, style::priority_color(&project.priority))
+    );
+    if let Some(due_date) = project.due_date {
+        println!("Due: {}", due_label(project, due_date));
+    }
+    if let Some(assignee) = &project.assignee {
+        println!("Assignee: {}", assignee);
+    }
     if let Some(milestone) = &project.milestone {
         println!("Milestone: {}", milestone);
     }
@@ -556,8 +1679,30 @@ fn print_project_full(project: &Project, ideas: &[Idea]) {
     if let Some(repo) = &project.repo {
         println!("  Repo: {}", repo);
     }
+    if !project.tags.is_empty() {
+        println!("Tags: {}", style::tags(&project.tags));
+    }
+
+    if let Some(parent_id) = project.parent_id {
+        match projects.iter().find(|parent| parent.id == parent_id) {
+            Some(parent) => println!(
+                "Part of: {} [{}]",
+                parent.title,
+                style::dim(short_id(&parent.id))
+            ),
+            None => println!("Part of: {} (not found)", parent_id),
+        }
+    }
 
     println!("Ideas: {} linked", project.idea_ids.len());
+    let (done, total) = task_progress(project.id, projects, session.tasks()?);
+    if total > 0 {
+        println!("Tasks: {} of {} done", done, total);
+    }
+    if !project.milestones.is_empty() {
+        let (completed, total) = project.milestone_progress();
+        println!("Milestones: {} of {} completed", completed, total);
+    }
     println!(
         "Created: {}",
         project.created_at.format("%Y-%m-%d %H:%M:%S UTC")
@@ -575,10 +1720,58 @@ fn print_project_full(project: &Project, ideas: &[Idea]) {
         println!("No description");
     }
 
+    if !project.milestones.is_empty() {
+        println!();
+        println!("{} Milestones:", symbols::MILESTONE);
+        print_milestones(&project.milestones);
+    }
+
+    if !project.depends_on.is_empty() {
+        println!();
+        let blocked_by = project.blocked_by(projects).len();
+        if blocked_by > 0 {
+            println!(
+                "{} Depends on ({}):",
+                symbols::PROJECT,
+                style::paint(format!("blocked by {}", blocked_by), Color::Red)
+            );
+        } else {
+            println!("{} Depends on:", symbols::PROJECT);
+        }
+        for id in &project.depends_on {
+            match projects.iter().find(|project| project.id == *id) {
+                Some(dependency) => println!(
+                    "  {} {} [{}]",
+                    symbols::project_status(&dependency.status),
+                    dependency.title,
+                    style::dim(short_id(&dependency.id))
+                ),
+                None => println!("  - {} (not found)", id),
+            }
+        }
+    }
+
+    let children: Vec<&Project> = projects
+        .iter()
+        .filter(|child| child.parent_id == Some(project.id))
+        .collect();
+    if !children.is_empty() {
+        println!();
+        println!("{} Sub-projects:", symbols::PROJECT);
+        for child in children {
+            println!(
+                "  {} {} [{}]",
+                symbols::project_status(&child.status),
+                child.title,
+                style::dim(short_id(&child.id))
+            );
+        }
+    }
+
     // Show linked ideas
     if !project.idea_ids.is_empty() {
         println!();
-        println!("💡 Linked Ideas:");
+        println!("{} Linked Ideas:", symbols::IDEA);
         for idea_id in &project.idea_ids {
             if let Some(idea) = ideas.iter().find(|i| i.id == *idea_id) {
                 print_idea_in_project(idea);
@@ -587,17 +1780,198 @@ fn print_project_full(project: &Project, ideas: &[Idea]) {
             }
         }
     }
+    Ok(())
 }
 
-fn print_idea_in_project(idea: &Idea) {
-    let status_emoji = match idea.status {
-        crate::models::idea::IdeaStatus::Brainstorming => "🧠",
-        crate::models::idea::IdeaStatus::Active => "🚀",
-        crate::models::idea::IdeaStatus::Completed => "✅",
-        crate::models::idea::IdeaStatus::Archived => "📦",
+/// The ideas, tasks, and latest changes of the project `id` as of `now`.
+pub fn project_stats(storage: &Storage, id: Uuid, now: DateTime<Utc>) -> Result<ProjectStats> {
+    let project = storage.get_project(id)?;
+    let ideas: Vec<Idea> = storage
+        .load_ideas()
+        .context("Failed to load ideas")?
+        .into_iter()
+        .filter(|idea| project.idea_ids.contains(&idea.id))
+        .collect();
+    let tasks: Vec<Task> = storage
+        .load_tasks()
+        .context("Failed to load tasks")?
+        .into_iter()
+        .filter(|task| task.project_id == Some(id))
+        .collect();
+    let open = || tasks.iter().filter(|task| !task.status.is_closed());
+
+    let ids: Vec<String> = std::iter::once(id)
+        .chain(tasks.iter().map(|task| task.id))
+        .map(|id| id.to_string())
+        .collect();
+    let mut recent_activity: Vec<HistoryEntry> = storage
+        .load_history()
+        .context("Failed to load history")?
+        .into_iter()
+        .filter(|entry| ids.contains(&entry.entity_id))
+        .collect();
+    recent_activity.reverse();
+    recent_activity.truncate(RECENT_ACTIVITY);
+
+    Ok(ProjectStats {
+        id,
+        title: project.title,
+        status: project.status,
+        ideas: count_by(ideas.iter().map(|idea| &idea.status)),
+        tasks: count_by(tasks.iter().map(|task| &task.status)),
+        task_priorities: count_by(tasks.iter().map(|task| &task.priority)),
+        overdue_tasks: open()
+            .filter(|task| task.due_date.is_some_and(|due| due < now))
+            .count(),
+        oldest_open_task: open().min_by_key(|task| task.created_at).cloned(),
+        recent_activity,
+    })
+}
+
+/// The Markdown status report on the project `id` for the period from
+/// `since` to `now`, covering the tasks of the projects under it too.
+pub fn project_report(
+    storage: &Storage,
+    id: Uuid,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<String> {
+    let project = storage.get_project(id)?;
+    let projects = storage.load_projects().context("Failed to load projects")?;
+    let mut ids = descendants(&projects, id);
+    ids.push(id);
+    let tasks: Vec<Task> = storage
+        .load_tasks()
+        .context("Failed to load tasks")?
+        .into_iter()
+        .filter(|task| {
+            task.project_id
+                .is_some_and(|project_id| ids.contains(&project_id))
+        })
+        .collect();
+    Ok(markdown::project_report(
+        &project,
+        &tasks,
+        &project.blocked_by(&projects),
+        since,
+        now,
+    ))
+}
+
+/// How many of the tasks in the project `id` and the projects under it are
+/// done or otherwise closed, and how many there are.
+pub fn task_progress(id: Uuid, projects: &[Project], tasks: &[Task]) -> (usize, usize) {
+    let mut ids = descendants(projects, id);
+    ids.push(id);
+    let tasks: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| {
+            task.project_id
+                .is_some_and(|project_id| ids.contains(&project_id))
+        })
+        .collect();
+    let done = tasks.iter().filter(|task| task.status.is_closed()).count();
+    (done, tasks.len())
+}
+
+/// `projects` as a tree, each under its parent, or at the top when its
+/// parent isn't among them.
+fn print_project_tree(projects: &[Project], all: &[Project], tasks: &[Task]) {
+    let shown: Vec<Uuid> = projects.iter().map(|project| project.id).collect();
+    let mut printed = Vec::new();
+    for project in projects {
+        if !project
+            .parent_id
+            .is_some_and(|parent| shown.contains(&parent))
+        {
+            print_project_branch(project, 0, projects, all, tasks, &mut printed);
+        }
+    }
+}
+
+fn print_project_branch(
+    project: &Project,
+    depth: usize,
+    projects: &[Project],
+    all: &[Project],
+    tasks: &[Task],
+    printed: &mut Vec<Uuid>,
+) {
+    if printed.contains(&project.id) {
+        return;
+    }
+    printed.push(project.id);
+
+    let indent = if depth == 0 {
+        String::new()
+    } else {
+        format!("{}└─ ", "   ".repeat(depth - 1))
+    };
+    let (done, total) = task_progress(project.id, all, tasks);
+    let progress = if total > 0 {
+        format!(" {}", style::dim(format!("{}/{} tasks done", done, total)))
+    } else {
+        String::new()
     };
+    println!(
+        "{}{} {} [{}]{}",
+        indent,
+        symbols::project_status(&project.status),
+        project.title,
+        style::dim(short_id(&project.id)),
+        progress
+    );
+    for child in projects
+        .iter()
+        .filter(|child| child.parent_id == Some(project.id))
+    {
+        print_project_branch(child, depth + 1, projects, all, tasks, printed);
+    }
+}
+
+/// A project's due date, in red and marked when the project is overdue.
+fn due_label(project: &Project, due_date: DateTime<Utc>) -> String {
+    if project.is_overdue(Utc::now()) {
+        style::paint(format!("{} (OVERDUE)", dates::day(due_date)), Color::Red)
+    } else {
+        dates::day(due_date)
+    }
+}
+
+/// `milestones` numbered in order, with when each is due or was completed.
+fn print_milestones(milestones: &[Milestone]) {
+    let now = Utc::now();
+    for (number, milestone) in milestones.iter().enumerate() {
+        let when = match (milestone.completed_at, milestone.due_date) {
+            (Some(completed_at), _) => {
+                style::dim(format!("completed {}", dates::day(completed_at)))
+            }
+            (None, Some(due)) if due < now => {
+                style::paint(format!("overdue, due {}", dates::day(due)), Color::Red)
+            }
+            (None, Some(due)) => style::dim(format!("due {}", dates::day(due))),
+            (None, None) => String::new(),
+        };
+        let line = format!(
+            "  {}. {} {} {}",
+            number + 1,
+            symbols::milestone_status(milestone.status),
+            milestone.title,
+            when
+        );
+        println!("{}", line.trim_end());
+    }
+}
 
-    println!("  {} {} [{}]", status_emoji, idea.title, idea.id);
+fn print_idea_in_project(idea: &Idea) {
+    let status_emoji = symbols::idea_status(&idea.status);
+
+    println!(
+        "  {} {} [{}]",
+        status_emoji,
+        style::paint(&idea.title, style::idea_status_color(&idea.status)),
+        style::dim(idea.id)
+    );
     if let Some(description) = &idea.description {
         let desc_preview = if description.len() > 80 {
             format!("{}...", &description[..80])
@@ -607,9 +1981,13 @@ fn print_idea_in_project(idea: &Idea) {
         println!("     {}", desc_preview);
     }
     if !idea.tags.is_empty() {
-        println!("     🏷️  {}", idea.tags.join(", "));
+        println!("     {} {}", symbols::TAGS, style::tags(&idea.tags));
     }
-    println!("     📅 {}", idea.updated_at.format("%Y-%m-%d %H:%M"));
+    println!(
+        "     {} {}",
+        symbols::DATE,
+        dates::moment(idea.updated_at, dates::DATE_TIME)
+    );
 }
 
 // Implement FromStr for ProjectStatus for CLI parsing
@@ -622,8 +2000,9 @@ impl std::str::FromStr for ProjectStatus {
             "inprogress" | "in-progress" | "progress" => Ok(ProjectStatus::InProgress),
             "completed" | "complete" | "done" => Ok(ProjectStatus::Completed),
             "onhold" | "on-hold" | "hold" => Ok(ProjectStatus::OnHold),
+            "archived" | "archive" => Ok(ProjectStatus::Archived),
             _ => Err(anyhow::anyhow!(
-                "Invalid status. Must be one of: Planning, InProgress, Completed, OnHold"
+                "Invalid status. Must be one of: Planning, InProgress, Completed, OnHold, Archived"
             )),
         }
     }
@@ -636,6 +2015,7 @@ impl std::fmt::Display for ProjectStatus {
             ProjectStatus::InProgress => write!(f, "InProgress"),
             ProjectStatus::Completed => write!(f, "Completed"),
             ProjectStatus::OnHold => write!(f, "OnHold"),
+            ProjectStatus::Archived => write!(f, "Archived"),
         }
     }
 }
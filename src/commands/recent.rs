@@ -0,0 +1,177 @@
+//! `recent`: ideas, projects, and tasks created or changed lately, newest
+//! first, for picking up where you left off.
+
+use super::table::short_id;
+use crate::dates;
+use crate::output;
+use crate::storage::history::EntityKind;
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Field order of recent items printed as JSON or YAML.
+const RECENT_FIELDS: &[&str] = &["kind", "id", "title", "status", "change", "changed_at"];
+
+#[derive(Args)]
+pub struct RecentArgs {
+    /// How many days back to look
+    #[arg(short, long, default_value_t = 7)]
+    pub days: u32,
+
+    /// Most items to show
+    #[arg(short, long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+
+    /// Print only the full ID of each item, one per line (short for --quiet)
+    #[arg(long)]
+    pub ids: bool,
+}
+
+/// An item created or changed lately.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentItem {
+    pub kind: EntityKind,
+    pub id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub change: Change,
+    /// When it was created or last changed
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Created,
+    Updated,
+}
+
+pub fn execute(args: RecentArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    let items = recent(&storage, args.days, args.limit, Utc::now())?;
+
+    if output::is_quiet() {
+        output::print_ids(items.iter().map(|item| item.id));
+        return Ok(());
+    }
+    if !output::is_table() {
+        return output::print_list(&items, RECENT_FIELDS);
+    }
+
+    if items.is_empty() {
+        println!(
+            "{} Nothing created or changed in the last {} day(s)",
+            symbols::RECENT,
+            args.days
+        );
+        return Ok(());
+    }
+    println!(
+        "{} Created or changed in the last {} day(s), newest first:",
+        symbols::RECENT,
+        args.days
+    );
+    println!();
+    for item in &items {
+        let marker = match item.kind {
+            EntityKind::Idea => symbols::IDEA,
+            EntityKind::Project => symbols::PROJECT,
+            _ => symbols::TASK,
+        };
+        let change = match item.change {
+            Change::Created => "created",
+            Change::Updated => "updated",
+        };
+        println!(
+            "   {} {} [{}] {}",
+            marker,
+            item.title,
+            style::dim(short_id(&item.id)),
+            style::dim(format!(
+                "{} · {} {}",
+                item.status,
+                change,
+                dates::moment(item.changed_at, dates::DATE_TIME)
+            ))
+        );
+    }
+    Ok(())
+}
+
+/// The at most `limit` ideas, projects, and tasks changed in the `days`
+/// before `now`, most recently changed first.
+pub fn recent(
+    storage: &Storage,
+    days: u32,
+    limit: usize,
+    now: DateTime<Utc>,
+) -> Result<Vec<RecentItem>> {
+    let since = now - Duration::days(i64::from(days));
+    let mut items = Vec::new();
+    let mut add = |kind, id, title: &str, status: String, created_at, updated_at| {
+        if updated_at >= since {
+            items.push(RecentItem {
+                kind,
+                id,
+                title: title.to_string(),
+                status,
+                change: change(created_at, updated_at),
+                changed_at: updated_at,
+            });
+        }
+    };
+
+    for idea in storage.load_ideas().context("Failed to load ideas")? {
+        add(
+            EntityKind::Idea,
+            idea.id,
+            &idea.title,
+            idea.status.to_string(),
+            idea.created_at,
+            idea.updated_at,
+        );
+    }
+    for project in storage.load_projects().context("Failed to load projects")? {
+        add(
+            EntityKind::Project,
+            project.id,
+            &project.title,
+            project.status.to_string(),
+            project.created_at,
+            project.updated_at,
+        );
+    }
+    for task in storage.load_tasks().context("Failed to load tasks")? {
+        add(
+            EntityKind::Task,
+            task.id,
+            &task.title,
+            task.status.to_string(),
+            task.created_at,
+            task.updated_at,
+        );
+    }
+
+    items.sort_by_key(|item| std::cmp::Reverse(item.changed_at));
+    items.truncate(limit);
+    Ok(items)
+}
+
+/// Whether an item was only created, or changed since: new items are stamped
+/// as updated a moment after they are created.
+fn change(created_at: DateTime<Utc>, updated_at: DateTime<Utc>) -> Change {
+    if updated_at - created_at < Duration::seconds(1) {
+        Change::Created
+    } else {
+        Change::Updated
+    }
+}
@@ -0,0 +1,420 @@
+//! `review`: a weekly review in the GTD style. Goes through what needs a
+//! decision — stale ideas, overdue and blocked tasks, and projects with no
+//! next action — one item at a time, acting on each in place.
+
+use super::table::short_id;
+use super::task::parse_due_date;
+use crate::dates;
+use crate::models::idea::{Idea, IdeaStatus};
+use crate::models::project::{Project, ProjectStatus};
+use crate::models::task::{Task, TaskStatus};
+use crate::say;
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+#[derive(Args)]
+pub struct ReviewArgs {
+    /// Ideas not changed in this many days count as stale
+    #[arg(short, long, default_value_t = 30)]
+    pub days: u32,
+}
+
+/// What needs a decision in the review.
+#[derive(Debug, Default, Clone)]
+pub struct Agenda {
+    /// Brainstorming and active ideas not changed lately, oldest first
+    pub stale_ideas: Vec<Idea>,
+    /// Open tasks due before today, most overdue first
+    pub overdue_tasks: Vec<Task>,
+    /// Blocked tasks that aren't overdue, oldest first
+    pub blocked_tasks: Vec<Task>,
+    /// Planning and in-progress projects without an open task
+    pub idle_projects: Vec<Project>,
+}
+
+/// What the review did.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Reviewed {
+    pub archived: usize,
+    pub rescheduled: usize,
+    pub status_changed: usize,
+    pub tasks_added: usize,
+    pub skipped: usize,
+}
+
+/// Something to do with an item under review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Archive,
+    Reschedule,
+    ChangeStatus,
+    AddTask,
+    Skip,
+}
+
+pub fn execute(args: ReviewArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    review(&storage, args.days, Utc::now(), &mut io::stdin().lock()).map(|_| ())
+}
+
+impl Agenda {
+    /// How many items need a decision.
+    pub fn len(&self) -> usize {
+        self.stale_ideas.len()
+            + self.overdue_tasks.len()
+            + self.blocked_tasks.len()
+            + self.idle_projects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The items that need a decision as of `now`, with ideas unchanged for
+/// `days` counting as stale.
+pub fn agenda(storage: &Storage, days: u32, now: DateTime<Utc>) -> Result<Agenda> {
+    let stale_before = now - Duration::days(i64::from(days));
+    let today = now.date_naive();
+    let tasks = storage.load_tasks().context("Failed to load tasks")?;
+    let is_overdue = |task: &Task| {
+        !task.status.is_closed() && task.due_date.is_some_and(|due| due.date_naive() < today)
+    };
+
+    let mut stale_ideas: Vec<Idea> = storage
+        .load_ideas()
+        .context("Failed to load ideas")?
+        .into_iter()
+        .filter(|idea| matches!(idea.status, IdeaStatus::Brainstorming | IdeaStatus::Active))
+        .filter(|idea| idea.updated_at < stale_before)
+        .collect();
+    stale_ideas.sort_by_key(|idea| idea.updated_at);
+
+    let mut overdue_tasks: Vec<Task> = tasks.iter().filter(|t| is_overdue(t)).cloned().collect();
+    overdue_tasks.sort_by_key(|task| task.due_date);
+
+    let mut blocked_tasks: Vec<Task> = tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Blocked && !is_overdue(task))
+        .cloned()
+        .collect();
+    blocked_tasks.sort_by_key(|task| task.updated_at);
+
+    let mut idle_projects: Vec<Project> = storage
+        .load_projects()
+        .context("Failed to load projects")?
+        .into_iter()
+        .filter(|project| {
+            matches!(
+                project.status,
+                ProjectStatus::Planning | ProjectStatus::InProgress
+            )
+        })
+        .filter(|project| {
+            !tasks
+                .iter()
+                .any(|task| task.project_id == Some(project.id) && !task.status.is_closed())
+        })
+        .collect();
+    idle_projects.sort_by_key(|project| project.updated_at);
+
+    Ok(Agenda {
+        stale_ideas,
+        overdue_tasks,
+        blocked_tasks,
+        idle_projects,
+    })
+}
+
+/// Go through the agenda one item at a time, reading one answer per line of
+/// `input`, until it is done or the user quits.
+pub fn review(
+    storage: &Storage,
+    days: u32,
+    now: DateTime<Utc>,
+    input: &mut impl BufRead,
+) -> Result<Reviewed> {
+    let agenda = agenda(storage, days, now)?;
+    let mut reviewed = Reviewed::default();
+    if agenda.is_empty() {
+        say!("{} Nothing to review", symbols::OK);
+        return Ok(reviewed);
+    }
+
+    let total = agenda.len();
+    let mut number = 0;
+    let mut next = |title: &str, id, detail: String| {
+        number += 1;
+        println!();
+        println!(
+            "   [{}/{}] {} [{}]",
+            number,
+            total,
+            title,
+            style::dim(short_id(&id))
+        );
+        println!("   {}", style::dim(detail));
+    };
+
+    'review: {
+        if !agenda.stale_ideas.is_empty() {
+            section(format!(
+                "{} Ideas not changed in {} day(s):",
+                symbols::IDEA,
+                days
+            ));
+        }
+        for idea in &agenda.stale_ideas {
+            next(
+                &idea.title,
+                idea.id,
+                format!(
+                    "{} · updated {}",
+                    idea.status,
+                    dates::moment(idea.updated_at, dates::DATE)
+                ),
+            );
+            let actions = [Action::Archive, Action::ChangeStatus, Action::Skip];
+            let Some(action) = ask_action(input, &actions)? else {
+                break 'review;
+            };
+            let status = match action {
+                Action::Archive => Some(IdeaStatus::Archived),
+                Action::ChangeStatus => ask_value(input, "New status", str::parse)?,
+                _ => None,
+            };
+            match status {
+                Some(status) => {
+                    storage.update_idea(idea.id, |idea| idea.set_status(status.clone()))?;
+                    if status == IdeaStatus::Archived {
+                        say!(
+                            "   {} Archived",
+                            symbols::idea_status(&IdeaStatus::Archived)
+                        );
+                        reviewed.archived += 1;
+                    } else {
+                        say!("   {} Now {}", symbols::EDITED, status);
+                        reviewed.status_changed += 1;
+                    }
+                }
+                None => reviewed.skipped += 1,
+            }
+        }
+
+        for (heading, tasks) in [
+            (
+                format!("{} Overdue tasks:", symbols::DUE),
+                &agenda.overdue_tasks,
+            ),
+            (
+                format!("{} Blocked tasks:", symbols::TASK),
+                &agenda.blocked_tasks,
+            ),
+        ] {
+            if !tasks.is_empty() {
+                section(heading);
+            }
+            for task in tasks {
+                let due = match task.due_date {
+                    Some(due) => format!("due {}", dates::day(due)),
+                    None => "no due date".to_string(),
+                };
+                next(&task.title, task.id, format!("{} · {}", task.status, due));
+                let actions = [Action::Reschedule, Action::ChangeStatus, Action::Skip];
+                let Some(action) = ask_action(input, &actions)? else {
+                    break 'review;
+                };
+                match action {
+                    Action::Reschedule => {
+                        match ask_value(input, "New due date (YYYY-MM-DD)", parse_due_date)? {
+                            Some(due) => {
+                                storage
+                                    .update_task(task.id, |task| task.set_due_date(Some(due)))?;
+                                say!("   {} Now due {}", symbols::DUE, dates::day(due));
+                                reviewed.rescheduled += 1;
+                            }
+                            None => reviewed.skipped += 1,
+                        }
+                    }
+                    Action::ChangeStatus => {
+                        match ask_value::<TaskStatus>(input, "New status", str::parse)? {
+                            Some(status) => {
+                                storage
+                                    .update_task(task.id, |task| task.set_status(status.clone()))?;
+                                say!("   {} Now {}", symbols::EDITED, status);
+                                reviewed.status_changed += 1;
+                            }
+                            None => reviewed.skipped += 1,
+                        }
+                    }
+                    _ => reviewed.skipped += 1,
+                }
+            }
+        }
+
+        if !agenda.idle_projects.is_empty() {
+            section(format!(
+                "{} Projects with no next action:",
+                symbols::PROJECT
+            ));
+        }
+        for project in &agenda.idle_projects {
+            next(
+                &project.title,
+                project.id,
+                format!(
+                    "{} · updated {}",
+                    project.status,
+                    dates::moment(project.updated_at, dates::DATE)
+                ),
+            );
+            let actions = [Action::AddTask, Action::ChangeStatus, Action::Skip];
+            let Some(action) = ask_action(input, &actions)? else {
+                break 'review;
+            };
+            match action {
+                Action::AddTask => {
+                    match ask_value(input, "Next task", |title| Ok(title.to_string()))? {
+                        Some(title) => {
+                            let task = Task::new(title).with_project(project.id);
+                            storage.add_task(&task).context("Failed to save task")?;
+                            say!("   {} Added task {}", symbols::NEW, short_id(&task.id));
+                            reviewed.tasks_added += 1;
+                        }
+                        None => reviewed.skipped += 1,
+                    }
+                }
+                Action::ChangeStatus => {
+                    match ask_value::<ProjectStatus>(input, "New status", str::parse)? {
+                        Some(status) => {
+                            storage.update_project(project.id, |project| {
+                                project.set_status(status.clone())
+                            })?;
+                            say!("   {} Now {}", symbols::EDITED, status);
+                            reviewed.status_changed += 1;
+                        }
+                        None => reviewed.skipped += 1,
+                    }
+                }
+                _ => reviewed.skipped += 1,
+            }
+        }
+    }
+
+    say!();
+    say!(
+        "{} Reviewed {} of {} item(s): {} archived, {} rescheduled, {} status change(s), {} task(s) added, {} skipped",
+        symbols::OK,
+        reviewed.archived
+            + reviewed.rescheduled
+            + reviewed.status_changed
+            + reviewed.tasks_added
+            + reviewed.skipped,
+        total,
+        reviewed.archived,
+        reviewed.rescheduled,
+        reviewed.status_changed,
+        reviewed.tasks_added,
+        reviewed.skipped
+    );
+    Ok(reviewed)
+}
+
+fn section(heading: String) {
+    println!();
+    println!("{}", style::bold(heading));
+}
+
+/// What to do with the current item, out of `actions`, or `None` to stop.
+fn ask_action(input: &mut impl BufRead, actions: &[Action]) -> Result<Option<Action>> {
+    let choices: Vec<String> = actions.iter().map(Action::to_string).collect();
+    loop {
+        print!("   {}, or [q]uit: ", choices.join(", "));
+        io::stdout().flush().context("Failed to flush output")?;
+
+        let Some(answer) = read_answer(input)? else {
+            return Ok(None);
+        };
+        if answer.is_empty() {
+            return Ok(Some(Action::Skip));
+        }
+        if answer == "q" || answer == "quit" {
+            return Ok(None);
+        }
+        match actions
+            .iter()
+            .find(|action| action.key() == answer.to_lowercase())
+        {
+            Some(action) => return Ok(Some(*action)),
+            None => println!("   Please answer one of the letters in brackets."),
+        }
+    }
+}
+
+/// A value read with `parse`, asked again until it parses, or `None` when
+/// the answer is left empty.
+fn ask_value<T>(
+    input: &mut impl BufRead,
+    prompt: &str,
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<Option<T>> {
+    loop {
+        print!("   {} (empty to skip): ", prompt);
+        io::stdout().flush().context("Failed to flush output")?;
+
+        let Some(answer) = read_answer(input)? else {
+            return Ok(None);
+        };
+        if answer.is_empty() {
+            return Ok(None);
+        }
+        match parse(&answer) {
+            Ok(value) => return Ok(Some(value)),
+            Err(error) => println!("   {}", error),
+        }
+    }
+}
+
+/// The next line of `input`, trimmed, or `None` when input ran out.
+fn read_answer(input: &mut impl BufRead) -> Result<Option<String>> {
+    let mut answer = String::new();
+    if input
+        .read_line(&mut answer)
+        .context("Failed to read input")?
+        == 0
+    {
+        println!();
+        return Ok(None);
+    }
+    Ok(Some(answer.trim().to_string()))
+}
+
+impl Action {
+    fn key(self) -> &'static str {
+        match self {
+            Action::Archive => "a",
+            Action::Reschedule => "r",
+            Action::ChangeStatus => "c",
+            Action::AddTask => "t",
+            Action::Skip => "s",
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Archive => write!(f, "[a]rchive"),
+            Action::Reschedule => write!(f, "[r]eschedule"),
+            Action::ChangeStatus => write!(f, "[c]hange status"),
+            Action::AddTask => write!(f, "add a next [t]ask"),
+            Action::Skip => write!(f, "[s]kip"),
+        }
+    }
+}
@@ -3,13 +3,20 @@
 //! Provides comprehensive search across ideas, projects, and tags with filtering
 //! and relevance ranking capabilities.
 
+use crate::dates;
 use crate::models::{Idea, Project, Tag};
+use crate::output;
 use crate::storage::Storage;
+use crate::style::{self, Color};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Field order of search results printed as JSON or YAML.
+const SEARCH_FIELDS: &[&str] = &["entity_type", "id", "title", "status", "relevance_score"];
 
 /// Search result with relevance score
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SearchResult {
     pub id: String,
     pub title: String,
@@ -23,7 +30,8 @@ pub struct SearchResult {
 }
 
 /// Entity types that can be searched
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum EntityType {
     Idea,
     Project,
@@ -157,6 +165,16 @@ impl SearchEngine {
             }
         }
 
+        // Check source match
+        if let Some(ref source) = idea.source {
+            if source.to_lowercase().contains(&query_lower) {
+                score += 30.0; // Source match
+                if snippet.is_none() {
+                    snippet = Some(format!("Source: {}", source));
+                }
+            }
+        }
+
         // Check tags match
         for tag in &idea.tags {
             let tag_lower = tag.to_lowercase();
@@ -226,6 +244,16 @@ impl SearchEngine {
             }
         }
 
+        // Check tags match
+        for tag in &project.tags {
+            if tag.to_lowercase().contains(&query_lower) {
+                score += 20.0; // Tag match
+                if snippet.is_none() {
+                    snippet = Some(format!("Tag: {}", tag));
+                }
+            }
+        }
+
         if score > 0.0 {
             Some(SearchResult {
                 id: project.id.to_string(),
@@ -236,7 +264,7 @@ impl SearchEngine {
                 relevance_score: score,
                 created_at: project.created_at,
                 snippet,
-                tags: Vec::new(), // Projects don't have tags in current model
+                tags: project.tags.clone(),
             })
         } else {
             None
@@ -301,17 +329,8 @@ impl SearchEngine {
             }
         }
 
-        // Tags filter
-        if !filters.tags_filter.is_empty() {
-            let idea_tags_lower: Vec<String> = idea.tags.iter().map(|t| t.to_lowercase()).collect();
-            for filter_tag in &filters.tags_filter {
-                if !idea_tags_lower
-                    .iter()
-                    .any(|t| t.contains(&filter_tag.to_lowercase()))
-                {
-                    return false;
-                }
-            }
+        if !matches_tags_filter(&idea.tags, filters) {
+            return false;
         }
 
         // Date range filter
@@ -340,6 +359,10 @@ impl SearchEngine {
             }
         }
 
+        if !matches_tags_filter(&project.tags, filters) {
+            return false;
+        }
+
         // Date range filter
         if let Some(date_from) = filters.date_from {
             if project.created_at < date_from {
@@ -392,12 +415,17 @@ pub fn display_search_results(results: &[SearchResult]) {
     let max_snippet_width = 50;
 
     for (i, result) in results.iter().enumerate() {
+        let type_color = match result.entity_type {
+            EntityType::Idea => Color::Magenta,
+            EntityType::Project => Color::Blue,
+            EntityType::Tag => Color::Cyan,
+        };
         println!(
             "{}. {} [{}] (ID: {})",
             i + 1,
-            truncate_string(&result.title, max_title_width),
-            result.entity_type,
-            &result.id[..8]
+            style::bold(truncate_string(&result.title, max_title_width)),
+            style::paint(&result.entity_type, type_color),
+            style::dim(&result.id[..8])
         );
 
         if let Some(ref description) = result.description {
@@ -410,7 +438,7 @@ pub fn display_search_results(results: &[SearchResult]) {
         println!(
             "   Status: {} | Created: {} | Score: {:.1}",
             result.status,
-            result.created_at.format("%Y-%m-%d %H:%M"),
+            dates::moment(result.created_at, dates::DATE_TIME),
             result.relevance_score
         );
 
@@ -419,13 +447,22 @@ pub fn display_search_results(results: &[SearchResult]) {
         }
 
         if !result.tags.is_empty() {
-            println!("   Tags: {}", result.tags.join(", "));
+            println!("   Tags: {}", style::tags(&result.tags));
         }
 
         println!();
     }
 }
 
+/// Whether `tags` has, for every tag filtered on, a tag containing it.
+fn matches_tags_filter(tags: &[String], filters: &SearchFilters) -> bool {
+    filters.tags_filter.iter().all(|filter_tag| {
+        let filter_tag = filter_tag.to_lowercase();
+        tags.iter()
+            .any(|tag| tag.to_lowercase().contains(&filter_tag))
+    })
+}
+
 /// Truncate string to specified length with ellipsis if needed
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -495,6 +532,9 @@ pub fn execute_search(args: SearchArgs) -> Result<()> {
     }
 
     let results = engine.search(&args.query, filters)?;
+    if !output::is_table() {
+        return output::print_list(&results, SEARCH_FIELDS);
+    }
 
     display_search_results(&results);
 
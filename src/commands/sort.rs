@@ -0,0 +1,188 @@
+use crate::models::{Idea, Project, Task, TaskPriority, Timestamped};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Sorting flags shared by the list commands. Without `--sort`, items are
+/// listed in the order they are stored.
+#[derive(Args, Clone, Debug, Default)]
+pub struct SortArgs {
+    /// Sort by created, updated, title, due, or priority
+    #[arg(long, value_name = "FIELD")]
+    pub sort: Option<SortKey>,
+
+    /// Reverse the order
+    #[arg(long)]
+    pub reverse: bool,
+}
+
+/// What a list can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Oldest first
+    Created,
+    /// Least recently changed first
+    Updated,
+    /// A to Z, ignoring case
+    Title,
+    /// Soonest first; items without a due date come last
+    Due,
+    /// Most urgent first
+    Priority,
+}
+
+const ALL_KEYS: &[SortKey] = &[
+    SortKey::Created,
+    SortKey::Updated,
+    SortKey::Title,
+    SortKey::Due,
+    SortKey::Priority,
+];
+
+/// Items a list command can sort.
+pub trait Sortable: Timestamped {
+    /// What this kind of item is called, for errors.
+    const KIND: &'static str;
+    /// The keys this kind of item can be sorted by.
+    const KEYS: &'static [SortKey];
+
+    fn title(&self) -> &str;
+
+    fn due(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn priority(&self) -> Option<&TaskPriority> {
+        None
+    }
+}
+
+impl Sortable for Idea {
+    const KIND: &'static str = "idea";
+    const KEYS: &'static [SortKey] = &[
+        SortKey::Created,
+        SortKey::Updated,
+        SortKey::Title,
+        SortKey::Priority,
+    ];
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn priority(&self) -> Option<&TaskPriority> {
+        Some(&self.priority)
+    }
+}
+
+impl Sortable for Project {
+    const KIND: &'static str = "project";
+    const KEYS: &'static [SortKey] = ALL_KEYS;
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn due(&self) -> Option<DateTime<Utc>> {
+        self.due_date
+    }
+
+    fn priority(&self) -> Option<&TaskPriority> {
+        Some(&self.priority)
+    }
+}
+
+impl Sortable for Task {
+    const KIND: &'static str = "task";
+    const KEYS: &'static [SortKey] = ALL_KEYS;
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn due(&self) -> Option<DateTime<Utc>> {
+        self.due_date
+    }
+
+    fn priority(&self) -> Option<&TaskPriority> {
+        Some(&self.priority)
+    }
+}
+
+/// Put `items` in the order `args` asks for. Items that compare equal keep
+/// their stored order.
+pub fn sort<T: Sortable>(items: &mut [T], args: &SortArgs) -> Result<()> {
+    let Some(key) = args.sort else {
+        if args.reverse {
+            items.reverse();
+        }
+        return Ok(());
+    };
+    if !T::KEYS.contains(&key) {
+        anyhow::bail!(
+            "Can't sort {}s by {}. Sort by one of: {}",
+            T::KIND,
+            key,
+            T::KEYS
+                .iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let direction = |ordering: Ordering| {
+        if args.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+    items.sort_by(|a, b| match key {
+        SortKey::Created => direction(a.created_at().cmp(&b.created_at())),
+        SortKey::Updated => direction(a.updated_at().cmp(&b.updated_at())),
+        SortKey::Title => direction(a.title().to_lowercase().cmp(&b.title().to_lowercase())),
+        // Undated items stay at the end either way
+        SortKey::Due => match (a.due(), b.due()) {
+            (Some(a), Some(b)) => direction(a.cmp(&b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+        SortKey::Priority => direction(b.priority().cmp(&a.priority())),
+    });
+    Ok(())
+}
+
+impl FromStr for SortKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(SortKey::Created),
+            "updated" => Ok(SortKey::Updated),
+            "title" => Ok(SortKey::Title),
+            "due" => Ok(SortKey::Due),
+            "priority" => Ok(SortKey::Priority),
+            _ => Err(anyhow::anyhow!(
+                "Invalid sort field. Must be one of: created, updated, title, due, priority"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SortKey::Created => "created",
+            SortKey::Updated => "updated",
+            SortKey::Title => "title",
+            SortKey::Due => "due",
+            SortKey::Priority => "priority",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,212 @@
+use crate::say;
+use crate::storage::{git, Storage};
+use crate::symbols;
+use crate::sync::{self, Conflict, ProviderKind, Side, SyncConfig};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::io::{self, IsTerminal, Write};
+
+pub use crate::sync::SyncSummary;
+
+#[derive(Args)]
+pub struct SyncArgs {
+    /// Set the remote to sync with: a git URL, WebDAV URL, or directory (saved for later syncs)
+    #[arg(long, value_name = "URL")]
+    pub remote: Option<String>,
+
+    /// Sync through git, webdav, or directory (saved for later syncs; defaults to git)
+    #[arg(long)]
+    pub provider: Option<ProviderKind>,
+
+    /// Username for WebDAV; the password is read from IDEAVAULT_SYNC_PASSWORD
+    #[arg(long)]
+    pub username: Option<String>,
+
+    /// Settle conflicting changes by keeping this machine's version
+    #[arg(long, conflicts_with = "prefer_remote")]
+    pub prefer_local: bool,
+
+    /// Settle conflicting changes by keeping the remote version
+    #[arg(long)]
+    pub prefer_remote: bool,
+}
+
+pub fn execute(args: SyncArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    sync(&storage, &args).map(|_| ())
+}
+
+/// Pull changes from the vault's remote and push local ones to it.
+pub fn sync(storage: &Storage, args: &SyncArgs) -> Result<SyncSummary> {
+    let data_dir = storage.data_dir();
+    let saved = SyncConfig::load(data_dir)?;
+    let provider = args
+        .provider
+        .or(saved.as_ref().map(|config| config.provider))
+        .unwrap_or(ProviderKind::Git);
+
+    if provider == ProviderKind::Git {
+        if saved.is_some() {
+            SyncConfig::remove(data_dir)?;
+        }
+        return sync_git(storage, args);
+    }
+
+    let saved = saved.filter(|config| config.provider == provider);
+    let Some(url) = args
+        .remote
+        .clone()
+        .or(saved.as_ref().map(|config| config.url.clone()))
+    else {
+        anyhow::bail!(
+            "No remote configured. Run `ideavault sync --provider {} --remote <url>` to set one.",
+            provider
+        );
+    };
+    let config = SyncConfig {
+        provider,
+        url,
+        username: args
+            .username
+            .clone()
+            .or(saved.as_ref().and_then(|config| config.username.clone())),
+    };
+    if saved.as_ref() != Some(&config) {
+        config
+            .save(data_dir)
+            .context("Failed to save sync settings")?;
+    }
+
+    let remote = config.provider()?;
+    say!("{} Syncing with {}...", symbols::SYNC, remote.location());
+    let summary = sync::sync_files(data_dir, remote.as_ref(), &mut resolver(args))
+        .context("Failed to sync files")?;
+
+    print_merged(&summary.conflicts);
+    if summary.pulled == 0 && summary.pushed == 0 {
+        say!("{} Already up to date", symbols::OK);
+    } else {
+        say!(
+            "{} Synced: pulled {} file(s), pushed {} file(s)",
+            symbols::OK,
+            summary.pulled,
+            summary.pushed
+        );
+    }
+    Ok(summary)
+}
+
+fn sync_git(storage: &Storage, args: &SyncArgs) -> Result<SyncSummary> {
+    let data_dir = storage.data_dir();
+    if !git::is_enabled(data_dir) {
+        anyhow::bail!("Git history is not enabled. Run `ideavault git init` first.");
+    }
+
+    if let Some(url) = &args.remote {
+        git::set_remote(data_dir, url).context("Failed to set remote")?;
+    }
+    let Some(url) = git::remote_url(data_dir)? else {
+        anyhow::bail!("No remote configured. Run `ideavault sync --remote <url>` to set one.");
+    };
+
+    git::commit(data_dir, "sync: commit local changes")
+        .context("Failed to commit local changes")?;
+
+    say!("{} Syncing with {}...", symbols::SYNC, url);
+    let (pulled, conflicts) =
+        git::pull(data_dir, &mut resolver(args)).context("Failed to pull from remote")?;
+    let pushed = git::push(data_dir).context("Failed to push to remote")?;
+
+    print_merged(&conflicts);
+    let summary = SyncSummary {
+        pulled,
+        pushed,
+        conflicts,
+    };
+    if summary.pulled == 0 && summary.pushed == 0 {
+        say!("{} Already up to date", symbols::OK);
+    } else {
+        say!(
+            "{} Synced: pulled {} commit(s), pushed {} commit(s)",
+            symbols::OK,
+            summary.pulled,
+            summary.pushed
+        );
+    }
+    Ok(summary)
+}
+
+/// How conflicting changes are settled: as the flags say, by asking, or, when
+/// nobody can be asked, in favour of the most recently updated side.
+fn resolver(args: &SyncArgs) -> impl FnMut(&Conflict) -> Result<Side> {
+    let preference = if args.prefer_local {
+        Some(Side::Local)
+    } else if args.prefer_remote {
+        Some(Side::Remote)
+    } else {
+        None
+    };
+    let interactive = io::stdin().is_terminal();
+
+    move |conflict| match preference {
+        Some(side) => Ok(side),
+        None if interactive => ask(conflict),
+        None => Ok(conflict.newest()),
+    }
+}
+
+fn ask(conflict: &Conflict) -> Result<Side> {
+    let updated = |time: Option<chrono::DateTime<chrono::Utc>>| {
+        time.map(|time| format!(" (updated {})", time.format("%Y-%m-%d %H:%M")))
+            .unwrap_or_default()
+    };
+    println!(
+        "{} Conflict in {}: {}",
+        symbols::WARNING,
+        conflict.path,
+        conflict.describe()
+    );
+    println!(
+        "   local{}:  {}",
+        updated(conflict.local_updated),
+        conflict.value(Side::Local)
+    );
+    println!(
+        "   remote{}: {}",
+        updated(conflict.remote_updated),
+        conflict.value(Side::Remote)
+    );
+
+    let newest = conflict.newest();
+    loop {
+        print!(
+            "Keep [l]ocal or [r]emote? [{}]: ",
+            match newest {
+                Side::Local => "l",
+                Side::Remote => "r",
+            }
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            anyhow::bail!("Sync cancelled");
+        }
+        match input.trim().to_lowercase().as_str() {
+            "" => return Ok(newest),
+            "l" | "local" => return Ok(Side::Local),
+            "r" | "remote" => return Ok(Side::Remote),
+            _ => println!("Please answer l or r."),
+        }
+    }
+}
+
+fn print_merged(paths: &[String]) {
+    if !paths.is_empty() {
+        say!(
+            "{} Merged changes made on both sides to: {}",
+            symbols::EDITED,
+            paths.join(", ")
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::style::{self, Color};
+use anyhow::Result;
+use clap::Args;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use uuid::Uuid;
+
+/// Space between columns.
+const GAP: &str = "  ";
+
+/// Characters of an ID shown in the `id` column; any unique prefix is
+/// accepted back as an ID.
+const SHORT_ID_LEN: usize = 8;
+
+/// Column flags shared by the list commands.
+#[derive(Args, Clone, Debug, Default)]
+pub struct TableArgs {
+    /// Columns to show, comma-separated (e.g. id,title,status,due)
+    #[arg(long, value_delimiter = ',', value_name = "COLUMNS")]
+    pub columns: Vec<String>,
+
+    /// Leave out the header row
+    #[arg(long)]
+    pub no_headers: bool,
+
+    /// Print only the full ID of each item, one per line (short for --quiet)
+    #[arg(long, conflicts_with_all = ["columns", "no_headers", "json"])]
+    pub ids: bool,
+}
+
+/// One value in a table, drawn in `color` when colors are on.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cell {
+    pub text: String,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+        }
+    }
+
+    pub fn colored(text: impl Into<String>, color: Color) -> Self {
+        Self {
+            text: text.into(),
+            color: Some(color),
+        }
+    }
+}
+
+/// A column a list can show: its name for `--columns`, the widest it gets
+/// before values are cut short, and how to fill it in.
+pub struct Column<'a, T> {
+    pub name: &'static str,
+    pub max_width: usize,
+    cell: Box<dyn Fn(&T) -> Cell + 'a>,
+}
+
+impl<'a, T> Column<'a, T> {
+    pub fn new(name: &'static str, max_width: usize, cell: impl Fn(&T) -> Cell + 'a) -> Self {
+        Self {
+            name,
+            max_width,
+            cell: Box::new(cell),
+        }
+    }
+}
+
+/// The columns of a list, picked from those it can show.
+pub struct Table<'a, T> {
+    columns: Vec<Column<'a, T>>,
+    headers: bool,
+}
+
+impl<'a, T> Table<'a, T> {
+    /// The columns named by `--columns`, or `defaults` when none are given.
+    pub fn new(
+        mut available: Vec<Column<'a, T>>,
+        defaults: &[&str],
+        args: &TableArgs,
+    ) -> Result<Self> {
+        let wanted: Vec<String> = if args.columns.is_empty() {
+            defaults.iter().map(|name| name.to_string()).collect()
+        } else {
+            args.columns
+                .iter()
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect()
+        };
+
+        let names: Vec<&str> = available.iter().map(|column| column.name).collect();
+        if let Some(unknown) = wanted.iter().find(|name| !names.contains(&name.as_str())) {
+            anyhow::bail!(
+                "Unknown column '{}'. Available columns: {}",
+                unknown,
+                names.join(", ")
+            );
+        }
+
+        let mut columns = Vec::new();
+        for name in &wanted {
+            match available.iter().position(|column| column.name == name) {
+                Some(index) => columns.push(available.remove(index)),
+                None => anyhow::bail!("Column '{}' is listed twice", name),
+            }
+        }
+        Ok(Self {
+            columns,
+            headers: !args.no_headers,
+        })
+    }
+
+    /// The rows for `items`, lined up, with a header row unless turned off.
+    pub fn render(&self, items: &[T]) -> String {
+        let rows: Vec<Vec<Cell>> = items
+            .iter()
+            .map(|item| {
+                self.columns
+                    .iter()
+                    .map(|column| {
+                        let mut cell = (column.cell)(item);
+                        cell.text = truncate(&cell.text, column.max_width);
+                        cell
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let header = if self.headers { column.name.width() } else { 0 };
+                rows.iter()
+                    .map(|row| row[index].text.width())
+                    .fold(header, usize::max)
+            })
+            .collect();
+
+        let mut out = String::new();
+        if self.headers {
+            let header: Vec<Cell> = self
+                .columns
+                .iter()
+                .map(|column| Cell::new(column.name.to_uppercase()))
+                .collect();
+            push_row(&mut out, &header, &widths, true);
+        }
+        for row in &rows {
+            push_row(&mut out, row, &widths, false);
+        }
+        out
+    }
+}
+
+fn push_row(out: &mut String, row: &[Cell], widths: &[usize], header: bool) {
+    let mut line = String::new();
+    for (index, cell) in row.iter().enumerate() {
+        if index > 0 {
+            line.push_str(GAP);
+        }
+        let text = match (header, cell.color) {
+            (true, _) => style::bold(&cell.text),
+            (false, Some(color)) => style::paint(&cell.text, color),
+            (false, None) => cell.text.clone(),
+        };
+        line.push_str(&text);
+        // No padding after the last column, so lines don't end in spaces
+        if index + 1 < row.len() {
+            line.push_str(&" ".repeat(widths[index].saturating_sub(cell.text.width())));
+        }
+    }
+    out.push_str(line.trim_end());
+    out.push('\n');
+}
+
+/// The start of `id`, as shown in tables.
+pub fn short_id(id: &Uuid) -> String {
+    id.simple().to_string()[..SHORT_ID_LEN].to_string()
+}
+
+/// `text` on one line and at most `max_width` columns wide, ending in `…`
+/// when it had to be cut.
+pub fn truncate(text: &str, max_width: usize) -> String {
+    let text = text.replace(['\n', '\r', '\t'], " ");
+    if text.width() <= max_width {
+        return text;
+    }
+    let mut out = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width + 1 > max_width {
+            break;
+        }
+        width += char_width;
+        out.push(c);
+    }
+    out.push('…');
+    out
+}
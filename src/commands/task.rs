@@ -1,11 +1,33 @@
-use crate::models::task::{Task, TaskPriority, TaskStatus};
-use crate::storage::Storage;
+use super::bulk::{self, Assignment, BulkArgs, BulkEditable};
+use super::id::IdArg;
+use super::input::{ids_or_stdin, text_or_stdin};
+use super::page::{Page, PageArgs};
+use super::pick::PickKind;
+use super::project::task_progress;
+use super::sort::{sort, SortArgs};
+use super::table::{short_id, Cell, Column, Table, TableArgs};
+use crate::dates;
+use crate::editor;
+use crate::export::TASK_FIELDS;
+use crate::front_matter;
+use crate::models::task::{priorities, statuses, Task, TaskPriority, TaskStatus};
+use crate::output;
+use crate::say;
+use crate::storage::history::EntityKind;
+use crate::storage::{Session, Storage};
+use crate::style::{self, Color};
+use crate::symbols;
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
 use clap::{Args, Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::io::{self, Write};
 use uuid::Uuid;
 
+/// Columns `task list` shows unless `--columns` says otherwise.
+const TASK_COLUMNS: &[&str] = &["id", "title", "status", "priority", "due", "tags"];
+
 #[derive(Parser)]
 #[command(name = "task")]
 #[command(about = "Manage tasks")]
@@ -24,6 +46,8 @@ pub enum TaskSubcommand {
     Show(ShowTaskArgs),
     /// Update the status of a task
     Status(StatusTaskArgs),
+    /// Mark one or more tasks done
+    Done(DoneTaskArgs),
     /// Update the priority of a task
     Priority(PriorityTaskArgs),
     /// Set due date for a task
@@ -36,23 +60,26 @@ pub enum TaskSubcommand {
     UnlinkProject(UnlinkProjectArgs),
     /// Unlink task from idea
     UnlinkIdea(UnlinkIdeaArgs),
-    /// Edit a task in $EDITOR
+    /// Edit a task in your editor
     Edit(EditTaskArgs),
     /// Delete a task with confirmation
     Delete(DeleteTaskArgs),
     Update(TaskUpdateArgs),
+    /// Change every task a filter matches, e.g. --status blocked --set priority=high
+    EditMany(EditManyTaskArgs),
 }
 
 #[derive(Args)]
 pub struct NewTaskArgs {
     /// The title of the task
-    title: String,
+    #[arg(required_unless_present = "edit")]
+    title: Option<String>,
 
-    /// Optional description for the task
+    /// Optional description for the task (`-` reads it from standard input)
     #[arg(short = 'd', long = "description")]
     description: Option<String>,
 
-    /// Optional priority (low|medium|high|urgent)
+    /// Optional priority (low|medium|high|urgent, or a custom priority)
     #[arg(short = 'p', long = "priority")]
     priority: Option<TaskPriority>,
 
@@ -64,22 +91,50 @@ pub struct NewTaskArgs {
     #[arg(short = 't', long = "tags", value_delimiter = ',')]
     tags: Vec<String>,
 
-    /// Optional project ID to link to
+    /// Optional project (ID or title) to link to
     #[arg(long = "project")]
-    project_id: Option<Uuid>,
+    project_id: Option<IdArg>,
 
-    /// Optional idea ID to link to
+    /// Optional idea (ID or title) to link to
     #[arg(long = "idea")]
-    idea_id: Option<Uuid>,
+    idea_id: Option<IdArg>,
+
+    /// Who is doing the task
+    #[arg(long = "assignee")]
+    assignee: Option<String>,
+
+    /// Write the task in your editor, starting from the other options
+    #[arg(short = 'e', long)]
+    edit: bool,
 }
 
 #[derive(Args)]
 pub struct ListTaskArgs {
-    /// Filter by status (todo|inprogress|blocked|done|cancelled)
+    #[command(flatten)]
+    filter: TaskFilterArgs,
+
+    #[command(flatten)]
+    sort: SortArgs,
+
+    #[command(flatten)]
+    page: PageArgs,
+
+    #[command(flatten)]
+    table: TableArgs,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Which tasks `list` and `edit-many` work on.
+#[derive(Args)]
+pub struct TaskFilterArgs {
+    /// Filter by status (todo|inprogress|blocked|done|cancelled, or a custom status)
     #[arg(short = 's', long = "status")]
     status: Option<TaskStatus>,
 
-    /// Filter by priority (low|medium|high|urgent)
+    /// Filter by priority (low|medium|high|urgent, or a custom priority)
     #[arg(short = 'p', long = "priority")]
     priority: Option<TaskPriority>,
 
@@ -87,38 +142,64 @@ pub struct ListTaskArgs {
     #[arg(short = 't', long = "tag")]
     tag: Option<String>,
 
-    /// Filter by project ID
+    /// Filter by project (ID or title)
     #[arg(long = "project")]
-    project_id: Option<Uuid>,
+    project_id: Option<IdArg>,
 
-    /// Filter by idea ID
+    /// Filter by idea (ID or title)
     #[arg(long = "idea")]
-    idea_id: Option<Uuid>,
+    idea_id: Option<IdArg>,
+
+    /// Only tasks assigned to this person
+    #[arg(long = "assignee")]
+    assignee: Option<String>,
 
     /// Show overdue tasks only
     #[arg(long = "overdue")]
     overdue: bool,
 }
 
+#[derive(Args)]
+pub struct EditManyTaskArgs {
+    #[command(flatten)]
+    filter: TaskFilterArgs,
+
+    #[command(flatten)]
+    changes: BulkArgs,
+}
+
 #[derive(Args)]
 pub struct ShowTaskArgs {
-    /// The UUID of the task to show
-    id: Uuid,
+    /// The IDs or titles of the tasks to show, or - to read IDs from standard
+    /// input; pick one from a list if left out
+    ids: Vec<IdArg>,
+
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
 }
 
 #[derive(Args)]
 pub struct StatusTaskArgs {
-    /// The UUID of the task to update
-    id: Uuid,
+    /// The ID or title of the task to update
+    id: IdArg,
 
     /// New status for the task
     status: TaskStatus,
 }
 
+#[derive(Args)]
+pub struct DoneTaskArgs {
+    /// The IDs or titles of the tasks to mark done, or - to read IDs from
+    /// standard input
+    #[arg(required = true)]
+    pub ids: Vec<IdArg>,
+}
+
 #[derive(Args)]
 pub struct PriorityTaskArgs {
-    /// The UUID of the task to update
-    id: Uuid,
+    /// The ID or title of the task to update
+    id: IdArg,
 
     /// New priority for the task
     priority: TaskPriority,
@@ -126,8 +207,8 @@ pub struct PriorityTaskArgs {
 
 #[derive(Args)]
 pub struct DueTaskArgs {
-    /// The UUID of the task to update
-    id: Uuid,
+    /// The ID or title of the task to update
+    id: IdArg,
 
     /// Due date (YYYY-MM-DD format) or "clear" to remove
     due_date: String,
@@ -135,64 +216,64 @@ pub struct DueTaskArgs {
 
 #[derive(Args)]
 pub struct LinkProjectArgs {
-    /// The UUID of the task
-    id: Uuid,
+    /// The ID or title of the task
+    id: IdArg,
 
-    /// The UUID of the project to link
-    project_id: Uuid,
+    /// The ID or title of the project to link
+    project_id: IdArg,
 }
 
 #[derive(Args)]
 pub struct LinkIdeaArgs {
-    /// The UUID of the task
-    id: Uuid,
+    /// The ID or title of the task
+    id: IdArg,
 
-    /// The UUID of the idea to link
-    idea_id: Uuid,
+    /// The ID or title of the idea to link
+    idea_id: IdArg,
 }
 
 #[derive(Args)]
 pub struct UnlinkProjectArgs {
-    /// The UUID of the task
-    id: Uuid,
+    /// The ID or title of the task
+    id: IdArg,
 }
 
 #[derive(Args)]
 pub struct UnlinkIdeaArgs {
-    /// The UUID of the task
-    id: Uuid,
+    /// The ID or title of the task
+    id: IdArg,
 }
 
 #[derive(Args)]
 pub struct EditTaskArgs {
-    /// The UUID of the task to edit
-    id: Uuid,
+    /// The ID or title of the task to edit; pick one from a list if left out
+    id: Option<IdArg>,
 }
 
 #[derive(Args)]
 pub struct DeleteTaskArgs {
-    /// The UUID of the task to delete
-    id: Uuid,
+    /// The ID or title of the task to delete
+    pub id: IdArg,
 
     /// Skip confirmation prompt
     #[arg(short, long)]
-    force: bool,
+    pub force: bool,
 }
 
 #[derive(Args)]
 pub struct TaskUpdateArgs {
-    /// Task ID to update
-    pub id: Uuid,
+    /// Task ID or title to update
+    pub id: IdArg,
 
     /// New title
     #[arg(short = 't', long = "title")]
     pub title: Option<String>,
 
-    /// New description
+    /// New description (`-` reads it from standard input)
     #[arg(short = 'd', long = "description")]
     pub description: Option<String>,
 
-    /// New priority (low|medium|high|urgent)
+    /// New priority (low|medium|high|urgent, or a custom priority)
     #[arg(short = 'p', long = "priority")]
     pub priority: Option<TaskPriority>,
 
@@ -200,28 +281,40 @@ pub struct TaskUpdateArgs {
     #[arg(short = 'D', long = "due")]
     pub due_date: Option<String>,
 
-    /// New status (todo|inprogress|blocked|done|cancelled)
+    /// New status (todo|inprogress|blocked|done|cancelled, or a custom status)
     #[arg(short = 's', long = "status")]
     pub status: Option<TaskStatus>,
 
+    /// Who is doing the task now
+    #[arg(long = "assignee")]
+    pub assignee: Option<String>,
+
     /// New tags (comma-separated, replaces existing tags)
     #[arg(short = 'g', long = "tags", value_delimiter = ',')]
     pub tags: Option<Vec<String>>,
 
-    /// Clear one or more optional fields (description, due_date, tags)
+    /// Clear one or more optional fields (description, due_date, assignee, tags)
     #[arg(long = "clear", value_name = "FIELD")]
     pub clear: Vec<String>,
 }
 
 impl TaskCommands {
     pub fn execute(&self) -> Result<()> {
+        // IDs piped to `show` and `done` are read before the vault is locked,
+        // as the command piping them in holds the lock until it exits
+        let shown = match &self.command {
+            TaskSubcommand::Show(args) => ids_or_stdin(&args.ids)?,
+            TaskSubcommand::Done(args) => ids_or_stdin(&args.ids)?,
+            _ => Vec::new(),
+        };
         let storage = Storage::new().context("Failed to initialize storage")?;
 
         match &self.command {
             TaskSubcommand::New(args) => Self::new_task(&storage, args),
             TaskSubcommand::List(args) => Self::list_tasks(&storage, args),
-            TaskSubcommand::Show(args) => Self::show_task(&storage, args),
+            TaskSubcommand::Show(_) => Self::show_task(&storage, &shown),
             TaskSubcommand::Status(args) => Self::update_status(&storage, args),
+            TaskSubcommand::Done(_) => Self::done_tasks(&storage, &shown),
             TaskSubcommand::Priority(args) => Self::update_priority(&storage, args),
             TaskSubcommand::Due(args) => Self::update_due_date(&storage, args),
             TaskSubcommand::LinkProject(args) => Self::link_project(&storage, args),
@@ -231,14 +324,15 @@ impl TaskCommands {
             TaskSubcommand::Edit(args) => Self::edit_task(&storage, args),
             TaskSubcommand::Delete(args) => Self::delete_task(&storage, args),
             TaskSubcommand::Update(args) => Self::update_task(&storage, args),
+            TaskSubcommand::EditMany(args) => Self::edit_many(&storage, args),
         }
     }
 
     fn new_task(storage: &Storage, args: &NewTaskArgs) -> Result<()> {
-        let mut task = Task::new(args.title.clone());
+        let mut task = Task::new(args.title.clone().unwrap_or_default());
 
         if let Some(description) = &args.description {
-            task = task.with_description(description.clone());
+            task = task.with_description(text_or_stdin(description)?);
         }
 
         if let Some(priority) = &args.priority {
@@ -250,28 +344,35 @@ impl TaskCommands {
         }
 
         if let Some(due_date_str) = &args.due_date {
-            let naive_date = NaiveDate::parse_from_str(due_date_str, "%Y-%m-%d")
-                .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD"))?;
-            let due_date = DateTime::<Utc>::from_naive_utc_and_offset(
-                naive_date.and_hms_opt(0, 0, 0).unwrap(),
-                Utc,
-            );
-            task = task.with_due_date(due_date);
+            task = task.with_due_date(parse_due_date(due_date_str)?);
         }
 
         if let Some(project_id) = &args.project_id {
-            task = task.with_project(*project_id);
+            task = task.with_project(project_id.project(storage)?);
         }
 
         if let Some(idea_id) = &args.idea_id {
-            task = task.with_idea(*idea_id);
+            task = task.with_idea(idea_id.idea(storage)?);
         }
 
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-        tasks.push(task.clone());
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
+        if let Some(assignee) = &args.assignee {
+            task = task.with_assignee(assignee.clone());
+        }
+
+        if args.edit {
+            let document = task_document(&task, "New task")?;
+            task = editor::edit_until_valid("new-task", &document, |content| {
+                apply_document(storage, task.clone(), content)
+            })?;
+        }
 
-        println!("✅ Created new task:");
+        storage.add_task(&task).context("Failed to save task")?;
+
+        if output::is_quiet() {
+            println!("{}", task.id);
+            return Ok(());
+        }
+        println!("{} Created new task:", symbols::OK);
         print_task_summary(&task);
         Ok(())
     }
@@ -279,329 +380,299 @@ impl TaskCommands {
     fn list_tasks(storage: &Storage, args: &ListTaskArgs) -> Result<()> {
         let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
 
-        if let Some(status_filter) = &args.status {
-            tasks.retain(|task| &task.status == status_filter);
-        }
-
-        if let Some(priority_filter) = &args.priority {
-            tasks.retain(|task| &task.priority == priority_filter);
-        }
-
-        if let Some(tag_filter) = &args.tag {
-            tasks.retain(|task| task.tags.contains(tag_filter));
-        }
-
-        if let Some(project_filter) = &args.project_id {
-            tasks.retain(|task| task.project_id == Some(*project_filter));
-        }
+        let matches = args.filter.matcher(storage)?;
+        tasks.retain(|task| matches(task));
 
-        if let Some(idea_filter) = &args.idea_id {
-            tasks.retain(|task| task.idea_id == Some(*idea_filter));
+        sort(&mut tasks, &args.sort)?;
+        let page = Page::from_config(tasks.len(), &args.page)?;
+        if !output::is_table() {
+            return output::print_list(&tasks[page.start..page.end], TASK_FIELDS);
         }
-
-        if args.overdue {
-            let now = Utc::now();
-            tasks.retain(|task| {
-                if let Some(due) = task.due_date {
-                    due < now
-                        && task.status != TaskStatus::Done
-                        && task.status != TaskStatus::Cancelled
-                } else {
-                    false
-                }
-            });
+        if output::is_quiet() {
+            output::print_ids(tasks[page.start..page.end].iter().map(|task| task.id));
+            return Ok(());
         }
 
         if tasks.is_empty() {
-            println!("📋 No tasks found");
+            println!("{} No tasks found", symbols::LIST);
             return Ok(());
         }
 
-        println!("📋 Found {} task(s):", tasks.len());
+        let projects = storage.load_projects().context("Failed to load projects")?;
+        let ideas = storage.load_ideas().context("Failed to load ideas")?;
+        let table = Table::new(task_columns(&projects, &ideas), TASK_COLUMNS, &args.table)?;
+        println!("{} Found {} task(s):", symbols::LIST, tasks.len());
         println!();
-
-        for task in &tasks {
-            print_task_summary(task);
+        print!("{}", table.render(&tasks[page.start..page.end]));
+        if let Some(footer) = page.footer("task") {
             println!();
+            println!("{}", footer);
         }
 
         Ok(())
     }
 
-    fn show_task(storage: &Storage, args: &ShowTaskArgs) -> Result<()> {
-        let tasks = storage.load_tasks().context("Failed to load tasks")?;
-        let projects = storage.load_projects().context("Failed to load projects")?;
-        let ideas = storage.load_ideas().context("Failed to load ideas")?;
-
-        let task = tasks
-            .iter()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
+    fn show_task(storage: &Storage, ids: &[IdArg]) -> Result<()> {
+        let ids = PickKind::Task.resolve_all(storage, ids)?;
+        let session = Session::new(storage);
+
+        let tasks = ids
+            .into_iter()
+            .map(|id| {
+                session
+                    .tasks()?
+                    .iter()
+                    .find(|task| task.id == id)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !output::is_table() {
+            return match tasks.as_slice() {
+                [task] => output::print_item(task, TASK_FIELDS),
+                _ => output::print_list(&tasks, TASK_FIELDS),
+            };
+        }
 
-        print_task_full(task, &projects, &ideas);
+        for (number, task) in tasks.iter().enumerate() {
+            if number > 0 {
+                println!();
+            }
+            print_task_full(task, session.projects()?, session.ideas()?);
+        }
         Ok(())
     }
 
     fn update_status(storage: &Storage, args: &StatusTaskArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
+        let id = args.id.task(storage)?;
+        let old_status = storage.update_task(id, |task| {
+            let old_status = task.status.clone();
+            if task.status != args.status {
+                task.set_status(args.status.clone());
+            }
+            old_status
+        })?;
 
-        let task = tasks
-            .iter_mut()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
+        say!("{} Updated status for task {}:", symbols::OK, id);
+        say!("   {} → {}", old_status, args.status);
+        Ok(())
+    }
 
-        let old_status = task.status.clone();
-        task.status = args.status.clone();
-        task.updated_at = Utc::now();
+    pub fn done_tasks(storage: &Storage, ids: &[IdArg]) -> Result<()> {
+        let mut ids = PickKind::Task.resolve_all(storage, ids)?;
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+        let mut completed: Vec<Task> = Vec::new();
+        let mut already: Vec<Task> = Vec::new();
+        for id in ids {
+            let (was_done, task) = storage.update_task(id, |task| {
+                let was_done = task.status.is_done();
+                if !was_done {
+                    task.set_status(TaskStatus::Done);
+                }
+                (was_done, task.clone())
+            })?;
+            if was_done {
+                already.push(task);
+            } else {
+                completed.push(task);
+            }
+        }
+        if output::is_quiet() {
+            output::print_ids(completed.iter().map(|task| task.id));
+            return Ok(());
+        }
 
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
+        if !completed.is_empty() {
+            println!("{} Completed {} task(s):", symbols::OK, completed.len());
+        }
+        for task in &completed {
+            let took = task
+                .completed_at
+                .map(|at| format!(" after {}", dates::length(at - task.created_at)))
+                .unwrap_or_default();
+            println!(
+                "   {} {} [{}]{}",
+                TaskStatus::Done.marker(),
+                task.title,
+                style::dim(short_id(&task.id)),
+                style::dim(took)
+            );
+        }
+        for task in &already {
+            println!(
+                "   {}",
+                style::dim(format!(
+                    "{} [{}] was already {}",
+                    task.title,
+                    short_id(&task.id),
+                    task.status
+                ))
+            );
+        }
 
-        println!("✅ Updated status for task {}:", args.id);
-        println!("   {} → {}", old_status, args.status);
+        // How far along the projects the tasks are in now are
+        let mut project_ids: Vec<Uuid> = Vec::new();
+        for project_id in completed.iter().filter_map(|task| task.project_id) {
+            if !project_ids.contains(&project_id) {
+                project_ids.push(project_id);
+            }
+        }
+        if project_ids.is_empty() {
+            return Ok(());
+        }
+        let projects = storage.load_projects().context("Failed to load projects")?;
+        let tasks = storage.load_tasks().context("Failed to load tasks")?;
+        for project in project_ids
+            .iter()
+            .filter_map(|id| projects.iter().find(|project| project.id == *id))
+        {
+            let (done, total) = task_progress(project.id, &projects, &tasks);
+            let marker = if done == total {
+                symbols::CELEBRATE
+            } else {
+                symbols::PROJECT
+            };
+            println!(
+                "{} {}: {} of {} task(s) done ({}%)",
+                marker,
+                style::bold(&project.title),
+                done,
+                total,
+                done * 100 / total
+            );
+        }
         Ok(())
     }
 
     fn update_priority(storage: &Storage, args: &PriorityTaskArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-
-        let task = tasks
-            .iter_mut()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
-
-        let old_priority = task.priority.clone();
-        task.priority = args.priority.clone();
-        task.updated_at = Utc::now();
-
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
-
-        println!("✅ Updated priority for task {}:", args.id);
-        println!("   {} → {}", old_priority, args.priority);
+        let id = args.id.task(storage)?;
+        let old_priority = storage.update_task(id, |task| {
+            let old_priority = task.priority.clone();
+            task.priority = args.priority.clone();
+            old_priority
+        })?;
+
+        say!("{} Updated priority for task {}:", symbols::OK, id);
+        say!("   {} → {}", old_priority, args.priority);
         Ok(())
     }
 
     fn update_due_date(storage: &Storage, args: &DueTaskArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
+        let id = args.id.task(storage)?;
+        let due_date = if args.due_date.to_lowercase() == "clear" {
+            None
+        } else {
+            Some(parse_due_date(&args.due_date)?)
+        };
 
-        let task = tasks
-            .iter_mut()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
+        storage.update_task(id, |task| task.due_date = due_date)?;
 
-        if args.due_date.to_lowercase() == "clear" {
-            task.due_date = None;
-            println!("✅ Cleared due date for task {}", args.id);
-        } else {
-            let naive_date = NaiveDate::parse_from_str(&args.due_date, "%Y-%m-%d")
-                .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD"))?;
-            let due_date = DateTime::<Utc>::from_naive_utc_and_offset(
-                naive_date.and_hms_opt(0, 0, 0).unwrap(),
-                Utc,
-            );
-            task.due_date = Some(due_date);
-            println!("✅ Set due date for task {} to {}", args.id, args.due_date);
+        match due_date {
+            Some(_) => say!(
+                "{} Set due date for task {} to {}",
+                symbols::OK,
+                id,
+                args.due_date
+            ),
+            None => say!("{} Cleared due date for task {}", symbols::OK, id),
         }
-        task.updated_at = Utc::now();
-
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
         Ok(())
     }
 
     fn link_project(storage: &Storage, args: &LinkProjectArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-        let projects = storage.load_projects().context("Failed to load projects")?;
-
-        if !projects.iter().any(|p| p.id == args.project_id) {
-            return Err(anyhow::anyhow!(
-                "Project with ID {} not found",
-                args.project_id
-            ));
-        }
-
-        let task = tasks
-            .iter_mut()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
-
-        task.project_id = Some(args.project_id);
-        task.updated_at = Utc::now();
-
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
-
-        println!("✅ Linked task {} to project {}", args.id, args.project_id);
+        let id = args.id.task(storage)?;
+        let project_id = args.project_id.project(storage)?;
+        storage.get_project(project_id)?;
+        storage.update_task(id, |task| task.project_id = Some(project_id))?;
+
+        say!(
+            "{} Linked task {} to project {}",
+            symbols::OK,
+            id,
+            project_id
+        );
         Ok(())
     }
 
     fn link_idea(storage: &Storage, args: &LinkIdeaArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-        let ideas = storage.load_ideas().context("Failed to load ideas")?;
-
-        if !ideas.iter().any(|i| i.id == args.idea_id) {
-            return Err(anyhow::anyhow!("Idea with ID {} not found", args.idea_id));
-        }
+        let id = args.id.task(storage)?;
+        let idea_id = args.idea_id.idea(storage)?;
+        storage.get_idea(idea_id)?;
+        storage.update_task(id, |task| task.idea_id = Some(idea_id))?;
 
-        let task = tasks
-            .iter_mut()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
-
-        task.idea_id = Some(args.idea_id);
-        task.updated_at = Utc::now();
-
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
-
-        println!("✅ Linked task {} to idea {}", args.id, args.idea_id);
+        say!("{} Linked task {} to idea {}", symbols::OK, id, idea_id);
         Ok(())
     }
 
     fn unlink_project(storage: &Storage, args: &UnlinkProjectArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-
-        let task = tasks
-            .iter_mut()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
-
-        if task.project_id.is_none() {
-            println!("⚠️  Task {} is not linked to any project", args.id);
+        let id = args.id.task(storage)?;
+        let previous = storage.update_task(id, |task| task.project_id.take())?;
+
+        if previous.is_none() {
+            say!(
+                "{} Task {} is not linked to any project",
+                symbols::WARNING,
+                id
+            );
             return Ok(());
         }
 
-        task.project_id = None;
-        task.updated_at = Utc::now();
-
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
-
-        println!("✅ Unlinked task {} from project", args.id);
+        say!("{} Unlinked task {} from project", symbols::OK, id);
         Ok(())
     }
 
     fn unlink_idea(storage: &Storage, args: &UnlinkIdeaArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-
-        let task = tasks
-            .iter_mut()
-            .find(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
+        let id = args.id.task(storage)?;
+        let previous = storage.update_task(id, |task| task.idea_id.take())?;
 
-        if task.idea_id.is_none() {
-            println!("⚠️  Task {} is not linked to any idea", args.id);
+        if previous.is_none() {
+            say!("{} Task {} is not linked to any idea", symbols::WARNING, id);
             return Ok(());
         }
 
-        task.idea_id = None;
-        task.updated_at = Utc::now();
-
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
-
-        println!("✅ Unlinked task {} from idea", args.id);
+        say!("{} Unlinked task {} from idea", symbols::OK, id);
         Ok(())
     }
 
     fn edit_task(storage: &Storage, args: &EditTaskArgs) -> Result<()> {
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-
-        let task_index = tasks
-            .iter()
-            .position(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
-
-        let temp_file = format!("{}.md", args.id);
-        let content = format!(
-            "# {}\n\n{}\n\nPriority: {}\nStatus: {}\nTags: {}\n\n",
-            tasks[task_index].title,
-            tasks[task_index].description.as_deref().unwrap_or(""),
-            tasks[task_index].priority,
-            tasks[task_index].status,
-            tasks[task_index].tags.join(", ")
-        );
-
-        std::fs::write(&temp_file, content).context("Failed to create temp file")?;
-
-        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
-        let status = std::process::Command::new(&editor)
-            .arg(&temp_file)
-            .status()
-            .context("Failed to open editor")?;
-
-        if !status.success() {
-            std::fs::remove_file(&temp_file)?;
-            return Err(anyhow::anyhow!("Editor exited with non-zero status"));
-        }
-
-        let updated_content =
-            std::fs::read_to_string(&temp_file).context("Failed to read updated content")?;
-        std::fs::remove_file(&temp_file)?;
-
-        let lines: Vec<&str> = updated_content.lines().collect();
-        if let Some(title_line) = lines.iter().find(|line| line.starts_with("# ")) {
-            tasks[task_index].title = title_line.strip_prefix("# ").unwrap().trim().to_string();
-        }
-
-        let mut description_parts = Vec::new();
-        let mut in_description = false;
-
-        for line in &lines {
-            if line.starts_with("# ") {
-                in_description = true;
-                continue;
-            } else if line.starts_with("Priority:")
-                || line.starts_with("Status:")
-                || line.starts_with("Tags:")
-            {
-                in_description = false;
-                continue;
-            } else if in_description && !line.trim().is_empty() {
-                description_parts.push(line.trim());
-            }
-        }
-
-        if !description_parts.is_empty() {
-            tasks[task_index].description = Some(description_parts.join("\n"));
-        }
-
-        for line in lines.iter().rev() {
-            if line.starts_with("Priority:") {
-                let priority_str = line.strip_prefix("Priority:").unwrap().trim();
-                if let Ok(parsed_priority) = priority_str.parse() {
-                    tasks[task_index].priority = parsed_priority;
-                }
-            } else if line.starts_with("Status:") {
-                let status_str = line.strip_prefix("Status:").unwrap().trim();
-                if let Ok(parsed_status) = status_str.parse() {
-                    tasks[task_index].status = parsed_status;
-                }
-            } else if line.starts_with("Tags:") {
-                let tags_str = line.strip_prefix("Tags:").unwrap().trim();
-                tasks[task_index].tags.clear();
-                if !tags_str.is_empty() {
-                    tasks[task_index].tags =
-                        tags_str.split(',').map(|t| t.trim().to_string()).collect();
-                }
-            }
-        }
-
-        tasks[task_index].updated_at = Utc::now();
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
-
-        println!("✅ Updated task {}:", args.id);
-        print_task_summary(&tasks[task_index]);
+        let id = PickKind::Task.resolve(storage, args.id.as_ref())?;
+        let task = storage.get_task(id)?;
+
+        let document = task_document(&task, &format!("Task {}", task.id))?;
+        let edited = editor::edit_until_valid(&id.to_string(), &document, |content| {
+            apply_document(storage, task.clone(), content)
+        })?;
+        let task = storage.update_task(id, |task| {
+            *task = edited;
+            task.clone()
+        })?;
+
+        say!("{} Updated task {}:", symbols::OK, id);
+        print_task_summary(&task);
         Ok(())
     }
 
-    fn delete_task(storage: &Storage, args: &DeleteTaskArgs) -> Result<()> {
+    fn edit_many(storage: &Storage, args: &EditManyTaskArgs) -> Result<()> {
+        args.changes.check(args.filter.is_set())?;
         let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
+        let matches = args.filter.matcher(storage)?;
+        let edit = bulk::apply(storage, &mut tasks, matches, &args.changes)?;
 
-        let task_index = tasks
-            .iter()
-            .position(|task| task.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
+        if !args.changes.dry_run && !edit.changes.is_empty() {
+            storage.save_tasks(&tasks).context("Failed to save tasks")?;
+        }
+        bulk::report(&tasks, &edit, args.changes.dry_run);
+        Ok(())
+    }
 
-        let task = &tasks[task_index];
+    pub fn delete_task(storage: &Storage, args: &DeleteTaskArgs) -> Result<()> {
+        let id = args.id.task(storage)?;
+        let task = storage.get_task(id)?;
 
         if !args.force {
-            print_task_summary(task);
-            println!();
+            print_task_summary(&task);
+            say!();
             print!("Are you sure you want to delete this task? [y/N]: ");
             io::stdout().flush().context("Failed to flush output")?;
 
@@ -612,20 +683,28 @@ impl TaskCommands {
 
             let response = input.trim().to_lowercase();
             if !matches!(response.as_str(), "y" | "yes") {
-                println!("❌ Deletion cancelled");
+                say!("{} Deletion cancelled", symbols::ERROR);
                 return Ok(());
             }
         }
 
-        let deleted_task = tasks.remove(task_index);
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
+        let deleted_task = storage.trash_task(task.id)?;
 
-        println!("✅ Deleted task: {}", deleted_task.title);
+        say!(
+            "{} Moved task to trash: {}",
+            symbols::TRASH,
+            deleted_task.title
+        );
+        say!(
+            "   Restore it with: ideavault trash restore {}",
+            deleted_task.id
+        );
         Ok(())
     }
 
     pub fn update_task(storage: &Storage, args: &TaskUpdateArgs) -> Result<()> {
-        const CLEARABLE_FIELDS: [&str; 3] = ["description", "due_date", "tags"];
+        let id = args.id.task(storage)?;
+        const CLEARABLE_FIELDS: [&str; 4] = ["description", "due_date", "assignee", "tags"];
 
         // Validate clear fields
         for field in &args.clear {
@@ -638,126 +717,373 @@ impl TaskCommands {
             }
         }
 
-        let mut tasks = storage.load_tasks().context("Failed to load tasks")?;
-
-        let task = tasks
-            .iter_mut()
-            .find(|t| t.id == args.id)
-            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", args.id))?;
-
-        let mut changes: Vec<String> = Vec::new();
-
-        // Update title
-        if let Some(title) = &args.title {
-            let old = task.title.clone();
-            task.update_title(title.clone());
-            changes.push(format!("title: \"{}\" → \"{}\"", old, title));
-        }
+        let due_date = match &args.due_date {
+            Some(due_date_str) if due_date_str.to_lowercase() == "clear" => Some(None),
+            Some(due_date_str) => Some(Some(parse_due_date(due_date_str)?)),
+            None => None,
+        };
 
-        // Update description
-        if let Some(desc) = &args.description {
-            let old = task.description.clone().unwrap_or_default();
-            task.update_description(Some(desc.clone()));
-            changes.push(format!("description: \"{}\" → \"{}\"", old, desc));
-        }
+        let description = args.description.as_deref().map(text_or_stdin).transpose()?;
+        let changes = storage.update_task(id, |task| {
+            let mut changes: Vec<String> = Vec::new();
 
-        // Update priority
-        if let Some(priority) = &args.priority {
-            let old = task.priority.clone();
-            task.set_priority(priority.clone());
-            changes.push(format!("priority: {} → {}", old, priority));
-        }
+            // Update title
+            if let Some(title) = &args.title {
+                let old = task.title.clone();
+                task.update_title(title.clone());
+                changes.push(format!("title: \"{}\" → \"{}\"", old, title));
+            }
 
-        // Update status
-        if let Some(status) = &args.status {
-            let old = task.status.clone();
-            task.set_status(status.clone());
-            changes.push(format!("status: {} → {}", old, status));
-        }
+            // Update description
+            if let Some(desc) = &description {
+                let old = task.description.clone().unwrap_or_default();
+                task.update_description(Some(desc.clone()));
+                changes.push(format!("description: \"{}\" → \"{}\"", old, desc));
+            }
 
-        // Update due date
-        if let Some(due_date_str) = &args.due_date {
-            if due_date_str.to_lowercase() == "clear" {
-                task.set_due_date(None);
-                changes.push("due_date: cleared".to_string());
-            } else {
-                let naive_date = NaiveDate::parse_from_str(due_date_str, "%Y-%m-%d")
-                    .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD"))?;
-                let due_date = DateTime::<Utc>::from_naive_utc_and_offset(
-                    naive_date.and_hms_opt(0, 0, 0).unwrap(),
-                    Utc,
-                );
-                let old = task
-                    .due_date
-                    .map(|d| d.format("%Y-%m-%d").to_string())
-                    .unwrap_or_else(|| "none".to_string());
-                task.set_due_date(Some(due_date));
-                changes.push(format!("due_date: {} → {}", old, due_date_str));
+            // Update priority
+            if let Some(priority) = &args.priority {
+                let old = task.priority.clone();
+                task.set_priority(priority.clone());
+                changes.push(format!("priority: {} → {}", old, priority));
             }
-        }
 
-        // Update tags
-        if let Some(tags) = &args.tags {
-            let old_tags = task.tags.clone();
-            task.update_tags(tags.clone());
-            changes.push(format!("tags: {:?} → {:?}", old_tags, tags));
-        }
+            // Update status
+            if let Some(status) = &args.status {
+                let old = task.status.clone();
+                task.set_status(status.clone());
+                changes.push(format!("status: {} → {}", old, status));
+            }
 
-        // Clear fields
-        for field in &args.clear {
-            match field.as_str() {
-                "description" => {
-                    task.update_description(None);
-                    changes.push("description: cleared".to_string());
-                }
-                "due_date" => {
+            // Update due date
+            match (due_date, &args.due_date) {
+                (Some(None), _) => {
                     task.set_due_date(None);
                     changes.push("due_date: cleared".to_string());
                 }
-                "tags" => {
-                    task.update_tags(Vec::new());
-                    changes.push("tags: cleared".to_string());
+                (Some(Some(due_date)), Some(due_date_str)) => {
+                    let old = task
+                        .due_date
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                        .unwrap_or_else(|| "none".to_string());
+                    task.set_due_date(Some(due_date));
+                    changes.push(format!("due_date: {} → {}", old, due_date_str));
                 }
-                _ => unreachable!(),
+                _ => {}
             }
-        }
+
+            // Update assignee
+            if let Some(assignee) = &args.assignee {
+                let old = task.assignee.clone().unwrap_or_default();
+                task.set_assignee(Some(assignee.clone()));
+                changes.push(match &task.assignee {
+                    Some(new) => format!("assignee: \"{}\" → \"{}\"", old, new),
+                    None => "assignee: cleared".to_string(),
+                });
+            }
+
+            // Update tags
+            if let Some(tags) = &args.tags {
+                let old_tags = task.tags.clone();
+                task.update_tags(tags.clone());
+                changes.push(format!("tags: {:?} → {:?}", old_tags, tags));
+            }
+
+            // Clear fields
+            for field in &args.clear {
+                match field.as_str() {
+                    "description" => {
+                        task.update_description(None);
+                        changes.push("description: cleared".to_string());
+                    }
+                    "due_date" => {
+                        task.set_due_date(None);
+                        changes.push("due_date: cleared".to_string());
+                    }
+                    "assignee" => {
+                        task.set_assignee(None);
+                        changes.push("assignee: cleared".to_string());
+                    }
+                    "tags" => {
+                        task.update_tags(Vec::new());
+                        changes.push("tags: cleared".to_string());
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            changes
+        })?;
 
         if changes.is_empty() {
-            println!("No changes specified for task {}", args.id);
-            println!("Use --help to see available options.");
+            say!("No changes specified for task {}", id);
+            say!("Use --help to see available options.");
             return Ok(());
         }
 
-        storage.save_tasks(&tasks).context("Failed to save tasks")?;
-
-        println!("✅ Updated task {}:", args.id);
+        say!("{} Updated task {}:", symbols::OK, id);
         for change in &changes {
-            println!("   {}", change);
+            say!("   {}", change);
         }
 
         Ok(())
     }
 }
 
-fn print_task_summary(task: &Task) {
-    let status_emoji = match task.status {
-        TaskStatus::Todo => "📋",
-        TaskStatus::InProgress => "🔄",
-        TaskStatus::Blocked => "🚫",
-        TaskStatus::Done => "✅",
-        TaskStatus::Cancelled => "❌",
+/// Parse a `YYYY-MM-DD` due date as midnight UTC.
+pub(crate) fn parse_due_date(date: &str) -> Result<DateTime<Utc>> {
+    let naive_date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD"))?;
+    Ok(DateTime::<Utc>::from_naive_utc_and_offset(
+        naive_date.and_hms_opt(0, 0, 0).unwrap(),
+        Utc,
+    ))
+}
+
+impl TaskFilterArgs {
+    /// Whether any filter is given.
+    pub fn is_set(&self) -> bool {
+        self.status.is_some()
+            || self.priority.is_some()
+            || self.tag.is_some()
+            || self.project_id.is_some()
+            || self.idea_id.is_some()
+            || self.assignee.is_some()
+            || self.overdue
+    }
+
+    /// Whether a task passes every filter, with linked items looked up once.
+    fn matcher(&self, storage: &Storage) -> Result<impl Fn(&Task) -> bool + '_> {
+        let project = self
+            .project_id
+            .as_ref()
+            .map(|id| id.project(storage))
+            .transpose()?;
+        let idea = self
+            .idea_id
+            .as_ref()
+            .map(|id| id.idea(storage))
+            .transpose()?;
+        let now = Utc::now();
+
+        Ok(move |task: &Task| {
+            self.status
+                .as_ref()
+                .is_none_or(|status| &task.status == status)
+                && self
+                    .priority
+                    .as_ref()
+                    .is_none_or(|priority| &task.priority == priority)
+                && self.tag.as_ref().is_none_or(|tag| task.tags.contains(tag))
+                && project.is_none_or(|project| task.project_id == Some(project))
+                && idea.is_none_or(|idea| task.idea_id == Some(idea))
+                && self
+                    .assignee
+                    .as_ref()
+                    .is_none_or(|assignee| task.is_assigned_to(assignee))
+                && (!self.overdue
+                    || task
+                        .due_date
+                        .is_some_and(|due| due < now && !task.status.is_closed()))
+        })
+    }
+}
+
+/// A `--set` change `task edit-many` makes.
+pub enum TaskChange {
+    Status(TaskStatus),
+    Priority(TaskPriority),
+    Due(Option<DateTime<Utc>>),
+    Project(Option<Uuid>),
+    Idea(Option<Uuid>),
+}
+
+/// What `--set` takes to clear a field.
+const NONE: &str = "none";
+
+impl BulkEditable for Task {
+    const ENTITY: EntityKind = EntityKind::Task;
+    const FIELDS: &'static [&'static str] = &["status", "priority", "due", "project", "idea"];
+    type Change = TaskChange;
+
+    fn parse_change(storage: &Storage, assignment: &Assignment) -> Result<TaskChange> {
+        let value = assignment.value.as_str();
+        let clear = value.eq_ignore_ascii_case(NONE);
+        Ok(match assignment.field.as_str() {
+            "status" => TaskChange::Status(value.parse()?),
+            "priority" => TaskChange::Priority(value.parse()?),
+            "due" if clear => TaskChange::Due(None),
+            "due" => TaskChange::Due(Some(parse_due_date(value)?)),
+            "project" if clear => TaskChange::Project(None),
+            "project" => TaskChange::Project(Some(linked(
+                value,
+                "project",
+                |id| id.project(storage),
+                |id| storage.load_project(id).map(|project| project.is_some()),
+            )?)),
+            "idea" if clear => TaskChange::Idea(None),
+            "idea" => TaskChange::Idea(Some(linked(
+                value,
+                "idea",
+                |id| id.idea(storage),
+                |id| storage.load_idea(id).map(|idea| idea.is_some()),
+            )?)),
+            field => unreachable!("{} is not in FIELDS", field),
+        })
+    }
+
+    fn apply(&mut self, change: &TaskChange) {
+        match change {
+            TaskChange::Status(status) if *status != self.status => self.set_status(status.clone()),
+            TaskChange::Status(_) => {}
+            TaskChange::Priority(priority) => self.priority = priority.clone(),
+            TaskChange::Due(due) => self.due_date = *due,
+            TaskChange::Project(project) => self.project_id = *project,
+            TaskChange::Idea(idea) => self.idea_id = *idea,
+        }
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+}
+
+/// The fields of a task the editor shows as front matter. The description
+/// is the body; links are IDs, though titles are accepted too.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TaskFields {
+    title: String,
+    status: String,
+    priority: String,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default)]
+    idea: Option<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// `task` as Markdown with front matter, headed by `heading`, for editing.
+fn task_document(task: &Task, heading: &str) -> Result<String> {
+    let fields = TaskFields {
+        title: task.title.clone(),
+        status: task.status.to_string(),
+        priority: task.priority.to_string(),
+        due: task.due_date.map(|due| due.format(dates::DATE).to_string()),
+        project: task.project_id.map(|id| id.to_string()),
+        idea: task.idea_id.map(|id| id.to_string()),
+        assignee: task.assignee.clone(),
+        tags: task.tags.clone(),
     };
+    let names = |names: Vec<&str>| names.join(", ");
+    let comments = [
+        heading.to_string(),
+        format!(
+            "Status: {}",
+            names(statuses().iter().map(|s| s.name.as_str()).collect())
+        ),
+        format!(
+            "Priority: {}",
+            names(priorities().iter().map(|p| p.name.as_str()).collect())
+        ),
+        "Due is YYYY-MM-DD; project and idea take an ID or title".to_string(),
+        "Write the description below the closing ---".to_string(),
+    ];
+    front_matter::render(&fields, &comments, task.description.as_deref())
+}
 
-    let priority_emoji = match task.priority {
-        TaskPriority::Low => "⬇️",
-        TaskPriority::Medium => "➡️",
-        TaskPriority::High => "⬆️",
-        TaskPriority::Urgent => "🔴",
+/// `task` with the changes in an edited [`task_document`], or what's wrong
+/// with them. Linked projects and ideas must exist.
+fn apply_document(storage: &Storage, mut task: Task, content: &str) -> Result<Task> {
+    let (fields, description): (TaskFields, _) = front_matter::parse(content)?;
+    let title = fields.title.trim();
+    if title.is_empty() {
+        anyhow::bail!("The title can't be empty");
+    }
+    let status: TaskStatus = fields
+        .status
+        .parse()
+        .map_err(|err| anyhow::anyhow!("status: {}", err))?;
+    if status != task.status {
+        task.set_status(status);
+    }
+    task.priority = fields
+        .priority
+        .parse()
+        .map_err(|err| anyhow::anyhow!("priority: {}", err))?;
+    let due = fields
+        .due
+        .as_deref()
+        .map(parse_due_date)
+        .transpose()
+        .map_err(|err| anyhow::anyhow!("due: {}", err))?;
+    // An unchanged date keeps its time of day
+    if task.due_date.map(|due| due.date_naive()) != due.map(|due| due.date_naive()) {
+        task.due_date = due;
+    }
+    task.project_id = match &fields.project {
+        Some(project) => Some(linked(
+            project,
+            "project",
+            |id| id.project(storage),
+            |id| storage.load_project(id).map(|project| project.is_some()),
+        )?),
+        None => None,
     };
+    task.idea_id = match &fields.idea {
+        Some(idea) => Some(linked(
+            idea,
+            "idea",
+            |id| id.idea(storage),
+            |id| storage.load_idea(id).map(|idea| idea.is_some()),
+        )?),
+        None => None,
+    };
+    task.assignee = fields
+        .assignee
+        .map(|assignee| assignee.trim().to_string())
+        .filter(|assignee| !assignee.is_empty());
+    task.title = title.to_string();
+    task.description = description;
+    task.tags = fields
+        .tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    Ok(task)
+}
 
-    println!(
+/// The existing item `text` names, for the `field` link of an edited task.
+fn linked(
+    text: &str,
+    field: &str,
+    resolve: impl FnOnce(&IdArg) -> Result<Uuid>,
+    exists: impl FnOnce(Uuid) -> Result<bool>,
+) -> Result<Uuid> {
+    let with_field = |err: anyhow::Error| anyhow::anyhow!("{}: {}", field, err);
+    let id = resolve(&text.parse().map_err(with_field)?).map_err(with_field)?;
+    if !exists(id)? {
+        anyhow::bail!("{}: no {} with ID {}", field, field, id);
+    }
+    Ok(id)
+}
+
+fn print_task_summary(task: &Task) {
+    let status_emoji = task.status.marker();
+
+    let priority_emoji = task.priority.marker();
+
+    say!(
         "{} {} {} [{}]",
-        status_emoji, priority_emoji, task.title, task.id
+        status_emoji,
+        priority_emoji,
+        style::paint(&task.title, style::task_status_color(&task.status)),
+        style::dim(task.id)
     );
 
     if let Some(description) = &task.description {
@@ -766,72 +1092,140 @@ fn print_task_summary(task: &Task) {
         } else {
             description.clone()
         };
-        println!("   {}", desc_preview);
+        say!("   {}", desc_preview);
     }
 
     if !task.tags.is_empty() {
-        println!("   🏷️  {}", task.tags.join(", "));
+        say!("   {} {}", symbols::TAGS, style::tags(&task.tags));
     }
 
     if let Some(due_date) = &task.due_date {
         let now = Utc::now();
-        let is_overdue = *due_date < now
-            && task.status != TaskStatus::Done
-            && task.status != TaskStatus::Cancelled;
+        let is_overdue = *due_date < now && !task.status.is_closed();
         if is_overdue {
-            println!("   ⏰ Due: {} (OVERDUE)", due_date.format("%Y-%m-%d"));
+            say!(
+                "   {} Due: {}",
+                symbols::DUE,
+                style::paint(format!("{} (OVERDUE)", dates::day(*due_date)), Color::Red)
+            );
         } else {
-            println!("   ⏰ Due: {}", due_date.format("%Y-%m-%d"));
+            say!("   {} Due: {}", symbols::DUE, dates::day(*due_date));
         }
     }
 
     if task.project_id.is_some() {
-        println!("   📁 Linked to project");
+        say!("   {} Linked to project", symbols::PROJECT);
     }
     if task.idea_id.is_some() {
-        println!("   💡 Linked to idea");
+        say!("   {} Linked to idea", symbols::IDEA);
+    }
+    if let Some(assignee) = &task.assignee {
+        say!("   Assignee: {}", assignee);
     }
 
-    println!(
-        "   📅 Updated: {}",
-        task.updated_at.format("%Y-%m-%d %H:%M")
+    say!(
+        "   {} Updated: {}",
+        symbols::DATE,
+        dates::moment(task.updated_at, dates::DATE_TIME)
     );
 }
 
+/// The columns `task list` can show, naming linked items from `projects` and `ideas`.
+fn task_columns<'a>(
+    projects: &'a [crate::models::Project],
+    ideas: &'a [crate::models::Idea],
+) -> Vec<Column<'a, Task>> {
+    vec![
+        Column::new("id", 8, |task: &Task| {
+            Cell::colored(short_id(&task.id), Color::Gray)
+        }),
+        Column::new("title", 40, |task: &Task| Cell::new(&task.title)),
+        Column::new("status", 14, |task: &Task| {
+            Cell::colored(
+                task.status.to_string(),
+                style::task_status_color(&task.status),
+            )
+        }),
+        Column::new("priority", 10, |task: &Task| {
+            Cell::colored(
+                task.priority.to_string(),
+                style::priority_color(&task.priority),
+            )
+        }),
+        Column::new("due", 14, |task: &Task| match task.due_date {
+            Some(due) if due < Utc::now() && !task.status.is_closed() => {
+                Cell::colored(dates::day(due), Color::Red)
+            }
+            Some(due) => Cell::new(dates::day(due)),
+            None => Cell::default(),
+        }),
+        Column::new("assignee", 16, |task: &Task| {
+            Cell::new(task.assignee.clone().unwrap_or_default())
+        }),
+        Column::new("tags", 30, |task: &Task| Cell::new(task.tags.join(", "))),
+        Column::new("project", 24, move |task: &Task| {
+            Cell::new(
+                task.project_id
+                    .and_then(|id| projects.iter().find(|project| project.id == id))
+                    .map(|project| project.title.clone())
+                    .unwrap_or_default(),
+            )
+        }),
+        Column::new("idea", 24, move |task: &Task| {
+            Cell::new(
+                task.idea_id
+                    .and_then(|id| ideas.iter().find(|idea| idea.id == id))
+                    .map(|idea| idea.title.clone())
+                    .unwrap_or_default(),
+            )
+        }),
+        Column::new("description", 40, |task: &Task| {
+            Cell::new(task.description.clone().unwrap_or_default())
+        }),
+        Column::new("created", 14, |task: &Task| {
+            Cell::new(dates::moment(task.created_at, dates::DATE))
+        }),
+        Column::new("updated", 14, |task: &Task| {
+            Cell::new(dates::moment(task.updated_at, dates::DATE))
+        }),
+    ]
+}
+
 fn print_task_full(
     task: &Task,
     projects: &[crate::models::Project],
     ideas: &[crate::models::Idea],
 ) {
-    let status_emoji = match task.status {
-        TaskStatus::Todo => "📋",
-        TaskStatus::InProgress => "🔄",
-        TaskStatus::Blocked => "🚫",
-        TaskStatus::Done => "✅",
-        TaskStatus::Cancelled => "❌",
-    };
+    let status_emoji = task.status.marker();
 
-    let priority_emoji = match task.priority {
-        TaskPriority::Low => "⬇️",
-        TaskPriority::Medium => "➡️",
-        TaskPriority::High => "⬆️",
-        TaskPriority::Urgent => "🔴",
-    };
+    let priority_emoji = task.priority.marker();
 
-    println!("{} {} {}", status_emoji, priority_emoji, task.title);
+    println!(
+        "{} {} {}",
+        status_emoji,
+        priority_emoji,
+        style::bold(&task.title)
+    );
     println!("ID: {}", task.id);
-    println!("Status: {}", task.status);
-    println!("Priority: {}", task.priority);
+    println!(
+        "Status: {}",
+        style::paint(&task.status, style::task_status_color(&task.status))
+    );
+    println!(
+        "Priority: {}",
+        style::paint(&task.priority, style::priority_color(&task.priority))
+    );
 
     if let Some(due_date) = &task.due_date {
         let now = Utc::now();
-        let is_overdue = *due_date < now
-            && task.status != TaskStatus::Done
-            && task.status != TaskStatus::Cancelled;
+        let is_overdue = *due_date < now && !task.status.is_closed();
         if is_overdue {
             println!(
-                "Due Date: {} (OVERDUE)",
-                due_date.format("%Y-%m-%d %H:%M UTC")
+                "Due Date: {}",
+                style::paint(
+                    format!("{} (OVERDUE)", due_date.format("%Y-%m-%d %H:%M UTC")),
+                    Color::Red
+                )
             );
         } else {
             println!("Due Date: {}", due_date.format("%Y-%m-%d %H:%M UTC"));
@@ -841,7 +1235,11 @@ fn print_task_full(
     }
 
     if !task.tags.is_empty() {
-        println!("Tags (Contexts): {}", task.tags.join(", "));
+        println!("Tags (Contexts): {}", style::tags(&task.tags));
+    }
+
+    if let Some(assignee) = &task.assignee {
+        println!("Assignee: {}", assignee);
     }
 
     if let Some(project_id) = &task.project_id {
@@ -874,6 +1272,12 @@ fn print_task_full(
         "Updated: {}",
         task.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
     );
+    if let Some(completed_at) = &task.completed_at {
+        println!(
+            "Completed: {}",
+            completed_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+    }
     println!();
 
     if let Some(description) = &task.description {
@@ -0,0 +1,188 @@
+//! `template`: save a project's shape under a name, to start new projects
+//! from with `project new --template`.
+
+use super::id::IdArg;
+use crate::dates;
+use crate::models::template::ProjectTemplate;
+use crate::output;
+use crate::say;
+use crate::storage::Storage;
+use crate::style;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+
+/// Field order of templates printed as JSON or YAML.
+const TEMPLATE_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "tags",
+    "milestones",
+    "tasks",
+    "created_at",
+];
+
+#[derive(Parser)]
+#[command(name = "template")]
+#[command(about = "Manage project templates")]
+pub struct TemplateCommands {
+    #[command(subcommand)]
+    pub command: TemplateSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum TemplateSubcommand {
+    /// Save a project's description, tags, milestones, and tasks as a template
+    Save(SaveTemplateArgs),
+    /// List saved templates
+    List(ListTemplateArgs),
+    /// Delete a template
+    Delete(DeleteTemplateArgs),
+}
+
+#[derive(Args)]
+pub struct SaveTemplateArgs {
+    /// The ID or title of the project to save
+    pub project: IdArg,
+
+    /// The name to save the template under
+    pub name: String,
+
+    /// Replace a template that already has this name
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct ListTemplateArgs {
+    /// Print as JSON for scripts (short for --format json)
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct DeleteTemplateArgs {
+    /// The name of the template to delete
+    pub name: String,
+}
+
+impl TemplateCommands {
+    pub fn execute(&self) -> Result<()> {
+        let storage = Storage::new().context("Failed to initialize storage")?;
+
+        match &self.command {
+            TemplateSubcommand::Save(args) => save_template(&storage, args),
+            TemplateSubcommand::List(_) => list_templates(&storage),
+            TemplateSubcommand::Delete(args) => delete_template(&storage, args),
+        }
+    }
+}
+
+pub fn save_template(storage: &Storage, args: &SaveTemplateArgs) -> Result<()> {
+    let name = args.name.trim();
+    if name.is_empty() {
+        anyhow::bail!("Template name cannot be empty");
+    }
+    let id = args.project.project(storage)?;
+    let project = storage.get_project(id)?;
+    let tasks = storage.load_tasks().context("Failed to load tasks")?;
+    let template = ProjectTemplate::from_project(name.to_string(), &project, &tasks);
+
+    let mut templates = storage
+        .load_templates()
+        .context("Failed to load templates")?;
+    match position(&templates, name) {
+        Some(_) if !args.force => anyhow::bail!(
+            "A template named '{}' already exists. Use --force to replace it",
+            name
+        ),
+        Some(index) => templates[index] = template.clone(),
+        None => templates.push(template.clone()),
+    }
+    storage
+        .save_templates(&templates)
+        .context("Failed to save templates")?;
+
+    say!(
+        "{} Saved project '{}' as template '{}'",
+        symbols::OK,
+        project.title,
+        template.name
+    );
+    say!(
+        "   {} milestone(s), {} task(s)",
+        template.milestones.len(),
+        template.tasks.len()
+    );
+    Ok(())
+}
+
+fn list_templates(storage: &Storage) -> Result<()> {
+    let templates = storage
+        .load_templates()
+        .context("Failed to load templates")?;
+    if !output::is_table() {
+        return output::print_list(&templates, TEMPLATE_FIELDS);
+    }
+    if templates.is_empty() {
+        println!(
+            "{} No templates yet. Save one with `ideavault template save <project> <name>`",
+            symbols::LIST
+        );
+        return Ok(());
+    }
+
+    println!("{} Found {} template(s):", symbols::LIST, templates.len());
+    println!();
+    for template in &templates {
+        println!(
+            "   {} {}",
+            template.name,
+            style::dim(format!(
+                "{} milestone(s) · {} task(s) · saved {}",
+                template.milestones.len(),
+                template.tasks.len(),
+                dates::moment(template.created_at, dates::DATE)
+            ))
+        );
+        if !template.tags.is_empty() {
+            println!("      {} {}", symbols::TAGS, template.tags.join(", "));
+        }
+    }
+    Ok(())
+}
+
+pub fn delete_template(storage: &Storage, args: &DeleteTemplateArgs) -> Result<()> {
+    let mut templates = storage
+        .load_templates()
+        .context("Failed to load templates")?;
+    let index = position(&templates, &args.name)
+        .ok_or_else(|| anyhow::anyhow!("No template named '{}'", args.name))?;
+    let removed = templates.remove(index);
+    storage
+        .save_templates(&templates)
+        .context("Failed to save templates")?;
+
+    say!("{} Deleted template '{}'", symbols::TRASH, removed.name);
+    Ok(())
+}
+
+/// The template named `name`, ignoring case.
+pub fn find_template(storage: &Storage, name: &str) -> Result<ProjectTemplate> {
+    let mut templates = storage
+        .load_templates()
+        .context("Failed to load templates")?;
+    let index = position(&templates, name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No template named '{}'. See `ideavault template list`",
+            name
+        )
+    })?;
+    Ok(templates.swap_remove(index))
+}
+
+fn position(templates: &[ProjectTemplate], name: &str) -> Option<usize> {
+    templates
+        .iter()
+        .position(|template| template.name.eq_ignore_ascii_case(name.trim()))
+}
@@ -0,0 +1,232 @@
+//! `project timeline`: projects laid out against the calendar, each as a bar
+//! from its creation to its due date with its milestones marked, so
+//! commitments that overlap stand out.
+
+use super::table::truncate;
+use super::task::parse_due_date;
+use crate::models::project::{Project, ProjectStatus};
+use crate::storage::Storage;
+use crate::style::{self, Color};
+use crate::symbols;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use clap::Args;
+use unicode_width::UnicodeWidthStr;
+
+/// The widest a project's title is shown.
+const LABEL_WIDTH: usize = 20;
+
+#[derive(Args)]
+pub struct TimelineArgs {
+    /// First day shown (YYYY-MM-DD); today if not given
+    #[arg(long, value_name = "DATE", value_parser = parse_due_date)]
+    pub from: Option<DateTime<Utc>>,
+
+    /// How many weeks the timeline covers
+    #[arg(long, default_value_t = 13, value_parser = clap::value_parser!(u16).range(1..))]
+    pub weeks: u16,
+
+    /// How wide the chart is, in characters
+    #[arg(short, long, default_value_t = 52, value_parser = clap::value_parser!(u16).range(10..))]
+    pub width: u16,
+
+    /// Only projects with this tag; repeat to require several
+    #[arg(short = 't', long = "tag")]
+    pub tags: Vec<String>,
+
+    /// Include completed projects, which are left out otherwise
+    #[arg(long)]
+    pub completed: bool,
+}
+
+pub fn show_timeline(storage: &Storage, args: &TimelineArgs) -> Result<()> {
+    let mut projects = storage.load_projects().context("Failed to load projects")?;
+    projects.retain(|project| {
+        project.status != ProjectStatus::Archived
+            && (args.completed || project.status != ProjectStatus::Completed)
+            && args.tags.iter().all(|tag| project.tags.contains(tag))
+    });
+    let now = Utc::now();
+    let from = args
+        .from
+        .unwrap_or_else(|| now.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc());
+    let days = i64::from(args.weeks) * 7;
+
+    println!(
+        "{} Timeline from {} to {}",
+        symbols::DATE,
+        from.format("%Y-%m-%d"),
+        (from + Duration::days(days - 1)).format("%Y-%m-%d")
+    );
+    println!();
+    print!(
+        "{}",
+        render_timeline(&projects, from, days, usize::from(args.width), now)
+    );
+    Ok(())
+}
+
+/// `projects` as a chart `width` characters wide covering the `days` days
+/// from `from`, one row per project that has a due date or a dated
+/// milestone, soonest due first. A row is a bar from the project's creation
+/// to its due date, with its milestones marked; arrows show a bar carrying on
+/// past either edge.
+pub fn render_timeline(
+    projects: &[Project],
+    from: DateTime<Utc>,
+    days: i64,
+    width: usize,
+    now: DateTime<Utc>,
+) -> String {
+    let plain = symbols::is_plain();
+    let glyph = |fancy: char, ascii: char| if plain { ascii } else { fancy };
+    let span = days * 24 * 60;
+    // The column a moment falls in, before the chart (negative) or after it
+    // (`width` or more) when it's outside
+    let column = |moment: DateTime<Utc>| -> i64 {
+        ((moment - from).num_minutes() * width as i64).div_euclid(span)
+    };
+
+    let mut dated: Vec<(&Project, DateTime<Utc>)> = projects
+        .iter()
+        .filter_map(|project| end_of(project).map(|end| (project, end)))
+        .collect();
+    let undated = projects.len() - dated.len();
+    dated.sort_by_key(|(project, end)| (*end, project.created_at));
+    if dated.is_empty() {
+        return format!(
+            "{} No projects with a due date or dated milestones\n",
+            symbols::LIST
+        );
+    }
+
+    let label_width = dated
+        .iter()
+        .map(|(project, _)| project.title.width())
+        .max()
+        .unwrap_or(0)
+        .clamp(5, LABEL_WIDTH);
+    let indent = " ".repeat(label_width + 2);
+
+    // Month names over the columns each month starts in
+    let mut months = vec![' '; width];
+    let mut axis = vec![glyph('─', '-'); width];
+    let mut month_start = None;
+    for index in 0..width {
+        let day = from + Duration::minutes(span * index as i64 / width as i64);
+        let month = (day.year(), day.month());
+        if month_start == Some(month) {
+            continue;
+        }
+        let name = if index == 0 || day.month() == 1 {
+            day.format("%b %Y").to_string()
+        } else {
+            day.format("%b").to_string()
+        };
+        let free = index == 0 || months[index - 1] == ' ';
+        if free && index + name.len() <= width {
+            for (offset, c) in name.chars().enumerate() {
+                months[index + offset] = c;
+            }
+        }
+        axis[index] = glyph('┬', '+');
+        month_start = Some(month);
+    }
+    let mut out = format!(
+        "{}{}\n",
+        indent,
+        months.iter().collect::<String>().trim_end()
+    );
+    out.push_str(&format!("{}{}\n", indent, axis.iter().collect::<String>()));
+
+    for (project, end) in dated {
+        let mut cells: Vec<(char, Option<Color>)> = vec![(' ', None); width];
+        let bar_color = Some(style::project_status_color(&project.status));
+        let start = column(project.created_at).max(0);
+        let stop = column(end);
+        let last = width as i64 - 1;
+        if stop >= 0 && start <= last {
+            for index in start..=stop.min(last) {
+                cells[index as usize] = (glyph('━', '='), bar_color);
+            }
+            if project.due_date.is_some() && stop <= last {
+                cells[stop as usize] = (glyph('┫', '|'), bar_color);
+            }
+            if stop > last {
+                cells[last as usize] = (glyph('▶', '>'), bar_color);
+            }
+        } else if stop < 0 {
+            cells[0] = (glyph('◀', '<'), Some(Color::Red));
+        }
+        for milestone in &project.milestones {
+            let Some(due) = milestone.due_date else {
+                continue;
+            };
+            let index = column(due);
+            if (0..=last).contains(&index) {
+                let marker = if milestone.is_completed() {
+                    glyph('◇', 'o')
+                } else {
+                    glyph('◆', '*')
+                };
+                cells[index as usize] = (marker, None);
+            }
+        }
+
+        let label = truncate(&project.title, label_width);
+        let mut line = format!(
+            "{}{}  ",
+            label,
+            " ".repeat(label_width.saturating_sub(label.width()))
+        );
+        for (c, color) in &cells {
+            match color {
+                Some(color) => line.push_str(&style::paint(c, *color)),
+                None => line.push(*c),
+            }
+        }
+        let note = match project.due_date {
+            Some(due) if project.is_overdue(now) => style::paint(
+                format!("overdue since {}", due.format("%Y-%m-%d")),
+                Color::Red,
+            ),
+            Some(due) => style::dim(format!("due {}", due.format("%Y-%m-%d"))),
+            None => style::dim(format!("last milestone {}", end.format("%Y-%m-%d"))),
+        };
+        out.push_str(&format!("{}  {}\n", line, note));
+    }
+
+    out.push('\n');
+    out.push_str(&style::dim(format!(
+        "{}{} creation to due date  {} due  {} milestone  {} reached",
+        indent,
+        glyph('━', '='),
+        glyph('┫', '|'),
+        glyph('◆', '*'),
+        glyph('◇', 'o')
+    )));
+    out.push('\n');
+    if undated > 0 {
+        out.push_str(&format!(
+            "{}{}\n",
+            indent,
+            style::dim(format!(
+                "{} project(s) without a due date or dated milestones not shown",
+                undated
+            ))
+        ));
+    }
+    out
+}
+
+/// When `project` ends on the timeline: its due date, or else its last dated
+/// milestone.
+fn end_of(project: &Project) -> Option<DateTime<Utc>> {
+    project.due_date.or_else(|| {
+        project
+            .milestones
+            .iter()
+            .filter_map(|milestone| milestone.due_date)
+            .max()
+    })
+}
@@ -0,0 +1,164 @@
+use super::id::IdArg;
+use super::page::{Page, PageArgs};
+use crate::dates;
+use crate::output;
+use crate::say;
+use crate::storage::{Storage, TrashEntry};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::io::{self, Write};
+use uuid::Uuid;
+
+#[derive(Parser)]
+#[command(name = "trash")]
+#[command(about = "Manage deleted items")]
+pub struct TrashCommands {
+    #[command(subcommand)]
+    pub command: TrashSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum TrashSubcommand {
+    /// List deleted items
+    List(ListTrashArgs),
+    /// Put a deleted item back where it was
+    Restore(RestoreTrashArgs),
+    /// Permanently delete everything in the trash
+    Empty(EmptyTrashArgs),
+}
+
+#[derive(Args)]
+pub struct ListTrashArgs {
+    #[command(flatten)]
+    pub page: PageArgs,
+
+    /// Print only the full ID of each item, one per line (short for --quiet)
+    #[arg(long)]
+    pub ids: bool,
+}
+
+#[derive(Args)]
+pub struct RestoreTrashArgs {
+    /// The ID or title of the deleted idea, project, or task
+    pub id: IdArg,
+}
+
+#[derive(Args)]
+pub struct EmptyTrashArgs {
+    /// Skip confirmation prompt
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+impl TrashCommands {
+    pub fn execute(&self) -> Result<()> {
+        let storage = Storage::new().context("Failed to initialize storage")?;
+
+        match &self.command {
+            TrashSubcommand::List(args) => Self::list_trash(&storage, args),
+            TrashSubcommand::Restore(args) => Self::restore_item(&storage, args),
+            TrashSubcommand::Empty(args) => Self::empty_trash(&storage, args),
+        }
+    }
+
+    fn list_trash(storage: &Storage, args: &ListTrashArgs) -> Result<()> {
+        storage.purge_trash().context("Failed to purge old trash")?;
+        let mut entries = storage.load_trash().context("Failed to load trash")?;
+        // Most recently deleted first
+        entries.reverse();
+        let page = Page::from_config(entries.len(), &args.page)?;
+        let shown = &entries[page.start..page.end];
+        if output::is_quiet() {
+            output::print_ids(shown.iter().map(|entry| entry.id()));
+            return Ok(());
+        }
+
+        if entries.is_empty() {
+            println!("{} Trash is empty", symbols::TRASH);
+            return Ok(());
+        }
+
+        println!(
+            "{} Found {} deleted item(s):",
+            symbols::TRASH,
+            entries.len()
+        );
+        println!();
+
+        for entry in shown {
+            print_trash_entry(entry);
+            println!();
+        }
+        if let Some(footer) = page.footer("deleted item") {
+            println!("{}", footer);
+        }
+
+        Ok(())
+    }
+
+    pub fn restore_item(storage: &Storage, args: &RestoreTrashArgs) -> Result<()> {
+        let entries = storage.load_trash().context("Failed to load trash")?;
+        let candidates: Vec<(Uuid, String)> = entries
+            .iter()
+            .map(|entry| (entry.id(), entry.title().to_string()))
+            .collect();
+        let id = args.id.resolve("trashed item", &candidates)?;
+        let entry = storage.restore_from_trash(id)?;
+
+        say!(
+            "{} Restored {}: {}",
+            symbols::OK,
+            entry.kind(),
+            entry.title()
+        );
+        Ok(())
+    }
+
+    pub fn empty_trash(storage: &Storage, args: &EmptyTrashArgs) -> Result<()> {
+        let entries = storage.load_trash().context("Failed to load trash")?;
+
+        if entries.is_empty() {
+            say!("{} Trash is already empty", symbols::TRASH);
+            return Ok(());
+        }
+
+        if !args.force {
+            print!(
+                "Permanently delete {} item(s) from the trash? [y/N]: ",
+                entries.len()
+            );
+            io::stdout().flush().context("Failed to flush output")?;
+
+            let mut input = String::new();
+            io::stdin()
+                .read_line(&mut input)
+                .context("Failed to read input")?;
+
+            let response = input.trim().to_lowercase();
+            if !matches!(response.as_str(), "y" | "yes") {
+                say!("{} Cancelled", symbols::ERROR);
+                return Ok(());
+            }
+        }
+
+        storage.save_trash(&[]).context("Failed to empty trash")?;
+
+        say!(
+            "{} Permanently deleted {} item(s)",
+            symbols::OK,
+            entries.len()
+        );
+        Ok(())
+    }
+}
+
+fn print_trash_entry(entry: &TrashEntry) {
+    println!("{} [{}] {}", symbols::TRASH, entry.kind(), entry.title());
+    println!("   {} {}", symbols::ID, entry.id());
+    println!(
+        "   {} Deleted: {}",
+        symbols::DATE,
+        dates::moment(entry.deleted_at, "%Y-%m-%d %H:%M:%S UTC")
+    );
+}
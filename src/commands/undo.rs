@@ -0,0 +1,129 @@
+use crate::say;
+use crate::storage::history::{Action, HistoryEntry, Operation};
+use crate::storage::Storage;
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::Args;
+use std::io::{self, Write};
+
+/// How many operations `undo --list` shows
+const LIST_LIMIT: usize = 20;
+
+#[derive(Args)]
+pub struct UndoArgs {
+    /// Show the operations that can be undone instead of undoing one
+    #[arg(short, long)]
+    pub list: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+pub fn execute(args: UndoArgs) -> Result<()> {
+    let storage = Storage::new().context("Failed to initialize storage")?;
+    if args.list {
+        list_undo_stack(&storage)
+    } else {
+        undo(&storage, &args)
+    }
+}
+
+pub fn undo(storage: &Storage, args: &UndoArgs) -> Result<()> {
+    let stack = storage.undo_stack().context("Failed to load history")?;
+    let Some(operation) = stack.first() else {
+        say!("{} Nothing to undo", symbols::LIST);
+        return Ok(());
+    };
+
+    if !args.force {
+        say!("This will revert:");
+        print_operation(operation);
+        print!("Undo this operation? [y/N]: ");
+        io::stdout().flush().context("Failed to flush output")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read input")?;
+
+        let response = input.trim().to_lowercase();
+        if !matches!(response.as_str(), "y" | "yes") {
+            say!("{} Undo cancelled", symbols::ERROR);
+            return Ok(());
+        }
+    }
+
+    let Some(operation) = storage.undo().context("Failed to undo")? else {
+        say!("{} Nothing to undo", symbols::LIST);
+        return Ok(());
+    };
+
+    say!("{} Undid {}", symbols::OK, describe(&operation));
+    Ok(())
+}
+
+pub fn list_undo_stack(storage: &Storage) -> Result<()> {
+    let stack = storage.undo_stack().context("Failed to load history")?;
+    if stack.is_empty() {
+        println!("{} Nothing to undo", symbols::LIST);
+        return Ok(());
+    }
+
+    println!(
+        "{} {} operation(s) can be undone, most recent first:",
+        symbols::LIST,
+        stack.len()
+    );
+    println!();
+    for operation in stack.iter().take(LIST_LIMIT) {
+        print_operation(operation);
+        println!();
+    }
+    if stack.len() > LIST_LIMIT {
+        println!("... and {} older", stack.len() - LIST_LIMIT);
+    }
+
+    Ok(())
+}
+
+fn print_operation(operation: &Operation) {
+    println!(
+        "{} {} — {}",
+        symbols::UNDO,
+        operation.timestamp().format("%Y-%m-%d %H:%M:%S UTC"),
+        describe(operation)
+    );
+    for entry in &operation.entries {
+        println!("   {}", describe_entry(entry));
+    }
+}
+
+fn describe(operation: &Operation) -> String {
+    match operation.command() {
+        Some(command) => format!("`{}` ({} change(s))", command, operation.entries.len()),
+        None => format!("{} change(s)", operation.entries.len()),
+    }
+}
+
+fn describe_entry(entry: &HistoryEntry) -> String {
+    let verb = match entry.action {
+        Action::Create => "created",
+        Action::Update => "updated",
+        Action::Delete => "deleted",
+    };
+    let mut line = format!("{} {} {}", verb, entry.entity, entry.entity_id);
+
+    if entry.action == Action::Update {
+        let fields: Vec<&str> = entry
+            .changes
+            .iter()
+            .map(|change| change.field.as_str())
+            .filter(|field| *field != "updated_at")
+            .collect();
+        if !fields.is_empty() {
+            line.push_str(&format!(" ({})", fields.join(", ")));
+        }
+    }
+    line
+}
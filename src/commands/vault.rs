@@ -0,0 +1,349 @@
+use crate::import::Importable;
+use crate::models::Timestamped;
+use crate::say;
+use crate::storage::{Storage, VaultRegistry};
+use crate::symbols;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+#[derive(Parser)]
+#[command(name = "vault")]
+#[command(about = "Manage separate vaults")]
+pub struct VaultCommands {
+    #[command(subcommand)]
+    pub command: VaultSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum VaultSubcommand {
+    /// Create a new, empty vault
+    New(NewVaultArgs),
+    /// List all vaults
+    List,
+    /// Make another vault the current one
+    Switch(SwitchVaultArgs),
+    /// Copy another vault's ideas, projects, tasks, and tags into this one
+    Merge(MergeVaultArgs),
+}
+
+#[derive(Args)]
+pub struct NewVaultArgs {
+    /// Name of the vault (letters, digits, '-' and '_')
+    pub name: String,
+
+    /// Directory to keep the vault in (defaults to a directory next to the default vault)
+    #[arg(short, long)]
+    pub path: Option<PathBuf>,
+
+    /// Switch to the new vault right away
+    #[arg(short, long)]
+    pub switch: bool,
+}
+
+#[derive(Args)]
+pub struct SwitchVaultArgs {
+    /// Name of the vault to use from now on
+    pub name: String,
+}
+
+#[derive(Args)]
+pub struct MergeVaultArgs {
+    /// Data directory of the vault to merge in, or the name of a registered vault
+    pub source: String,
+
+    /// Show what would be merged without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// What merging one entity type did.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MergeCounts {
+    /// Titles of entities only the other vault had
+    pub added: Vec<String>,
+    /// Titles of entities the other vault changed more recently
+    pub updated: Vec<String>,
+    /// Entities changed more recently here
+    pub kept: usize,
+    /// Entities that are the same in both vaults
+    pub identical: usize,
+    /// Entities the other vault has under a different ID but otherwise identical
+    pub duplicates: usize,
+}
+
+impl MergeCounts {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MergeSummary {
+    pub ideas: MergeCounts,
+    pub projects: MergeCounts,
+    pub tasks: MergeCounts,
+    /// Names of tags only the other vault had
+    pub tags: Vec<String>,
+}
+
+impl VaultCommands {
+    pub fn execute(&self) -> Result<()> {
+        let mut registry = VaultRegistry::open().context("Failed to load vault registry")?;
+
+        match &self.command {
+            VaultSubcommand::New(args) => Self::new_vault(&mut registry, args),
+            VaultSubcommand::List => Self::list_vaults(&registry),
+            VaultSubcommand::Switch(args) => Self::switch_vault(&mut registry, args),
+            VaultSubcommand::Merge(args) => Self::merge_vault(&registry, args),
+        }
+    }
+
+    pub fn new_vault(registry: &mut VaultRegistry, args: &NewVaultArgs) -> Result<()> {
+        let data_dir = registry.create(&args.name, args.path.clone())?;
+        if args.switch {
+            registry.switch(&args.name)?;
+        }
+        registry.save().context("Failed to save vault registry")?;
+
+        say!(
+            "{} Created vault '{}' at {}",
+            symbols::OK,
+            args.name,
+            data_dir.display()
+        );
+        if args.switch {
+            say!("   Now using vault '{}'", args.name);
+        } else {
+            say!("   Switch to it with: ideavault vault switch {}", args.name);
+        }
+        Ok(())
+    }
+
+    fn list_vaults(registry: &VaultRegistry) -> Result<()> {
+        let vaults = registry.list();
+        println!("{} Found {} vault(s):", symbols::LIST, vaults.len());
+        println!();
+
+        for (name, data_dir) in vaults {
+            let marker = if name == registry.active_name() {
+                "*"
+            } else {
+                " "
+            };
+            println!("{} {:<16} {}", marker, name, data_dir.display());
+        }
+        Ok(())
+    }
+
+    pub fn switch_vault(registry: &mut VaultRegistry, args: &SwitchVaultArgs) -> Result<()> {
+        registry.switch(&args.name)?;
+        registry.save().context("Failed to save vault registry")?;
+
+        say!("{} Now using vault '{}'", symbols::OK, args.name);
+        Ok(())
+    }
+
+    fn merge_vault(registry: &VaultRegistry, args: &MergeVaultArgs) -> Result<()> {
+        let path = PathBuf::from(&args.source);
+        let source_dir = if path.is_dir() {
+            path
+        } else {
+            registry.data_dir(&args.source).with_context(|| {
+                format!("{} is neither a directory nor a vault name", args.source)
+            })?
+        };
+
+        let target = Storage::new().context("Failed to initialize storage")?;
+        let same = match (source_dir.canonicalize(), target.data_dir().canonicalize()) {
+            (Ok(source), Ok(target)) => source == target,
+            _ => source_dir == target.data_dir(),
+        };
+        if same {
+            anyhow::bail!("Cannot merge a vault into itself");
+        }
+
+        let source = Storage::new_with_path(source_dir.clone())
+            .with_context(|| format!("Failed to open vault at {}", source_dir.display()))?;
+        merge(&target, &source, args).map(|_| ())
+    }
+}
+
+/// Merge everything in `source` into `target`, matching entities by ID. Where
+/// both vaults have an entity, the more recently updated version wins.
+/// Entities only `source` has are added unless `target` already has an
+/// identical one under another ID, in which case links to it are pointed at
+/// the existing one.
+pub fn merge(target: &Storage, source: &Storage, args: &MergeVaultArgs) -> Result<MergeSummary> {
+    let mut summary = MergeSummary::default();
+    // Source IDs of duplicates, mapped to the matching target IDs
+    let mut remap: HashMap<Uuid, Uuid> = HashMap::new();
+    let remapped = |remap: &HashMap<Uuid, Uuid>, id: Uuid| remap.get(&id).copied().unwrap_or(id);
+
+    let mut ideas = target.load_ideas().context("Failed to load ideas")?;
+    let incoming = source
+        .load_ideas()
+        .context("Failed to load other vault's ideas")?;
+    merge_items(&mut ideas, incoming, &mut remap, &mut summary.ideas);
+    // Links between incoming items are fixed up once all their duplicates
+    // are known
+    for idea in &mut ideas {
+        remap_all(&mut idea.related_ids, &remap);
+    }
+
+    let mut projects = target.load_projects().context("Failed to load projects")?;
+    let mut incoming = source
+        .load_projects()
+        .context("Failed to load other vault's projects")?;
+    for project in &mut incoming {
+        for idea_id in &mut project.idea_ids {
+            *idea_id = remapped(&remap, *idea_id);
+        }
+    }
+    merge_items(&mut projects, incoming, &mut remap, &mut summary.projects);
+    for project in &mut projects {
+        project.parent_id = project.parent_id.map(|id| remapped(&remap, id));
+        remap_all(&mut project.depends_on, &remap);
+    }
+
+    let mut tasks = target.load_tasks().context("Failed to load tasks")?;
+    let mut incoming = source
+        .load_tasks()
+        .context("Failed to load other vault's tasks")?;
+    for task in &mut incoming {
+        task.idea_id = task.idea_id.map(|id| remapped(&remap, id));
+        task.project_id = task.project_id.map(|id| remapped(&remap, id));
+    }
+    merge_items(&mut tasks, incoming, &mut remap, &mut summary.tasks);
+
+    let mut tags = target.load_tags().context("Failed to load tags")?;
+    for tag in source
+        .load_tags()
+        .context("Failed to load other vault's tags")?
+    {
+        if !tags.iter().any(|t| t.name == tag.name) {
+            summary.tags.push(tag.name.clone());
+            tags.push(tag);
+        }
+    }
+
+    if !args.dry_run {
+        if !summary.ideas.is_empty() {
+            target.save_ideas(&ideas).context("Failed to save ideas")?;
+        }
+        if !summary.projects.is_empty() {
+            target
+                .save_projects(&projects)
+                .context("Failed to save projects")?;
+        }
+        if !summary.tasks.is_empty() {
+            target.save_tasks(&tasks).context("Failed to save tasks")?;
+        }
+        if !summary.tags.is_empty() {
+            target.save_tags(&tags).context("Failed to save tags")?;
+        }
+    }
+
+    print_merge_summary(source, &summary, args);
+    Ok(summary)
+}
+
+fn merge_items<T>(
+    target: &mut Vec<T>,
+    incoming: Vec<T>,
+    remap: &mut HashMap<Uuid, Uuid>,
+    counts: &mut MergeCounts,
+) where
+    T: Importable + Timestamped + Serialize,
+{
+    for item in incoming {
+        if let Some(existing) = target.iter_mut().find(|t| t.id() == item.id()) {
+            if content(existing, true) == content(&item, true) {
+                counts.identical += 1;
+            } else if item.updated_at() > existing.updated_at() {
+                counts.updated.push(item.title().to_string());
+                *existing = item;
+            } else {
+                counts.kept += 1;
+            }
+            continue;
+        }
+
+        let fields = content(&item, false);
+        if let Some(duplicate) = target.iter().find(|t| content(*t, false) == fields) {
+            remap.insert(item.id(), duplicate.id());
+            counts.duplicates += 1;
+            continue;
+        }
+
+        counts.added.push(item.title().to_string());
+        target.push(item);
+    }
+}
+
+/// Point `ids` at the items the duplicates among them were merged into,
+/// dropping any repeats that leaves.
+fn remap_all(ids: &mut Vec<Uuid>, remap: &HashMap<Uuid, Uuid>) {
+    let mut seen = Vec::new();
+    for id in ids.drain(..) {
+        let id = remap.get(&id).copied().unwrap_or(id);
+        if !seen.contains(&id) {
+            seen.push(id);
+        }
+    }
+    *ids = seen;
+}
+
+/// An entity as JSON, without its ID and timestamps unless `all` is set, so
+/// that copies made separately compare equal.
+fn content<T: Serialize>(item: &T, all: bool) -> Value {
+    let mut value = serde_json::to_value(item).unwrap_or_default();
+    if let (false, Some(fields)) = (all, value.as_object_mut()) {
+        for field in ["id", "created_at", "updated_at"] {
+            fields.remove(field);
+        }
+    }
+    value
+}
+
+fn print_merge_summary(source: &Storage, summary: &MergeSummary, args: &MergeVaultArgs) {
+    let from = source.data_dir().display();
+    if args.dry_run {
+        say!(
+            "{} Dry run: merging the vault at {} would change:",
+            symbols::LIST,
+            from
+        );
+    } else {
+        say!("{} Merged the vault at {}:", symbols::OK, from);
+    }
+
+    for (kind, counts) in [
+        ("Ideas", &summary.ideas),
+        ("Projects", &summary.projects),
+        ("Tasks", &summary.tasks),
+    ] {
+        say!(
+            "   {}: {} added, {} updated, {} newer here, {} identical, {} duplicate(s) skipped",
+            kind,
+            counts.added.len(),
+            counts.updated.len(),
+            counts.kept,
+            counts.identical,
+            counts.duplicates
+        );
+        for title in &counts.added {
+            say!("      {} Added: {}", symbols::NEW, title);
+        }
+        for title in &counts.updated {
+            say!("      {} Updated: {}", symbols::EDITED, title);
+        }
+    }
+    if !summary.tags.is_empty() {
+        say!("   Tags added: {}", summary.tags.join(", "));
+    }
+}
@@ -1,9 +1,9 @@
+use super::github::{self, Release};
 use anyhow::Result;
 use clap::Args;
-use serde::Deserialize;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
-const GITHUB_API_URL: &str = "https://api.github.com/repos/bigknoxy/ideavault/releases/latest";
+const LATEST_RELEASE: &str = "/repos/bigknoxy/ideavault/releases/latest";
 
 #[derive(Debug, Args)]
 pub struct VersionArgs {
@@ -12,11 +12,6 @@ pub struct VersionArgs {
     pub check: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct Release {
-    tag_name: String,
-}
-
 pub fn execute(args: VersionArgs) -> Result<()> {
     println!("IdeaVault v{VERSION}");
 
@@ -42,9 +37,7 @@ pub fn execute(args: VersionArgs) -> Result<()> {
 }
 
 fn check_latest_version() -> Result<String> {
-    let response = ureq::get(GITHUB_API_URL)
-        .set("User-Agent", &format!("IdeaVault/{VERSION}"))
-        .call()?
-        .into_json::<Release>()?;
-    Ok(response.tag_name)
+    let release: Release = github::get(LATEST_RELEASE)?
+        .ok_or_else(|| anyhow::anyhow!("no release has been published"))?;
+    Ok(release.tag_name)
 }
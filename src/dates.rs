@@ -0,0 +1,130 @@
+//! How lists and summaries show times: relative to now ("3 hours ago",
+//! "in 2 days") unless the `relative_dates` setting is off, in which case
+//! as plain dates. Full details (`show`) always give the exact time. Also
+//! reads the spans filters take, such as "7d".
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::sync::OnceLock;
+
+/// Date only, for table columns.
+pub const DATE: &str = "%Y-%m-%d";
+
+/// Date and time to the minute, for summaries.
+pub const DATE_TIME: &str = "%Y-%m-%d %H:%M";
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+const MONTH: u64 = 30 * DAY;
+const YEAR: u64 = 365 * DAY;
+
+/// Largest unit first, so a span is given in the largest unit it fills.
+const UNITS: &[(u64, &str)] = &[
+    (YEAR, "year"),
+    (MONTH, "month"),
+    (WEEK, "week"),
+    (DAY, "day"),
+    (HOUR, "hour"),
+    (MINUTE, "minute"),
+];
+
+static RELATIVE: OnceLock<bool> = OnceLock::new();
+
+/// Show times relative to now (or not) for the rest of the process.
+pub fn set_relative(relative: bool) {
+    let _ = RELATIVE.set(relative);
+}
+
+pub fn is_relative() -> bool {
+    RELATIVE.get().copied().unwrap_or(true)
+}
+
+/// `time` as "3 hours ago" or "in 2 days", or in `format` when relative
+/// dates are off.
+pub fn moment(time: DateTime<Utc>, format: &str) -> String {
+    if is_relative() {
+        relative(time, Utc::now())
+    } else {
+        time.format(format).to_string()
+    }
+}
+
+/// The day of `time` as "today", "tomorrow", or "in 2 weeks", or as
+/// YYYY-MM-DD when relative dates are off. For due dates, where the time of
+/// day doesn't matter.
+pub fn day(time: DateTime<Utc>) -> String {
+    if is_relative() {
+        relative_day(time.date_naive(), Utc::now().date_naive())
+    } else {
+        time.format(DATE).to_string()
+    }
+}
+
+/// How long before or after `now` `time` is, e.g. "5 minutes ago".
+pub fn relative(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - time).num_seconds();
+    if seconds.unsigned_abs() < MINUTE {
+        return "just now".to_string();
+    }
+    with_direction(span(seconds.unsigned_abs()), seconds > 0)
+}
+
+/// How many days before or after `today` `day` is, e.g. "in 3 days".
+pub fn relative_day(day: NaiveDate, today: NaiveDate) -> String {
+    let days = (today - day).num_days();
+    match days {
+        0 => "today".to_string(),
+        -1 => "tomorrow".to_string(),
+        1 => "yesterday".to_string(),
+        _ => with_direction(span(days.unsigned_abs() * DAY), days > 0),
+    }
+}
+
+/// How long `duration` is, in the largest unit it fills, e.g. "2 weeks".
+pub fn length(duration: Duration) -> String {
+    span(duration.num_seconds().unsigned_abs())
+}
+
+/// `seconds` in the largest unit it fills, e.g. "2 weeks".
+fn span(seconds: u64) -> String {
+    let (size, unit) = UNITS
+        .iter()
+        .find(|(size, _)| seconds >= *size)
+        .unwrap_or(&(MINUTE, "minute"));
+    let count = (seconds / size).max(1);
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+/// Read a span such as "12h", "7d", or "2w": a whole number of hours, days,
+/// or weeks.
+pub fn parse_span(text: &str) -> Result<Duration> {
+    let text = text.trim();
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid span '{}'. Use a number and h, d, or w, such as 7d",
+            text
+        )
+    };
+    let split = text.len().saturating_sub(1);
+    let (count, unit) = (
+        text.get(..split).ok_or_else(invalid)?,
+        text.get(split..).ok_or_else(invalid)?,
+    );
+    let count = i64::from(count.parse::<u32>().map_err(|_| invalid())?);
+    match unit.to_ascii_lowercase().as_str() {
+        "h" => Ok(Duration::hours(count)),
+        "d" => Ok(Duration::days(count)),
+        "w" => Ok(Duration::weeks(count)),
+        _ => Err(invalid()),
+    }
+}
+
+fn with_direction(span: String, past: bool) -> String {
+    if past {
+        format!("{} ago", span)
+    } else {
+        format!("in {}", span)
+    }
+}
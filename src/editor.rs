@@ -0,0 +1,137 @@
+//! Opening files in the user's editor.
+//!
+//! The editor is the configured `default_editor`, else `$VISUAL`, else
+//! `$EDITOR`, else vim. It may include arguments, such as `code --wait`;
+//! quote parts containing spaces.
+
+use crate::models::config::Config;
+use crate::symbols;
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::process::Command;
+
+const FALLBACK_EDITOR: &str = "vim";
+
+/// The editor command line to use when `configured` is the config's `default_editor`.
+pub fn editor_command(configured: Option<&str>) -> String {
+    let from_env = |name| env::var(name).ok().filter(|value| !value.trim().is_empty());
+    configured
+        .filter(|editor| !editor.trim().is_empty())
+        .map(str::to_string)
+        .or_else(|| from_env("VISUAL"))
+        .or_else(|| from_env("EDITOR"))
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+/// Split an editor command line into the program and its arguments. Single
+/// and double quotes group words and a backslash escapes the next character.
+pub fn split_command(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some('"') | None, '\\') => {
+                if let Some(escaped) = chars.next() {
+                    word.push(escaped);
+                }
+                in_word = true;
+            }
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        anyhow::bail!("Unterminated quote in editor command: {}", command);
+    }
+    if in_word {
+        words.push(word);
+    }
+    if words.is_empty() {
+        anyhow::bail!("Editor command is empty");
+    }
+    Ok(words)
+}
+
+/// Let the user edit `template` in their editor until `parse` accepts what
+/// they saved, like `git commit`. `name` names the temporary file, which is
+/// removed afterwards. When `parse` fails, the problem is shown and, at a
+/// terminal, the user can fix it or give up; otherwise it is returned.
+pub fn edit_until_valid<T>(
+    name: &str,
+    template: &str,
+    mut parse: impl FnMut(&str) -> Result<T>,
+) -> Result<T> {
+    let temp_file = env::temp_dir().join(format!("ideavault-{}-{}.md", name, std::process::id()));
+    fs::write(&temp_file, template).context("Failed to create temp file")?;
+
+    let result = loop {
+        let edited = open(&temp_file)
+            .and_then(|_| fs::read_to_string(&temp_file).context("Failed to read edited content"));
+        let err = match edited.and_then(|edited| parse(&edited)) {
+            Ok(value) => break Ok(value),
+            Err(err) => err,
+        };
+        if !io::stdin().is_terminal() {
+            break Err(err);
+        }
+        println!("{} {:#}", symbols::ERROR, err);
+        match edit_again() {
+            Ok(true) => {}
+            Ok(false) => break Err(anyhow::anyhow!("Changes discarded")),
+            Err(err) => break Err(err),
+        }
+    };
+
+    let _ = fs::remove_file(&temp_file);
+    result
+}
+
+/// Ask whether to reopen a file whose edit couldn't be used.
+pub fn edit_again() -> Result<bool> {
+    print!("Edit it again? [Y/n]: ");
+    io::stdout().flush().context("Failed to flush output")?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .context("Failed to read input")?;
+    Ok(!matches!(input.trim().to_lowercase().as_str(), "n" | "no"))
+}
+
+/// Open `path` in the user's editor and wait for it to close.
+pub fn open(path: &Path) -> Result<()> {
+    let config = Config::load().context("Failed to load config")?;
+    let command = editor_command(config.default_editor.as_deref());
+    let words = split_command(&command)?;
+
+    let status = Command::new(&words[0])
+        .args(&words[1..])
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to open editor: {}", command))?;
+    if !status.success() {
+        anyhow::bail!("Editor exited with non-zero status");
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Minimal CSV reading and writing (RFC 4180).
+//!
+//! Lists are joined with `;` and missing values are left empty, so every row
+//! has the same columns as the header.
+
+use super::Record;
+use serde_json::Value;
+
+/// Separator used for list values such as tags or linked IDs.
+pub const LIST_SEPARATOR: &str = ";";
+
+/// Render `records` with a header row. Columns appear in the order they are
+/// first seen.
+pub fn render(records: &[Record]) -> String {
+    let mut columns: Vec<&str> = Vec::new();
+    for record in records {
+        for (field, _) in record {
+            if !columns.contains(&field.as_str()) {
+                columns.push(field);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    push_row(&mut out, columns.iter().map(|column| column.to_string()));
+    for record in records {
+        push_row(
+            &mut out,
+            columns.iter().map(|column| {
+                record
+                    .iter()
+                    .find(|(field, _)| field == column)
+                    .map(|(_, value)| cell(value))
+                    .unwrap_or_default()
+            }),
+        );
+    }
+    out
+}
+
+fn push_row(out: &mut String, cells: impl Iterator<Item = String>) {
+    let row: Vec<String> = cells.map(|cell| escape(&cell)).collect();
+    out.push_str(&row.join(","));
+    out.push_str("\r\n");
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(cell)
+            .collect::<Vec<_>>()
+            .join(LIST_SEPARATOR),
+        other => other.to_string(),
+    }
+}
+
+/// Quote `field` if it contains a separator, quote, or line break.
+pub fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split CSV `content` into records of fields. Quoted fields may contain
+/// separators, doubled quotes, and line breaks; blank lines are skipped.
+pub fn parse(content: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                other => field.push(other),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => end_record(&mut records, &mut record, &mut field),
+            other => field.push(other),
+        }
+    }
+    if in_quotes {
+        anyhow::bail!("Unterminated quoted field in CSV");
+    }
+    end_record(&mut records, &mut record, &mut field);
+    Ok(records)
+}
+
+fn end_record(records: &mut Vec<Vec<String>>, record: &mut Vec<String>, field: &mut String) {
+    record.push(std::mem::take(field));
+    let record = std::mem::take(record);
+    if !(record.len() == 1 && record[0].is_empty()) {
+        records.push(record);
+    }
+}
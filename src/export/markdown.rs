@@ -0,0 +1,420 @@
+//! Markdown output: one readable document per entity type, with linked
+//! entities listed by title, or a document of its own for one idea.
+
+use super::{yaml, Export, Record};
+use crate::models::idea::{AttachmentKind, Idea};
+use crate::models::project::Project;
+use crate::models::task::{Task, TaskStatus};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Render the `section` ("ideas", "projects", or "tasks") of `export`.
+pub fn render(export: &Export, section: &str) -> String {
+    let mut out = String::new();
+    match section {
+        "ideas" => {
+            out.push_str("# Ideas\n");
+            for idea in &export.ideas {
+                push_idea(&mut out, export, idea);
+            }
+        }
+        "projects" => {
+            out.push_str("# Projects\n");
+            for project in &export.projects {
+                push_project(&mut out, export, project);
+            }
+        }
+        _ => {
+            out.push_str("# Tasks\n");
+            for task in &export.tasks {
+                push_task(&mut out, export, task);
+            }
+        }
+    }
+    out
+}
+
+/// `idea` as a document of its own, to share outside the vault: its
+/// metadata as front matter, then its description, linked projects and
+/// tasks, related ideas, notes, and attachments.
+pub fn idea_document(export: &Export, idea: &Idea) -> String {
+    let mut front: Record = vec![
+        ("id".to_string(), Value::from(idea.id.to_string())),
+        ("title".to_string(), Value::from(idea.title.as_str())),
+        (
+            "status".to_string(),
+            Value::from(format!("{:?}", idea.status)),
+        ),
+        (
+            "priority".to_string(),
+            Value::from(idea.priority.to_string()),
+        ),
+    ];
+    if let Some(effort) = idea.effort {
+        front.push(("effort".to_string(), Value::from(effort.value())));
+    }
+    if let Some(impact) = idea.impact {
+        front.push(("impact".to_string(), Value::from(impact.value())));
+    }
+    if let Some(source) = &idea.source {
+        front.push(("source".to_string(), Value::from(source.as_str())));
+    }
+    if !idea.tags.is_empty() {
+        front.push(("tags".to_string(), Value::from(idea.tags.clone())));
+    }
+    front.push((
+        "created".to_string(),
+        Value::from(idea.created_at.to_rfc3339()),
+    ));
+    front.push((
+        "updated".to_string(),
+        Value::from(idea.updated_at.to_rfc3339()),
+    ));
+
+    let mut out = format!(
+        "---\n{}---\n\n# {}\n",
+        yaml::render_mapping(&front),
+        idea.title
+    );
+    if let Some(description) = &idea.description {
+        out.push('\n');
+        out.push_str(description.trim_end());
+        out.push('\n');
+    }
+
+    let projects: Vec<String> = export
+        .projects_of_idea(idea.id)
+        .map(|project| format!("**{}** ({:?})", project.title, project.status))
+        .collect();
+    push_section(&mut out, "Projects", &projects);
+    let tasks: Vec<String> = export
+        .tasks_of(idea.id)
+        .map(|task| {
+            let check = if task.status.is_closed() { "x" } else { " " };
+            match task.due_date {
+                Some(due) => format!(
+                    "[{}] {} (due {})",
+                    check,
+                    task.title,
+                    due.format("%Y-%m-%d")
+                ),
+                None => format!("[{}] {}", check, task.title),
+            }
+        })
+        .collect();
+    push_section(&mut out, "Tasks", &tasks);
+    let related: Vec<String> = idea
+        .related_ids
+        .iter()
+        .map(|id| title_or_id(export.idea_title(*id), *id))
+        .collect();
+    push_section(&mut out, "Related Ideas", &related);
+
+    if !idea.notes.is_empty() {
+        out.push_str("\n## Notes\n");
+        for note in &idea.notes {
+            out.push_str(&format!(
+                "\n### {}\n\n{}\n",
+                note.created_at.format("%Y-%m-%d %H:%M"),
+                note.body.trim_end()
+            ));
+        }
+    }
+    let attachments: Vec<String> = idea
+        .attachments
+        .iter()
+        .map(|attachment| match attachment.kind {
+            AttachmentKind::Url => format!("<{}>", attachment.location),
+            AttachmentKind::File => attachment.name.clone(),
+        })
+        .collect();
+    push_section(&mut out, "Attachments", &attachments);
+    out
+}
+
+/// A status report on `project` for the period from `since` to `now`, to
+/// paste into an update: a summary, its milestones, the tasks among `tasks`
+/// finished in the period, the work still to do, and what's in the way,
+/// including the unfinished projects in `blocked_by`.
+pub fn project_report(
+    project: &Project,
+    tasks: &[Task],
+    blocked_by: &[&Project],
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> String {
+    let day = |moment: DateTime<Utc>| moment.format("%Y-%m-%d").to_string();
+    let mut out = format!(
+        "# {} — status report\n\n_{} to {}_\n",
+        project.title,
+        day(since),
+        day(now)
+    );
+
+    out.push_str("\n## Summary\n\n");
+    push_field(&mut out, "Status", &project.status.to_string());
+    push_field(&mut out, "Priority", &project.priority.to_string());
+    if let Some(due) = project.due_date {
+        let overdue = if project.is_overdue(now) {
+            " (overdue)"
+        } else {
+            ""
+        };
+        push_field(&mut out, "Due", &format!("{}{}", day(due), overdue));
+    }
+    let done = tasks.iter().filter(|task| task.status.is_closed()).count();
+    push_field(
+        &mut out,
+        "Tasks",
+        &format!(
+            "{} of {} done{}",
+            done,
+            tasks.len(),
+            percent(done, tasks.len())
+        ),
+    );
+    if !project.milestones.is_empty() {
+        let (reached, total) = project.milestone_progress();
+        push_field(
+            &mut out,
+            "Milestones",
+            &format!("{} of {} reached", reached, total),
+        );
+    }
+
+    let milestones: Vec<String> = project
+        .milestones
+        .iter()
+        .map(
+            |milestone| match (milestone.completed_at, milestone.due_date) {
+                (Some(completed), _) => {
+                    format!("[x] {} (reached {})", milestone.title, day(completed))
+                }
+                (None, Some(due)) if due < now => {
+                    format!("[ ] {} (due {}, overdue)", milestone.title, day(due))
+                }
+                (None, Some(due)) => format!("[ ] {} (due {})", milestone.title, day(due)),
+                (None, None) => format!("[ ] {}", milestone.title),
+            },
+        )
+        .collect();
+    push_section(&mut out, "Milestones", &milestones);
+
+    // Tasks finished before `completed_at` was recorded fall back to their
+    // last update
+    let finished = |task: &Task| task.completed_at.unwrap_or(task.updated_at);
+    let mut completed: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| task.status.is_done() && finished(task) >= since)
+        .collect();
+    completed.sort_by_key(|task| finished(task));
+    let completed: Vec<String> = completed
+        .iter()
+        .map(|task| format!("{} ({})", task.title, day(finished(task))))
+        .collect();
+    push_report_section(
+        &mut out,
+        "Completed",
+        &completed,
+        "Nothing finished in this period.",
+    );
+
+    let mut upcoming: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| !task.status.is_closed() && task.status != TaskStatus::Blocked)
+        .collect();
+    upcoming.sort_by(|a, b| {
+        // Dated tasks first, soonest first, then the most urgent
+        a.due_date
+            .is_none()
+            .cmp(&b.due_date.is_none())
+            .then_with(|| a.due_date.cmp(&b.due_date))
+            .then_with(|| b.priority.cmp(&a.priority))
+    });
+    let upcoming: Vec<String> = upcoming
+        .iter()
+        .map(|task| {
+            let mut line = format!("{} ({}, {})", task.title, task.status, task.priority);
+            if let Some(due) = task.due_date {
+                line = format!("{} — due {}", line, day(due));
+                if due < now {
+                    line.push_str(", overdue");
+                }
+            }
+            line
+        })
+        .collect();
+    push_report_section(&mut out, "Upcoming", &upcoming, "No open tasks.");
+
+    let mut blockers: Vec<String> = blocked_by
+        .iter()
+        .map(|blocker| format!("Waiting on project {} ({})", blocker.title, blocker.status))
+        .collect();
+    blockers.extend(
+        tasks
+            .iter()
+            .filter(|task| task.status == TaskStatus::Blocked)
+            .map(|task| format!("{} (blocked task)", task.title)),
+    );
+    push_report_section(&mut out, "Blockers", &blockers, "None.");
+    out
+}
+
+/// `done` out of `total` as a percentage in brackets, or nothing when there
+/// is no total.
+fn percent(done: usize, total: usize) -> String {
+    if total == 0 {
+        String::new()
+    } else {
+        format!(" ({}%)", done * 100 / total)
+    }
+}
+
+/// Like [`push_section`], but a section with no items says `empty` rather
+/// than being left out.
+fn push_report_section(out: &mut String, heading: &str, items: &[String], empty: &str) {
+    if items.is_empty() {
+        out.push_str(&format!("\n## {}\n\n{}\n", heading, empty));
+    } else {
+        push_section(out, heading, items);
+    }
+}
+
+fn push_section(out: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n## {}\n\n", heading));
+    for item in items {
+        out.push_str(&format!("- {}\n", item));
+    }
+}
+
+fn push_idea(out: &mut String, export: &Export, idea: &Idea) {
+    push_heading(out, &idea.title, idea.id);
+    push_field(out, "Status", &format!("{:?}", idea.status));
+    push_field(out, "Priority", &idea.priority.to_string());
+    if let Some(effort) = idea.effort {
+        push_field(out, "Effort", &effort.to_string());
+    }
+    if let Some(impact) = idea.impact {
+        push_field(out, "Impact", &impact.to_string());
+    }
+    if let Some(source) = &idea.source {
+        push_field(out, "Source", source);
+    }
+    if !idea.tags.is_empty() {
+        push_field(out, "Tags", &idea.tags.join(", "));
+    }
+    let projects: Vec<&str> = export
+        .projects_of_idea(idea.id)
+        .map(|project| project.title.as_str())
+        .collect();
+    push_links(out, "Projects", &projects);
+    let tasks: Vec<&str> = export
+        .tasks_of(idea.id)
+        .map(|task| task.title.as_str())
+        .collect();
+    push_links(out, "Tasks", &tasks);
+    push_dates(out, idea.created_at, idea.updated_at);
+    push_description(out, idea.description.as_deref());
+}
+
+fn push_project(out: &mut String, export: &Export, project: &Project) {
+    push_heading(out, &project.title, project.id);
+    push_field(out, "Status", &format!("{:?}", project.status));
+    push_field(out, "Priority", &project.priority.to_string());
+    if let Some(due) = project.due_date {
+        push_field(out, "Due", &due.format("%Y-%m-%d").to_string());
+    }
+    if let Some(milestone) = &project.milestone {
+        push_field(out, "Milestone", milestone);
+    }
+    if let Some(url) = &project.url {
+        push_field(out, "URL", url);
+    }
+    if let Some(repo) = &project.repo {
+        push_field(out, "Repository", repo);
+    }
+    if !project.tags.is_empty() {
+        push_field(out, "Tags", &project.tags.join(", "));
+    }
+    let ideas: Vec<String> = project
+        .idea_ids
+        .iter()
+        .map(|id| title_or_id(export.idea_title(*id), *id))
+        .collect();
+    push_links(out, "Ideas", &ideas);
+    let tasks: Vec<&str> = export
+        .tasks_of(project.id)
+        .map(|task| task.title.as_str())
+        .collect();
+    push_links(out, "Tasks", &tasks);
+    push_dates(out, project.created_at, project.updated_at);
+    push_description(out, project.description.as_deref());
+}
+
+fn push_task(out: &mut String, export: &Export, task: &Task) {
+    push_heading(out, &task.title, task.id);
+    push_field(out, "Status", &task.status.to_string());
+    push_field(out, "Priority", &task.priority.to_string());
+    if let Some(due) = task.due_date {
+        push_field(out, "Due", &due.format("%Y-%m-%d").to_string());
+    }
+    if let Some(id) = task.project_id {
+        push_field(out, "Project", &title_or_id(export.project_title(id), id));
+    }
+    if let Some(id) = task.idea_id {
+        push_field(out, "Idea", &title_or_id(export.idea_title(id), id));
+    }
+    if !task.tags.is_empty() {
+        push_field(out, "Tags", &task.tags.join(", "));
+    }
+    push_dates(out, task.created_at, task.updated_at);
+    push_description(out, task.description.as_deref());
+}
+
+fn push_heading(out: &mut String, title: &str, id: Uuid) {
+    out.push_str(&format!("\n## {}\n\n", title));
+    push_field(out, "ID", &format!("`{}`", id));
+}
+
+fn push_field(out: &mut String, name: &str, value: &str) {
+    out.push_str(&format!("- **{}:** {}\n", name, value));
+}
+
+fn push_links<S: AsRef<str>>(out: &mut String, name: &str, titles: &[S]) {
+    if titles.is_empty() {
+        return;
+    }
+    let titles: Vec<&str> = titles.iter().map(|title| title.as_ref()).collect();
+    push_field(out, name, &titles.join(", "));
+}
+
+fn push_dates(out: &mut String, created_at: DateTime<Utc>, updated_at: DateTime<Utc>) {
+    push_field(
+        out,
+        "Created",
+        &created_at.format("%Y-%m-%d %H:%M").to_string(),
+    );
+    push_field(
+        out,
+        "Updated",
+        &updated_at.format("%Y-%m-%d %H:%M").to_string(),
+    );
+}
+
+fn push_description(out: &mut String, description: Option<&str>) {
+    if let Some(description) = description {
+        out.push('\n');
+        out.push_str(description.trim_end());
+        out.push('\n');
+    }
+}
+
+fn title_or_id(title: Option<&str>, id: Uuid) -> String {
+    title
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("`{}`", id))
+}
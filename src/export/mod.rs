@@ -0,0 +1,366 @@
+//! Exporting the vault to other formats.
+//!
+//! An [`Export`] is a filtered snapshot of the vault. [`Export::write`] renders
+//! it as one file per entity type (`ideas.csv`, `projects.csv`, ...) in the
+//! chosen [`ExportFormat`]. Links between entities are kept: the structured
+//! formats carry the linked IDs, and Markdown names the linked entities.
+
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::task::Task;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub mod csv;
+pub mod markdown;
+pub mod obsidian;
+pub mod org;
+pub mod yaml;
+
+/// Field order for ideas; any field not listed follows in alphabetical order.
+pub const IDEA_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "source",
+    "status",
+    "priority",
+    "effort",
+    "impact",
+    "tags",
+    "related_ids",
+    "created_at",
+    "updated_at",
+];
+
+/// Field order for projects; any field not listed follows in alphabetical order.
+pub const PROJECT_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "due_date",
+    "assignee",
+    "milestone",
+    "url",
+    "repo",
+    "tags",
+    "idea_ids",
+    "depends_on",
+    "parent_id",
+    "created_at",
+    "updated_at",
+];
+
+/// Field order for tasks; any field not listed follows in alphabetical order.
+pub const TASK_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "due_date",
+    "assignee",
+    "project_id",
+    "idea_id",
+    "tags",
+    "completed_at",
+    "created_at",
+    "updated_at",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Yaml,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Yaml => "yaml",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            "yaml" | "yml" => Ok(ExportFormat::Yaml),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            _ => Err(anyhow::anyhow!(
+                "Invalid format: {}. Valid options: json, csv, yaml, md",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Json => write!(f, "json"),
+            ExportFormat::Csv => write!(f, "csv"),
+            ExportFormat::Yaml => write!(f, "yaml"),
+            ExportFormat::Markdown => write!(f, "md"),
+        }
+    }
+}
+
+/// The kinds of entity that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityType {
+    Idea,
+    Project,
+    Task,
+}
+
+impl FromStr for EntityType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "idea" | "ideas" => Ok(EntityType::Idea),
+            "project" | "projects" => Ok(EntityType::Project),
+            "task" | "tasks" => Ok(EntityType::Task),
+            _ => Err(anyhow::anyhow!(
+                "Invalid type: {}. Valid options: idea, project, task",
+                s
+            )),
+        }
+    }
+}
+
+/// Which entities to include in an export. Empty fields don't filter.
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+    pub types: Vec<EntityType>,
+    /// Matched against each entity's own status, ignoring case, `-` and `_`
+    pub status: Option<String>,
+    /// Only entities carrying this tag
+    pub tag: Option<String>,
+}
+
+impl ExportFilter {
+    fn includes(&self, kind: EntityType) -> bool {
+        self.types.is_empty() || self.types.contains(&kind)
+    }
+
+    fn matches(&self, status: &str, tags: &[String]) -> bool {
+        if let Some(wanted) = &self.status {
+            if normalize(status) != normalize(wanted) {
+                return false;
+            }
+        }
+        match &self.tag {
+            Some(tag) => tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            None => true,
+        }
+    }
+}
+
+fn normalize(status: &str) -> String {
+    status
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// A filtered snapshot of the vault, plus the full vault for naming linked
+/// entities that were filtered out.
+pub struct Export {
+    pub ideas: Vec<Idea>,
+    pub projects: Vec<Project>,
+    pub tasks: Vec<Task>,
+    all_ideas: Vec<Idea>,
+    all_projects: Vec<Project>,
+    all_tasks: Vec<Task>,
+}
+
+impl Export {
+    pub fn new(
+        ideas: Vec<Idea>,
+        projects: Vec<Project>,
+        tasks: Vec<Task>,
+        filter: &ExportFilter,
+    ) -> Self {
+        let selected_ideas = if filter.includes(EntityType::Idea) {
+            ideas
+                .iter()
+                .filter(|idea| filter.matches(&format!("{:?}", idea.status), &idea.tags))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let selected_projects = if filter.includes(EntityType::Project) {
+            projects
+                .iter()
+                .filter(|project| filter.matches(&format!("{:?}", project.status), &project.tags))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let selected_tasks = if filter.includes(EntityType::Task) {
+            tasks
+                .iter()
+                .filter(|task| filter.matches(&task.status.to_string(), &task.tags))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            ideas: selected_ideas,
+            projects: selected_projects,
+            tasks: selected_tasks,
+            all_ideas: ideas,
+            all_projects: projects,
+            all_tasks: tasks,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ideas.is_empty() && self.projects.is_empty() && self.tasks.is_empty()
+    }
+
+    /// Write one file per non-empty entity type into `out_dir`, creating it if
+    /// needed. Returns the written files.
+    pub fn write(&self, format: ExportFormat, out_dir: &Path) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(out_dir)
+            .with_context(|| format!("Failed to create export directory: {:?}", out_dir))?;
+
+        let mut written = Vec::new();
+        let sections = [
+            ("ideas", self.ideas.is_empty()),
+            ("projects", self.projects.is_empty()),
+            ("tasks", self.tasks.is_empty()),
+        ];
+        for (name, empty) in sections {
+            if empty {
+                continue;
+            }
+            let content = match (format, name) {
+                (ExportFormat::Markdown, _) => markdown::render(self, name),
+                (_, "ideas") => render_structured(format, &self.ideas, IDEA_FIELDS)?,
+                (_, "projects") => render_structured(format, &self.projects, PROJECT_FIELDS)?,
+                _ => render_structured(format, &self.tasks, TASK_FIELDS)?,
+            };
+            let path = out_dir.join(format!("{}.{}", name, format.extension()));
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write export file: {:?}", path))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Write the export as an Obsidian vault in `dir`. Returns the notes written.
+    pub fn write_obsidian(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        obsidian::write(self, dir)
+    }
+
+    /// Write the projects and tasks of the export as an org file at `path`.
+    pub fn write_org(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+        fs::write(path, org::render(self))
+            .with_context(|| format!("Failed to write org file: {:?}", path))
+    }
+
+    pub fn idea_title(&self, id: uuid::Uuid) -> Option<&str> {
+        self.all_ideas
+            .iter()
+            .find(|idea| idea.id == id)
+            .map(|idea| idea.title.as_str())
+    }
+
+    pub fn project_title(&self, id: uuid::Uuid) -> Option<&str> {
+        self.all_projects
+            .iter()
+            .find(|project| project.id == id)
+            .map(|project| project.title.as_str())
+    }
+
+    /// Projects that link to the idea `id`.
+    pub fn projects_of_idea(&self, id: uuid::Uuid) -> impl Iterator<Item = &Project> {
+        self.all_projects
+            .iter()
+            .filter(move |project| project.idea_ids.contains(&id))
+    }
+
+    /// Tasks that link to the idea or project `id`.
+    pub fn tasks_of(&self, id: uuid::Uuid) -> impl Iterator<Item = &Task> {
+        self.all_tasks
+            .iter()
+            .filter(move |task| task.idea_id == Some(id) || task.project_id == Some(id))
+    }
+}
+
+fn render_structured<T: Serialize>(
+    format: ExportFormat,
+    items: &[T],
+    fields: &[&str],
+) -> Result<String> {
+    match format {
+        ExportFormat::Json => {
+            let mut content = serde_json::to_string_pretty(items)?;
+            content.push('\n');
+            Ok(content)
+        }
+        ExportFormat::Csv => Ok(csv::render(&records(items, fields)?)),
+        ExportFormat::Yaml => Ok(yaml::render(&records(items, fields)?)),
+        ExportFormat::Markdown => unreachable!("markdown is rendered per entity type"),
+    }
+}
+
+/// An entity's fields in export order.
+pub type Record = Vec<(String, Value)>;
+
+/// Serialize `items` into records whose fields follow `order`.
+pub fn records<T: Serialize>(items: &[T], order: &[&str]) -> Result<Vec<Record>> {
+    items
+        .iter()
+        .map(|item| {
+            let mut object = match serde_json::to_value(item)
+                .context("Failed to serialize entity for export")?
+            {
+                Value::Object(object) => object,
+                other => anyhow::bail!("Expected an object for export, got {}", other),
+            };
+            Ok(order_fields(&mut object, order))
+        })
+        .collect()
+}
+
+fn order_fields(object: &mut Map<String, Value>, order: &[&str]) -> Record {
+    let mut record: Record = order
+        .iter()
+        .filter_map(|field| {
+            object
+                .remove(*field)
+                .map(|value| (field.to_string(), value))
+        })
+        .collect();
+    // Map iterates in key order, so the rest come out alphabetically
+    record.extend(std::mem::take(object));
+    record
+}
@@ -0,0 +1,306 @@
+//! Obsidian vault output.
+//!
+//! Every idea, project, and task becomes a note in `Ideas/`, `Projects/`, or
+//! `Tasks/`, named after its title. Each note has YAML front matter with the
+//! entity's metadata, and links to related notes as `[[wiki-links]]` both in
+//! the front matter and in a "Links" section, so Obsidian's graph view shows
+//! how ideas, projects, and tasks connect.
+
+use super::{yaml, Export, Record};
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::task::Task;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const IDEAS_DIR: &str = "Ideas";
+const PROJECTS_DIR: &str = "Projects";
+const TASKS_DIR: &str = "Tasks";
+
+/// Write a note for every entity in `export` under `dir`. Returns the notes.
+pub fn write(export: &Export, dir: &Path) -> Result<Vec<PathBuf>> {
+    let notes = NoteNames::new(export);
+    let mut written = Vec::new();
+
+    for idea in &export.ideas {
+        written.push(write_note(
+            dir,
+            &notes.link_target(idea.id),
+            &idea_note(export, &notes, idea),
+        )?);
+    }
+    for project in &export.projects {
+        written.push(write_note(
+            dir,
+            &notes.link_target(project.id),
+            &project_note(export, &notes, project),
+        )?);
+    }
+    for task in &export.tasks {
+        written.push(write_note(
+            dir,
+            &notes.link_target(task.id),
+            &task_note(&notes, task),
+        )?);
+    }
+    Ok(written)
+}
+
+fn write_note(dir: &Path, target: &str, content: &str) -> Result<PathBuf> {
+    let path = dir.join(format!("{}.md", target));
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write note: {:?}", path))?;
+    Ok(path)
+}
+
+/// Unique note names for every entity in the vault, so links resolve even to
+/// entities that were filtered out of this export.
+struct NoteNames {
+    // Note name, folder, and the title to show in links
+    names: HashMap<Uuid, (String, &'static str, String)>,
+}
+
+impl NoteNames {
+    fn new(export: &Export) -> Self {
+        let mut names = HashMap::new();
+        let entities = [
+            (
+                IDEAS_DIR,
+                export
+                    .all_ideas
+                    .iter()
+                    .map(|i| (i.id, i.title.as_str()))
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                PROJECTS_DIR,
+                export
+                    .all_projects
+                    .iter()
+                    .map(|p| (p.id, p.title.as_str()))
+                    .collect(),
+            ),
+            (
+                TASKS_DIR,
+                export
+                    .all_tasks
+                    .iter()
+                    .map(|t| (t.id, t.title.as_str()))
+                    .collect(),
+            ),
+        ];
+
+        for (folder, items) in entities {
+            let mut taken = HashSet::new();
+            for (id, title) in items {
+                let mut name = file_name(title);
+                if !taken.insert(name.to_lowercase()) {
+                    name = format!("{} ({})", name, &id.to_string()[..8]);
+                    taken.insert(name.to_lowercase());
+                }
+                let label = title.replace(['|', '[', ']'], " ");
+                names.insert(id, (name, folder, label));
+            }
+        }
+        Self { names }
+    }
+
+    /// The note's path inside the vault, without the `.md` extension.
+    fn link_target(&self, id: Uuid) -> String {
+        match self.names.get(&id) {
+            Some((name, folder, _)) => format!("{}/{}", folder, name),
+            None => id.to_string(),
+        }
+    }
+
+    /// A wiki-link to the entity `id`, showing its title.
+    fn link(&self, id: Uuid) -> String {
+        match self.names.get(&id) {
+            Some((name, folder, label)) => format!("[[{}/{}|{}]]", folder, name, label),
+            None => format!("[[{}]]", id),
+        }
+    }
+
+    /// Extra front matter naming the entity by its title when the note name differs.
+    fn aliases(&self, id: Uuid, title: &str, front: &mut Record) {
+        if let Some((name, _, _)) = self.names.get(&id) {
+            if name != title {
+                front.push(("aliases".to_string(), Value::from(vec![title])));
+            }
+        }
+    }
+}
+
+/// Strip characters Obsidian doesn't allow in note names.
+fn file_name(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let cleaned = cleaned.trim_start_matches('.').to_string();
+    if cleaned.is_empty() {
+        "Untitled".to_string()
+    } else {
+        cleaned
+    }
+}
+
+fn idea_note(export: &Export, notes: &NoteNames, idea: &Idea) -> String {
+    let projects: Vec<String> = export
+        .projects_of_idea(idea.id)
+        .map(|project| notes.link(project.id))
+        .collect();
+    let tasks: Vec<String> = export
+        .tasks_of(idea.id)
+        .map(|task| notes.link(task.id))
+        .collect();
+
+    let mut front = base_front_matter(idea.id, "idea", &format!("{:?}", idea.status));
+    notes.aliases(idea.id, &idea.title, &mut front);
+    front.push((
+        "priority".to_string(),
+        Value::from(format!("{:?}", idea.priority)),
+    ));
+    if let Some(source) = &idea.source {
+        front.push(("source".to_string(), Value::from(source.as_str())));
+    }
+    push_list(&mut front, "tags", &idea.tags);
+    push_list(&mut front, "projects", &projects);
+    push_list(&mut front, "tasks", &tasks);
+    push_dates(&mut front, idea.created_at, idea.updated_at);
+
+    let mut body = String::new();
+    push_links(&mut body, "Projects", &projects);
+    push_links(&mut body, "Tasks", &tasks);
+    note(&front, &idea.title, idea.description.as_deref(), &body)
+}
+
+fn project_note(export: &Export, notes: &NoteNames, project: &Project) -> String {
+    let ideas: Vec<String> = project.idea_ids.iter().map(|id| notes.link(*id)).collect();
+    let tasks: Vec<String> = export
+        .tasks_of(project.id)
+        .map(|task| notes.link(task.id))
+        .collect();
+
+    let mut front = base_front_matter(project.id, "project", &format!("{:?}", project.status));
+    notes.aliases(project.id, &project.title, &mut front);
+    front.push((
+        "priority".to_string(),
+        Value::from(format!("{:?}", project.priority)),
+    ));
+    for (field, value) in [
+        ("milestone", &project.milestone),
+        ("url", &project.url),
+        ("repo", &project.repo),
+    ] {
+        if let Some(value) = value {
+            front.push((field.to_string(), Value::from(value.as_str())));
+        }
+    }
+    if let Some(due) = project.due_date {
+        front.push((
+            "due".to_string(),
+            Value::from(due.format("%Y-%m-%d").to_string()),
+        ));
+    }
+    push_list(&mut front, "tags", &project.tags);
+    push_list(&mut front, "ideas", &ideas);
+    push_list(&mut front, "tasks", &tasks);
+    push_dates(&mut front, project.created_at, project.updated_at);
+
+    let mut body = String::new();
+    push_links(&mut body, "Ideas", &ideas);
+    push_links(&mut body, "Tasks", &tasks);
+    note(
+        &front,
+        &project.title,
+        project.description.as_deref(),
+        &body,
+    )
+}
+
+fn task_note(notes: &NoteNames, task: &Task) -> String {
+    let mut front = base_front_matter(task.id, "task", &task.status.to_string());
+    notes.aliases(task.id, &task.title, &mut front);
+    front.push((
+        "priority".to_string(),
+        Value::from(format!("{:?}", task.priority)),
+    ));
+    if let Some(due) = task.due_date {
+        front.push((
+            "due".to_string(),
+            Value::from(due.format("%Y-%m-%d").to_string()),
+        ));
+    }
+    let project = task.project_id.map(|id| notes.link(id));
+    let idea = task.idea_id.map(|id| notes.link(id));
+    if let Some(project) = &project {
+        front.push(("project".to_string(), Value::from(project.as_str())));
+    }
+    if let Some(idea) = &idea {
+        front.push(("idea".to_string(), Value::from(idea.as_str())));
+    }
+    push_list(&mut front, "tags", &task.tags);
+    push_dates(&mut front, task.created_at, task.updated_at);
+
+    let mut body = String::new();
+    push_links(&mut body, "Project", project.as_slice());
+    push_links(&mut body, "Idea", idea.as_slice());
+    note(&front, &task.title, task.description.as_deref(), &body)
+}
+
+fn base_front_matter(id: Uuid, kind: &str, status: &str) -> Record {
+    vec![
+        ("id".to_string(), Value::from(id.to_string())),
+        ("type".to_string(), Value::from(kind)),
+        ("status".to_string(), Value::from(status)),
+    ]
+}
+
+fn push_list(front: &mut Record, field: &str, items: &[String]) {
+    if !items.is_empty() {
+        front.push((field.to_string(), Value::from(items.to_vec())));
+    }
+}
+
+fn push_dates(front: &mut Record, created_at: DateTime<Utc>, updated_at: DateTime<Utc>) {
+    front.push(("created".to_string(), Value::from(created_at.to_rfc3339())));
+    front.push(("updated".to_string(), Value::from(updated_at.to_rfc3339())));
+}
+
+fn push_links(body: &mut String, heading: &str, links: &[String]) {
+    if links.is_empty() {
+        return;
+    }
+    body.push_str(&format!("\n### {}\n\n", heading));
+    for link in links {
+        body.push_str(&format!("- {}\n", link));
+    }
+}
+
+fn note(front: &Record, title: &str, description: Option<&str>, links: &str) -> String {
+    let mut out = format!("---\n{}---\n\n# {}\n", yaml::render_mapping(front), title);
+    if let Some(description) = description {
+        out.push('\n');
+        out.push_str(description.trim_end());
+        out.push('\n');
+    }
+    if !links.is_empty() {
+        out.push_str("\n## Links\n");
+        out.push_str(links);
+    }
+    out
+}
@@ -0,0 +1,224 @@
+//! Org-mode output for Emacs.
+//!
+//! Projects become top-level headings and their tasks become TODO entries
+//! beneath them, with priorities, tags, and DEADLINE timestamps from due dates,
+//! so the file can be added to `org-agenda-files` as-is. Tasks that don't
+//! belong to an exported project are collected under a heading of their own.
+
+use super::Export;
+use crate::models::project::Project;
+use crate::models::task::{self, Task, TaskPriority, TaskStatus};
+use chrono::{DateTime, Utc};
+
+/// Heading for tasks without an exported project.
+const UNASSIGNED_HEADING: &str = "Tasks without a project";
+
+/// Render the projects and tasks of `export` as an org document.
+pub fn render(export: &Export) -> String {
+    let mut out = String::new();
+    out.push_str("#+TITLE: IdeaVault\n");
+    out.push_str(&todo_keywords());
+    out.push_str(&format!(
+        "#+PRIORITIES: A {} {}\n",
+        priority_letter(task::priorities().len() - 1),
+        priority(&TaskPriority::middle())
+    ));
+
+    for project in &export.projects {
+        push_project(&mut out, project);
+        for task in export
+            .tasks
+            .iter()
+            .filter(|task| task.project_id == Some(project.id))
+        {
+            push_task(&mut out, export, task, 2);
+        }
+    }
+
+    let unassigned: Vec<&Task> = export
+        .tasks
+        .iter()
+        .filter(|task| {
+            !task
+                .project_id
+                .is_some_and(|id| export.projects.iter().any(|project| project.id == id))
+        })
+        .collect();
+    if !unassigned.is_empty() {
+        out.push_str(&format!("\n* {}\n", UNASSIGNED_HEADING));
+        for task in unassigned {
+            push_task(&mut out, export, task, 2);
+        }
+    }
+    out
+}
+
+fn push_project(out: &mut String, project: &Project) {
+    out.push_str(&format!(
+        "\n* {}{}\n",
+        headline_text(&project.title),
+        headline_tags(&project.tags)
+    ));
+    if let Some(due) = project.due_date {
+        out.push_str(&format!(
+            "{}DEADLINE: {}\n",
+            indent(1),
+            active_timestamp(due)
+        ));
+    }
+    let mut properties = vec![
+        ("ID", project.id.to_string()),
+        ("STATUS", format!("{:?}", project.status)),
+    ];
+    for (name, value) in [
+        ("MILESTONE", &project.milestone),
+        ("URL", &project.url),
+        ("REPO", &project.repo),
+    ] {
+        if let Some(value) = value {
+            properties.push((name, value.clone()));
+        }
+    }
+    properties.push(("CREATED", inactive_timestamp(project.created_at)));
+    push_properties(out, &properties, 1);
+    push_body(out, project.description.as_deref(), 1);
+}
+
+fn push_task(out: &mut String, export: &Export, task: &Task, level: usize) {
+    let mut headline = format!(
+        "{} {} [#{}] {}",
+        "*".repeat(level),
+        keyword(&task.status),
+        priority(&task.priority),
+        headline_text(&task.title)
+    );
+    headline.push_str(&headline_tags(&task.tags));
+    out.push_str(&headline);
+    out.push('\n');
+
+    if let Some(due) = task.due_date {
+        out.push_str(&format!(
+            "{}DEADLINE: {}\n",
+            indent(level),
+            active_timestamp(due)
+        ));
+    }
+
+    let mut properties = vec![("ID", task.id.to_string())];
+    if let Some(idea_id) = task.idea_id {
+        let idea = export
+            .idea_title(idea_id)
+            .map(str::to_string)
+            .unwrap_or_else(|| idea_id.to_string());
+        properties.push(("IDEA", idea));
+    }
+    properties.push(("CREATED", inactive_timestamp(task.created_at)));
+    push_properties(out, &properties, level);
+    push_body(out, task.description.as_deref(), level);
+}
+
+/// The `#+TODO:` line, with the open statuses before the `|` and the
+/// closed ones after it.
+fn todo_keywords() -> String {
+    let (closed, open): (Vec<_>, Vec<_>) = task::statuses()
+        .iter()
+        .map(|status| TaskStatus::named(&status.name))
+        .partition(TaskStatus::is_closed);
+    let words =
+        |statuses: Vec<TaskStatus>| statuses.iter().map(keyword).collect::<Vec<_>>().join(" ");
+    format!("#+TODO: {} | {}\n", words(open), words(closed))
+}
+
+fn keyword(status: &TaskStatus) -> String {
+    match status {
+        TaskStatus::Todo => "TODO".to_string(),
+        TaskStatus::InProgress => "IN-PROGRESS".to_string(),
+        TaskStatus::Blocked => "BLOCKED".to_string(),
+        TaskStatus::Done => "DONE".to_string(),
+        TaskStatus::Cancelled => "CANCELLED".to_string(),
+        TaskStatus::Custom(name) => name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+            .to_uppercase(),
+    }
+}
+
+/// Org priorities are letters from `A`, the most urgent, in the order of
+/// the configured scale.
+fn priority(priority: &TaskPriority) -> char {
+    priority_letter(priority.rank().min(task::priorities().len() - 1))
+}
+
+fn priority_letter(rank: usize) -> char {
+    (b'A' + rank.min(25) as u8) as char
+}
+
+fn push_properties(out: &mut String, properties: &[(&str, String)], level: usize) {
+    let pad = indent(level);
+    out.push_str(&format!("{}:PROPERTIES:\n", pad));
+    for (name, value) in properties {
+        out.push_str(&format!("{}:{}: {}\n", pad, name, value));
+    }
+    out.push_str(&format!("{}:END:\n", pad));
+}
+
+/// Body text is indented under its heading, which also keeps lines starting
+/// with `*` from being read as headings.
+fn push_body(out: &mut String, description: Option<&str>, level: usize) {
+    let Some(description) = description else {
+        return;
+    };
+    let pad = indent(level);
+    for line in description.trim_end().lines() {
+        if line.trim().is_empty() {
+            out.push('\n');
+        } else {
+            out.push_str(&format!("{}{}\n", pad, line));
+        }
+    }
+}
+
+fn indent(level: usize) -> String {
+    " ".repeat(level + 1)
+}
+
+/// Headlines are a single line.
+fn headline_text(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `tags` as they end a headline, e.g. " :rust:cli:", or nothing.
+fn headline_tags(tags: &[String]) -> String {
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|tag| tag_name(tag))
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    if tags.is_empty() {
+        String::new()
+    } else {
+        format!(" :{}:", tags.join(":"))
+    }
+}
+
+/// Org tags may only contain letters, digits, `_`, `@`, `#`, and `%`.
+fn tag_name(tag: &str) -> String {
+    tag.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn active_timestamp(date: DateTime<Utc>) -> String {
+    date.format("<%Y-%m-%d %a>").to_string()
+}
+
+fn inactive_timestamp(date: DateTime<Utc>) -> String {
+    date.format("[%Y-%m-%d %a %H:%M]").to_string()
+}
@@ -0,0 +1,108 @@
+//! Minimal YAML output.
+//!
+//! Strings are always written double-quoted with JSON escaping, which YAML
+//! accepts as-is, so no value can be misread as a number, boolean, or null.
+
+use super::Record;
+use serde_json::Value;
+
+/// Render `records` as a YAML sequence of mappings.
+pub fn render(records: &[Record]) -> String {
+    if records.is_empty() {
+        return "[]\n".to_string();
+    }
+
+    let mut out = String::new();
+    for record in records {
+        for (index, (field, value)) in record.iter().enumerate() {
+            let prefix = if index == 0 { "- " } else { "  " };
+            push_entry(&mut out, prefix, field, value, 2);
+        }
+    }
+    out
+}
+
+/// Render a single `record` as a top-level YAML mapping.
+pub fn render_mapping(record: &Record) -> String {
+    let mut out = String::new();
+    for (field, value) in record {
+        push_entry(&mut out, "", field, value, 0);
+    }
+    out
+}
+
+fn push_entry(out: &mut String, prefix: &str, key: &str, value: &Value, indent: usize) {
+    out.push_str(prefix);
+    out.push_str(&scalar_key(key));
+    out.push(':');
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            out.push('\n');
+            for item in items {
+                push_item(out, item, indent + 2);
+            }
+        }
+        Value::Object(map) if !map.is_empty() => {
+            out.push('\n');
+            for (field, value) in map {
+                push_entry(out, &" ".repeat(indent + 2), field, value, indent + 2);
+            }
+        }
+        _ => {
+            out.push(' ');
+            out.push_str(&inline(value));
+            out.push('\n');
+        }
+    }
+}
+
+fn push_item(out: &mut String, value: &Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (index, (field, value)) in map.iter().enumerate() {
+                let prefix = if index == 0 {
+                    format!("{}- ", pad)
+                } else {
+                    format!("{}  ", pad)
+                };
+                push_entry(out, &prefix, field, value, indent + 2);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push_str(&pad);
+            out.push_str("-\n");
+            for item in items {
+                push_item(out, item, indent + 2);
+            }
+        }
+        _ => {
+            out.push_str(&pad);
+            out.push_str("- ");
+            out.push_str(&inline(value));
+            out.push('\n');
+        }
+    }
+}
+
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        // JSON's string, number, and boolean syntax is valid YAML
+        other => other.to_string(),
+    }
+}
+
+fn scalar_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
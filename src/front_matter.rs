@@ -0,0 +1,75 @@
+//! Items as Markdown with YAML front matter, the format `edit` commands open
+//! in the editor: the item's fields between `---` lines, then its
+//! description as the body.
+//!
+//! ```text
+//! ---
+//! title: Fix the sink
+//! tags:
+//! - home
+//! ---
+//!
+//! It drips.
+//! ```
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const FENCE: &str = "---";
+
+/// `fields` as front matter, preceded by `comments` (one `#` comment per
+/// line), followed by `body`.
+pub fn render<T: Serialize>(fields: &T, comments: &[String], body: Option<&str>) -> Result<String> {
+    let yaml = serde_yaml::to_string(fields).context("Failed to write front matter")?;
+    let mut out = format!("{}\n", FENCE);
+    for comment in comments {
+        out.push_str(&format!("# {}\n", comment));
+    }
+    out.push_str(&yaml);
+    out.push_str(&format!("{}\n\n", FENCE));
+    if let Some(body) = body {
+        out.push_str(body);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// The fields and body of `content`. Fails, saying where, if the front
+/// matter is missing, isn't valid YAML, or has fields `T` doesn't know.
+/// An empty body is `None`.
+pub fn parse<T: DeserializeOwned>(content: &str) -> Result<(T, Option<String>)> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines = content.split_inclusive('\n');
+    if lines.next().map(str::trim_end) != Some(FENCE) {
+        anyhow::bail!("The file must start with a {} line", FENCE);
+    }
+
+    let mut yaml = String::new();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim_end() == FENCE {
+            closed = true;
+            break;
+        }
+        yaml.push_str(line);
+    }
+    if !closed {
+        anyhow::bail!("The front matter has no closing {} line", FENCE);
+    }
+
+    let fields = serde_yaml::from_str(&yaml).map_err(|err| {
+        let message = err.to_string();
+        match err.location() {
+            // Line 1 of the YAML is line 2 of the file
+            Some(location) => {
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                let message = message.strip_suffix(&suffix).unwrap_or(&message);
+                anyhow::anyhow!("Line {}: {}", location.line() + 1, message)
+            }
+            None => anyhow::anyhow!(message),
+        }
+    })?;
+    let body = lines.collect::<String>().trim().to_string();
+    Ok((fields, (!body.is_empty()).then_some(body)))
+}
@@ -1,30 +0,0 @@
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
-use chrono::{DateTime, Utc};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Idea {
-    pub id: Uuid,
-    pub title: String,
-    pub description: Option<String>,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-}
-
-impl Idea {
-    pub fn new(title: String, description: Option<String>) -> Self {
-        let now = Utc::now();
-        Self {
-            id: Uuid::new_v4(),
-            title,
-            description,
-            created_at: now,
-            updated_at: now,
-        }
-    }
-
-    pub fn update_description(&mut self, description: Option<String>) {
-        self.description = description;
-        self.updated_at = Utc::now();
-    }
-}
\ No newline at end of file
@@ -0,0 +1,490 @@
+//! Importing ideas, projects, and tasks from JSON and CSV files.
+//!
+//! Files written by `ideavault export` import as-is. Other spreadsheets work as
+//! long as their columns can be matched to fields: headers are compared
+//! case-insensitively, common synonyms such as "name" or "notes" are
+//! recognised, and anything else can be mapped explicitly. Rows that would
+//! duplicate an existing entity, by ID or by title, are skipped.
+
+use crate::commands::search::parse_date;
+use crate::export::{csv, EntityType, IDEA_FIELDS, PROJECT_FIELDS, TASK_FIELDS};
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::task::Task;
+use crate::models::Identifiable;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Column names that mean the same as a field.
+const ALIASES: &[(&str, &str)] = &[
+    ("name", "title"),
+    ("summary", "description"),
+    ("notes", "description"),
+    ("body", "description"),
+    ("details", "description"),
+    ("state", "status"),
+    ("tag", "tags"),
+    ("labels", "tags"),
+    ("due", "due_date"),
+    ("deadline", "due_date"),
+    ("project", "project_id"),
+    ("idea", "idea_id"),
+    ("ideas", "idea_ids"),
+    ("created", "created_at"),
+    ("updated", "updated_at"),
+];
+
+/// One record from the input file, with its original column names.
+#[derive(Debug, Clone)]
+pub struct Row {
+    /// 1-based position among the data rows
+    pub number: usize,
+    pub columns: Vec<(String, String)>,
+}
+
+/// Read the rows of a JSON array of objects or a CSV file with a header row.
+/// The format is chosen by extension, falling back to sniffing the content.
+pub fn read_rows(path: &Path) -> Result<Vec<Row>> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let is_json = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("json") => true,
+        Some(ext) if ext.eq_ignore_ascii_case("csv") => false,
+        _ => content.trim_start().starts_with(['[', '{']),
+    };
+
+    if is_json {
+        json_rows(&content).with_context(|| format!("Failed to parse JSON in {:?}", path))
+    } else {
+        csv_rows(&content).with_context(|| format!("Failed to parse CSV in {:?}", path))
+    }
+}
+
+fn json_rows(content: &str) -> Result<Vec<Row>> {
+    let objects = match serde_json::from_str(content)? {
+        Value::Array(items) => items,
+        object @ Value::Object(_) => vec![object],
+        _ => anyhow::bail!("Expected an array of objects"),
+    };
+
+    objects
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let Value::Object(object) = item else {
+                anyhow::bail!("Item {} is not an object", index + 1);
+            };
+            let columns = object
+                .into_iter()
+                .filter_map(|(key, value)| text(&value).map(|value| (key, value)))
+                .collect();
+            Ok(Row {
+                number: index + 1,
+                columns,
+            })
+        })
+        .collect()
+}
+
+/// A JSON value as the text a spreadsheet cell would hold.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(text)
+                .collect::<Vec<_>>()
+                .join(csv::LIST_SEPARATOR),
+        ),
+        other => Some(other.to_string()),
+    }
+}
+
+fn csv_rows(content: &str) -> Result<Vec<Row>> {
+    let mut records = csv::parse(content)?.into_iter();
+    let Some(headers) = records.next() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(records
+        .enumerate()
+        .map(|(index, record)| Row {
+            number: index + 1,
+            columns: headers.iter().cloned().zip(record).collect(),
+        })
+        .collect())
+}
+
+/// Guess the entity type from a file name such as `ideas.csv`.
+pub fn detect_type(path: &Path) -> Option<EntityType> {
+    let stem = path.file_stem()?.to_str()?.to_lowercase();
+    if stem.contains("idea") {
+        Some(EntityType::Idea)
+    } else if stem.contains("project") {
+        Some(EntityType::Project)
+    } else if stem.contains("task") {
+        Some(EntityType::Task)
+    } else {
+        None
+    }
+}
+
+/// Maps input columns to entity fields.
+#[derive(Debug, Clone)]
+pub struct ColumnMap {
+    fields: &'static [&'static str],
+    explicit: HashMap<String, String>,
+}
+
+impl ColumnMap {
+    pub fn new(kind: EntityType) -> Self {
+        let fields = match kind {
+            EntityType::Idea => IDEA_FIELDS,
+            EntityType::Project => PROJECT_FIELDS,
+            EntityType::Task => TASK_FIELDS,
+        };
+        Self {
+            fields,
+            explicit: HashMap::new(),
+        }
+    }
+
+    /// Map the column `column` to `field`, overriding the automatic match.
+    pub fn with_mapping(mut self, column: &str, field: &str) -> Result<Self> {
+        let field = field.trim().to_lowercase();
+        if !self.fields.contains(&field.as_str()) {
+            anyhow::bail!(
+                "Unknown field '{}'. Valid fields: {}",
+                field,
+                self.fields.join(", ")
+            );
+        }
+        self.explicit.insert(normalize(column), field);
+        Ok(self)
+    }
+
+    /// Parse mappings written as `column=field`.
+    pub fn with_mappings(self, mappings: &[String]) -> Result<Self> {
+        mappings.iter().try_fold(self, |map, mapping| {
+            let (column, field) = mapping.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!("Invalid mapping '{}'. Use COLUMN=FIELD", mapping)
+            })?;
+            map.with_mapping(column, field)
+        })
+    }
+
+    /// The field `column` holds, if any.
+    pub fn field(&self, column: &str) -> Option<&str> {
+        let column = normalize(column);
+        if let Some(field) = self.explicit.get(&column) {
+            return Some(field);
+        }
+        if let Some(field) = self.fields.iter().find(|field| **field == column) {
+            return Some(field);
+        }
+        ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == column)
+            .map(|(_, field)| *field)
+            .filter(|field| self.fields.contains(field))
+    }
+}
+
+fn normalize(column: &str) -> String {
+    column.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+/// A row's values keyed by field name. Blank values count as absent.
+pub struct Fields<'a> {
+    values: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Fields<'a> {
+    pub fn get(&self, field: &str) -> Option<&'a str> {
+        self.values.get(field).copied()
+    }
+
+    fn required(&self, field: &str) -> Result<String> {
+        self.get(field)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("missing {}", field))
+    }
+
+    fn parsed<T: std::str::FromStr<Err = anyhow::Error>>(&self, field: &str) -> Result<Option<T>> {
+        self.get(field)
+            .map(|value| value.parse().with_context(|| format!("invalid {}", field)))
+            .transpose()
+    }
+
+    fn uuid(&self, field: &str) -> Result<Option<Uuid>> {
+        self.get(field)
+            .map(|value| {
+                Uuid::parse_str(value).with_context(|| format!("invalid {}: {}", field, value))
+            })
+            .transpose()
+    }
+
+    fn date(&self, field: &str) -> Result<Option<DateTime<Utc>>> {
+        self.get(field)
+            .map(|value| {
+                DateTime::parse_from_rfc3339(value)
+                    .map(|date| date.with_timezone(&Utc))
+                    .or_else(|_| parse_date(value))
+                    .with_context(|| format!("invalid {}", field))
+            })
+            .transpose()
+    }
+
+    fn list(&self, field: &str) -> Vec<String> {
+        self.get(field)
+            .map(|value| {
+                value
+                    .split([';', ','])
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Creation and update times, defaulting to each other and then to now.
+    fn timestamps(&self) -> Result<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        let created = self.date("created_at")?;
+        let updated = self.date("updated_at")?;
+        Ok(match (created, updated) {
+            (None, None) => None,
+            (Some(created), None) => Some((created, created)),
+            (None, Some(updated)) => Some((updated, updated)),
+            (Some(created), Some(updated)) => Some((created, updated)),
+        })
+    }
+}
+
+/// An entity that can be built from an imported row.
+pub trait Importable: Identifiable + Sized {
+    const KIND: &'static str;
+
+    fn title(&self) -> &str;
+    fn from_fields(fields: &Fields) -> Result<Self>;
+}
+
+impl Importable for Idea {
+    const KIND: &'static str = "idea";
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn from_fields(fields: &Fields) -> Result<Self> {
+        let mut idea = Idea::new(fields.required("title")?);
+        if let Some(id) = fields.uuid("id")? {
+            idea.id = id;
+        }
+        idea.description = fields.get("description").map(str::to_string);
+        idea.source = fields.get("source").map(str::to_string);
+        idea.effort = fields.parsed("effort")?;
+        idea.impact = fields.parsed("impact")?;
+        if let Some(status) = fields.parsed("status")? {
+            idea.status = status;
+        }
+        if let Some(priority) = fields.parsed("priority")? {
+            idea.priority = priority;
+        }
+        idea.tags = fields.list("tags");
+        idea.related_ids = fields
+            .list("related_ids")
+            .iter()
+            .map(|id| Uuid::parse_str(id).with_context(|| format!("invalid related_ids: {}", id)))
+            .collect::<Result<_>>()?;
+        if let Some((created, updated)) = fields.timestamps()? {
+            idea.created_at = created;
+            idea.updated_at = updated;
+        }
+        Ok(idea)
+    }
+}
+
+impl Importable for Project {
+    const KIND: &'static str = "project";
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn from_fields(fields: &Fields) -> Result<Self> {
+        let mut project = Project::new(fields.required("title")?);
+        if let Some(id) = fields.uuid("id")? {
+            project.id = id;
+        }
+        project.description = fields.get("description").map(str::to_string);
+        project.milestone = fields.get("milestone").map(str::to_string);
+        project.url = fields.get("url").map(str::to_string);
+        project.repo = fields.get("repo").map(str::to_string);
+        project.tags = fields.list("tags");
+        if let Some(priority) = fields.parsed("priority")? {
+            project.priority = priority;
+        }
+        project.due_date = fields.date("due_date")?;
+        project.assignee = fields.get("assignee").map(str::to_string);
+        if let Some(status) = fields.parsed("status")? {
+            project.status = status;
+        }
+        project.idea_ids = fields
+            .list("idea_ids")
+            .iter()
+            .map(|id| Uuid::parse_str(id).with_context(|| format!("invalid idea_ids: {}", id)))
+            .collect::<Result<_>>()?;
+        project.depends_on = fields
+            .list("depends_on")
+            .iter()
+            .map(|id| Uuid::parse_str(id).with_context(|| format!("invalid depends_on: {}", id)))
+            .collect::<Result<_>>()?;
+        project.parent_id = fields.uuid("parent_id")?;
+        if let Some((created, updated)) = fields.timestamps()? {
+            project.created_at = created;
+            project.updated_at = updated;
+        }
+        Ok(project)
+    }
+}
+
+impl Importable for Task {
+    const KIND: &'static str = "task";
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn from_fields(fields: &Fields) -> Result<Self> {
+        let mut task = Task::new(fields.required("title")?);
+        if let Some(id) = fields.uuid("id")? {
+            task.id = id;
+        }
+        task.description = fields.get("description").map(str::to_string);
+        if let Some(status) = fields.parsed("status")? {
+            task.status = status;
+        }
+        if let Some(priority) = fields.parsed("priority")? {
+            task.priority = priority;
+        }
+        task.due_date = fields.date("due_date")?;
+        task.project_id = fields.uuid("project_id")?;
+        task.idea_id = fields.uuid("idea_id")?;
+        task.tags = fields.list("tags");
+        task.assignee = fields.get("assignee").map(str::to_string);
+        task.completed_at = fields.date("completed_at")?;
+        if let Some((created, updated)) = fields.timestamps()? {
+            task.created_at = created;
+            task.updated_at = updated;
+        }
+        Ok(task)
+    }
+}
+
+/// A row that won't be imported, and why.
+#[derive(Debug, Clone)]
+pub struct Skipped {
+    pub row: usize,
+    pub title: Option<String>,
+    pub reason: String,
+}
+
+/// What importing a file would do.
+#[derive(Debug)]
+pub struct ImportPlan<T> {
+    pub create: Vec<T>,
+    pub skipped: Vec<Skipped>,
+    /// Columns that didn't match any field
+    pub ignored_columns: Vec<String>,
+}
+
+/// Work out which rows become new entities, given the `existing` ones.
+pub fn plan<T: Importable>(rows: &[Row], columns: &ColumnMap, existing: &[T]) -> ImportPlan<T> {
+    let mut ids: HashMap<Uuid, String> = existing
+        .iter()
+        .map(|item| (item.id(), format!("existing {} {}", T::KIND, item.id())))
+        .collect();
+    let mut titles: HashMap<String, String> = existing
+        .iter()
+        .map(|item| {
+            (
+                title_key(item.title()),
+                format!("existing {} {}", T::KIND, item.id()),
+            )
+        })
+        .collect();
+
+    let mut ignored_columns: Vec<String> = Vec::new();
+    let mut create = Vec::new();
+    let mut skipped = Vec::new();
+
+    for row in rows {
+        let mut values = HashMap::new();
+        for (column, value) in &row.columns {
+            match columns.field(column) {
+                Some(field) if !value.trim().is_empty() => {
+                    values.insert(field, value.trim());
+                }
+                Some(_) => {}
+                None if !ignored_columns.contains(column) => ignored_columns.push(column.clone()),
+                None => {}
+            }
+        }
+        let fields = Fields { values };
+        let title = fields.get("title").map(str::to_string);
+
+        let item = match T::from_fields(&fields) {
+            Ok(item) => item,
+            Err(err) => {
+                skipped.push(Skipped {
+                    row: row.number,
+                    title,
+                    reason: format!("{:#}", err),
+                });
+                continue;
+            }
+        };
+
+        let duplicate = ids
+            .get(&item.id())
+            .map(|of| format!("same ID as {}", of))
+            .or_else(|| {
+                titles
+                    .get(&title_key(item.title()))
+                    .map(|of| format!("same title as {}", of))
+            });
+        if let Some(reason) = duplicate {
+            skipped.push(Skipped {
+                row: row.number,
+                title,
+                reason,
+            });
+            continue;
+        }
+
+        ids.insert(item.id(), format!("row {}", row.number));
+        titles.insert(title_key(item.title()), format!("row {}", row.number));
+        create.push(item);
+    }
+
+    ImportPlan {
+        create,
+        skipped,
+        ignored_columns,
+    }
+}
+
+fn title_key(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
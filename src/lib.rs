@@ -1,14 +1,24 @@
 pub mod cli;
 pub mod commands;
+pub mod dates;
+pub mod editor;
+pub mod export;
+pub mod front_matter;
+pub mod import;
+pub mod logging;
 pub mod models;
+pub mod output;
 pub mod storage;
+pub mod style;
+pub mod symbols;
+pub mod sync;
 
 pub use cli::{Cli, Commands};
 pub use models::idea::Idea;
 pub use models::project::Project;
 pub use models::tag::Tag;
 pub use models::task::Task;
-pub use storage::Storage;
+pub use storage::{Session, Storage, StorageBackend};
 
 #[cfg(test)]
 mod tests {
@@ -79,6 +89,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
     fn test_storage_initialization() {
         let storage = Storage::new();
         assert!(storage.is_ok());
@@ -0,0 +1,39 @@
+//! Diagnostic logging to standard error, for debugging slow commands or
+//! surprising data. Off unless asked for with `--verbose` or `RUST_LOG`, so
+//! normal output and scripts reading it are unaffected.
+
+use std::env;
+use std::io::{self, IsTerminal};
+use tracing_subscriber::EnvFilter;
+
+/// What is logged without `--verbose` or `RUST_LOG`: only warnings.
+const DEFAULT_FILTER: &str = "warn";
+
+/// Start logging for the rest of the process. `verbosity` is how many times
+/// `--verbose` was given: once logs which files are read and written and how
+/// long storage operations take, twice adds every entity file. `RUST_LOG`, when
+/// set, takes precedence (e.g. `RUST_LOG=ideavault::storage=trace`). Colors
+/// follow `wanted` as for normal output, but for standard error.
+pub fn init(verbosity: u8, wanted: bool) {
+    let filter = match env::var("RUST_LOG") {
+        Ok(spec) if !spec.is_empty() => EnvFilter::new(spec),
+        _ => EnvFilter::new(filter_for(verbosity)),
+    };
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_ansi(wanted && !no_color && io::stderr().is_terminal())
+        .with_timer(tracing_subscriber::fmt::time::uptime())
+        .try_init();
+}
+
+/// The filter `--verbose` given `verbosity` times stands for.
+pub fn filter_for(verbosity: u8) -> &'static str {
+    match verbosity {
+        0 => DEFAULT_FILTER,
+        1 => "warn,ideavault=debug",
+        _ => "warn,ideavault=trace",
+    }
+}
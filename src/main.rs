@@ -1,10 +1,19 @@
 use anyhow::Result;
-use clap::Parser;
-use ideavault::cli::Cli;
+use clap::{CommandFactory, FromArgMatches};
+use ideavault::cli::{command_path, wants_ids, wants_json, Cli};
 use ideavault::commands::execute_search;
+use ideavault::models::config::{Config, OutputFormat};
+use ideavault::models::task;
 use std::env;
 
 fn main() -> Result<()> {
+    // Stop quietly when the output is piped into something that quits early,
+    // such as `head`, instead of panicking on the broken pipe
+    #[cfg(unix)]
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+
     // Check for markdown-help flag before parsing
     let args: Vec<String> = env::args().collect();
     if args.contains(&"--markdown-help".to_string()) {
@@ -12,9 +21,40 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let cli = Cli::parse();
+    // A broken config file is reported by the command that needs it. Statuses
+    // and priorities are set first, as parsing the arguments needs them
+    let config = Config::load().unwrap_or_default();
+    task::set_statuses(config.task_statuses.clone());
+    task::set_priorities(config.task_priorities.clone());
 
-    match cli.command {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    ideavault::logging::init(cli.verbose, config.use_colors && !cli.no_color);
+    ideavault::storage::history::set_command(command_path(&matches));
+    if let Some(vault) = &cli.vault {
+        ideavault::storage::vault::select(vault);
+    }
+    if let Some(data_dir) = &cli.data_dir {
+        ideavault::storage::set_data_dir(data_dir.clone());
+    }
+    ideavault::style::init(config.use_colors && !cli.no_color);
+    ideavault::symbols::init(config.use_emoji && !cli.no_emoji);
+    ideavault::dates::set_relative(config.relative_dates);
+    // A list's own --json or --ids wins over --format and the config
+    let format = if wants_json(&matches) {
+        OutputFormat::Json
+    } else if wants_ids(&matches) {
+        OutputFormat::Table
+    } else {
+        cli.format.clone().unwrap_or(config.default_format)
+    };
+    ideavault::output::set_format(format);
+    ideavault::output::set_quiet(cli.quiet || wants_ids(&matches));
+
+    let Some(command) = cli.command else {
+        return ideavault::commands::dashboard::execute();
+    };
+    match command {
         ideavault::cli::Commands::Idea(idea_cmd) => {
             idea_cmd.execute()?;
         }
@@ -24,9 +64,72 @@ fn main() -> Result<()> {
         ideavault::cli::Commands::Task(task_cmd) => {
             task_cmd.execute()?;
         }
+        ideavault::cli::Commands::Template(template_cmd) => {
+            template_cmd.execute()?;
+        }
+        ideavault::cli::Commands::Capture(capture_args) => {
+            ideavault::commands::inbox::execute_capture(capture_args)?;
+        }
+        ideavault::cli::Commands::Inbox(inbox_cmd) => {
+            inbox_cmd.execute()?;
+        }
+        ideavault::cli::Commands::Backup(backup_cmd) => {
+            backup_cmd.execute()?;
+        }
+        ideavault::cli::Commands::Export(export_cmd) => {
+            export_cmd.execute()?;
+        }
+        ideavault::cli::Commands::Import(import_args) => {
+            ideavault::commands::import::execute(import_args)?;
+        }
+        ideavault::cli::Commands::Doctor(doctor_args) => {
+            ideavault::commands::doctor::execute(doctor_args)?;
+        }
+        ideavault::cli::Commands::Migrate(migrate_args) => {
+            ideavault::commands::migrate::execute(migrate_args)?;
+        }
+        ideavault::cli::Commands::Compact(compact_args) => {
+            ideavault::commands::compact::execute(compact_args)?;
+        }
+        ideavault::cli::Commands::Trash(trash_cmd) => {
+            trash_cmd.execute()?;
+        }
+        ideavault::cli::Commands::History(history_args) => {
+            ideavault::commands::history::execute(history_args)?;
+        }
+        ideavault::cli::Commands::Undo(undo_args) => {
+            ideavault::commands::undo::execute(undo_args)?;
+        }
+        ideavault::cli::Commands::Vault(vault_cmd) => {
+            vault_cmd.execute()?;
+        }
+        ideavault::cli::Commands::Config(config_cmd) => {
+            config_cmd.execute()?;
+        }
+        ideavault::cli::Commands::Git(git_cmd) => {
+            git_cmd.execute()?;
+        }
+        ideavault::cli::Commands::Sync(sync_args) => {
+            ideavault::commands::sync::execute(sync_args)?;
+        }
+        ideavault::cli::Commands::Pick(pick_args) => {
+            ideavault::commands::pick::execute(pick_args)?;
+        }
+        ideavault::cli::Commands::Recent(recent_args) => {
+            ideavault::commands::recent::execute(recent_args)?;
+        }
+        ideavault::cli::Commands::Review(review_args) => {
+            ideavault::commands::review::execute(review_args)?;
+        }
         ideavault::cli::Commands::Search(search_args) => {
             execute_search(search_args)?;
         }
+        ideavault::cli::Commands::Path(path_args) => {
+            ideavault::commands::path::execute(path_args)?;
+        }
+        ideavault::cli::Commands::OpenDataDir => {
+            ideavault::commands::path::execute_open_data_dir()?;
+        }
         ideavault::cli::Commands::Version(version_args) => {
             ideavault::commands::version::execute(version_args)?;
         }
@@ -1,9 +1,37 @@
+//! User settings, kept as `config.json` in the platform config directory.
+
+use crate::models::task::{self, PriorityDefinition, StatusDefinition};
+use crate::storage::json::write_atomic;
+use anyhow::{Context, Result};
+use directories::{BaseDirs, ProjectDirs};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE: &str = "config.json";
+
+/// Prefix of the environment variables that override settings.
+pub const ENV_PREFIX: &str = "IDEAVAULT_";
+
+/// Short environment variable names for common settings. They win over the
+/// long names (`IDEAVAULT_DEFAULT_FORMAT`) when both are set.
+pub const ENV_ALIASES: &[(&str, &str)] = &[
+    ("IDEAVAULT_FORMAT", "default_format"),
+    ("IDEAVAULT_EDITOR", "default_editor"),
+];
+
+/// Turns colors off when set to anything but `0` or `false`.
+pub const NO_COLOR_ENV: &str = "IDEAVAULT_NO_COLOR";
+
+/// `data_dir` has no override here: `IDEAVAULT_DATA_DIR` already replaces
+/// the vault directory altogether, like `--data-dir`.
+const NOT_FROM_ENV: &[&str] = &["data_dir"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
-    /// Path to the data directory where ideas and projects are stored
+    /// Path to the default vault's data directory; a leading `~` is the home directory
     pub data_dir: PathBuf,
 
     /// Default output format (json, table, yaml)
@@ -12,20 +40,47 @@ pub struct Config {
     /// Whether to show timestamps by default in table output
     pub show_timestamps: bool,
 
+    /// Whether lists and summaries show times as "3 hours ago" rather than as dates
+    pub relative_dates: bool,
+
     /// Maximum number of items to show in list commands (None for no limit)
     pub max_list_items: Option<usize>,
 
     /// Whether to use colors in output
     pub use_colors: bool,
 
+    /// Whether output marks lines with emoji rather than plain ASCII labels
+    pub use_emoji: bool,
+
     /// Default editor for editing ideas/projects
     pub default_editor: Option<String>,
 
     /// Backup configuration
     pub backup: BackupConfig,
+
+    /// Compression applied to the data files
+    #[serde(default)]
+    pub compression: Compression,
+
+    /// Trash configuration
+    #[serde(default)]
+    pub trash: TrashConfig,
+
+    /// What running `ideavault` without a command shows
+    #[serde(default)]
+    pub dashboard: DashboardConfig,
+
+    /// The statuses tasks can have, in workflow order
+    #[serde(deserialize_with = "task::deserialize_statuses")]
+    pub task_statuses: Vec<StatusDefinition>,
+
+    /// The priority levels tasks can have, most urgent first
+    #[serde(deserialize_with = "task::deserialize_priorities")]
+    pub task_priorities: Vec<PriorityDefinition>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BackupConfig {
     /// Whether to enable automatic backups
     pub enabled: bool,
@@ -39,26 +94,106 @@ pub struct BackupConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub enum OutputFormat {
+    #[serde(alias = "json")]
     Json,
     #[default]
+    #[serde(alias = "table")]
     Table,
+    #[serde(alias = "yaml")]
     Yaml,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashConfig {
+    /// Days after which deleted items are purged from the trash (None to keep forever)
+    pub auto_purge_days: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DashboardConfig {
+    /// The sections to show, in order
+    pub sections: Vec<DashboardSection>,
+
+    /// Most items listed in each section
+    pub max_items: usize,
+}
+
+/// A part of the dashboard.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardSection {
+    /// Open tasks due before today
+    Overdue,
+    /// Open tasks due today
+    DueToday,
+    /// Tasks and projects in progress, and active ideas
+    InProgress,
+    /// The most recently created ideas
+    RecentIdeas,
+    /// How many ideas, projects, and tasks have each status
+    Counts,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "table" => Ok(OutputFormat::Table),
+            "yaml" => Ok(OutputFormat::Yaml),
+            _ => Err(anyhow::anyhow!(
+                "Invalid format. Must be one of: table, json, yaml"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Table => write!(f, "table"),
+            OutputFormat::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
+/// Compression applied to storage files. Reading always detects the format,
+/// so changing this only affects how files are written from then on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum Compression {
+    #[default]
+    #[serde(alias = "none")]
+    None,
+    #[serde(alias = "gzip")]
+    Gzip,
+    #[serde(alias = "zstd")]
+    Zstd,
+}
+
 impl Config {
     pub fn new() -> Self {
         Self {
-            data_dir: PathBuf::from("~/.ideavault/data"),
+            data_dir: default_data_dir(),
             default_format: OutputFormat::Table,
             show_timestamps: true,
+            relative_dates: true,
             max_list_items: Some(50),
             use_colors: true,
+            use_emoji: true,
             default_editor: None,
             backup: BackupConfig {
                 enabled: true,
                 max_backups: 10,
                 interval_hours: 24,
             },
+            compression: Compression::None,
+            trash: TrashConfig::default(),
+            dashboard: DashboardConfig::default(),
+            task_statuses: task::default_statuses(),
+            task_priorities: task::default_priorities(),
         }
     }
 
@@ -77,6 +212,11 @@ impl Config {
         self
     }
 
+    pub fn with_relative_dates(mut self, relative: bool) -> Self {
+        self.relative_dates = relative;
+        self
+    }
+
     pub fn with_max_list_items(mut self, max: Option<usize>) -> Self {
         self.max_list_items = max;
         self
@@ -87,6 +227,21 @@ impl Config {
         self
     }
 
+    pub fn with_emoji(mut self, use_emoji: bool) -> Self {
+        self.use_emoji = use_emoji;
+        self
+    }
+
+    pub fn with_task_statuses(mut self, statuses: Vec<StatusDefinition>) -> Self {
+        self.task_statuses = statuses;
+        self
+    }
+
+    pub fn with_task_priorities(mut self, priorities: Vec<PriorityDefinition>) -> Self {
+        self.task_priorities = priorities;
+        self
+    }
+
     pub fn with_editor<S: Into<String>>(mut self, editor: S) -> Self {
         self.default_editor = Some(editor.into());
         self
@@ -97,27 +252,231 @@ impl Config {
         self
     }
 
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_trash_config(mut self, trash: TrashConfig) -> Self {
+        self.trash = trash;
+        self
+    }
+
+    pub fn with_dashboard_config(mut self, dashboard: DashboardConfig) -> Self {
+        self.dashboard = dashboard;
+        self
+    }
+
+    /// Where the config file lives: `config.json` in the platform config directory.
+    pub fn path() -> Result<PathBuf> {
+        let proj_dirs = ProjectDirs::from("com", "ideavault", "ideavault")
+            .context("Failed to get project directories")?;
+        Ok(proj_dirs.config_dir().join(CONFIG_FILE))
+    }
+
+    /// Load the config file, writing one with the defaults on first run, and
+    /// apply any `IDEAVAULT_*` environment variables on top.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::path()?)?.with_env(|name| std::env::var(name).ok())
+    }
+
+    /// Override settings from environment variables, looked up with `var`.
+    /// Each setting has one named after its key (`IDEAVAULT_BACKUP_ENABLED`),
+    /// plus the shorthands in [`ENV_ALIASES`] and `IDEAVAULT_NO_COLOR`.
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        for (name, key) in env_overrides(&self, &var)? {
+            let value = var(&name).unwrap_or_default();
+            if name == NO_COLOR_ENV {
+                self.use_colors = !is_truthy(&value);
+            } else {
+                self.set(&key, &value)
+                    .with_context(|| format!("Invalid value in {}", name))?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// The environment variables `var` has set that override a setting, with
+    /// the key each overrides.
+    pub fn env_overrides(
+        &self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Vec<(String, String)>> {
+        env_overrides(self, &var)
+    }
+
+    /// Load the config stored at `path`, creating it with the defaults if it
+    /// doesn't exist. Settings missing from the file take their defaults.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            let config = Self::new();
+            config.save_to(path)?;
+            return Ok(config);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {:?}", path))
+    }
+
+    /// Write the config file.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create config directory: {:?}", parent))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(path, content.as_bytes())
+            .with_context(|| format!("Failed to write config file: {:?}", path))
+    }
+
+    /// Every setting as a dotted key (`backup.enabled`) and its value, sorted by key.
+    pub fn entries(&self) -> Result<Vec<(String, Value)>> {
+        let mut entries = Vec::new();
+        flatten("", &serde_json::to_value(self)?, &mut entries);
+        Ok(entries)
+    }
+
+    /// The value of the setting `key`, e.g. `data_dir` or `backup.enabled`.
+    pub fn get(&self, key: &str) -> Result<Value> {
+        let mut value = serde_json::to_value(self)?;
+        Ok(setting(&mut value, key)?.clone())
+    }
+
+    /// Change the setting `key`. `value` is read as JSON where that makes a
+    /// valid setting (`true`, `20`, `null`), and as plain text otherwise.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let mut json = serde_json::to_value(&*self)?;
+        let mut candidates: Vec<Value> = serde_json::from_str(value).into_iter().collect();
+        candidates.push(Value::String(value.to_string()));
+
+        let mut error = None;
+        for candidate in candidates {
+            *setting(&mut json, key)? = candidate;
+            match serde_json::from_value(json.clone()) {
+                Ok(config) => {
+                    *self = config;
+                    return Ok(());
+                }
+                Err(err) => error = Some(err),
+            }
+        }
+        match error {
+            Some(err) => anyhow::bail!("Invalid value '{}' for {}: {}", value, key, err),
+            None => unreachable!("there is always a text candidate"),
+        }
+    }
+
+    /// The data directory with a leading `~` expanded.
+    pub fn resolved_data_dir(&self) -> PathBuf {
+        match (self.data_dir.strip_prefix("~"), BaseDirs::new()) {
+            (Ok(rest), Some(dirs)) => dirs.home_dir().join(rest),
+            _ => self.data_dir.clone(),
+        }
+    }
+
     /// Get the ideas file path
     pub fn ideas_file(&self) -> PathBuf {
-        self.data_dir.join("ideas.json")
+        self.resolved_data_dir().join("ideas.json")
     }
 
     /// Get the projects file path
     pub fn projects_file(&self) -> PathBuf {
-        self.data_dir.join("projects.json")
+        self.resolved_data_dir().join("projects.json")
     }
 
     /// Get the tags file path
     pub fn tags_file(&self) -> PathBuf {
-        self.data_dir.join("tags.json")
+        self.resolved_data_dir().join("tags.json")
     }
 
     /// Get the backup directory path
     pub fn backup_dir(&self) -> PathBuf {
-        self.data_dir.join("backups")
+        self.resolved_data_dir().join("backups")
     }
 }
 
+/// The value at the dotted `key` inside a serialized [`Config`]. Only leaves
+/// are settings; `backup` on its own is not.
+fn setting<'a>(config: &'a mut Value, key: &str) -> Result<&'a mut Value> {
+    let unknown = || {
+        anyhow::anyhow!(
+            "Unknown setting '{}'. Run `ideavault config list` to see them all",
+            key
+        )
+    };
+    let mut value = config;
+    for part in key.split('.') {
+        value = value.get_mut(part).ok_or_else(unknown)?;
+    }
+    if value.is_object() {
+        return Err(unknown());
+    }
+    Ok(value)
+}
+
+fn flatten(prefix: &str, value: &Value, entries: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                let key = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                flatten(&key, value, entries);
+            }
+        }
+        _ => entries.push((prefix.to_string(), value.clone())),
+    }
+}
+
+fn env_overrides(
+    config: &Config,
+    var: &dyn Fn(&str) -> Option<String>,
+) -> Result<Vec<(String, String)>> {
+    let mut names: Vec<(String, String)> = config
+        .entries()?
+        .into_iter()
+        .map(|(key, _)| key)
+        .filter(|key| !NOT_FROM_ENV.contains(&key.as_str()))
+        .map(|key| {
+            let name = format!("{}{}", ENV_PREFIX, key.to_uppercase().replace('.', "_"));
+            (name, key)
+        })
+        .collect();
+    // Applied last, so they win
+    names.extend(
+        ENV_ALIASES
+            .iter()
+            .map(|(name, key)| (name.to_string(), key.to_string())),
+    );
+    names.push((NO_COLOR_ENV.to_string(), "use_colors".to_string()));
+
+    Ok(names
+        .into_iter()
+        .filter(|(name, _)| var(name).is_some_and(|value| !value.is_empty()))
+        .collect())
+}
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.trim().to_lowercase().as_str(),
+        "" | "0" | "false" | "no"
+    )
+}
+
+/// The platform data directory, where the default vault has always lived.
+fn default_data_dir() -> PathBuf {
+    ProjectDirs::from("com", "ideavault", "ideavault")
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("~/.ideavault/data"))
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self::new()
@@ -133,3 +492,26 @@ impl Default for BackupConfig {
         }
     }
 }
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            auto_purge_days: Some(30),
+        }
+    }
+}
+
+impl Default for DashboardConfig {
+    fn default() -> Self {
+        Self {
+            sections: vec![
+                DashboardSection::Overdue,
+                DashboardSection::DueToday,
+                DashboardSection::InProgress,
+                DashboardSection::RecentIdeas,
+                DashboardSection::Counts,
+            ],
+            max_items: 5,
+        }
+    }
+}
@@ -1,26 +1,177 @@
-use chrono::{DateTime, Utc};
+use super::task::TaskPriority;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum IdeaStatus {
+    /// Captured but not yet looked at; see `ideavault inbox process`
+    Inbox,
     Brainstorming,
     Active,
     Completed,
     Archived,
 }
 
+/// A move of an idea from one status to another.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatusChange {
+    pub from: IdeaStatus,
+    pub to: IdeaStatus,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// A dated entry in an idea's log of thoughts, kept apart from the
+/// description so earlier thinking isn't overwritten.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Note {
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Note {
+    pub fn new(body: String) -> Self {
+        Self {
+            body,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A file or link kept with an idea.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    /// The file's original name, or the URL
+    pub name: String,
+    pub kind: AttachmentKind,
+    /// The URL, or where the file was copied to under the attachments
+    /// directory
+    pub location: String,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentKind {
+    File,
+    Url,
+}
+
+impl Attachment {
+    pub fn file(name: String, location: String) -> Self {
+        Self {
+            name,
+            kind: AttachmentKind::File,
+            location,
+            added_at: Utc::now(),
+        }
+    }
+
+    pub fn url(url: String) -> Self {
+        Self {
+            name: url.clone(),
+            kind: AttachmentKind::Url,
+            location: url,
+            added_at: Utc::now(),
+        }
+    }
+}
+
+/// A rating from 1 (lowest) to 5 (highest), for an idea's effort or impact.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct Rating(u8);
+
+impl Rating {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 5;
+
+    pub fn new(value: u8) -> Option<Self> {
+        (Self::MIN..=Self::MAX)
+            .contains(&value)
+            .then_some(Self(value))
+    }
+
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Whether the rating is on the high side of the scale: 3 or more.
+    pub fn is_high(self) -> bool {
+        self.0 >= 3
+    }
+}
+
+impl std::str::FromStr for Rating {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        s.trim()
+            .parse()
+            .ok()
+            .and_then(Self::new)
+            .ok_or_else(|| anyhow::anyhow!("Invalid rating. Must be a whole number from 1 to 5"))
+    }
+}
+
+impl std::fmt::Display for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.0, Self::MAX)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Idea {
     pub id: Uuid,
     pub title: String,
     pub description: Option<String>,
+    /// Where the idea came from, such as a book, a conversation, or a URL
+    #[serde(default)]
+    pub source: Option<String>,
     pub tags: Vec<String>,
     pub status: IdeaStatus,
+    /// How promising the idea is. Ideas saved before ideas had a priority
+    /// read as the middle level.
+    #[serde(default = "TaskPriority::middle")]
+    pub priority: TaskPriority,
+    /// How much work the idea would take, if rated
+    #[serde(default)]
+    pub effort: Option<Rating>,
+    /// How much difference the idea would make, if rated
+    #[serde(default)]
+    pub impact: Option<Rating>,
+    /// Ideas this one is related to. Relations go both ways: each idea lists
+    /// the other.
+    #[serde(default)]
+    pub related_ids: Vec<Uuid>,
+    /// Notes added over time, oldest first
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    /// Files and links kept with the idea, in the order they were added
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Every status change since the idea was created, oldest first. Ideas
+    /// saved before changes were recorded start with none.
+    #[serde(default)]
+    pub status_history: Vec<StatusChange>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// How alike two titles' letters must be, from 0 to 1, for
+/// [`Idea::has_similar_title`] to call them the same.
+const SIMILAR_TITLE: f64 = 0.85;
+
+/// The words of a title, lowercased, with punctuation dropped.
+fn title_words(title: &str) -> Vec<String> {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 impl Idea {
     pub fn new(title: String) -> Self {
         let now = Utc::now();
@@ -28,8 +179,16 @@ impl Idea {
             id: Uuid::new_v4(),
             title,
             description: None,
+            source: None,
             tags: Vec::new(),
             status: IdeaStatus::Brainstorming,
+            priority: TaskPriority::middle(),
+            effort: None,
+            impact: None,
+            related_ids: Vec::new(),
+            notes: Vec::new(),
+            attachments: Vec::new(),
+            status_history: Vec::new(),
             created_at: now,
             updated_at: now,
         }
@@ -41,6 +200,12 @@ impl Idea {
         self
     }
 
+    pub fn with_source(mut self, source: String) -> Self {
+        self.source = Some(source);
+        self.updated_at = Utc::now();
+        self
+    }
+
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self.updated_at = Utc::now();
@@ -53,6 +218,12 @@ impl Idea {
         self
     }
 
+    pub fn with_priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = priority;
+        self.updated_at = Utc::now();
+        self
+    }
+
     pub fn update_title(&mut self, title: String) {
         self.title = title;
         self.updated_at = Utc::now();
@@ -63,6 +234,22 @@ impl Idea {
         self.updated_at = Utc::now();
     }
 
+    pub fn set_source(&mut self, source: Option<String>) {
+        self.source = source;
+        self.updated_at = Utc::now();
+    }
+
+    /// Replace the idea's tags with `tags`, dropping repeats.
+    pub fn update_tags(&mut self, tags: Vec<String>) {
+        self.tags.clear();
+        for tag in tags {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+        self.updated_at = Utc::now();
+    }
+
     pub fn add_tag(&mut self, tag: String) {
         if !self.tags.contains(&tag) {
             self.tags.push(tag);
@@ -77,8 +264,107 @@ impl Idea {
         }
     }
 
+    /// Move the idea to `status`, recording the change in its history.
     pub fn set_status(&mut self, status: IdeaStatus) {
+        let now = Utc::now();
+        if status != self.status {
+            self.status_history.push(StatusChange {
+                from: self.status.clone(),
+                to: status.clone(),
+                changed_at: now,
+            });
+        }
         self.status = status;
+        self.updated_at = now;
+    }
+
+    /// How long the idea has spent in each status it has had up to `now`, in
+    /// the order it first had them. Time before the first recorded change
+    /// counts towards the status that change left.
+    pub fn time_in_status(&self, now: DateTime<Utc>) -> Vec<(IdeaStatus, Duration)> {
+        let mut times: Vec<(IdeaStatus, Duration)> = Vec::new();
+        let mut add = |status: &IdeaStatus, time: Duration| match times
+            .iter_mut()
+            .find(|(known, _)| known == status)
+        {
+            Some((_, total)) => *total += time,
+            None => times.push((status.clone(), time)),
+        };
+
+        let mut since = self.created_at;
+        for change in &self.status_history {
+            add(&change.from, change.changed_at - since);
+            since = change.changed_at;
+        }
+        add(&self.status, now - since);
+        times
+    }
+
+    /// Whether `title` reads as a re-capture of this idea: the same words
+    /// once case and punctuation are ignored, in any order, or a typo or two
+    /// away. Titles with different numbers in them are told apart, so
+    /// "Part 1" and "Part 2" are not duplicates.
+    pub fn has_similar_title(&self, title: &str) -> bool {
+        let (ours, theirs) = (title_words(&self.title), title_words(title));
+        if ours.is_empty() || theirs.is_empty() {
+            return false;
+        }
+        let numbers = |words: &[String]| -> Vec<String> {
+            let mut numbers: Vec<String> = words
+                .iter()
+                .filter(|word| word.chars().all(|c| c.is_ascii_digit()))
+                .cloned()
+                .collect();
+            numbers.sort();
+            numbers
+        };
+        if numbers(&ours) != numbers(&theirs) {
+            return false;
+        }
+        let (mut ours_sorted, mut theirs_sorted) = (ours.clone(), theirs.clone());
+        ours_sorted.sort();
+        theirs_sorted.sort();
+        ours_sorted == theirs_sorted
+            || strsim::normalized_damerau_levenshtein(&ours.join(" "), &theirs.join(" "))
+                >= SIMILAR_TITLE
+    }
+
+    pub fn add_related(&mut self, id: Uuid) {
+        if !self.related_ids.contains(&id) {
+            self.related_ids.push(id);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn remove_related(&mut self, id: &Uuid) {
+        if let Some(pos) = self.related_ids.iter().position(|related| related == id) {
+            self.related_ids.remove(pos);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn add_note(&mut self, note: Note) {
+        self.notes.push(note);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn add_attachment(&mut self, attachment: Attachment) {
+        self.attachments.push(attachment);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_effort(&mut self, effort: Option<Rating>) {
+        self.effort = effort;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_impact(&mut self, impact: Option<Rating>) {
+        self.impact = impact;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_priority(&mut self, priority: TaskPriority) {
+        self.priority = priority;
         self.updated_at = Utc::now();
     }
 }
@@ -4,6 +4,7 @@
 //! - Ideas: Individual thoughts, concepts, or notes
 //! - Projects: Collections of ideas organized toward a goal
 //! - Tags: Labels for categorizing ideas
+//! - Templates: Saved project shapes for starting new projects
 //! - Config: Application configuration settings
 
 pub mod config;
@@ -11,13 +12,15 @@ pub mod idea;
 pub mod project;
 pub mod tag;
 pub mod task;
+pub mod template;
 
 // Re-export the main types for convenience
-pub use config::{BackupConfig, Config, OutputFormat};
-pub use idea::{Idea, IdeaStatus};
-pub use project::{Project, ProjectStatus};
+pub use config::{BackupConfig, Compression, Config, OutputFormat, TrashConfig};
+pub use idea::{Attachment, AttachmentKind, Idea, IdeaStatus, Note, Rating, StatusChange};
+pub use project::{Milestone, MilestoneStatus, Project, ProjectStatus};
 pub use tag::Tag;
 pub use task::{Task, TaskPriority, TaskStatus};
+pub use template::ProjectTemplate;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -105,6 +108,26 @@ impl Statusful for Project {
     }
 }
 
+impl Timestamped for Task {
+    fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.updated_at
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = chrono::Utc::now();
+    }
+}
+
+impl Identifiable for Task {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
 /// Result type for model operations
 pub type ModelResult<T> = Result<T, ModelError>;
 
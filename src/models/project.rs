@@ -1,5 +1,7 @@
+use super::task::TaskPriority;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -8,6 +10,53 @@ pub enum ProjectStatus {
     InProgress,
     Completed,
     OnHold,
+    /// Done with or given up on, and kept out of lists unless asked for
+    Archived,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MilestoneStatus {
+    Open,
+    Completed,
+}
+
+/// A checkpoint on the way to finishing a project. Milestones live on their
+/// project, in the order they are to be reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub id: Uuid,
+    pub title: String,
+    pub due_date: Option<DateTime<Utc>>,
+    pub status: MilestoneStatus,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Milestone {
+    pub fn new(title: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            title,
+            due_date: None,
+            status: MilestoneStatus::Open,
+            completed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_due_date(mut self, due_date: DateTime<Utc>) -> Self {
+        self.due_date = Some(due_date);
+        self
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.status == MilestoneStatus::Completed
+    }
+
+    pub fn complete(&mut self) {
+        self.status = MilestoneStatus::Completed;
+        self.completed_at = Some(Utc::now());
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,8 +67,29 @@ pub struct Project {
     pub milestone: Option<String>,
     pub url: Option<String>,
     pub repo: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub status: ProjectStatus,
+    /// How pressing the project is. Projects saved before projects had a
+    /// priority read as the middle level.
+    #[serde(default = "TaskPriority::middle")]
+    pub priority: TaskPriority,
+    /// When the project is meant to be finished
+    #[serde(default)]
+    pub due_date: Option<DateTime<Utc>>,
+    /// Who the project is with, in a vault shared by a team
+    #[serde(default)]
+    pub assignee: Option<String>,
     pub idea_ids: Vec<Uuid>,
+    /// Projects that have to be completed before this one can be
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
+    /// The project this one is a part of
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// In the order they are to be reached
+    #[serde(default)]
+    pub milestones: Vec<Milestone>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -34,8 +104,15 @@ impl Project {
             milestone: None,
             url: None,
             repo: None,
+            tags: Vec::new(),
             status: ProjectStatus::Planning,
+            priority: TaskPriority::middle(),
+            due_date: None,
+            assignee: None,
             idea_ids: Vec::new(),
+            depends_on: Vec::new(),
+            parent_id: None,
+            milestones: Vec::new(),
             created_at: now,
             updated_at: now,
         }
@@ -65,6 +142,35 @@ impl Project {
         self
     }
 
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self.updated_at = Utc::now();
+        self
+    }
+
+    pub fn with_priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = priority;
+        self.updated_at = Utc::now();
+        self
+    }
+
+    pub fn with_due_date(mut self, due_date: DateTime<Utc>) -> Self {
+        self.due_date = Some(due_date);
+        self.updated_at = Utc::now();
+        self
+    }
+
+    pub fn with_assignee(mut self, assignee: String) -> Self {
+        self.set_assignee(Some(assignee));
+        self
+    }
+
+    pub fn with_parent(mut self, parent_id: Uuid) -> Self {
+        self.parent_id = Some(parent_id);
+        self.updated_at = Utc::now();
+        self
+    }
+
     pub fn with_ideas(mut self, idea_ids: Vec<Uuid>) -> Self {
         self.idea_ids = idea_ids;
         self.updated_at = Utc::now();
@@ -102,6 +208,63 @@ impl Project {
         self.updated_at = Utc::now();
     }
 
+    /// Set who is doing the project, trimmed; a blank name clears it
+    pub fn set_assignee(&mut self, assignee: Option<String>) {
+        self.assignee = assignee
+            .map(|assignee| assignee.trim().to_string())
+            .filter(|assignee| !assignee.is_empty());
+        self.updated_at = Utc::now();
+    }
+
+    /// Whether the project is assigned to `name`, ignoring case.
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        self.assignee
+            .as_deref()
+            .is_some_and(|assignee| assignee.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn set_priority(&mut self, priority: TaskPriority) {
+        self.priority = priority;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_due_date(&mut self, due_date: Option<DateTime<Utc>>) {
+        self.due_date = due_date;
+        self.updated_at = Utc::now();
+    }
+
+    /// Whether the project's due date is before `now` and it isn't completed
+    /// or archived.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        !matches!(
+            self.status,
+            ProjectStatus::Completed | ProjectStatus::Archived
+        ) && self.due_date.is_some_and(|due| due < now)
+    }
+
+    /// Replace the project's tags with `tags`, dropping repeats.
+    pub fn update_tags(&mut self, tags: Vec<String>) {
+        self.tags.clear();
+        for tag in tags {
+            self.add_tag(tag);
+        }
+        self.updated_at = Utc::now();
+    }
+
+    pub fn add_tag(&mut self, tag: String) {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        if let Some(pos) = self.tags.iter().position(|t| t == tag) {
+            self.tags.remove(pos);
+            self.updated_at = Utc::now();
+        }
+    }
+
     pub fn add_idea(&mut self, idea_id: Uuid) {
         if !self.idea_ids.contains(&idea_id) {
             self.idea_ids.push(idea_id);
@@ -121,7 +284,129 @@ impl Project {
         self.updated_at = Utc::now();
     }
 
+    /// Add `milestone` at `position`, counting from 1, or after the others.
+    /// Returns the position it ends up at.
+    pub fn add_milestone(&mut self, milestone: Milestone, position: Option<usize>) -> usize {
+        let index = position
+            .map(|position| position.saturating_sub(1).min(self.milestones.len()))
+            .unwrap_or(self.milestones.len());
+        self.milestones.insert(index, milestone);
+        self.updated_at = Utc::now();
+        index + 1
+    }
+
+    /// The index of the milestone `which` names, by its position counting
+    /// from 1 or by its title, ignoring case.
+    pub fn find_milestone(&self, which: &str) -> Option<usize> {
+        match which.parse::<usize>() {
+            Ok(position) => position
+                .checked_sub(1)
+                .filter(|index| *index < self.milestones.len()),
+            Err(_) => self
+                .milestones
+                .iter()
+                .position(|milestone| milestone.title.eq_ignore_ascii_case(which)),
+        }
+    }
+
+    /// Mark the milestone at `index` completed.
+    pub fn complete_milestone(&mut self, index: usize) {
+        self.milestones[index].complete();
+        self.updated_at = Utc::now();
+    }
+
+    pub fn add_dependency(&mut self, project_id: Uuid) {
+        if !self.depends_on.contains(&project_id) {
+            self.depends_on.push(project_id);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn remove_dependency(&mut self, project_id: &Uuid) {
+        if let Some(pos) = self.depends_on.iter().position(|id| id == project_id) {
+            self.depends_on.remove(pos);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// The projects among `projects` this one depends on that aren't
+    /// completed yet. Dependencies that no longer exist don't block.
+    pub fn blocked_by<'a>(&self, projects: &'a [Project]) -> Vec<&'a Project> {
+        projects
+            .iter()
+            .filter(|project| {
+                self.depends_on.contains(&project.id) && project.status != ProjectStatus::Completed
+            })
+            .collect()
+    }
+
+    /// How many of the project's milestones are completed, and how many it has.
+    pub fn milestone_progress(&self) -> (usize, usize) {
+        let completed = self
+            .milestones
+            .iter()
+            .filter(|milestone| milestone.is_completed())
+            .count();
+        (completed, self.milestones.len())
+    }
+
     pub fn get_idea_count(&self) -> usize {
         self.idea_ids.len()
     }
 }
+
+/// The IDs of the projects under the project `id` among `projects`: its
+/// sub-projects, theirs, and so on.
+pub fn descendants(projects: &[Project], id: Uuid) -> Vec<Uuid> {
+    let mut found = Vec::new();
+    let mut queue = vec![id];
+    while let Some(parent) = queue.pop() {
+        for project in projects {
+            if project.parent_id == Some(parent) && project.id != id && !found.contains(&project.id)
+            {
+                found.push(project.id);
+                queue.push(project.id);
+            }
+        }
+    }
+    found
+}
+
+/// The chain of dependencies leading from the project `from` to the project
+/// `to` among `projects`, both ends included, if there is one. Making `to`
+/// depend on `from` would close that chain into a cycle.
+pub fn dependency_path(projects: &[Project], from: Uuid, to: Uuid) -> Option<Vec<Uuid>> {
+    let mut path = vec![from];
+    let mut seen = HashSet::new();
+    if walk_dependencies(projects, to, &mut path, &mut seen) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+fn walk_dependencies(
+    projects: &[Project],
+    to: Uuid,
+    path: &mut Vec<Uuid>,
+    seen: &mut HashSet<Uuid>,
+) -> bool {
+    let current = path[path.len() - 1];
+    if current == to {
+        return true;
+    }
+    if !seen.insert(current) {
+        return false;
+    }
+    let Some(project) = projects.iter().find(|project| project.id == current) else {
+        return false;
+    };
+    for next in &project.depends_on {
+        path.push(*next);
+        if walk_dependencies(projects, to, path, seen) {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
@@ -1,22 +1,245 @@
+use crate::style::Color;
+use crate::symbols;
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::OnceLock;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// A task's status: one of the built-ins, or a status defined in the
+/// `task_statuses` setting. Stored by name either way.
+#[derive(Debug, Clone, PartialEq)]
 pub enum TaskStatus {
     Todo,
     InProgress,
     Blocked,
     Done,
     Cancelled,
+    Custom(String),
 }
 
+/// A status tasks can have, as defined in the `task_statuses` setting.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatusDefinition {
+    pub name: String,
+
+    /// Whether tasks with this status are still to be done
+    #[serde(default)]
+    pub category: StatusCategory,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusCategory {
+    /// Still to be done; counts towards overdue tasks
+    #[default]
+    Open,
+    /// Finished with, whether done or not
+    Closed,
+}
+
+/// Emoji for statuses that don't set one.
+const DEFAULT_STATUS_EMOJI: &str = "📌";
+
+static STATUSES: OnceLock<Vec<StatusDefinition>> = OnceLock::new();
+static BUILT_IN_STATUSES: OnceLock<Vec<StatusDefinition>> = OnceLock::new();
+
+impl StatusDefinition {
+    pub fn new(name: &str, category: StatusCategory) -> Self {
+        Self {
+            name: name.to_string(),
+            category,
+            emoji: None,
+            color: None,
+        }
+    }
+
+    pub fn with_emoji(mut self, emoji: &str) -> Self {
+        self.emoji = Some(emoji.to_string());
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// The statuses tasks have unless the `task_statuses` setting says otherwise.
+pub fn default_statuses() -> Vec<StatusDefinition> {
+    use StatusCategory::{Closed, Open};
+    vec![
+        StatusDefinition::new("Todo", Open)
+            .with_emoji("📋")
+            .with_color(Color::Blue),
+        StatusDefinition::new("InProgress", Open)
+            .with_emoji("🔄")
+            .with_color(Color::Cyan),
+        StatusDefinition::new("Blocked", Open)
+            .with_emoji("🚫")
+            .with_color(Color::Red),
+        StatusDefinition::new("Done", Closed)
+            .with_emoji("✅")
+            .with_color(Color::Green),
+        StatusDefinition::new("Cancelled", Closed)
+            .with_emoji("❌")
+            .with_color(Color::Gray),
+    ]
+}
+
+/// Use `statuses` as the task statuses for the rest of the process.
+pub fn set_statuses(statuses: Vec<StatusDefinition>) {
+    let _ = STATUSES.set(statuses);
+}
+
+/// The task statuses in use: the configured ones, or the built-ins.
+pub fn statuses() -> &'static [StatusDefinition] {
+    match STATUSES.get() {
+        Some(statuses) => statuses,
+        None => BUILT_IN_STATUSES.get_or_init(default_statuses),
+    }
+}
+
+/// Read the `task_statuses` setting, which must name at least one status
+/// and no status twice.
+pub fn deserialize_statuses<'de, D>(deserializer: D) -> Result<Vec<StatusDefinition>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let statuses = Vec::<StatusDefinition>::deserialize(deserializer)?;
+    check_names(statuses.iter().map(|status| status.name.as_str()), "status")
+        .map_err(serde::de::Error::custom)?;
+    Ok(statuses)
+}
+
+/// Read the `task_priorities` setting, which must name at least one
+/// priority and no priority twice.
+pub fn deserialize_priorities<'de, D>(deserializer: D) -> Result<Vec<PriorityDefinition>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let priorities = Vec::<PriorityDefinition>::deserialize(deserializer)?;
+    check_names(
+        priorities.iter().map(|priority| priority.name.as_str()),
+        "priority",
+    )
+    .map_err(serde::de::Error::custom)?;
+    Ok(priorities)
+}
+
+fn check_names<'a>(names: impl Iterator<Item = &'a str>, kind: &str) -> Result<(), String> {
+    let mut seen = Vec::new();
+    for name in names {
+        let normalized = normalize(name);
+        if normalized.is_empty() {
+            return Err(format!("every task {} needs a name", kind));
+        }
+        if seen.contains(&normalized) {
+            return Err(format!("task {} '{}' is defined twice", kind, name));
+        }
+        seen.push(normalized);
+    }
+    if seen.is_empty() {
+        return Err(format!("at least one task {} is needed", kind));
+    }
+    Ok(())
+}
+
+/// A status or priority name as typed on the command line: case, spaces,
+/// `-` and `_` don't matter.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// A task's priority: one of the built-ins, or a level defined in the
+/// `task_priorities` setting. Stored by name either way, and ordered by the
+/// setting, so the most urgent priority is the greatest.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TaskPriority {
     Low,
     Medium,
     High,
     Urgent,
+    Custom(String),
+}
+
+/// A priority level, as defined in the `task_priorities` setting.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PriorityDefinition {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+}
+
+/// Emoji for priorities that don't set one.
+const DEFAULT_PRIORITY_EMOJI: &str = "🔹";
+
+static PRIORITIES: OnceLock<Vec<PriorityDefinition>> = OnceLock::new();
+static BUILT_IN_PRIORITIES: OnceLock<Vec<PriorityDefinition>> = OnceLock::new();
+
+impl PriorityDefinition {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            emoji: None,
+            color: None,
+        }
+    }
+
+    pub fn with_emoji(mut self, emoji: &str) -> Self {
+        self.emoji = Some(emoji.to_string());
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// The priority levels unless the `task_priorities` setting says otherwise,
+/// most urgent first.
+pub fn default_priorities() -> Vec<PriorityDefinition> {
+    vec![
+        PriorityDefinition::new("Urgent")
+            .with_emoji("🔴")
+            .with_color(Color::Red),
+        PriorityDefinition::new("High")
+            .with_emoji("⬆️")
+            .with_color(Color::Yellow),
+        PriorityDefinition::new("Medium")
+            .with_emoji("➡️")
+            .with_color(Color::Blue),
+        PriorityDefinition::new("Low")
+            .with_emoji("⬇️")
+            .with_color(Color::Gray),
+    ]
+}
+
+/// Use `priorities` (most urgent first) for the rest of the process.
+pub fn set_priorities(priorities: Vec<PriorityDefinition>) {
+    let _ = PRIORITIES.set(priorities);
+}
+
+/// The priority levels in use, most urgent first: the configured ones, or
+/// the built-ins.
+pub fn priorities() -> &'static [PriorityDefinition] {
+    match PRIORITIES.get() {
+        Some(priorities) => priorities,
+        None => BUILT_IN_PRIORITIES.get_or_init(default_priorities),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +253,12 @@ pub struct Task {
     pub project_id: Option<Uuid>,
     pub idea_id: Option<Uuid>,
     pub tags: Vec<String>,
+    /// Who is doing the task, in a vault shared by a team
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// When the task was finished; cleared if it's reopened
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,11 +271,13 @@ impl Task {
             title,
             description: None,
             status: TaskStatus::Todo,
-            priority: TaskPriority::Medium,
+            priority: TaskPriority::middle(),
             due_date: None,
             project_id: None,
             idea_id: None,
             tags: Vec::new(),
+            assignee: None,
+            completed_at: None,
             created_at: now,
             updated_at: now,
         }
@@ -65,8 +296,7 @@ impl Task {
     }
 
     pub fn with_status(mut self, status: TaskStatus) -> Self {
-        self.status = status;
-        self.updated_at = Utc::now();
+        self.set_status(status);
         self
     }
 
@@ -94,6 +324,11 @@ impl Task {
         self
     }
 
+    pub fn with_assignee(mut self, assignee: String) -> Self {
+        self.set_assignee(Some(assignee));
+        self
+    }
+
     /// Update the title of the task
     pub fn update_title(&mut self, title: String) {
         self.title = title;
@@ -118,10 +353,17 @@ impl Task {
         self.updated_at = Utc::now();
     }
 
-    /// Set the status of the task
+    /// Set the status of the task, stamping when it was finished, or
+    /// clearing that if it's reopened
     pub fn set_status(&mut self, status: TaskStatus) {
+        let now = Utc::now();
+        if !status.is_done() {
+            self.completed_at = None;
+        } else if !self.status.is_done() {
+            self.completed_at = Some(now);
+        }
         self.status = status;
-        self.updated_at = Utc::now();
+        self.updated_at = now;
     }
 
     /// Update tags for the task
@@ -129,20 +371,105 @@ impl Task {
         self.tags = tags;
         self.updated_at = Utc::now();
     }
+
+    /// Set who is doing the task, trimmed; a blank name clears it
+    pub fn set_assignee(&mut self, assignee: Option<String>) {
+        self.assignee = assignee
+            .map(|assignee| assignee.trim().to_string())
+            .filter(|assignee| !assignee.is_empty());
+        self.updated_at = Utc::now();
+    }
+
+    /// Whether the task is assigned to `name`, ignoring case.
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        self.assignee
+            .as_deref()
+            .is_some_and(|assignee| assignee.eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl TaskStatus {
+    /// The status called `name`: a built-in if it is one, custom otherwise.
+    pub fn named(name: &str) -> Self {
+        match name {
+            "Todo" => TaskStatus::Todo,
+            "InProgress" => TaskStatus::InProgress,
+            "Blocked" => TaskStatus::Blocked,
+            "Done" => TaskStatus::Done,
+            "Cancelled" => TaskStatus::Cancelled,
+            _ => TaskStatus::Custom(name.to_string()),
+        }
+    }
+
+    /// How this status is configured. Built-ins that were left out of the
+    /// setting keep their defaults; other unknown statuses have none.
+    pub fn definition(&self) -> Option<&'static StatusDefinition> {
+        let name = self.to_string();
+        statuses()
+            .iter()
+            .find(|status| status.name == name)
+            .or_else(|| {
+                BUILT_IN_STATUSES
+                    .get_or_init(default_statuses)
+                    .iter()
+                    .find(|status| status.name == name)
+            })
+    }
+
+    /// Whether tasks with this status are finished with.
+    pub fn is_closed(&self) -> bool {
+        self.definition()
+            .is_some_and(|status| status.category == StatusCategory::Closed)
+    }
+
+    /// Whether tasks with this status were finished rather than given up on:
+    /// closed, but not cancelled.
+    pub fn is_done(&self) -> bool {
+        self.is_closed() && *self != TaskStatus::Cancelled
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        self.definition()
+            .and_then(|status| status.emoji.as_deref())
+            .unwrap_or(DEFAULT_STATUS_EMOJI)
+    }
+
+    /// The status's emoji, or its name when output is plain ASCII.
+    pub fn marker(&self) -> String {
+        symbols::configured(self.emoji(), &self.to_string())
+    }
 }
 
 impl std::str::FromStr for TaskStatus {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "todo" | "t" => Ok(TaskStatus::Todo),
-            "inprogress" | "in-progress" | "progress" | "ip" => Ok(TaskStatus::InProgress),
-            "blocked" | "block" | "b" => Ok(TaskStatus::Blocked),
-            "done" | "complete" | "d" | "x" => Ok(TaskStatus::Done),
-            "cancelled" | "cancel" | "c" => Ok(TaskStatus::Cancelled),
+        let wanted = normalize(s);
+        let configured = statuses();
+        if let Some(status) = configured
+            .iter()
+            .find(|status| normalize(&status.name) == wanted)
+        {
+            return Ok(TaskStatus::named(&status.name));
+        }
+
+        let shorthand = match wanted.as_str() {
+            "t" => Some(TaskStatus::Todo),
+            "progress" | "ip" => Some(TaskStatus::InProgress),
+            "block" | "b" => Some(TaskStatus::Blocked),
+            "complete" | "d" | "x" => Some(TaskStatus::Done),
+            "cancel" | "c" => Some(TaskStatus::Cancelled),
+            _ => None,
+        };
+        match shorthand {
+            Some(status) if configured.iter().any(|s| s.name == status.to_string()) => Ok(status),
             _ => Err(anyhow::anyhow!(
-                "Invalid status. Must be one of: todo, inprogress, blocked, done, cancelled"
+                "Invalid status. Must be one of: {}",
+                configured
+                    .iter()
+                    .map(|status| status.name.to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(", ")
             )),
         }
     }
@@ -156,21 +483,124 @@ impl std::fmt::Display for TaskStatus {
             TaskStatus::Blocked => write!(f, "Blocked"),
             TaskStatus::Done => write!(f, "Done"),
             TaskStatus::Cancelled => write!(f, "Cancelled"),
+            TaskStatus::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl Serialize for TaskStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(TaskStatus::named(&String::deserialize(deserializer)?))
+    }
+}
+
+impl TaskPriority {
+    /// The priority called `name`: a built-in if it is one, custom otherwise.
+    pub fn named(name: &str) -> Self {
+        match name {
+            "Low" => TaskPriority::Low,
+            "Medium" => TaskPriority::Medium,
+            "High" => TaskPriority::High,
+            "Urgent" => TaskPriority::Urgent,
+            _ => TaskPriority::Custom(name.to_string()),
         }
     }
+
+    /// The level new tasks get: the middle of the scale, `Medium` by default.
+    pub fn middle() -> Self {
+        let priorities = priorities();
+        TaskPriority::named(&priorities[priorities.len() / 2].name)
+    }
+
+    /// Position on the scale, 0 being the most urgent. Priorities that
+    /// aren't configured come after all that are.
+    pub fn rank(&self) -> usize {
+        let name = self.to_string();
+        priorities()
+            .iter()
+            .position(|priority| priority.name == name)
+            .unwrap_or(usize::MAX)
+    }
+
+    /// How this priority is configured. Built-ins that were left out of the
+    /// setting keep their defaults; other unknown priorities have none.
+    pub fn definition(&self) -> Option<&'static PriorityDefinition> {
+        let name = self.to_string();
+        priorities()
+            .iter()
+            .find(|priority| priority.name == name)
+            .or_else(|| {
+                BUILT_IN_PRIORITIES
+                    .get_or_init(default_priorities)
+                    .iter()
+                    .find(|priority| priority.name == name)
+            })
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        self.definition()
+            .and_then(|priority| priority.emoji.as_deref())
+            .unwrap_or(DEFAULT_PRIORITY_EMOJI)
+    }
+
+    /// The priority's emoji, or its name when output is plain ASCII.
+    pub fn marker(&self) -> String {
+        symbols::configured(self.emoji(), &self.to_string())
+    }
+}
+
+impl Ord for TaskPriority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .rank()
+            .cmp(&self.rank())
+            .then_with(|| self.to_string().cmp(&other.to_string()))
+    }
+}
+
+impl PartialOrd for TaskPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl std::str::FromStr for TaskPriority {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "low" | "l" => Ok(TaskPriority::Low),
-            "medium" | "m" | "med" => Ok(TaskPriority::Medium),
-            "high" | "h" => Ok(TaskPriority::High),
-            "urgent" | "u" | "crit" | "critical" => Ok(TaskPriority::Urgent),
+        let wanted = normalize(s);
+        let configured = priorities();
+        if let Some(priority) = configured
+            .iter()
+            .find(|priority| normalize(&priority.name) == wanted)
+        {
+            return Ok(TaskPriority::named(&priority.name));
+        }
+
+        let shorthand = match wanted.as_str() {
+            "l" => Some(TaskPriority::Low),
+            "m" | "med" => Some(TaskPriority::Medium),
+            "h" => Some(TaskPriority::High),
+            "u" | "crit" | "critical" => Some(TaskPriority::Urgent),
+            _ => None,
+        };
+        match shorthand {
+            Some(priority) if configured.iter().any(|p| p.name == priority.to_string()) => {
+                Ok(priority)
+            }
             _ => Err(anyhow::anyhow!(
-                "Invalid priority. Must be one of: low, medium, high, urgent"
+                "Invalid priority. Must be one of: {}",
+                configured
+                    .iter()
+                    .map(|priority| priority.name.to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(", ")
             )),
         }
     }
@@ -183,6 +613,19 @@ impl std::fmt::Display for TaskPriority {
             TaskPriority::Medium => write!(f, "Medium"),
             TaskPriority::High => write!(f, "High"),
             TaskPriority::Urgent => write!(f, "Urgent"),
+            TaskPriority::Custom(name) => write!(f, "{}", name),
         }
     }
 }
+
+impl Serialize for TaskPriority {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskPriority {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(TaskPriority::named(&String::deserialize(deserializer)?))
+    }
+}
@@ -0,0 +1,107 @@
+//! Project templates: the shape of a project saved under a name, for starting
+//! projects that follow the same pattern.
+
+use super::project::{Milestone, Project};
+use super::task::{Task, TaskPriority, TaskStatus};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a new project starts with: a description skeleton, tags, milestones,
+/// and starter tasks, without anything tied to one project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub milestones: Vec<TemplateMilestone>,
+    #[serde(default)]
+    pub tasks: Vec<TemplateTask>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateMilestone {
+    pub title: String,
+    /// Days from the start of the project until the milestone is due
+    pub due_after_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: TaskPriority,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl ProjectTemplate {
+    /// A template named `name` with the description, tags, and milestones of
+    /// `project`, and its tasks among `tasks` as starter tasks. Cancelled
+    /// tasks are left out, and milestone due dates become days from the
+    /// project's creation.
+    pub fn from_project(name: String, project: &Project, tasks: &[Task]) -> Self {
+        Self {
+            name,
+            description: project.description.clone(),
+            tags: project.tags.clone(),
+            milestones: project
+                .milestones
+                .iter()
+                .map(|milestone| TemplateMilestone {
+                    title: milestone.title.clone(),
+                    due_after_days: milestone
+                        .due_date
+                        .map(|due| (due - project.created_at).num_days().max(0)),
+                })
+                .collect(),
+            tasks: tasks
+                .iter()
+                .filter(|task| {
+                    task.project_id == Some(project.id) && task.status != TaskStatus::Cancelled
+                })
+                .map(|task| TemplateTask {
+                    title: task.title.clone(),
+                    description: task.description.clone(),
+                    priority: task.priority.clone(),
+                    tags: task.tags.clone(),
+                })
+                .collect(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// A new project titled `title` built from the template, with its starter
+    /// tasks. Milestone due dates count from now.
+    pub fn instantiate(&self, title: String) -> (Project, Vec<Task>) {
+        let mut project = Project::new(title).with_tags(self.tags.clone());
+        if let Some(description) = &self.description {
+            project = project.with_description(description.clone());
+        }
+        for milestone in &self.milestones {
+            let mut added = Milestone::new(milestone.title.clone());
+            if let Some(days) = milestone.due_after_days {
+                added = added.with_due_date(project.created_at + Duration::days(days));
+            }
+            project.add_milestone(added, None);
+        }
+
+        let tasks = self
+            .tasks
+            .iter()
+            .map(|task| {
+                let mut added = Task::new(task.title.clone())
+                    .with_priority(task.priority.clone())
+                    .with_tags(task.tags.clone())
+                    .with_project(project.id);
+                if let Some(description) = &task.description {
+                    added = added.with_description(description.clone());
+                }
+                added
+            })
+            .collect();
+        (project, tasks)
+    }
+}
@@ -0,0 +1,78 @@
+//! How list, show, and search commands print their results: for people
+//! (`table`, the default) or as JSON or YAML for scripts, chosen once per
+//! process with `--format` or the `default_format` setting. `--quiet`
+//! leaves out the messages meant for people, so scripts get only the lines
+//! they need, such as the ID of a new item.
+
+use crate::export::{records, yaml};
+use crate::models::config::OutputFormat;
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+static QUIET: OnceLock<bool> = OnceLock::new();
+
+/// `println!`, unless `--quiet` was given. For confirmations, hints, and
+/// other chatter that scripts don't need.
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Use `format` for the rest of the process.
+pub fn set_format(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+pub fn format() -> OutputFormat {
+    FORMAT.get().cloned().unwrap_or_default()
+}
+
+/// Print only what scripts need for the rest of the process.
+pub fn set_quiet(quiet: bool) {
+    let _ = QUIET.set(quiet);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.get().copied().unwrap_or(false)
+}
+
+/// Whether results are printed for people rather than scripts.
+pub fn is_table() -> bool {
+    format() == OutputFormat::Table
+}
+
+/// Print `items` as a JSON array or YAML sequence, with each item's fields
+/// in `order` first.
+pub fn print_list<T: Serialize>(items: &[T], order: &[&str]) -> Result<()> {
+    match format() {
+        OutputFormat::Yaml => print!("{}", yaml::render(&records(items, order)?)),
+        _ => println!("{}", serde_json::to_string_pretty(items)?),
+    }
+    Ok(())
+}
+
+/// Print one `item` as a JSON object or YAML mapping.
+pub fn print_item<T: Serialize>(item: &T, order: &[&str]) -> Result<()> {
+    match format() {
+        OutputFormat::Yaml => {
+            let record = records(std::slice::from_ref(item), order)?;
+            print!("{}", yaml::render_mapping(&record[0]));
+        }
+        _ => println!("{}", serde_json::to_string_pretty(item)?),
+    }
+    Ok(())
+}
+
+/// Print one full ID per line, as lists do in quiet mode.
+pub fn print_ids(ids: impl IntoIterator<Item = Uuid>) {
+    for id in ids {
+        println!("{}", id);
+    }
+}
@@ -0,0 +1,247 @@
+//! Whole-vault archives for offsite backup.
+//!
+//! An archive is a zstd-compressed tar of everything in the data directory
+//! (data files, backups, history) except the lock file, plus the config file
+//! from the platform config directory as [`CONFIG_ENTRY`]. An
+//! encrypted archive wraps that in ChaCha20-Poly1305 under a key derived from
+//! a passphrase with Argon2id:
+//!
+//! ```text
+//! "IVARCH01" | salt (16 bytes) | nonce (12 bytes) | ciphertext + tag
+//! ```
+//!
+//! The magic and salt are authenticated too, so a wrong passphrase and a
+//! tampered file are both refused before anything is restored.
+
+use super::LOCK_FILE_NAME;
+use anyhow::{Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the archive passphrase, for scripts.
+pub const PASSPHRASE_ENV: &str = "IDEAVAULT_ARCHIVE_PASSPHRASE";
+
+/// Where the config file is stored in an archive.
+pub const CONFIG_ENTRY: &str = "config/config.json";
+
+const MAGIC: &[u8; 8] = b"IVARCH01";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const ZSTD_LEVEL: i32 = 19;
+
+/// Pack the data directory, and the config file at `config` if there is one,
+/// into an archive at `out`, encrypted if a passphrase is given. Returns the
+/// archived paths, relative to `data_dir`.
+pub fn write(
+    data_dir: &Path,
+    config: Option<&Path>,
+    out: &Path,
+    passphrase: Option<&str>,
+) -> Result<Vec<String>> {
+    let out_file = out.canonicalize().ok();
+    let mut files = Vec::new();
+    collect_files(data_dir, data_dir, &mut files)?;
+    // An archive written into the data directory mustn't contain itself
+    if let Some(out_file) = out_file {
+        files.retain(|file| file.canonicalize().ok().as_ref() != Some(&out_file));
+    }
+
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut archived = Vec::new();
+    for file in &files {
+        let name = relative_name(data_dir, file)?;
+        builder
+            .append_path_with_name(file, &name)
+            .with_context(|| format!("Failed to archive {:?}", file))?;
+        archived.push(name);
+    }
+    if let Some(config) = config.filter(|config| config.is_file()) {
+        builder
+            .append_path_with_name(config, CONFIG_ENTRY)
+            .with_context(|| format!("Failed to archive {:?}", config))?;
+        archived.push(CONFIG_ENTRY.to_string());
+    }
+    let tarball = builder.into_inner().context("Failed to build archive")?;
+
+    let compressed =
+        zstd::encode_all(tarball.as_slice(), ZSTD_LEVEL).context("Failed to compress archive")?;
+    let content = match passphrase {
+        Some(passphrase) => encrypt(&compressed, passphrase)?,
+        None => compressed,
+    };
+    fs::write(out, content).with_context(|| format!("Failed to write archive: {:?}", out))?;
+    Ok(archived)
+}
+
+/// Whether the archive at `path` needs a passphrase to open.
+pub fn is_encrypted(path: &Path) -> Result<bool> {
+    let mut magic = [0u8; MAGIC.len()];
+    let mut file =
+        fs::File::open(path).with_context(|| format!("Failed to open archive: {:?}", path))?;
+    let read = file.read(&mut magic)?;
+    Ok(read == MAGIC.len() && &magic == MAGIC)
+}
+
+/// The paths stored in the archive at `path`.
+pub fn list(path: &Path, passphrase: Option<&str>) -> Result<Vec<String>> {
+    let tarball = open(path, passphrase)?;
+    entries(&tarball)
+}
+
+/// Replace the contents of `data_dir` with the archive at `path`, writing its
+/// config file, if it has one, to `config`. The archive is fully read and
+/// checked before anything is removed. Returns the restored paths.
+pub fn restore(
+    path: &Path,
+    data_dir: &Path,
+    config: Option<&Path>,
+    passphrase: Option<&str>,
+) -> Result<Vec<String>> {
+    let tarball = open(path, passphrase)?;
+    let names = entries(&tarball)?;
+
+    for entry in fs::read_dir(data_dir).with_context(|| format!("Failed to read {:?}", data_dir))? {
+        let entry = entry?;
+        if entry.file_name() == LOCK_FILE_NAME {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        }
+        .with_context(|| format!("Failed to remove {:?}", path))?;
+    }
+
+    let mut archive = tar::Archive::new(tarball.as_slice());
+    archive.set_preserve_mtime(true);
+    for entry in archive.entries().context("Failed to read archive")? {
+        let mut entry = entry.context("Failed to read archive")?;
+        let is_config = entry
+            .path()
+            .is_ok_and(|name| name == Path::new(CONFIG_ENTRY));
+        match config {
+            Some(config) if is_config => {
+                if let Some(dir) = config.parent() {
+                    fs::create_dir_all(dir)
+                        .with_context(|| format!("Failed to create {:?}", dir))?;
+                }
+                entry
+                    .unpack(config)
+                    .with_context(|| format!("Failed to restore {:?}", config))?;
+            }
+            None if is_config => {}
+            _ => {
+                entry
+                    .unpack_in(data_dir)
+                    .with_context(|| format!("Failed to restore into {:?}", data_dir))?;
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// The decrypted, decompressed tar stream of the archive at `path`.
+fn open(path: &Path, passphrase: Option<&str>) -> Result<Vec<u8>> {
+    let content = fs::read(path).with_context(|| format!("Failed to read archive: {:?}", path))?;
+    let compressed = if content.starts_with(MAGIC) {
+        let passphrase = passphrase.ok_or_else(|| {
+            anyhow::anyhow!("Archive {:?} is encrypted; a passphrase is needed", path)
+        })?;
+        decrypt(&content, passphrase)?
+    } else {
+        content
+    };
+    zstd::decode_all(compressed.as_slice())
+        .with_context(|| format!("{:?} is not an ideavault archive", path))
+}
+
+fn entries(tarball: &[u8]) -> Result<Vec<String>> {
+    let mut archive = tar::Archive::new(tarball);
+    let mut names = Vec::new();
+    for entry in archive.entries().context("Failed to read archive")? {
+        let entry = entry.context("Failed to read archive")?;
+        let name = entry.path().context("Invalid path in archive")?;
+        names.push(name.to_string_lossy().replace('\\', "/"));
+    }
+    Ok(names)
+}
+
+fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let mut header = MAGIC.to_vec();
+    header.extend_from_slice(&salt);
+    let ciphertext = cipher(passphrase, &salt)?
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &header,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt archive"))?;
+
+    let mut content = header;
+    content.extend_from_slice(&nonce);
+    content.extend_from_slice(&ciphertext);
+    Ok(content)
+}
+
+fn decrypt(content: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let header_len = MAGIC.len() + SALT_LEN;
+    if content.len() < header_len + NONCE_LEN {
+        anyhow::bail!("Encrypted archive is truncated");
+    }
+    let (header, rest) = content.split_at(header_len);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    cipher(passphrase, &header[MAGIC.len()..])?
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase, or the archive is damaged"))
+}
+
+fn cipher(passphrase: &str, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| anyhow::anyhow!("Failed to derive archive key: {}", err))?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+fn relative_name(root: &Path, file: &Path) -> Result<String> {
+    let relative = file
+        .strip_prefix(root)
+        .with_context(|| format!("{:?} is outside the data directory", file))?;
+    Ok(relative.to_string_lossy().replace('\\', "/"))
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {:?}", dir))?
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if file_type.is_file() && !(dir == root && entry.file_name() == LOCK_FILE_NAME) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use anyhow::Result;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Persistence for the vault's entity collections.
+///
+/// Implement this to plug a different store (database, remote API, in-memory
+/// fixtures) into [`Storage`](super::Storage); every command works through it.
+/// Each `save_*s` call replaces the whole collection for that entity type.
+///
+/// The single-entity methods (`load_task`, `save_task`, `delete_task`, ...) fall
+/// back to rewriting the whole collection; backends that can address entities
+/// individually override them so one change costs one write.
+pub trait StorageBackend {
+    fn load_ideas(&self) -> Result<Vec<Idea>>;
+    fn save_ideas(&self, ideas: &[Idea]) -> Result<()>;
+
+    fn load_projects(&self) -> Result<Vec<Project>>;
+    fn save_projects(&self, projects: &[Project]) -> Result<()>;
+
+    fn load_tags(&self) -> Result<Vec<Tag>>;
+    fn save_tags(&self, tags: &[Tag]) -> Result<()>;
+
+    fn load_tasks(&self) -> Result<Vec<Task>>;
+    fn save_tasks(&self, tasks: &[Task]) -> Result<()>;
+
+    fn load_idea(&self, id: Uuid) -> Result<Option<Idea>> {
+        Ok(self.load_ideas()?.into_iter().find(|i| i.id == id))
+    }
+
+    /// Insert `idea`, or replace the stored idea with the same ID.
+    fn save_idea(&self, idea: &Idea) -> Result<()> {
+        let mut ideas = self.load_ideas()?;
+        match ideas.iter_mut().find(|i| i.id == idea.id) {
+            Some(existing) => *existing = idea.clone(),
+            None => ideas.push(idea.clone()),
+        }
+        self.save_ideas(&ideas)
+    }
+
+    /// Remove the idea with `id`, returning whether it existed.
+    fn delete_idea(&self, id: Uuid) -> Result<bool> {
+        let mut ideas = self.load_ideas()?;
+        let before = ideas.len();
+        ideas.retain(|i| i.id != id);
+        if ideas.len() == before {
+            return Ok(false);
+        }
+        self.save_ideas(&ideas)?;
+        Ok(true)
+    }
+
+    fn load_project(&self, id: Uuid) -> Result<Option<Project>> {
+        Ok(self.load_projects()?.into_iter().find(|p| p.id == id))
+    }
+
+    /// Insert `project`, or replace the stored project with the same ID.
+    fn save_project(&self, project: &Project) -> Result<()> {
+        let mut projects = self.load_projects()?;
+        match projects.iter_mut().find(|p| p.id == project.id) {
+            Some(existing) => *existing = project.clone(),
+            None => projects.push(project.clone()),
+        }
+        self.save_projects(&projects)
+    }
+
+    /// Remove the project with `id`, returning whether it existed.
+    fn delete_project(&self, id: Uuid) -> Result<bool> {
+        let mut projects = self.load_projects()?;
+        let before = projects.len();
+        projects.retain(|p| p.id != id);
+        if projects.len() == before {
+            return Ok(false);
+        }
+        self.save_projects(&projects)?;
+        Ok(true)
+    }
+
+    fn load_task(&self, id: Uuid) -> Result<Option<Task>> {
+        Ok(self.load_tasks()?.into_iter().find(|t| t.id == id))
+    }
+
+    /// Insert `task`, or replace the stored task with the same ID.
+    fn save_task(&self, task: &Task) -> Result<()> {
+        let mut tasks = self.load_tasks()?;
+        match tasks.iter_mut().find(|t| t.id == task.id) {
+            Some(existing) => *existing = task.clone(),
+            None => tasks.push(task.clone()),
+        }
+        self.save_tasks(&tasks)
+    }
+
+    /// Remove the task with `id`, returning whether it existed.
+    fn delete_task(&self, id: Uuid) -> Result<bool> {
+        let mut tasks = self.load_tasks()?;
+        let before = tasks.len();
+        tasks.retain(|t| t.id != id);
+        if tasks.len() == before {
+            return Ok(false);
+        }
+        self.save_tasks(&tasks)?;
+        Ok(true)
+    }
+
+    /// Files on disk that hold this backend's data, used for backups.
+    /// Backends that don't live on the local filesystem return nothing.
+    fn data_files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Fold a log of changes into a snapshot, returning how many changes were
+    /// folded in. Backends that don't keep a log return `None`.
+    fn compact(&self) -> Result<Option<usize>> {
+        Ok(None)
+    }
+}
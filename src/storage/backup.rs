@@ -0,0 +1,169 @@
+//! Snapshots of the vault's data files.
+//!
+//! Each backup is a directory under `<data_dir>/backups/` named after the time it
+//! was taken, containing plain copies of the backend's data files at the same
+//! paths they have relative to the data directory.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BACKUP_PREFIX: &str = "backup-";
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+const LAST_BACKUP_FILE: &str = ".last_backup";
+
+/// A snapshot found in the backup directory.
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    /// Paths relative to the snapshot (and data) directory, `/`-separated
+    pub files: Vec<String>,
+}
+
+/// Copy `files` into a new snapshot directory under `backup_dir`, keeping their
+/// paths relative to `root`. Files that don't exist yet are skipped.
+pub fn create_backup(backup_dir: &Path, root: &Path, files: &[PathBuf]) -> Result<BackupInfo> {
+    fs::create_dir_all(backup_dir)
+        .with_context(|| format!("Failed to create backup directory: {:?}", backup_dir))?;
+
+    let now = Utc::now();
+    let base = format!("{}{}", BACKUP_PREFIX, now.format(BACKUP_TIME_FORMAT));
+    // Suffix past every snapshot from the same millisecond, including ones
+    // whose siblings were pruned, so the new one always sorts last
+    let last_suffix = fs::read_dir(backup_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            match file_name.strip_prefix(&base)? {
+                "" => Some(0),
+                rest => rest.strip_prefix('-')?.parse::<u32>().ok(),
+            }
+        })
+        .max();
+    let name = match last_suffix {
+        Some(suffix) => format!("{}-{}", base, suffix + 1),
+        None => base,
+    };
+
+    let path = backup_dir.join(&name);
+    fs::create_dir(&path).with_context(|| format!("Failed to create backup: {:?}", path))?;
+
+    for file in files.iter().filter(|f| f.is_file()) {
+        let relative = match file.strip_prefix(root) {
+            Ok(relative) => relative,
+            Err(_) => Path::new(
+                file.file_name()
+                    .with_context(|| format!("Invalid data file path: {:?}", file))?,
+            ),
+        };
+        let target = path.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(file, &target).with_context(|| format!("Failed to back up {:?}", file))?;
+    }
+
+    fs::write(backup_dir.join(LAST_BACKUP_FILE), now.to_rfc3339())
+        .with_context(|| "Failed to record last backup time")?;
+
+    read_backup(&path)?.with_context(|| format!("Backup {:?} disappeared", path))
+}
+
+/// All snapshots in `backup_dir`, oldest first.
+pub fn list_backups(backup_dir: &Path) -> Result<Vec<BackupInfo>> {
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(backup_dir)
+        .with_context(|| format!("Failed to read backup directory: {:?}", backup_dir))?
+    {
+        if let Some(info) = read_backup(&entry?.path())? {
+            backups.push(info);
+        }
+    }
+
+    // Shorter names first so "-9" sorts before "-10" within a millisecond
+    backups.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then(a.name.len().cmp(&b.name.len()))
+            .then(a.name.cmp(&b.name))
+    });
+    Ok(backups)
+}
+
+/// Delete the oldest snapshots so that at most `keep` remain. Returns the removed ones.
+pub fn prune_backups(backup_dir: &Path, keep: usize) -> Result<Vec<BackupInfo>> {
+    let backups = list_backups(backup_dir)?;
+    let excess = backups.len().saturating_sub(keep);
+
+    let removed: Vec<BackupInfo> = backups.into_iter().take(excess).collect();
+    for backup in &removed {
+        fs::remove_dir_all(&backup.path)
+            .with_context(|| format!("Failed to remove backup: {:?}", backup.path))?;
+    }
+
+    Ok(removed)
+}
+
+/// When the last snapshot was taken, if ever.
+pub fn last_backup_time(backup_dir: &Path) -> Option<DateTime<Utc>> {
+    let recorded = fs::read_to_string(backup_dir.join(LAST_BACKUP_FILE)).ok()?;
+    DateTime::parse_from_rfc3339(recorded.trim())
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn read_backup(path: &Path) -> Result<Option<BackupInfo>> {
+    let name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) if path.is_dir() && name.starts_with(BACKUP_PREFIX) => name.to_string(),
+        _ => return Ok(None),
+    };
+
+    // Names may carry a "-N" suffix when two snapshots land in the same millisecond
+    let stamp = &name[BACKUP_PREFIX.len()..];
+    let stamp = stamp.get(..19).unwrap_or(stamp);
+    let created_at = match NaiveDateTime::parse_from_str(stamp, BACKUP_TIME_FORMAT) {
+        Ok(naive) => DateTime::from_naive_utc_and_offset(naive, Utc),
+        Err(_) => return Ok(None),
+    };
+
+    let mut size_bytes = 0;
+    let mut files = Vec::new();
+    collect_files(path, "", &mut size_bytes, &mut files)?;
+    files.sort();
+
+    Ok(Some(BackupInfo {
+        name,
+        path: path.to_path_buf(),
+        created_at,
+        size_bytes,
+        files,
+    }))
+}
+
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    size_bytes: &mut u64,
+    files: &mut Vec<String>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if metadata.is_dir() {
+            collect_files(&entry.path(), &format!("{}/", name), size_bytes, files)?;
+        } else if metadata.is_file() {
+            *size_bytes += metadata.len();
+            files.push(name);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Transparent compression of storage files.
+//!
+//! Files keep their usual names whatever the compression; the format is
+//! recognised from the leading magic bytes, so plain JSON written by older
+//! versions (or by hand) still loads.
+
+use crate::models::config::Compression;
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use std::io::{Read, Write};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const ZSTD_LEVEL: i32 = 3;
+
+/// Work out how `bytes` were compressed.
+pub fn detect(bytes: &[u8]) -> Compression {
+    if bytes.starts_with(&GZIP_MAGIC) {
+        Compression::Gzip
+    } else if bytes.starts_with(&ZSTD_MAGIC) {
+        Compression::Zstd
+    } else {
+        Compression::None
+    }
+}
+
+/// Compress `bytes` with `compression`.
+pub fn encode(bytes: &[u8], compression: Compression) -> Result<Vec<u8>> {
+    match compression {
+        Compression::None => Ok(bytes.to_vec()),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(bytes).context("Failed to gzip data")?;
+            encoder.finish().context("Failed to gzip data")
+        }
+        Compression::Zstd => zstd::encode_all(bytes, ZSTD_LEVEL).context("Failed to zstd data"),
+    }
+}
+
+/// Decompress `bytes`, whichever supported format they are in.
+pub fn decode(bytes: &[u8]) -> Result<Vec<u8>> {
+    match detect(bytes) {
+        Compression::None => Ok(bytes.to_vec()),
+        Compression::Gzip => {
+            let mut decoded = Vec::new();
+            GzDecoder::new(bytes)
+                .read_to_end(&mut decoded)
+                .context("Failed to decompress gzip data")?;
+            Ok(decoded)
+        }
+        Compression::Zstd => zstd::decode_all(bytes).context("Failed to decompress zstd data"),
+    }
+}
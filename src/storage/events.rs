@@ -0,0 +1,395 @@
+//! Event-log backend: every change appends one line to a log instead of
+//! rewriting a collection, and the log is folded into a snapshot from time to
+//! time.
+//!
+//! ```text
+//! <data_dir>/
+//!   .layout          marks the vault as event-sourced
+//!   snapshot.json    every collection as of the last compaction
+//!   events.jsonl     one change per line since then
+//! ```
+//!
+//! Loading replays the log over the snapshot. Once the log holds
+//! [`DEFAULT_COMPACT_AFTER`] events it is compacted automatically.
+
+use super::json::write_atomic;
+use super::{compression, Layout, StorageBackend, LAYOUT_FILE};
+use crate::models::config::Compression;
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// How many events the log may hold before it is compacted.
+pub const DEFAULT_COMPACT_AFTER: usize = 1000;
+
+/// Everything in the vault at one point in time.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Snapshot {
+    #[serde(default)]
+    ideas: Vec<Idea>,
+    #[serde(default)]
+    projects: Vec<Project>,
+    #[serde(default)]
+    tags: Vec<Tag>,
+    #[serde(default)]
+    tasks: Vec<Task>,
+}
+
+/// One line of the log.
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    at: DateTime<Utc>,
+    #[serde(flatten)]
+    event: Event,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Event {
+    PutIdea { idea: Idea },
+    DeleteIdea { id: Uuid },
+    PutProject { project: Project },
+    DeleteProject { id: Uuid },
+    PutTag { tag: Tag },
+    DeleteTag { name: String },
+    PutTask { task: Task },
+    DeleteTask { id: Uuid },
+}
+
+impl Snapshot {
+    fn apply(&mut self, event: Event) {
+        match event {
+            Event::PutIdea { idea } => put(&mut self.ideas, idea, |i| i.id),
+            Event::DeleteIdea { id } => self.ideas.retain(|i| i.id != id),
+            Event::PutProject { project } => put(&mut self.projects, project, |p| p.id),
+            Event::DeleteProject { id } => self.projects.retain(|p| p.id != id),
+            Event::PutTag { tag } => put(&mut self.tags, tag, |t| t.name.clone()),
+            Event::DeleteTag { name } => self.tags.retain(|t| t.name != name),
+            Event::PutTask { task } => put(&mut self.tasks, task, |t| t.id),
+            Event::DeleteTask { id } => self.tasks.retain(|t| t.id != id),
+        }
+    }
+}
+
+/// Replace the item with the same key, or append it.
+fn put<T, K: PartialEq>(items: &mut Vec<T>, item: T, key: impl Fn(&T) -> K) {
+    match items
+        .iter_mut()
+        .find(|existing| key(existing) == key(&item))
+    {
+        Some(existing) => *existing = item,
+        None => items.push(item),
+    }
+}
+
+/// The events turning `before` into `after`: a put for every new or changed
+/// item and a delete for every item that is gone.
+fn diff<T: Serialize + Clone, K: PartialEq>(
+    before: &[T],
+    after: &[T],
+    key: impl Fn(&T) -> K,
+    on_put: impl Fn(T) -> Event,
+    on_delete: impl Fn(K) -> Event,
+) -> Vec<Event> {
+    let mut events = Vec::new();
+    for item in after {
+        let unchanged = before.iter().any(|old| {
+            key(old) == key(item)
+                && serde_json::to_value(old).ok() == serde_json::to_value(item).ok()
+        });
+        if !unchanged {
+            events.push(on_put(item.clone()));
+        }
+    }
+    for old in before {
+        if !after.iter().any(|item| key(item) == key(old)) {
+            events.push(on_delete(key(old)));
+        }
+    }
+    events
+}
+
+pub struct EventLogBackend {
+    snapshot_file: PathBuf,
+    events_file: PathBuf,
+    layout_file: PathBuf,
+    compression: Compression,
+    compact_after: usize,
+}
+
+impl EventLogBackend {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            snapshot_file: data_dir.join("snapshot.json"),
+            events_file: data_dir.join("events.jsonl"),
+            layout_file: data_dir.join(LAYOUT_FILE),
+            compression: Compression::None,
+            compact_after: DEFAULT_COMPACT_AFTER,
+        }
+    }
+
+    /// Compress the snapshot with `compression` when it is next written.
+    /// The log itself stays plain text so it can be appended to.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Compact automatically once the log holds `events` events.
+    pub fn with_compact_after(mut self, events: usize) -> Self {
+        self.compact_after = events;
+        self
+    }
+
+    fn read_snapshot(&self) -> Result<Snapshot> {
+        let content = match fs::read(&self.snapshot_file) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Snapshot::default()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read snapshot: {:?}", self.snapshot_file))
+            }
+        };
+        tracing::debug!(path = ?self.snapshot_file, bytes = content.len(), "read snapshot");
+        let content = compression::decode(&content)
+            .with_context(|| format!("Failed to read snapshot: {:?}", self.snapshot_file))?;
+        serde_json::from_slice(&content).context("Failed to parse snapshot JSON")
+    }
+
+    fn read_events(&self) -> Result<Vec<Event>> {
+        let content = match fs::read_to_string(&self.events_file) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read event log: {:?}", self.events_file))
+            }
+        };
+
+        let mut events = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Record>(line) {
+                Ok(record) => events.push(record.event),
+                // A crash mid-append leaves a truncated last line; the change was never saved
+                Err(_) if index + 1 == lines.len() && !content.ends_with('\n') => {}
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!("Failed to parse line {} of the event log", index + 1)
+                    })
+                }
+            }
+        }
+        tracing::debug!(path = ?self.events_file, events = events.len(), "read event log");
+        Ok(events)
+    }
+
+    /// The current state: the snapshot with the log replayed over it.
+    fn state(&self) -> Result<Snapshot> {
+        let mut state = self.read_snapshot()?;
+        for event in self.read_events()? {
+            state.apply(event);
+        }
+        Ok(state)
+    }
+
+    fn mark_layout(&self) -> Result<()> {
+        Layout::Events.mark(&self.layout_file)
+    }
+
+    fn append(&self, events: Vec<Event>) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.mark_layout()?;
+
+        let at = Utc::now();
+        let count = events.len();
+        let mut lines = String::new();
+        for event in events {
+            let line = serde_json::to_string(&Record { at, event })
+                .context("Failed to serialize event")?;
+            lines.push_str(&line);
+            lines.push('\n');
+        }
+
+        let existing = self.read_log()?;
+        let complete = existing
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |end| end + 1);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.events_file)
+            .with_context(|| format!("Failed to open event log: {:?}", self.events_file))?;
+        // Drop a line left half-written by a crash so the new events start on their own line
+        if complete < existing.len() {
+            file.set_len(complete as u64)
+                .with_context(|| format!("Failed to repair event log: {:?}", self.events_file))?;
+        }
+        file.write_all(lines.as_bytes())
+            .with_context(|| format!("Failed to append to event log: {:?}", self.events_file))?;
+        file.sync_data()
+            .with_context(|| format!("Failed to sync event log: {:?}", self.events_file))?;
+        drop(file);
+        tracing::debug!(path = ?self.events_file, events = count, "appended to event log");
+
+        let logged = existing[..complete].iter().filter(|&&b| b == b'\n').count();
+        if logged + count >= self.compact_after {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    fn read_log(&self) -> Result<Vec<u8>> {
+        match fs::read(&self.events_file) {
+            Ok(content) => Ok(content),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err)
+                .with_context(|| format!("Failed to read event log: {:?}", self.events_file)),
+        }
+    }
+}
+
+impl StorageBackend for EventLogBackend {
+    fn load_ideas(&self) -> Result<Vec<Idea>> {
+        Ok(self.state()?.ideas)
+    }
+
+    fn save_ideas(&self, ideas: &[Idea]) -> Result<()> {
+        let before = self.load_ideas()?;
+        self.append(diff(
+            &before,
+            ideas,
+            |i| i.id,
+            |idea| Event::PutIdea { idea },
+            |id| Event::DeleteIdea { id },
+        ))
+    }
+
+    fn load_projects(&self) -> Result<Vec<Project>> {
+        Ok(self.state()?.projects)
+    }
+
+    fn save_projects(&self, projects: &[Project]) -> Result<()> {
+        let before = self.load_projects()?;
+        self.append(diff(
+            &before,
+            projects,
+            |p| p.id,
+            |project| Event::PutProject { project },
+            |id| Event::DeleteProject { id },
+        ))
+    }
+
+    fn load_tags(&self) -> Result<Vec<Tag>> {
+        Ok(self.state()?.tags)
+    }
+
+    fn save_tags(&self, tags: &[Tag]) -> Result<()> {
+        let before = self.load_tags()?;
+        self.append(diff(
+            &before,
+            tags,
+            |t| t.name.clone(),
+            |tag| Event::PutTag { tag },
+            |name| Event::DeleteTag { name },
+        ))
+    }
+
+    fn load_tasks(&self) -> Result<Vec<Task>> {
+        Ok(self.state()?.tasks)
+    }
+
+    fn save_tasks(&self, tasks: &[Task]) -> Result<()> {
+        let before = self.load_tasks()?;
+        self.append(diff(
+            &before,
+            tasks,
+            |t| t.id,
+            |task| Event::PutTask { task },
+            |id| Event::DeleteTask { id },
+        ))
+    }
+
+    fn save_idea(&self, idea: &Idea) -> Result<()> {
+        self.append(vec![Event::PutIdea { idea: idea.clone() }])
+    }
+
+    fn delete_idea(&self, id: Uuid) -> Result<bool> {
+        if self.load_idea(id)?.is_none() {
+            return Ok(false);
+        }
+        self.append(vec![Event::DeleteIdea { id }])?;
+        Ok(true)
+    }
+
+    fn save_project(&self, project: &Project) -> Result<()> {
+        self.append(vec![Event::PutProject {
+            project: project.clone(),
+        }])
+    }
+
+    fn delete_project(&self, id: Uuid) -> Result<bool> {
+        if self.load_project(id)?.is_none() {
+            return Ok(false);
+        }
+        self.append(vec![Event::DeleteProject { id }])?;
+        Ok(true)
+    }
+
+    fn save_task(&self, task: &Task) -> Result<()> {
+        self.append(vec![Event::PutTask { task: task.clone() }])
+    }
+
+    fn delete_task(&self, id: Uuid) -> Result<bool> {
+        if self.load_task(id)?.is_none() {
+            return Ok(false);
+        }
+        self.append(vec![Event::DeleteTask { id }])?;
+        Ok(true)
+    }
+
+    fn data_files(&self) -> Vec<PathBuf> {
+        vec![
+            self.layout_file.clone(),
+            self.snapshot_file.clone(),
+            self.events_file.clone(),
+        ]
+    }
+
+    /// Write the replayed state as the new snapshot and start an empty log.
+    /// If interrupted before the log is removed, replaying it again over the
+    /// new snapshot gives the same state, so nothing is lost.
+    fn compact(&self) -> Result<Option<usize>> {
+        self.mark_layout()?;
+        let events = self.read_log()?.iter().filter(|&&b| b == b'\n').count();
+        if events == 0 {
+            return Ok(Some(0));
+        }
+
+        let state = self.state()?;
+        let content =
+            serde_json::to_vec_pretty(&state).context("Failed to serialize snapshot to JSON")?;
+        let content = compression::encode(&content, self.compression)?;
+        write_atomic(&self.snapshot_file, &content)
+            .with_context(|| format!("Failed to write snapshot: {:?}", self.snapshot_file))?;
+
+        fs::remove_file(&self.events_file)
+            .with_context(|| format!("Failed to clear event log: {:?}", self.events_file))?;
+        Ok(Some(events))
+    }
+}
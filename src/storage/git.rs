@@ -0,0 +1,385 @@
+//! Optional git history for the data directory.
+//!
+//! When the data directory is a git repository (see [`init`]), every change
+//! saved through [`Storage`](super::Storage) is committed with a message
+//! describing it, such as `task status a1b2c3d4: Todo → Done`. This relies on
+//! the `git` executable being on `PATH`. With a remote configured, vaults on
+//! several machines can be kept in step with [`pull`] and [`push`]; data files
+//! both machines changed are merged with [`merge`] rather than by git.
+
+use super::history::{Action, EntityKind, FieldChange, HistoryEntry};
+use super::json::write_atomic;
+use super::LOCK_FILE_NAME;
+use crate::sync::merge::{self, Conflict, Resolver, Side};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Fields that change on every update and would only add noise to messages
+const HIDDEN_FIELDS: [&str; 1] = ["updated_at"];
+const MAX_VALUE_LEN: usize = 40;
+
+/// Used when git has no identity configured, so commits never fail on it.
+const FALLBACK_NAME: &str = "IdeaVault";
+const FALLBACK_EMAIL: &str = "ideavault@localhost";
+
+/// The remote `ideavault sync` pulls from and pushes to.
+pub const REMOTE: &str = "origin";
+
+/// Whether `data_dir` is itself a git repository, which turns on auto-commits.
+pub fn is_enabled(data_dir: &Path) -> bool {
+    data_dir.join(".git").exists()
+}
+
+/// Make `data_dir` a git repository and commit its current contents.
+/// Returns `false` if it already was one.
+pub fn init(data_dir: &Path) -> Result<bool> {
+    if is_enabled(data_dir) {
+        return Ok(false);
+    }
+    run(data_dir, &["init", "--quiet"])?;
+
+    let gitignore = data_dir.join(".gitignore");
+    if !gitignore.exists() {
+        let content = format!("{}\n.*.tmp\nbackups/\n", LOCK_FILE_NAME);
+        fs::write(&gitignore, content)
+            .with_context(|| format!("Failed to write {:?}", gitignore))?;
+    }
+
+    // The history and event logs are append-only, so both sides' lines can simply be kept
+    let gitattributes = data_dir.join(".gitattributes");
+    if !gitattributes.exists() {
+        fs::write(
+            &gitattributes,
+            "history.jsonl merge=union\nevents.jsonl merge=union\n",
+        )
+        .with_context(|| format!("Failed to write {:?}", gitattributes))?;
+    }
+
+    commit(data_dir, "Initialize IdeaVault history")?;
+    Ok(true)
+}
+
+/// Stage everything in `data_dir` and commit it with `message`.
+/// Returns `false` if there was nothing to commit.
+pub fn commit(data_dir: &Path, message: &str) -> Result<bool> {
+    run(data_dir, &["add", "--all"])?;
+    let staged = git(data_dir, &["diff", "--cached", "--quiet"])?;
+    if staged.status.success() {
+        return Ok(false);
+    }
+
+    let mut args = identity_args(data_dir)?;
+    args.extend(["commit", "--quiet", "--no-verify", "-m", message].map(String::from));
+    run(data_dir, &args)?;
+    Ok(true)
+}
+
+/// The URL of the `origin` remote, if one is configured.
+pub fn remote_url(data_dir: &Path) -> Result<Option<String>> {
+    let output = git(data_dir, &["remote", "get-url", REMOTE])?;
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()))
+}
+
+/// Point the `origin` remote at `url`, adding it if needed.
+pub fn set_remote(data_dir: &Path, url: &str) -> Result<()> {
+    if remote_url(data_dir)?.is_some() {
+        run(data_dir, &["remote", "set-url", REMOTE, url])?;
+    } else {
+        run(data_dir, &["remote", "add", REMOTE, url])?;
+    }
+    Ok(())
+}
+
+/// Merge the current branch of `origin` into the local one. Files changed on
+/// both sides are merged entity by entity, with `resolve` deciding conflicts.
+/// Returns the number of commits brought in and the files that were merged.
+pub fn pull(data_dir: &Path, resolve: &mut Resolver) -> Result<(usize, Vec<String>)> {
+    let branch = current_branch(data_dir)?;
+    run(data_dir, &["fetch", "--quiet", REMOTE])?;
+    let upstream = format!("{}/{}", REMOTE, branch);
+    if !has_revision(data_dir, &upstream)? {
+        return Ok((0, Vec::new()));
+    }
+
+    let incoming = count(data_dir, &format!("HEAD..{}", upstream))?;
+    if incoming == 0 {
+        return Ok((0, Vec::new()));
+    }
+    let mut args = identity_args(data_dir)?;
+    args.extend(
+        [
+            "merge",
+            "--quiet",
+            "--no-edit",
+            "--allow-unrelated-histories",
+            &upstream,
+        ]
+        .map(String::from),
+    );
+    let Err(err) = run(data_dir, &args) else {
+        return Ok((incoming, Vec::new()));
+    };
+
+    let conflicts: Vec<String> = run(data_dir, &["diff", "--name-only", "--diff-filter=U"])?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    if conflicts.is_empty() {
+        return Err(err);
+    }
+    let resolved = resolve_conflicts(data_dir, &conflicts, &upstream, resolve).and_then(|_| {
+        let mut args = identity_args(data_dir)?;
+        args.extend(["commit", "--quiet", "--no-edit", "--no-verify"].map(String::from));
+        run(data_dir, &args)
+    });
+    if let Err(err) = resolved {
+        run(data_dir, &["merge", "--abort"])?;
+        return Err(err.context("Nothing was merged"));
+    }
+    Ok((incoming, conflicts))
+}
+
+/// Settle the files left conflicted by a merge with `upstream` and stage them.
+fn resolve_conflicts(
+    data_dir: &Path,
+    paths: &[String],
+    upstream: &str,
+    resolve: &mut Resolver,
+) -> Result<()> {
+    for path in paths {
+        let base = stage(data_dir, 1, path)?;
+        let ours = stage(data_dir, 2, path)?;
+        let theirs = stage(data_dir, 3, path)?;
+
+        let merged = match (&ours, &theirs) {
+            (Some(ours), Some(theirs)) => {
+                merge::merge_file(path, base.as_deref(), ours, theirs, resolve)?
+            }
+            _ => None,
+        };
+        if let Some(content) = merged {
+            write_atomic(&data_dir.join(path), &content)?;
+            run(data_dir, &["add", "--", path])?;
+            continue;
+        }
+
+        let local_updated = match ours {
+            Some(_) => last_changed(data_dir, "HEAD", path)?,
+            None => None,
+        };
+        let remote_updated = match theirs {
+            Some(_) => last_changed(data_dir, upstream, path)?,
+            None => None,
+        };
+        let conflict = Conflict::file(path, local_updated, remote_updated);
+        let (side, exists) = match resolve(&conflict)? {
+            Side::Local => ("--ours", ours.is_some()),
+            Side::Remote => ("--theirs", theirs.is_some()),
+        };
+        if exists {
+            run(data_dir, &["checkout", side, "--", path])?;
+            run(data_dir, &["add", "--", path])?;
+        } else {
+            run(data_dir, &["rm", "--quiet", "--", path])?;
+        }
+    }
+    Ok(())
+}
+
+/// The content of `path` at merge stage `number` (1: base, 2: ours, 3: theirs).
+fn stage(data_dir: &Path, number: u8, path: &str) -> Result<Option<Vec<u8>>> {
+    let output = git(data_dir, &["show", &format!(":{}:{}", number, path)])?;
+    Ok(output.status.success().then_some(output.stdout))
+}
+
+/// When `path` was last changed in the history of `revision`.
+fn last_changed(data_dir: &Path, revision: &str, path: &str) -> Result<Option<DateTime<Utc>>> {
+    let date = run(
+        data_dir,
+        &["log", "-1", "--format=%cI", revision, "--", path],
+    )?;
+    Ok(DateTime::parse_from_rfc3339(date.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc)))
+}
+
+/// Push the current branch to `origin`. Returns the number of commits sent.
+pub fn push(data_dir: &Path) -> Result<usize> {
+    let branch = current_branch(data_dir)?;
+    let upstream = format!("{}/{}", REMOTE, branch);
+    let outgoing = if has_revision(data_dir, &upstream)? {
+        count(data_dir, &format!("{}..HEAD", upstream))?
+    } else {
+        count(data_dir, "HEAD")?
+    };
+    if outgoing > 0 {
+        run(
+            data_dir,
+            &["push", "--quiet", "--set-upstream", REMOTE, &branch],
+        )?;
+    }
+    Ok(outgoing)
+}
+
+fn current_branch(data_dir: &Path) -> Result<String> {
+    Ok(run(data_dir, &["rev-parse", "--abbrev-ref", "HEAD"])?
+        .trim()
+        .to_string())
+}
+
+fn has_revision(data_dir: &Path, revision: &str) -> Result<bool> {
+    let output = git(data_dir, &["rev-parse", "--verify", "--quiet", revision])?;
+    Ok(output.status.success())
+}
+
+fn count(data_dir: &Path, range: &str) -> Result<usize> {
+    run(data_dir, &["rev-list", "--count", range])?
+        .trim()
+        .parse()
+        .context("Unexpected output from git rev-list")
+}
+
+/// The git log of `data_dir`, one commit per line, newest first.
+pub fn log(data_dir: &Path, limit: usize) -> Result<String> {
+    let limit = format!("-{}", limit);
+    run(
+        data_dir,
+        &[
+            "log",
+            &limit,
+            "--date=format:%Y-%m-%d %H:%M",
+            "--format=%h  %ad  %s",
+        ],
+    )
+}
+
+/// Describe the entries written by one save as a commit message: a subject
+/// line for the first change and, when there are several, one line per change.
+pub fn message(entries: &[HistoryEntry]) -> String {
+    let Some(first) = entries.first() else {
+        return String::new();
+    };
+    let mut subject = summary(first);
+    if entries.len() > 1 {
+        subject.push_str(&format!(" (+{} more)", entries.len() - 1));
+        let body: Vec<String> = entries
+            .iter()
+            .map(|entry| format!("- {}", summary(entry)))
+            .collect();
+        format!("{}\n\n{}", subject, body.join("\n"))
+    } else {
+        subject
+    }
+}
+
+fn summary(entry: &HistoryEntry) -> String {
+    let origin = entry.command.clone().unwrap_or_else(|| {
+        let verb = match entry.action {
+            Action::Create => "create",
+            Action::Update => "update",
+            Action::Delete => "delete",
+        };
+        format!("{} {}", entry.entity, verb)
+    });
+    let id = match entry.entity {
+        EntityKind::Tag => entry.entity_id.clone(),
+        _ => entry.entity_id.chars().take(8).collect(),
+    };
+
+    let detail = match entry.action {
+        Action::Create => format!("created {}", title(entry, |change| &change.after)),
+        Action::Delete => format!("deleted {}", title(entry, |change| &change.before)),
+        Action::Update => {
+            let changes: Vec<_> = entry
+                .changes
+                .iter()
+                .filter(|change| !HIDDEN_FIELDS.contains(&change.field.as_str()))
+                .collect();
+            match changes.as_slice() {
+                [change] => format!("{} → {}", describe(&change.before), describe(&change.after)),
+                changes => changes
+                    .iter()
+                    .map(|change| change.field.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }
+        }
+    };
+    format!("{} {}: {}", origin, id, detail)
+}
+
+/// The quoted title (or tag name) recorded in a create or delete entry.
+fn title(entry: &HistoryEntry, value: impl Fn(&FieldChange) -> &Value) -> String {
+    entry
+        .changes
+        .iter()
+        .find(|change| change.field == "title" || change.field == "name")
+        .map(|change| format!("\"{}\"", describe(value(change))))
+        .unwrap_or_else(|| entry.entity.to_string())
+}
+
+fn describe(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "(none)".to_string(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_VALUE_LEN {
+        let truncated: String = text.chars().take(MAX_VALUE_LEN - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        text
+    }
+}
+
+/// `-c` options supplying an identity when git has none configured.
+fn identity_args(data_dir: &Path) -> Result<Vec<String>> {
+    let name = git(data_dir, &["config", "user.name"])?;
+    let email = git(data_dir, &["config", "user.email"])?;
+    if name.status.success() && email.status.success() {
+        return Ok(Vec::new());
+    }
+    Ok(vec![
+        "-c".to_string(),
+        format!("user.name={}", FALLBACK_NAME),
+        "-c".to_string(),
+        format!("user.email={}", FALLBACK_EMAIL),
+    ])
+}
+
+/// Run git in `data_dir` and return its output, failing on a non-zero exit.
+fn run<S: AsRef<str>>(data_dir: &Path, args: &[S]) -> Result<String> {
+    let output = git(data_dir, args)?;
+    if !output.status.success() {
+        // Name the subcommand, skipping any leading `-c name=value` options
+        let mut args = args.iter().map(|arg| arg.as_ref());
+        let mut subcommand = args.next().unwrap_or_default();
+        while subcommand == "-c" {
+            args.next();
+            subcommand = args.next().unwrap_or_default();
+        }
+        anyhow::bail!(
+            "git {} failed: {}",
+            subcommand,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git<S: AsRef<str>>(data_dir: &Path, args: &[S]) -> Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(data_dir)
+        .args(args.iter().map(|arg| arg.as_ref()))
+        .output()
+        .context("Failed to run git; is it installed?")
+}
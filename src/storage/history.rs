@@ -0,0 +1,309 @@
+//! Append-only audit log of entity changes.
+//!
+//! Every create, update, and delete that goes through [`Storage`](super::Storage)
+//! is appended to `<data_dir>/history.jsonl` as one JSON object per line, holding
+//! the before and after value of each changed field. Entries written as part of
+//! the same operation share an operation ID, so a command that touches several
+//! entities can be identified, and undone, as a unit.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+static COMMAND: OnceLock<String> = OnceLock::new();
+
+/// Record `command` (e.g. "task status") as the origin of this process's changes.
+pub fn set_command(command: impl Into<String>) {
+    let _ = COMMAND.set(command.into());
+}
+
+/// The command recorded with [`set_command`], if any.
+pub fn command() -> Option<&'static str> {
+    COMMAND.get().map(String::as_str)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    Idea,
+    Project,
+    Tag,
+    Task,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Create,
+    Update,
+    Delete,
+}
+
+/// One field's value before and after a change (`null` when absent).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub operation_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    pub entity: EntityKind,
+    /// UUID of the entity, or the name for tags
+    pub entity_id: String,
+    pub action: Action,
+    pub changes: Vec<FieldChange>,
+    /// The operation this entry reverts, when it was written by an undo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<Uuid>,
+}
+
+/// The entries that share one operation ID, in the order they were written.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub id: Uuid,
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl Operation {
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.entries[0].timestamp
+    }
+
+    pub fn command(&self) -> Option<&str> {
+        self.entries[0].command.as_deref()
+    }
+}
+
+impl std::fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityKind::Idea => write!(f, "idea"),
+            EntityKind::Project => write!(f, "project"),
+            EntityKind::Tag => write!(f, "tag"),
+            EntityKind::Task => write!(f, "task"),
+        }
+    }
+}
+
+/// Compare two versions of a collection and describe every entity that was
+/// created, changed, or removed. `key` identifies an entity across versions.
+pub fn diff<T: Serialize>(
+    entity: EntityKind,
+    before: &[T],
+    after: &[T],
+    key: impl Fn(&T) -> String,
+    operation_id: Uuid,
+) -> Result<Vec<HistoryEntry>> {
+    let mut old: HashMap<String, Map<String, Value>> = HashMap::new();
+    for item in before {
+        old.insert(key(item), to_object(item)?);
+    }
+
+    let mut entries = Vec::new();
+    for item in after {
+        let id = key(item);
+        let new = to_object(item)?;
+        match old.remove(&id) {
+            None => entries.push(entry(
+                entity,
+                id,
+                Action::Create,
+                &Map::new(),
+                &new,
+                operation_id,
+            )),
+            Some(previous) if previous != new => entries.push(entry(
+                entity,
+                id,
+                Action::Update,
+                &previous,
+                &new,
+                operation_id,
+            )),
+            Some(_) => {}
+        }
+    }
+
+    // Whatever is left wasn't in the new version
+    let mut removed: Vec<(String, Map<String, Value>)> = old.into_iter().collect();
+    removed.sort_by(|a, b| a.0.cmp(&b.0));
+    for (id, previous) in removed {
+        entries.push(entry(
+            entity,
+            id,
+            Action::Delete,
+            &previous,
+            &Map::new(),
+            operation_id,
+        ));
+    }
+
+    Ok(entries)
+}
+
+/// Append `entries` to the log at `path`.
+pub fn append(path: &Path, entries: &[HistoryEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut content = Vec::new();
+    for entry in entries {
+        serde_json::to_writer(&mut content, entry)?;
+        content.push(b'\n');
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open history log: {:?}", path))?;
+    file.write_all(&content)
+        .with_context(|| format!("Failed to write history log: {:?}", path))?;
+    file.sync_data()
+        .with_context(|| format!("Failed to sync history log: {:?}", path))
+}
+
+/// Every entry in the log at `path`, oldest first.
+pub fn read(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read history log: {:?}", path))
+        }
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid history entry on line {}", number + 1))
+        })
+        .collect()
+}
+
+/// Operations that can still be undone, most recent first. Undo operations
+/// themselves, and operations they reverted, are left out.
+pub fn undo_stack(entries: Vec<HistoryEntry>) -> Vec<Operation> {
+    let undone: HashSet<Uuid> = entries.iter().filter_map(|entry| entry.undoes).collect();
+
+    let mut operations: Vec<Operation> = Vec::new();
+    let mut positions: HashMap<Uuid, usize> = HashMap::new();
+    for entry in entries {
+        if entry.undoes.is_some() || undone.contains(&entry.operation_id) {
+            continue;
+        }
+        match positions.get(&entry.operation_id) {
+            Some(&index) => operations[index].entries.push(entry),
+            None => {
+                positions.insert(entry.operation_id, operations.len());
+                operations.push(Operation {
+                    id: entry.operation_id,
+                    entries: vec![entry],
+                });
+            }
+        }
+    }
+
+    operations.reverse();
+    operations
+}
+
+/// The state `current` had before `entry` was applied: `None` if `entry`
+/// created it, otherwise the recorded values of the changed fields put back.
+pub fn revert<T: Serialize + DeserializeOwned>(
+    current: Option<T>,
+    entry: &HistoryEntry,
+) -> Result<Option<T>> {
+    let mut object = match (entry.action, current) {
+        (Action::Create, _) => return Ok(None),
+        (Action::Update, Some(current)) => to_object(&current)?,
+        (Action::Update, None) => anyhow::bail!(
+            "Cannot undo change to {} {}: it no longer exists",
+            entry.entity,
+            entry.entity_id
+        ),
+        (Action::Delete, None) => Map::new(),
+        (Action::Delete, Some(_)) => anyhow::bail!(
+            "Cannot restore {} {}: an entity with that ID already exists",
+            entry.entity,
+            entry.entity_id
+        ),
+    };
+
+    for change in &entry.changes {
+        match &change.before {
+            Value::Null => object.remove(&change.field),
+            value => object.insert(change.field.clone(), value.clone()),
+        };
+    }
+
+    let reverted = serde_json::from_value(Value::Object(object)).with_context(|| {
+        format!(
+            "Failed to rebuild {} {} from history",
+            entry.entity, entry.entity_id
+        )
+    })?;
+    Ok(Some(reverted))
+}
+
+fn to_object<T: Serialize>(item: &T) -> Result<Map<String, Value>> {
+    match serde_json::to_value(item).context("Failed to serialize entity for history")? {
+        Value::Object(map) => Ok(map),
+        other => anyhow::bail!("Expected an object for history, got {}", other),
+    }
+}
+
+fn entry(
+    entity: EntityKind,
+    entity_id: String,
+    action: Action,
+    before: &Map<String, Value>,
+    after: &Map<String, Value>,
+    operation_id: Uuid,
+) -> HistoryEntry {
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    let changes = fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = before.get(field).cloned().unwrap_or(Value::Null);
+            let new = after.get(field).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                before: old,
+                after: new,
+            })
+        })
+        .collect();
+
+    HistoryEntry {
+        timestamp: Utc::now(),
+        operation_id,
+        command: command().map(str::to_string),
+        entity,
+        entity_id,
+        action,
+        changes,
+        undoes: None,
+    }
+}
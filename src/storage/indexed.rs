@@ -0,0 +1,249 @@
+//! Indexed backend: one file per idea, project, and task, so changing a single
+//! entity rewrites only that entity's file.
+//!
+//! ```text
+//! <data_dir>/
+//!   .layout             marks the vault as indexed
+//!   ideas/<id>.json
+//!   projects/<id>.json
+//!   tasks/<id>.json
+//!   tags.json           tags are few and small, so they stay in one file
+//! ```
+//!
+//! Collections are returned in creation order.
+
+use super::json::{read_collection, write_atomic, write_collection};
+use super::{compression, Layout, StorageBackend, LAYOUT_FILE};
+use crate::models::config::Compression;
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use crate::models::{Identifiable, Timestamped};
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+pub struct IndexedBackend {
+    ideas_dir: PathBuf,
+    projects_dir: PathBuf,
+    tasks_dir: PathBuf,
+    tags_file: PathBuf,
+    layout_file: PathBuf,
+    compression: Compression,
+}
+
+impl IndexedBackend {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            ideas_dir: data_dir.join("ideas"),
+            projects_dir: data_dir.join("projects"),
+            tasks_dir: data_dir.join("tasks"),
+            tags_file: data_dir.join("tags.json"),
+            layout_file: data_dir.join(LAYOUT_FILE),
+            compression: Compression::None,
+        }
+    }
+
+    /// Compress files with `compression` when they are next written.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Record the layout so the vault is opened with this backend next time.
+    fn mark_layout(&self) -> Result<()> {
+        Layout::Indexed.mark(&self.layout_file)
+    }
+
+    fn write_entity<T: Serialize + Identifiable>(
+        &self,
+        dir: &Path,
+        what: &str,
+        item: &T,
+    ) -> Result<()> {
+        self.mark_layout()?;
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+
+        let content = serde_json::to_vec_pretty(item)
+            .with_context(|| format!("Failed to serialize {} to JSON", what))?;
+        let content = compression::encode(&content, self.compression)?;
+        let path = entity_path(dir, item.id());
+
+        // Bulk saves pass every entity; skip the ones that haven't changed
+        if fs::read(&path).is_ok_and(|existing| existing == content) {
+            return Ok(());
+        }
+        write_atomic(&path, &content)
+            .with_context(|| format!("Failed to write {} file: {:?}", what, path))
+    }
+
+    fn write_all<T: Serialize + Identifiable>(
+        &self,
+        dir: &Path,
+        what: &str,
+        items: &[T],
+    ) -> Result<()> {
+        for item in items {
+            self.write_entity(dir, what, item)?;
+        }
+
+        // Whatever isn't in `items` any more has been deleted
+        let keep: Vec<PathBuf> = items.iter().map(|i| entity_path(dir, i.id())).collect();
+        for path in entity_files(dir)? {
+            if !keep.contains(&path) {
+                remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StorageBackend for IndexedBackend {
+    fn load_ideas(&self) -> Result<Vec<Idea>> {
+        read_all(&self.ideas_dir, "ideas")
+    }
+
+    fn save_ideas(&self, ideas: &[Idea]) -> Result<()> {
+        self.write_all(&self.ideas_dir, "idea", ideas)
+    }
+
+    fn load_projects(&self) -> Result<Vec<Project>> {
+        read_all(&self.projects_dir, "projects")
+    }
+
+    fn save_projects(&self, projects: &[Project]) -> Result<()> {
+        self.write_all(&self.projects_dir, "project", projects)
+    }
+
+    fn load_tags(&self) -> Result<Vec<Tag>> {
+        read_collection(&self.tags_file, "tags")
+    }
+
+    fn save_tags(&self, tags: &[Tag]) -> Result<()> {
+        self.mark_layout()?;
+        write_collection(&self.tags_file, "tags", tags, self.compression)
+    }
+
+    fn load_tasks(&self) -> Result<Vec<Task>> {
+        read_all(&self.tasks_dir, "tasks")
+    }
+
+    fn save_tasks(&self, tasks: &[Task]) -> Result<()> {
+        self.write_all(&self.tasks_dir, "task", tasks)
+    }
+
+    fn load_idea(&self, id: Uuid) -> Result<Option<Idea>> {
+        read_one(&entity_path(&self.ideas_dir, id), "idea")
+    }
+
+    fn save_idea(&self, idea: &Idea) -> Result<()> {
+        self.write_entity(&self.ideas_dir, "idea", idea)
+    }
+
+    fn delete_idea(&self, id: Uuid) -> Result<bool> {
+        remove_file(&entity_path(&self.ideas_dir, id))
+    }
+
+    fn load_project(&self, id: Uuid) -> Result<Option<Project>> {
+        read_one(&entity_path(&self.projects_dir, id), "project")
+    }
+
+    fn save_project(&self, project: &Project) -> Result<()> {
+        self.write_entity(&self.projects_dir, "project", project)
+    }
+
+    fn delete_project(&self, id: Uuid) -> Result<bool> {
+        remove_file(&entity_path(&self.projects_dir, id))
+    }
+
+    fn load_task(&self, id: Uuid) -> Result<Option<Task>> {
+        read_one(&entity_path(&self.tasks_dir, id), "task")
+    }
+
+    fn save_task(&self, task: &Task) -> Result<()> {
+        self.write_entity(&self.tasks_dir, "task", task)
+    }
+
+    fn delete_task(&self, id: Uuid) -> Result<bool> {
+        remove_file(&entity_path(&self.tasks_dir, id))
+    }
+
+    fn data_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![self.layout_file.clone(), self.tags_file.clone()];
+        for dir in [&self.ideas_dir, &self.projects_dir, &self.tasks_dir] {
+            files.extend(entity_files(dir).unwrap_or_default());
+        }
+        files
+    }
+}
+
+fn entity_path(dir: &Path, id: Uuid) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+/// Entity files in `dir`, sorted by name. Temp files from interrupted writes
+/// start with a dot and are skipped.
+fn entity_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {:?}", dir)),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !name.starts_with('.') && name.ends_with(".json") && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn read_one<T: DeserializeOwned>(path: &Path, what: &str) -> Result<Option<T>> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read {} file: {:?}", what, path))
+        }
+    };
+    tracing::trace!(?path, bytes = content.len(), "read {}", what);
+    let content = compression::decode(&content)
+        .with_context(|| format!("Failed to read {} file: {:?}", what, path))?;
+
+    serde_json::from_slice(&content)
+        .map(Some)
+        .with_context(|| format!("Failed to parse {} file: {:?}", what, path))
+}
+
+fn read_all<T: DeserializeOwned + Identifiable + Timestamped>(
+    dir: &Path,
+    what: &str,
+) -> Result<Vec<T>> {
+    let mut items = Vec::new();
+    let files = entity_files(dir)?;
+    tracing::debug!(?dir, files = files.len(), "reading {}", what);
+    for path in files {
+        if let Some(item) = read_one(&path, what)? {
+            items.push(item);
+        }
+    }
+    items.sort_by_key(|item: &T| (item.created_at(), item.id()));
+    Ok(items)
+}
+
+fn remove_file(path: &Path) -> Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err).with_context(|| format!("Failed to delete {:?}", path)),
+    }
+}
@@ -0,0 +1,150 @@
+//! Default backend: one pretty-printed JSON array per entity type, optionally
+//! compressed (see [`super::compression`]).
+
+use crate::models::config::Compression;
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use crate::storage::{compression, legacy, StorageBackend};
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub struct JsonBackend {
+    ideas_file: PathBuf,
+    projects_file: PathBuf,
+    tags_file: PathBuf,
+    tasks_file: PathBuf,
+    compression: Compression,
+}
+
+impl JsonBackend {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            ideas_file: data_dir.join("ideas.json"),
+            projects_file: data_dir.join("projects.json"),
+            tags_file: data_dir.join("tags.json"),
+            tasks_file: data_dir.join("tasks.json"),
+            compression: Compression::None,
+        }
+    }
+
+    /// Compress files with `compression` when they are next written.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+}
+
+impl StorageBackend for JsonBackend {
+    fn load_ideas(&self) -> Result<Vec<Idea>> {
+        read_collection(&self.ideas_file, "ideas").or_else(|err| {
+            // Files from before ideas had a status and tags are upgraded as they're
+            // read, and written in the current format on the next save
+            legacy::read_ideas(&self.ideas_file)?.ok_or(err)
+        })
+    }
+
+    fn save_ideas(&self, ideas: &[Idea]) -> Result<()> {
+        write_collection(&self.ideas_file, "ideas", ideas, self.compression)
+    }
+
+    fn load_projects(&self) -> Result<Vec<Project>> {
+        read_collection(&self.projects_file, "projects")
+    }
+
+    fn save_projects(&self, projects: &[Project]) -> Result<()> {
+        write_collection(&self.projects_file, "projects", projects, self.compression)
+    }
+
+    fn load_tags(&self) -> Result<Vec<Tag>> {
+        read_collection(&self.tags_file, "tags")
+    }
+
+    fn save_tags(&self, tags: &[Tag]) -> Result<()> {
+        write_collection(&self.tags_file, "tags", tags, self.compression)
+    }
+
+    fn load_tasks(&self) -> Result<Vec<Task>> {
+        read_collection(&self.tasks_file, "tasks")
+    }
+
+    fn save_tasks(&self, tasks: &[Task]) -> Result<()> {
+        write_collection(&self.tasks_file, "tasks", tasks, self.compression)
+    }
+
+    fn data_files(&self) -> Vec<PathBuf> {
+        vec![
+            self.ideas_file.clone(),
+            self.projects_file.clone(),
+            self.tags_file.clone(),
+            self.tasks_file.clone(),
+        ]
+    }
+}
+
+pub(super) fn read_collection<T: DeserializeOwned>(path: &Path, what: &str) -> Result<Vec<T>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content =
+        fs::read(path).with_context(|| format!("Failed to read {} file: {:?}", what, path))?;
+    tracing::debug!(?path, bytes = content.len(), "read {}", what);
+    let content = compression::decode(&content)
+        .with_context(|| format!("Failed to read {} file: {:?}", what, path))?;
+
+    serde_json::from_slice(&content).with_context(|| format!("Failed to parse {} JSON", what))
+}
+
+pub(super) fn write_collection<T: Serialize>(
+    path: &Path,
+    what: &str,
+    items: &[T],
+    compression: Compression,
+) -> Result<()> {
+    let content = serde_json::to_vec_pretty(items)
+        .with_context(|| format!("Failed to serialize {} to JSON", what))?;
+    let content = compression::encode(&content, compression)?;
+
+    write_atomic(path, &content)
+        .with_context(|| format!("Failed to write {} file: {:?}", what, path))
+}
+
+/// Write `content` to `path` without ever leaving a partially written file behind.
+///
+/// The data is written to a temporary file in the same directory, flushed to disk,
+/// and then renamed over the destination, so readers see either the old or the new
+/// contents. A crash mid-write only leaves a stale temporary file, which is
+/// overwritten on the next save.
+pub(crate) fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Invalid storage file path: {:?}", path))?;
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+    let mut file = File::create(&temp_path)
+        .with_context(|| format!("Failed to create temp file: {:?}", temp_path))?;
+    file.write_all(content)
+        .with_context(|| format!("Failed to write temp file: {:?}", temp_path))?;
+    file.sync_all()
+        .with_context(|| format!("Failed to sync temp file: {:?}", temp_path))?;
+    drop(file);
+
+    fs::rename(&temp_path, path)
+        .with_context(|| format!("Failed to replace {:?} with {:?}", path, temp_path))?;
+    tracing::debug!(?path, bytes = content.len(), "wrote file");
+
+    // Persist the rename itself; not supported on every platform, so best effort.
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+
+    Ok(())
+}
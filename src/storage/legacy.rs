@@ -0,0 +1,81 @@
+//! The idea format written before ideas had a status and tags.
+//!
+//! Vaults from those versions hold an `ideas.json` whose entries have only an
+//! ID, title, description, and timestamps, which the current [`Idea`] can't
+//! read. [`read_ideas`] recognises such a file and upgrades its entries: each
+//! becomes a brainstorming idea of middle priority without tags, keeping its
+//! ID and timestamps.
+
+use super::compression;
+use crate::models::idea::{Idea, IdeaStatus};
+use crate::models::task::TaskPriority;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegacyIdea {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<LegacyIdea> for Idea {
+    fn from(legacy: LegacyIdea) -> Self {
+        Idea {
+            id: legacy.id,
+            title: legacy.title,
+            description: legacy.description,
+            source: None,
+            effort: None,
+            impact: None,
+            tags: Vec::new(),
+            status: IdeaStatus::Brainstorming,
+            priority: TaskPriority::middle(),
+            related_ids: Vec::new(),
+            notes: Vec::new(),
+            attachments: Vec::new(),
+            status_history: Vec::new(),
+            created_at: legacy.created_at,
+            updated_at: legacy.updated_at,
+        }
+    }
+}
+
+/// Whether `items` are ideas in the legacy format: every entry has a title
+/// but neither a status nor tags.
+pub fn is_legacy(items: &[Value]) -> bool {
+    !items.is_empty()
+        && items.iter().all(|item| {
+            item.get("title").is_some()
+                && item.get("status").is_none()
+                && item.get("tags").is_none()
+        })
+}
+
+/// The ideas in the file at `path`, upgraded, if it holds legacy ideas.
+/// Returns `None` for a missing file or one in any other format.
+pub fn read_ideas(path: &Path) -> Result<Option<Vec<Idea>>> {
+    let Ok(content) = fs::read(path) else {
+        return Ok(None);
+    };
+    let Ok(content) = compression::decode(&content) else {
+        return Ok(None);
+    };
+    let Ok(items) = serde_json::from_slice::<Vec<Value>>(&content) else {
+        return Ok(None);
+    };
+    if !is_legacy(&items) {
+        return Ok(None);
+    }
+
+    let ideas: Vec<LegacyIdea> = serde_json::from_value(Value::Array(items))
+        .with_context(|| format!("Failed to parse legacy ideas in {:?}", path))?;
+    Ok(Some(ideas.into_iter().map(Idea::from).collect()))
+}
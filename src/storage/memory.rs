@@ -0,0 +1,62 @@
+//! Volatile backend that keeps everything in memory. Handy for tests and for
+//! embedding the command layer without touching the filesystem.
+
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use crate::storage::StorageBackend;
+use anyhow::Result;
+use std::cell::RefCell;
+
+#[derive(Default)]
+pub struct MemoryBackend {
+    ideas: RefCell<Vec<Idea>>,
+    projects: RefCell<Vec<Project>>,
+    tags: RefCell<Vec<Tag>>,
+    tasks: RefCell<Vec<Task>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn load_ideas(&self) -> Result<Vec<Idea>> {
+        Ok(self.ideas.borrow().clone())
+    }
+
+    fn save_ideas(&self, ideas: &[Idea]) -> Result<()> {
+        *self.ideas.borrow_mut() = ideas.to_vec();
+        Ok(())
+    }
+
+    fn load_projects(&self) -> Result<Vec<Project>> {
+        Ok(self.projects.borrow().clone())
+    }
+
+    fn save_projects(&self, projects: &[Project]) -> Result<()> {
+        *self.projects.borrow_mut() = projects.to_vec();
+        Ok(())
+    }
+
+    fn load_tags(&self) -> Result<Vec<Tag>> {
+        Ok(self.tags.borrow().clone())
+    }
+
+    fn save_tags(&self, tags: &[Tag]) -> Result<()> {
+        *self.tags.borrow_mut() = tags.to_vec();
+        Ok(())
+    }
+
+    fn load_tasks(&self) -> Result<Vec<Task>> {
+        Ok(self.tasks.borrow().clone())
+    }
+
+    fn save_tasks(&self, tasks: &[Task]) -> Result<()> {
+        *self.tasks.borrow_mut() = tasks.to_vec();
+        Ok(())
+    }
+}
@@ -0,0 +1,1109 @@
+//! Persistence for IdeaVault.
+//!
+//! [`Storage`] is what the commands talk to. It owns the data directory and the
+//! vault lock, and delegates the actual reading and writing of entities to a
+//! [`StorageBackend`]: JSON files by default (one array per entity type, or one
+//! file per entity with the [`Layout::Indexed`] layout, or an append-only log of
+//! changes with [`Layout::Events`]), SQLite with the `sqlite`
+//! feature, or anything a library user plugs in via [`Storage::with_backend`].
+
+use crate::models::config::{BackupConfig, Compression, Config, TrashConfig};
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use crate::models::template::ProjectTemplate;
+use crate::models::Timestamped;
+use crate::symbols;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use history::{Action, EntityKind, HistoryEntry, Operation};
+use serde::Serialize;
+use std::cell::Cell;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+pub mod archive;
+pub mod backend;
+pub mod backup;
+pub mod compression;
+pub mod events;
+pub mod git;
+pub mod history;
+pub mod indexed;
+pub mod json;
+pub mod legacy;
+pub mod memory;
+pub mod session;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod trash;
+pub mod vault;
+
+pub use backend::StorageBackend;
+pub use events::EventLogBackend;
+pub use indexed::IndexedBackend;
+pub use json::JsonBackend;
+pub use memory::MemoryBackend;
+pub use session::Session;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteBackend;
+pub use trash::{TrashEntry, TrashedItem};
+pub use vault::VaultRegistry;
+
+/// How long to wait for another process to release the vault lock by default.
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Environment variable overriding the lock timeout, in seconds.
+pub const LOCK_TIMEOUT_ENV: &str = "IDEAVAULT_LOCK_TIMEOUT";
+
+const LOCK_FILE_NAME: &str = ".ideavault.lock";
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Make [`Storage::new`] open `data_dir` instead of a vault (`--data-dir`).
+pub fn set_data_dir(data_dir: PathBuf) {
+    let _ = DATA_DIR.set(data_dir);
+}
+
+/// The directory [`Storage::new`] opens: the one given with `--data-dir`, or
+/// else the active vault's.
+pub fn active_data_dir(config: &Config) -> Result<PathBuf> {
+    match DATA_DIR.get() {
+        Some(data_dir) => Ok(data_dir.clone()),
+        None => VaultRegistry::open_with(config)
+            .context("Failed to load vault registry")?
+            .active_data_dir(),
+    }
+}
+
+/// Where backups of the vault at `data_dir` are kept.
+pub fn backup_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("backups")
+}
+
+/// Marker file recording a non-default [`Layout`].
+const LAYOUT_FILE: &str = ".layout";
+
+/// How a file-based vault arranges its data on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// One JSON array per entity type (`ideas.json`, `tasks.json`, ...)
+    Json,
+    /// One file per entity, so single-entity updates only touch one file
+    Indexed,
+    /// A snapshot plus a log that every change is appended to
+    Events,
+}
+
+impl Layout {
+    /// The layout of the vault in `data_dir`; new vaults start as [`Layout::Json`].
+    pub fn detect(data_dir: &Path) -> Self {
+        fs::read_to_string(data_dir.join(LAYOUT_FILE))
+            .ok()
+            .and_then(|content| content.trim().parse().ok())
+            .unwrap_or(Layout::Json)
+    }
+
+    /// Record this layout in the marker file at `path`, unless it already does.
+    fn mark(self, path: &Path) -> Result<()> {
+        let current = fs::read_to_string(path).ok();
+        if current.as_deref().map(str::trim) != Some(self.to_string().as_str()) {
+            json::write_atomic(path, self.to_string().as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Layout {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Layout::Json),
+            "indexed" => Ok(Layout::Indexed),
+            "events" => Ok(Layout::Events),
+            _ => Err(anyhow::anyhow!(
+                "Invalid layout: {}. Valid options: json, indexed, events",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layout::Json => write!(f, "json"),
+            Layout::Indexed => write!(f, "indexed"),
+            Layout::Events => write!(f, "events"),
+        }
+    }
+}
+
+/// Entities that lost their link to a deleted idea or project.
+#[derive(Debug, Default)]
+pub struct Unlinked {
+    pub ideas: Vec<Idea>,
+    pub projects: Vec<Project>,
+    pub tasks: Vec<Task>,
+}
+
+impl Unlinked {
+    pub fn is_empty(&self) -> bool {
+        self.ideas.is_empty() && self.projects.is_empty() && self.tasks.is_empty()
+    }
+}
+
+pub struct Storage {
+    data_dir: PathBuf,
+    backend: Box<dyn StorageBackend>,
+    backup: BackupConfig,
+    trash: TrashConfig,
+    // Set for the built-in file backends, which can be migrated between layouts
+    layout: Option<Layout>,
+    compression: Compression,
+    // Groups the history entries written by one command
+    operation: Cell<Uuid>,
+    // Set while an undo is replaying, so the log records what it reverted
+    undoing: Cell<Option<Uuid>>,
+    // Held for the lifetime of the storage; the OS releases the lock when it is dropped.
+    _lock: File,
+}
+
+impl Storage {
+    /// Open the directory given with `--data-dir`, or else the active vault: the
+    /// one selected with `--vault`, or the current one. Backups, trash, and
+    /// compression follow the user's config file.
+    pub fn new() -> Result<Self> {
+        let config = Config::load().context("Failed to load config")?;
+        let data_dir = active_data_dir(&config)?;
+        Ok(Self::new_compressed(data_dir, config.compression)?
+            .with_backup_config(config.backup)
+            .with_trash_config(config.trash))
+    }
+
+    /// Create storage with a custom data directory path.
+    /// Useful for testing with temporary directories.
+    pub fn new_with_path(data_dir: PathBuf) -> Result<Self> {
+        Self::new_with_lock_timeout(data_dir, lock_timeout_from_env())
+    }
+
+    /// Create storage at `data_dir`, waiting at most `lock_timeout` for other
+    /// ideavault processes using the same directory to finish.
+    pub fn new_with_lock_timeout(data_dir: PathBuf, lock_timeout: Duration) -> Result<Self> {
+        Self::open_files(data_dir, Compression::None, lock_timeout)
+    }
+
+    /// Create file storage at `data_dir` whose files are written with `compression`.
+    /// Existing files are read whatever their compression.
+    pub fn new_compressed(data_dir: PathBuf, compression: Compression) -> Result<Self> {
+        Self::open_files(data_dir, compression, lock_timeout_from_env())
+    }
+
+    /// Open the built-in file backend matching the vault's current layout.
+    /// The layout is read after taking the lock so a concurrent migration can't
+    /// change it underneath us.
+    fn open_files(data_dir: PathBuf, compression: Compression, timeout: Duration) -> Result<Self> {
+        let lock = Self::prepare_dir(&data_dir, timeout)?;
+        let layout = Layout::detect(&data_dir);
+        tracing::debug!(dir = ?data_dir, ?layout, ?compression, "opened vault");
+        Ok(Self {
+            backend: file_backend(&data_dir, layout, compression),
+            data_dir,
+            backup: BackupConfig::default(),
+            trash: TrashConfig::default(),
+            layout: Some(layout),
+            compression,
+            operation: Cell::new(Uuid::new_v4()),
+            undoing: Cell::new(None),
+            _lock: lock,
+        })
+    }
+
+    /// Create storage backed by a SQLite database at `db_path` instead of JSON files.
+    /// The database's parent directory is used as the data directory.
+    #[cfg(feature = "sqlite")]
+    pub fn new_sqlite(db_path: PathBuf) -> Result<Self> {
+        let data_dir = db_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+        let lock = Self::prepare_dir(&data_dir, lock_timeout_from_env())?;
+        let backend = SqliteBackend::open(&db_path)?;
+        Ok(Self {
+            data_dir,
+            backend: Box::new(backend),
+            backup: BackupConfig::default(),
+            trash: TrashConfig::default(),
+            layout: None,
+            compression: Compression::None,
+            operation: Cell::new(Uuid::new_v4()),
+            undoing: Cell::new(None),
+            _lock: lock,
+        })
+    }
+
+    /// Create storage that persists through a custom backend. `data_dir` is still
+    /// locked and used for vault-level files that live outside the backend.
+    pub fn with_backend(data_dir: PathBuf, backend: Box<dyn StorageBackend>) -> Result<Self> {
+        Self::open(data_dir, backend, lock_timeout_from_env())
+    }
+
+    fn open(
+        data_dir: PathBuf,
+        backend: Box<dyn StorageBackend>,
+        lock_timeout: Duration,
+    ) -> Result<Self> {
+        let lock = Self::prepare_dir(&data_dir, lock_timeout)?;
+        Ok(Self {
+            data_dir,
+            backend,
+            backup: BackupConfig::default(),
+            trash: TrashConfig::default(),
+            layout: None,
+            compression: Compression::None,
+            operation: Cell::new(Uuid::new_v4()),
+            undoing: Cell::new(None),
+            _lock: lock,
+        })
+    }
+
+    /// Ensure the data directory exists and take the vault lock.
+    fn prepare_dir(data_dir: &Path, lock_timeout: Duration) -> Result<File> {
+        fs::create_dir_all(data_dir)
+            .with_context(|| format!("Failed to create data directory: {:?}", data_dir))?;
+        acquire_lock(data_dir, lock_timeout)
+    }
+
+    /// Use `backup` to decide when automatic snapshots are taken.
+    pub fn with_backup_config(mut self, backup: BackupConfig) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// Use `trash` to decide when deleted items are purged.
+    pub fn with_trash_config(mut self, trash: TrashConfig) -> Self {
+        self.trash = trash;
+        self
+    }
+
+    /// The directory holding this vault's files.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// The on-disk layout, or `None` for SQLite and custom backends.
+    pub fn layout(&self) -> Option<Layout> {
+        self.layout
+    }
+
+    /// Rewrite the vault in `layout` and remove the files of the previous one.
+    /// A backup is taken first. Returns `false` if the vault already uses `layout`.
+    pub fn migrate(&mut self, layout: Layout) -> Result<bool> {
+        let current = self
+            .layout
+            .context("Only JSON file vaults can change layout")?;
+        if current == layout {
+            return Ok(false);
+        }
+
+        let old_files = self.backend.data_files();
+        if old_files.iter().any(|file| file.is_file()) {
+            self.create_backup()
+                .context("Failed to back up vault before migrating")?;
+        }
+
+        let ideas = self.backend.load_ideas().context("Failed to load ideas")?;
+        let projects = self
+            .backend
+            .load_projects()
+            .context("Failed to load projects")?;
+        let tags = self.backend.load_tags().context("Failed to load tags")?;
+        let tasks = self.backend.load_tasks().context("Failed to load tasks")?;
+
+        let target = file_backend(&self.data_dir, layout, self.compression);
+        target.save_ideas(&ideas).context("Failed to write ideas")?;
+        target
+            .save_projects(&projects)
+            .context("Failed to write projects")?;
+        target.save_tags(&tags).context("Failed to write tags")?;
+        target.save_tasks(&tasks).context("Failed to write tasks")?;
+        // Start an event-log vault from a snapshot rather than one event per entity
+        target.compact().context("Failed to write snapshot")?;
+
+        // Only drop the old files once everything is safely in the new layout
+        let new_files = target.data_files();
+        for file in old_files.iter().filter(|f| !new_files.contains(f)) {
+            if file.is_file() {
+                fs::remove_file(file)
+                    .with_context(|| format!("Failed to remove old data file: {:?}", file))?;
+            }
+        }
+        for dir in ["ideas", "projects", "tasks"] {
+            // Leaves the directory alone unless it's empty
+            let _ = fs::remove_dir(self.data_dir.join(dir));
+        }
+
+        self.backend = target;
+        self.layout = Some(layout);
+        Ok(true)
+    }
+
+    /// Fold the event log into a new snapshot. Returns how many events were
+    /// folded in, or `None` if the vault doesn't keep an event log.
+    pub fn compact(&self) -> Result<Option<usize>> {
+        self.backend.compact()
+    }
+
+    /// Files on disk holding the backend's data (empty for non-file backends).
+    pub fn data_files(&self) -> Vec<PathBuf> {
+        self.backend.data_files()
+    }
+
+    /// Where snapshots of the data files are kept.
+    pub fn backup_dir(&self) -> PathBuf {
+        backup_dir(&self.data_dir)
+    }
+
+    /// Snapshot the current data files right now, regardless of the schedule.
+    pub fn create_backup(&self) -> Result<backup::BackupInfo> {
+        backup::create_backup(&self.backup_dir(), &self.data_dir, &self.backup_files())
+    }
+
+    /// Everything a snapshot should contain: the backend's files plus the trash
+    /// and the project templates.
+    fn backup_files(&self) -> Vec<PathBuf> {
+        let mut files = self.backend.data_files();
+        files.push(self.trash_file());
+        files.push(self.templates_file());
+        files
+    }
+
+    /// Take a scheduled snapshot if automatic backups are enabled and the interval
+    /// has elapsed, then drop snapshots beyond `max_backups`.
+    fn backup_if_due(&self) -> Result<()> {
+        if !self.backup.enabled {
+            return Ok(());
+        }
+
+        let files = self.backup_files();
+        if !files.iter().any(|file| file.is_file()) {
+            return Ok(());
+        }
+
+        let backup_dir = self.backup_dir();
+        let interval = chrono::Duration::hours(self.backup.interval_hours as i64);
+        if let Some(last) = backup::last_backup_time(&backup_dir) {
+            if Utc::now() - last < interval {
+                return Ok(());
+            }
+        }
+
+        backup::create_backup(&backup_dir, &self.data_dir, &files)
+            .context("Automatic backup failed")?;
+        backup::prune_backups(&backup_dir, self.backup.max_backups)?;
+        Ok(())
+    }
+
+    pub fn load_ideas(&self) -> Result<Vec<Idea>> {
+        timed("load ideas", || self.backend.load_ideas())
+    }
+
+    pub fn save_ideas(&self, ideas: &[Idea]) -> Result<()> {
+        self.backup_if_due()?;
+        let before = self.backend.load_ideas()?;
+        timed("save ideas", || self.backend.save_ideas(ideas))?;
+        self.record_history(EntityKind::Idea, &before, ideas, |i| i.id.to_string())
+    }
+
+    pub fn load_projects(&self) -> Result<Vec<Project>> {
+        timed("load projects", || self.backend.load_projects())
+    }
+
+    pub fn save_projects(&self, projects: &[Project]) -> Result<()> {
+        self.backup_if_due()?;
+        let before = self.backend.load_projects()?;
+        timed("save projects", || self.backend.save_projects(projects))?;
+        self.record_history(EntityKind::Project, &before, projects, |p| p.id.to_string())
+    }
+
+    pub fn load_tags(&self) -> Result<Vec<Tag>> {
+        timed("load tags", || self.backend.load_tags())
+    }
+
+    pub fn save_tags(&self, tags: &[Tag]) -> Result<()> {
+        self.backup_if_due()?;
+        let before = self.backend.load_tags()?;
+        timed("save tags", || self.backend.save_tags(tags))?;
+        self.record_history(EntityKind::Tag, &before, tags, |t| t.name.clone())
+    }
+
+    pub fn load_tasks(&self) -> Result<Vec<Task>> {
+        timed("load tasks", || self.backend.load_tasks())
+    }
+
+    pub fn save_tasks(&self, tasks: &[Task]) -> Result<()> {
+        self.backup_if_due()?;
+        let before = self.backend.load_tasks()?;
+        timed("save tasks", || self.backend.save_tasks(tasks))?;
+        self.record_history(EntityKind::Task, &before, tasks, |t| t.id.to_string())
+    }
+
+    pub fn load_idea(&self, id: Uuid) -> Result<Option<Idea>> {
+        timed("load idea", || self.backend.load_idea(id))
+    }
+
+    /// Insert or replace a single idea.
+    pub fn save_idea(&self, idea: &Idea) -> Result<()> {
+        self.backup_if_due()?;
+        let before = self.backend.load_idea(idea.id)?;
+        timed("save idea", || self.backend.save_idea(idea))?;
+        self.record_history(
+            EntityKind::Idea,
+            before.as_slice(),
+            std::slice::from_ref(idea),
+            |i| i.id.to_string(),
+        )
+    }
+
+    pub fn delete_idea(&self, id: Uuid) -> Result<bool> {
+        self.backup_if_due()?;
+        let before = self.backend.load_idea(id)?;
+        let deleted = timed("delete idea", || self.backend.delete_idea(id))?;
+        self.record_history(EntityKind::Idea, before.as_slice(), &[], |i| {
+            i.id.to_string()
+        })?;
+        Ok(deleted)
+    }
+
+    pub fn load_project(&self, id: Uuid) -> Result<Option<Project>> {
+        timed("load project", || self.backend.load_project(id))
+    }
+
+    /// Insert or replace a single project.
+    pub fn save_project(&self, project: &Project) -> Result<()> {
+        self.backup_if_due()?;
+        let before = self.backend.load_project(project.id)?;
+        timed("save project", || self.backend.save_project(project))?;
+        self.record_history(
+            EntityKind::Project,
+            before.as_slice(),
+            std::slice::from_ref(project),
+            |p| p.id.to_string(),
+        )
+    }
+
+    pub fn delete_project(&self, id: Uuid) -> Result<bool> {
+        self.backup_if_due()?;
+        let before = self.backend.load_project(id)?;
+        let deleted = timed("delete project", || self.backend.delete_project(id))?;
+        self.record_history(EntityKind::Project, before.as_slice(), &[], |p| {
+            p.id.to_string()
+        })?;
+        Ok(deleted)
+    }
+
+    pub fn load_task(&self, id: Uuid) -> Result<Option<Task>> {
+        timed("load task", || self.backend.load_task(id))
+    }
+
+    /// Insert or replace a single task.
+    pub fn save_task(&self, task: &Task) -> Result<()> {
+        self.backup_if_due()?;
+        let before = self.backend.load_task(task.id)?;
+        timed("save task", || self.backend.save_task(task))?;
+        self.record_history(
+            EntityKind::Task,
+            before.as_slice(),
+            std::slice::from_ref(task),
+            |t| t.id.to_string(),
+        )
+    }
+
+    pub fn delete_task(&self, id: Uuid) -> Result<bool> {
+        self.backup_if_due()?;
+        let before = self.backend.load_task(id)?;
+        let deleted = timed("delete task", || self.backend.delete_task(id))?;
+        self.record_history(EntityKind::Task, before.as_slice(), &[], |t| {
+            t.id.to_string()
+        })?;
+        Ok(deleted)
+    }
+
+    /// Add a new idea. Fails if one with the same ID already exists.
+    pub fn add_idea(&self, idea: &Idea) -> Result<()> {
+        if self.backend.load_idea(idea.id)?.is_some() {
+            anyhow::bail!("Idea with ID {} already exists", idea.id);
+        }
+        self.save_idea(idea)
+    }
+
+    /// The idea with `id`, or an error if there is none.
+    pub fn get_idea(&self, id: Uuid) -> Result<Idea> {
+        self.load_idea(id)
+            .context("Failed to load idea")?
+            .ok_or_else(|| anyhow::anyhow!("Idea with ID {} not found", id))
+    }
+
+    /// Apply `f` to the idea with `id` and save it if that changed anything,
+    /// bumping its `updated_at`. Returns what `f` returned.
+    pub fn update_idea<R>(&self, id: Uuid, f: impl FnOnce(&mut Idea) -> R) -> Result<R> {
+        let mut idea = self.get_idea(id)?;
+        let (result, changed) = modify(&mut idea, f);
+        if changed {
+            self.save_idea(&idea).context("Failed to save idea")?;
+        }
+        Ok(result)
+    }
+
+    /// Move the idea with `id` to the trash. Returns the removed idea.
+    pub fn trash_idea(&self, id: Uuid) -> Result<Idea> {
+        let idea = self.get_idea(id)?;
+        self.move_to_trash(TrashedItem::Idea(idea.clone()))
+            .context("Failed to move idea to trash")?;
+        self.delete_idea(id).context("Failed to delete idea")?;
+        Ok(idea)
+    }
+
+    /// Add a new project. Fails if one with the same ID already exists.
+    pub fn add_project(&self, project: &Project) -> Result<()> {
+        if self.backend.load_project(project.id)?.is_some() {
+            anyhow::bail!("Project with ID {} already exists", project.id);
+        }
+        self.save_project(project)
+    }
+
+    /// The project with `id`, or an error if there is none.
+    pub fn get_project(&self, id: Uuid) -> Result<Project> {
+        self.load_project(id)
+            .context("Failed to load project")?
+            .ok_or_else(|| anyhow::anyhow!("Project with ID {} not found", id))
+    }
+
+    /// Apply `f` to the project with `id` and save it if that changed anything,
+    /// bumping its `updated_at`. Returns what `f` returned.
+    pub fn update_project<R>(&self, id: Uuid, f: impl FnOnce(&mut Project) -> R) -> Result<R> {
+        let mut project = self.get_project(id)?;
+        let (result, changed) = modify(&mut project, f);
+        if changed {
+            self.save_project(&project)
+                .context("Failed to save project")?;
+        }
+        Ok(result)
+    }
+
+    /// Move the project with `id` to the trash. Returns the removed project.
+    pub fn trash_project(&self, id: Uuid) -> Result<Project> {
+        let project = self.get_project(id)?;
+        self.move_to_trash(TrashedItem::Project(project.clone()))
+            .context("Failed to move project to trash")?;
+        self.delete_project(id)
+            .context("Failed to delete project")?;
+        Ok(project)
+    }
+
+    /// Add a new task. Fails if one with the same ID already exists.
+    pub fn add_task(&self, task: &Task) -> Result<()> {
+        if self.backend.load_task(task.id)?.is_some() {
+            anyhow::bail!("Task with ID {} already exists", task.id);
+        }
+        self.save_task(task)
+    }
+
+    /// The task with `id`, or an error if there is none.
+    pub fn get_task(&self, id: Uuid) -> Result<Task> {
+        self.load_task(id)
+            .context("Failed to load task")?
+            .ok_or_else(|| anyhow::anyhow!("Task with ID {} not found", id))
+    }
+
+    /// Apply `f` to the task with `id` and save it if that changed anything,
+    /// bumping its `updated_at`. Returns what `f` returned.
+    pub fn update_task<R>(&self, id: Uuid, f: impl FnOnce(&mut Task) -> R) -> Result<R> {
+        let mut task = self.get_task(id)?;
+        let (result, changed) = modify(&mut task, f);
+        if changed {
+            self.save_task(&task).context("Failed to save task")?;
+        }
+        Ok(result)
+    }
+
+    /// Move the task with `id` to the trash. Returns the removed task.
+    pub fn trash_task(&self, id: Uuid) -> Result<Task> {
+        let task = self.get_task(id)?;
+        self.move_to_trash(TrashedItem::Task(task.clone()))
+            .context("Failed to move task to trash")?;
+        self.delete_task(id).context("Failed to delete task")?;
+        Ok(task)
+    }
+
+    /// Remove the idea with `id` from every idea, project, and task linking
+    /// to it.
+    pub fn unlink_idea(&self, id: Uuid) -> Result<Unlinked> {
+        let mut unlinked = Unlinked::default();
+        for idea in self.load_ideas().context("Failed to load ideas")? {
+            if idea.related_ids.contains(&id) {
+                unlinked.ideas.push(self.update_idea(idea.id, |idea| {
+                    idea.remove_related(&id);
+                    idea.clone()
+                })?);
+            }
+        }
+        for project in self.load_projects().context("Failed to load projects")? {
+            if project.idea_ids.contains(&id) {
+                unlinked
+                    .projects
+                    .push(self.update_project(project.id, |project| {
+                        project.remove_idea(&id);
+                        project.clone()
+                    })?);
+            }
+        }
+        for task in self.load_tasks().context("Failed to load tasks")? {
+            if task.idea_id == Some(id) {
+                unlinked.tasks.push(self.update_task(task.id, |task| {
+                    task.idea_id = None;
+                    task.clone()
+                })?);
+            }
+        }
+        Ok(unlinked)
+    }
+
+    /// Point every idea, project, and task linking to the idea `from` at the
+    /// idea `to` instead, without linking anything to `to` twice.
+    pub fn relink_idea(&self, from: Uuid, to: Uuid) -> Result<Unlinked> {
+        let mut relinked = Unlinked::default();
+        for idea in self.load_ideas().context("Failed to load ideas")? {
+            if idea.related_ids.contains(&from) {
+                relinked.ideas.push(self.update_idea(idea.id, |idea| {
+                    idea.remove_related(&from);
+                    if idea.id != to {
+                        idea.add_related(to);
+                    }
+                    idea.clone()
+                })?);
+            }
+        }
+        for project in self.load_projects().context("Failed to load projects")? {
+            if project.idea_ids.contains(&from) {
+                relinked
+                    .projects
+                    .push(self.update_project(project.id, |project| {
+                        project.remove_idea(&from);
+                        project.add_idea(to);
+                        project.clone()
+                    })?);
+            }
+        }
+        for task in self.load_tasks().context("Failed to load tasks")? {
+            if task.idea_id == Some(from) {
+                relinked.tasks.push(self.update_task(task.id, |task| {
+                    task.idea_id = Some(to);
+                    task.clone()
+                })?);
+            }
+        }
+        Ok(relinked)
+    }
+
+    /// Detach every task from the project with `id`.
+    pub fn unlink_project(&self, id: Uuid) -> Result<Unlinked> {
+        let mut unlinked = Unlinked::default();
+        for task in self.load_tasks().context("Failed to load tasks")? {
+            if task.project_id == Some(id) {
+                unlinked.tasks.push(self.update_task(task.id, |task| {
+                    task.project_id = None;
+                    task.clone()
+                })?);
+            }
+        }
+        Ok(unlinked)
+    }
+
+    /// Where files attached to ideas are copied, in a directory per idea.
+    pub fn attachments_dir(&self) -> PathBuf {
+        self.data_dir.join("attachments")
+    }
+
+    /// Copy the file at `source` into the attachments of the idea `idea_id`,
+    /// numbering the copy if the idea already has a file of that name.
+    /// Returns where the copy is, relative to [`Self::attachments_dir`].
+    pub fn store_attachment(&self, idea_id: Uuid, source: &Path) -> Result<PathBuf> {
+        let name = source
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("{} is not a file", source.display()))?;
+        let dir = PathBuf::from(idea_id.to_string());
+        let full_dir = self.attachments_dir().join(&dir);
+        fs::create_dir_all(&full_dir)
+            .with_context(|| format!("Failed to create {}", full_dir.display()))?;
+
+        let stem = Path::new(name)
+            .file_stem()
+            .unwrap_or(name)
+            .to_string_lossy();
+        let extension = Path::new(name)
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+        let mut file_name = name.to_string_lossy().into_owned();
+        let mut number = 1;
+        while full_dir.join(&file_name).exists() {
+            number += 1;
+            file_name = format!("{}-{}{}", stem, number, extension);
+        }
+
+        fs::copy(source, full_dir.join(&file_name))
+            .with_context(|| format!("Failed to copy {}", source.display()))?;
+        Ok(dir.join(file_name))
+    }
+
+    /// The append-only log of entity changes.
+    pub fn history_file(&self) -> PathBuf {
+        self.data_dir.join("history.jsonl")
+    }
+
+    /// Every recorded change, oldest first.
+    pub fn load_history(&self) -> Result<Vec<HistoryEntry>> {
+        history::read(&self.history_file())
+    }
+
+    /// Start a new operation: changes saved from now on are undone separately
+    /// from earlier ones. Each `Storage` starts its own operation when opened.
+    pub fn begin_operation(&self) {
+        self.operation.set(Uuid::new_v4());
+        self.undoing.set(None);
+    }
+
+    /// Operations that `undo` can revert, most recent first.
+    pub fn undo_stack(&self) -> Result<Vec<Operation>> {
+        Ok(history::undo_stack(self.load_history()?))
+    }
+
+    /// Revert the most recent operation on the undo stack by replaying the
+    /// inverse of its changes. Returns the reverted operation, or `None` if
+    /// there is nothing to undo.
+    pub fn undo(&self) -> Result<Option<Operation>> {
+        let Some(operation) = self.undo_stack()?.into_iter().next() else {
+            return Ok(None);
+        };
+
+        self.begin_operation();
+        self.undoing.set(Some(operation.id));
+        let result = operation
+            .entries
+            .iter()
+            .rev()
+            .try_for_each(|entry| self.revert_entry(entry));
+        self.begin_operation();
+
+        result?;
+        Ok(Some(operation))
+    }
+
+    fn revert_entry(&self, entry: &HistoryEntry) -> Result<()> {
+        if entry.entity == EntityKind::Tag {
+            let mut tags = self.load_tags()?;
+            let index = tags.iter().position(|tag| tag.name == entry.entity_id);
+            let current = index.map(|i| tags[i].clone());
+            match (history::revert(current, entry)?, index) {
+                (Some(tag), Some(i)) => tags[i] = tag,
+                (Some(tag), None) => tags.push(tag),
+                (None, Some(i)) => {
+                    tags.remove(i);
+                }
+                (None, None) => return Ok(()),
+            }
+            return self.save_tags(&tags);
+        }
+
+        let id: Uuid = entry.entity_id.parse().with_context(|| {
+            format!(
+                "Invalid {} ID in history: {}",
+                entry.entity, entry.entity_id
+            )
+        })?;
+        // Undoing a create moves the entity to the trash rather than dropping it
+        match entry.entity {
+            EntityKind::Idea => {
+                let current = self.load_idea(id)?;
+                match (history::revert(current.clone(), entry)?, current) {
+                    (Some(idea), _) => self.save_idea(&idea)?,
+                    (None, Some(idea)) => {
+                        self.move_to_trash(TrashedItem::Idea(idea))?;
+                        self.delete_idea(id)?;
+                    }
+                    (None, None) => {}
+                }
+            }
+            EntityKind::Project => {
+                let current = self.load_project(id)?;
+                match (history::revert(current.clone(), entry)?, current) {
+                    (Some(project), _) => self.save_project(&project)?,
+                    (None, Some(project)) => {
+                        self.move_to_trash(TrashedItem::Project(project))?;
+                        self.delete_project(id)?;
+                    }
+                    (None, None) => {}
+                }
+            }
+            EntityKind::Task => {
+                let current = self.load_task(id)?;
+                match (history::revert(current.clone(), entry)?, current) {
+                    (Some(task), _) => self.save_task(&task)?,
+                    (None, Some(task)) => {
+                        self.move_to_trash(TrashedItem::Task(task))?;
+                        self.delete_task(id)?;
+                    }
+                    (None, None) => {}
+                }
+            }
+            EntityKind::Tag => unreachable!("tags are reverted above"),
+        }
+
+        // A deleted entity that's back shouldn't also linger in the trash
+        if entry.action == Action::Delete {
+            let mut entries = self.load_trash()?;
+            if let Some(index) = entries.iter().rposition(|trashed| trashed.id() == id) {
+                entries.remove(index);
+                self.save_trash(&entries)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Log the differences between two versions of a collection.
+    fn record_history<T: Serialize>(
+        &self,
+        entity: EntityKind,
+        before: &[T],
+        after: &[T],
+        key: impl Fn(&T) -> String,
+    ) -> Result<()> {
+        let mut entries = history::diff(entity, before, after, key, self.operation.get())?;
+        for entry in &mut entries {
+            entry.undoes = self.undoing.get();
+        }
+        history::append(&self.history_file(), &entries).context("Failed to record history")?;
+
+        if !entries.is_empty() && git::is_enabled(&self.data_dir) {
+            git::commit(&self.data_dir, &git::message(&entries))
+                .context("Failed to commit changes to git")?;
+        }
+        Ok(())
+    }
+
+    /// Where project templates are kept.
+    pub fn templates_file(&self) -> PathBuf {
+        self.data_dir.join("templates.json")
+    }
+
+    /// Every saved project template, in the order they were saved.
+    pub fn load_templates(&self) -> Result<Vec<ProjectTemplate>> {
+        json::read_collection(&self.templates_file(), "templates")
+    }
+
+    pub fn save_templates(&self, templates: &[ProjectTemplate]) -> Result<()> {
+        self.backup_if_due()?;
+        json::write_collection(
+            &self.templates_file(),
+            "templates",
+            templates,
+            self.compression,
+        )
+    }
+
+    /// Where soft-deleted entities are kept.
+    pub fn trash_file(&self) -> PathBuf {
+        self.data_dir.join("trash.json")
+    }
+
+    /// Everything in the trash, oldest deletion first.
+    pub fn load_trash(&self) -> Result<Vec<TrashEntry>> {
+        json::read_collection(&self.trash_file(), "trash")
+    }
+
+    pub fn save_trash(&self, entries: &[TrashEntry]) -> Result<()> {
+        self.backup_if_due()?;
+        json::write_collection(&self.trash_file(), "trash", entries, self.compression)
+    }
+
+    /// Put a deleted entity in the trash. Call this before removing it from its
+    /// collection, so a crash in between leaves a copy rather than nothing.
+    pub fn move_to_trash(&self, item: TrashedItem) -> Result<()> {
+        let mut entries = self.load_trash()?;
+        entries.push(TrashEntry::new(item));
+        self.save_trash(&entries)
+    }
+
+    /// Drop trash entries older than the configured auto-purge age.
+    /// Returns the purged entries.
+    pub fn purge_trash(&self) -> Result<Vec<TrashEntry>> {
+        let Some(days) = self.trash.auto_purge_days else {
+            return Ok(Vec::new());
+        };
+        let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+
+        let (purged, kept): (Vec<TrashEntry>, Vec<TrashEntry>) = self
+            .load_trash()?
+            .into_iter()
+            .partition(|entry| entry.deleted_at < cutoff);
+        if !purged.is_empty() {
+            self.save_trash(&kept)?;
+        }
+        Ok(purged)
+    }
+
+    /// Move the entity with `id` out of the trash and back into its collection.
+    pub fn restore_from_trash(&self, id: Uuid) -> Result<TrashEntry> {
+        let mut entries = self.load_trash()?;
+        let index = entries
+            .iter()
+            .rposition(|entry| entry.id() == id)
+            .ok_or_else(|| anyhow::anyhow!("No item with ID {} in the trash", id))?;
+        let entry = entries.remove(index);
+
+        let exists = match &entry.item {
+            TrashedItem::Idea(idea) => self.load_idea(idea.id)?.is_some(),
+            TrashedItem::Project(project) => self.load_project(project.id)?.is_some(),
+            TrashedItem::Task(task) => self.load_task(task.id)?.is_some(),
+        };
+        if exists {
+            anyhow::bail!(
+                "Cannot restore {} {}: an entity with that ID already exists",
+                entry.kind(),
+                id
+            );
+        }
+
+        match &entry.item {
+            TrashedItem::Idea(idea) => self.save_idea(idea)?,
+            TrashedItem::Project(project) => self.save_project(project)?,
+            TrashedItem::Task(task) => self.save_task(task)?,
+        }
+        self.save_trash(&entries)?;
+        Ok(entry)
+    }
+}
+
+impl Drop for Storage {
+    /// Commit vault files changed outside the entity collections, such as the
+    /// trash or a restored backup, so a git-backed vault is left clean.
+    fn drop(&mut self) {
+        if git::is_enabled(&self.data_dir) {
+            let message = format!(
+                "{}: update vault files",
+                history::command().unwrap_or("ideavault")
+            );
+            if let Err(err) = git::commit(&self.data_dir, &message) {
+                eprintln!(
+                    "{} Failed to commit changes to git: {:#}",
+                    symbols::WARNING,
+                    err
+                );
+            }
+        }
+    }
+}
+
+/// Apply `f` to `entity`, bumping `updated_at` if it changed anything.
+/// Returns what `f` returned and whether the entity changed.
+fn modify<T: Serialize + Timestamped, R>(entity: &mut T, f: impl FnOnce(&mut T) -> R) -> (R, bool) {
+    let before = serde_json::to_value(&*entity).ok();
+    let result = f(entity);
+    let changed = serde_json::to_value(&*entity).ok() != before;
+    if changed {
+        entity.touch();
+    }
+    (result, changed)
+}
+
+fn file_backend(
+    data_dir: &Path,
+    layout: Layout,
+    compression: Compression,
+) -> Box<dyn StorageBackend> {
+    match layout {
+        Layout::Json => Box::new(JsonBackend::new(data_dir).with_compression(compression)),
+        Layout::Indexed => Box::new(IndexedBackend::new(data_dir).with_compression(compression)),
+        Layout::Events => Box::new(EventLogBackend::new(data_dir).with_compression(compression)),
+    }
+}
+
+fn lock_timeout_from_env() -> Duration {
+    std::env::var(LOCK_TIMEOUT_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+        .unwrap_or(DEFAULT_LOCK_TIMEOUT)
+}
+
+/// Run one storage operation, logging how long it took.
+fn timed<T>(operation: &str, run: impl FnOnce() -> Result<T>) -> Result<T> {
+    let started = Instant::now();
+    let result = run();
+    tracing::debug!(elapsed = ?started.elapsed(), ok = result.is_ok(), "{}", operation);
+    result
+}
+
+/// Take an exclusive advisory lock on the vault, retrying until `timeout` elapses.
+fn acquire_lock(data_dir: &Path, timeout: Duration) -> Result<File> {
+    let lock_path = data_dir.join(LOCK_FILE_NAME);
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open lock file: {:?}", lock_path))?;
+
+    let started = Instant::now();
+    let deadline = started + timeout;
+    loop {
+        match file.try_lock() {
+            Ok(()) => {
+                tracing::debug!(path = ?lock_path, waited = ?started.elapsed(), "locked vault");
+                break;
+            }
+            Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                thread::sleep(Duration::from_millis(50));
+            }
+            Err(TryLockError::WouldBlock) => {
+                let mut holder = String::new();
+                let _ = file.read_to_string(&mut holder);
+                let holder = holder.trim();
+                let holder = if holder.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (pid {})", holder)
+                };
+                anyhow::bail!(
+                    "Vault at {:?} is locked by another ideavault process{}. \
+                     Gave up after {:.1}s; set {} to wait longer.",
+                    data_dir,
+                    holder,
+                    timeout.as_secs_f64(),
+                    LOCK_TIMEOUT_ENV
+                );
+            }
+            Err(TryLockError::Error(err)) => {
+                return Err(err).with_context(|| format!("Failed to lock vault: {:?}", lock_path));
+            }
+        }
+    }
+
+    // Record the owner so a blocked process can say who holds the lock.
+    file.set_len(0)?;
+    write!(file, "{}", std::process::id())?;
+    file.flush()?;
+
+    Ok(file)
+}
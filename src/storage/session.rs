@@ -0,0 +1,153 @@
+//! Per-command cache over [`Storage`].
+//!
+//! A [`Session`] loads each collection at most once, hands out shared or mutable
+//! access to it, and remembers which collections were borrowed mutably so that
+//! [`Session::flush`] writes only those, once, at the end of the command.
+
+use super::Storage;
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use anyhow::{Context, Result};
+use std::cell::OnceCell;
+
+pub struct Session<'a> {
+    storage: &'a Storage,
+    ideas: OnceCell<Vec<Idea>>,
+    projects: OnceCell<Vec<Project>>,
+    tags: OnceCell<Vec<Tag>>,
+    tasks: OnceCell<Vec<Task>>,
+    dirty: Dirty,
+}
+
+#[derive(Default)]
+struct Dirty {
+    ideas: bool,
+    projects: bool,
+    tags: bool,
+    tasks: bool,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(storage: &'a Storage) -> Self {
+        Self {
+            storage,
+            ideas: OnceCell::new(),
+            projects: OnceCell::new(),
+            tags: OnceCell::new(),
+            tasks: OnceCell::new(),
+            dirty: Dirty::default(),
+        }
+    }
+
+    pub fn storage(&self) -> &'a Storage {
+        self.storage
+    }
+
+    pub fn ideas(&self) -> Result<&[Idea]> {
+        cached(&self.ideas, || {
+            self.storage.load_ideas().context("Failed to load ideas")
+        })
+        .map(Vec::as_slice)
+    }
+
+    /// Mutable access to the ideas; they will be written on the next flush.
+    pub fn ideas_mut(&mut self) -> Result<&mut Vec<Idea>> {
+        self.ideas()?;
+        self.dirty.ideas = true;
+        Ok(self.ideas.get_mut().expect("ideas were loaded above"))
+    }
+
+    pub fn projects(&self) -> Result<&[Project]> {
+        cached(&self.projects, || {
+            self.storage
+                .load_projects()
+                .context("Failed to load projects")
+        })
+        .map(Vec::as_slice)
+    }
+
+    /// Mutable access to the projects; they will be written on the next flush.
+    pub fn projects_mut(&mut self) -> Result<&mut Vec<Project>> {
+        self.projects()?;
+        self.dirty.projects = true;
+        Ok(self.projects.get_mut().expect("projects were loaded above"))
+    }
+
+    pub fn tags(&self) -> Result<&[Tag]> {
+        cached(&self.tags, || {
+            self.storage.load_tags().context("Failed to load tags")
+        })
+        .map(Vec::as_slice)
+    }
+
+    /// Mutable access to the tags; they will be written on the next flush.
+    pub fn tags_mut(&mut self) -> Result<&mut Vec<Tag>> {
+        self.tags()?;
+        self.dirty.tags = true;
+        Ok(self.tags.get_mut().expect("tags were loaded above"))
+    }
+
+    pub fn tasks(&self) -> Result<&[Task]> {
+        cached(&self.tasks, || {
+            self.storage.load_tasks().context("Failed to load tasks")
+        })
+        .map(Vec::as_slice)
+    }
+
+    /// Mutable access to the tasks; they will be written on the next flush.
+    pub fn tasks_mut(&mut self) -> Result<&mut Vec<Task>> {
+        self.tasks()?;
+        self.dirty.tasks = true;
+        Ok(self.tasks.get_mut().expect("tasks were loaded above"))
+    }
+
+    /// Whether any collection has been borrowed mutably since the last flush.
+    pub fn is_dirty(&self) -> bool {
+        let Dirty {
+            ideas,
+            projects,
+            tags,
+            tasks,
+        } = self.dirty;
+        ideas || projects || tags || tasks
+    }
+
+    /// Save every dirty collection. Clean ones are never written.
+    pub fn flush(&mut self) -> Result<()> {
+        if let (true, Some(ideas)) = (self.dirty.ideas, self.ideas.get()) {
+            self.storage
+                .save_ideas(ideas)
+                .context("Failed to save ideas")?;
+            self.dirty.ideas = false;
+        }
+        if let (true, Some(projects)) = (self.dirty.projects, self.projects.get()) {
+            self.storage
+                .save_projects(projects)
+                .context("Failed to save projects")?;
+            self.dirty.projects = false;
+        }
+        if let (true, Some(tags)) = (self.dirty.tags, self.tags.get()) {
+            self.storage
+                .save_tags(tags)
+                .context("Failed to save tags")?;
+            self.dirty.tags = false;
+        }
+        if let (true, Some(tasks)) = (self.dirty.tasks, self.tasks.get()) {
+            self.storage
+                .save_tasks(tasks)
+                .context("Failed to save tasks")?;
+            self.dirty.tasks = false;
+        }
+        Ok(())
+    }
+}
+
+fn cached<T>(cell: &OnceCell<Vec<T>>, load: impl FnOnce() -> Result<Vec<T>>) -> Result<&Vec<T>> {
+    if let Some(items) = cell.get() {
+        return Ok(items);
+    }
+    let items = load()?;
+    Ok(cell.get_or_init(|| items))
+}
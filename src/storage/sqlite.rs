@@ -0,0 +1,310 @@
+//! SQLite persistence for IdeaVault
+//!
+//! Each entity type lives in its own table. The full entity is stored as a JSON
+//! document in the `data` column, while frequently filtered fields (status, dates,
+//! links) are mirrored into indexed columns.
+
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::tag::Tag;
+use crate::models::task::Task;
+use crate::storage::StorageBackend;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS ideas (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_ideas_status ON ideas(status);
+CREATE INDEX IF NOT EXISTS idx_ideas_updated_at ON ideas(updated_at);
+
+CREATE TABLE IF NOT EXISTS projects (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_projects_status ON projects(status);
+CREATE INDEX IF NOT EXISTS idx_projects_updated_at ON projects(updated_at);
+
+CREATE TABLE IF NOT EXISTS tasks (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    status TEXT NOT NULL,
+    priority TEXT NOT NULL,
+    due_date TEXT,
+    project_id TEXT,
+    idea_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status);
+CREATE INDEX IF NOT EXISTS idx_tasks_due_date ON tasks(due_date);
+CREATE INDEX IF NOT EXISTS idx_tasks_project_id ON tasks(project_id);
+CREATE INDEX IF NOT EXISTS idx_tasks_idea_id ON tasks(idea_id);
+
+CREATE TABLE IF NOT EXISTS tags (
+    name TEXT PRIMARY KEY,
+    color TEXT
+);
+";
+
+pub struct SqliteBackend {
+    conn: Connection,
+    path: PathBuf,
+}
+
+impl SqliteBackend {
+    /// Open (or create) the database at `path` and make sure the schema exists.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database: {:?}", path))?;
+        conn.execute_batch(SCHEMA)
+            .with_context(|| "Failed to initialize SQLite schema")?;
+        tracing::debug!(?path, "opened SQLite database");
+        Ok(Self {
+            conn,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Load every JSON document from `table`, preserving insertion order.
+    fn load_documents<T: DeserializeOwned>(&self, table: &str) -> Result<Vec<T>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT data FROM {} ORDER BY rowid", table))?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()
+            .with_context(|| format!("Failed to load {} from SQLite", table))?;
+
+        rows.iter()
+            .map(|data| {
+                serde_json::from_str(data)
+                    .with_context(|| format!("Failed to parse {} row from SQLite", table))
+            })
+            .collect()
+    }
+
+    /// Load the JSON document with `id` from `table`, if there is one.
+    fn load_document<T: DeserializeOwned>(&self, table: &str, id: Uuid) -> Result<Option<T>> {
+        let data: Option<String> = self
+            .conn
+            .query_row(
+                &format!("SELECT data FROM {} WHERE id = ?1", table),
+                params![id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()
+            .with_context(|| format!("Failed to load {} row from SQLite", table))?;
+        data.map(|data| {
+            serde_json::from_str(&data)
+                .with_context(|| format!("Failed to parse {} row from SQLite", table))
+        })
+        .transpose()
+    }
+
+    /// Remove the row with `id` from `table`, returning whether it existed.
+    fn delete_document(&self, table: &str, id: Uuid) -> Result<bool> {
+        let deleted = self
+            .conn
+            .execute(
+                &format!("DELETE FROM {} WHERE id = ?1", table),
+                params![id.to_string()],
+            )
+            .with_context(|| format!("Failed to delete {} row from SQLite", table))?;
+        Ok(deleted > 0)
+    }
+}
+
+// Upserts keep an updated row's rowid, so collections load in the same order
+// after one of their entities changes.
+
+fn put_idea(conn: &Connection, idea: &Idea) -> Result<()> {
+    conn.prepare_cached(
+        "INSERT INTO ideas (id, title, status, created_at, updated_at, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(id) DO UPDATE SET title = excluded.title, status = excluded.status,
+             created_at = excluded.created_at, updated_at = excluded.updated_at,
+             data = excluded.data",
+    )?
+    .execute(params![
+        idea.id.to_string(),
+        idea.title,
+        idea.status.to_string(),
+        idea.created_at.to_rfc3339(),
+        idea.updated_at.to_rfc3339(),
+        serde_json::to_string(idea)?,
+    ])?;
+    Ok(())
+}
+
+fn put_project(conn: &Connection, project: &Project) -> Result<()> {
+    conn.prepare_cached(
+        "INSERT INTO projects (id, title, status, created_at, updated_at, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(id) DO UPDATE SET title = excluded.title, status = excluded.status,
+             created_at = excluded.created_at, updated_at = excluded.updated_at,
+             data = excluded.data",
+    )?
+    .execute(params![
+        project.id.to_string(),
+        project.title,
+        project.status.to_string(),
+        project.created_at.to_rfc3339(),
+        project.updated_at.to_rfc3339(),
+        serde_json::to_string(project)?,
+    ])?;
+    Ok(())
+}
+
+fn put_task(conn: &Connection, task: &Task) -> Result<()> {
+    conn.prepare_cached(
+        "INSERT INTO tasks (id, title, status, priority, due_date, project_id, idea_id,
+                            created_at, updated_at, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+         ON CONFLICT(id) DO UPDATE SET title = excluded.title, status = excluded.status,
+             priority = excluded.priority, due_date = excluded.due_date,
+             project_id = excluded.project_id, idea_id = excluded.idea_id,
+             created_at = excluded.created_at, updated_at = excluded.updated_at,
+             data = excluded.data",
+    )?
+    .execute(params![
+        task.id.to_string(),
+        task.title,
+        task.status.to_string(),
+        task.priority.to_string(),
+        task.due_date.map(|d| d.to_rfc3339()),
+        task.project_id.map(|id| id.to_string()),
+        task.idea_id.map(|id| id.to_string()),
+        task.created_at.to_rfc3339(),
+        task.updated_at.to_rfc3339(),
+        serde_json::to_string(task)?,
+    ])?;
+    Ok(())
+}
+
+impl StorageBackend for SqliteBackend {
+    fn load_ideas(&self) -> Result<Vec<Idea>> {
+        self.load_documents("ideas")
+    }
+
+    fn save_ideas(&self, ideas: &[Idea]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM ideas", [])?;
+        for idea in ideas {
+            put_idea(&tx, idea)?;
+        }
+        tx.commit()
+            .with_context(|| "Failed to save ideas to SQLite")
+    }
+
+    fn load_idea(&self, id: Uuid) -> Result<Option<Idea>> {
+        self.load_document("ideas", id)
+    }
+
+    fn save_idea(&self, idea: &Idea) -> Result<()> {
+        put_idea(&self.conn, idea).with_context(|| "Failed to save idea to SQLite")
+    }
+
+    fn delete_idea(&self, id: Uuid) -> Result<bool> {
+        self.delete_document("ideas", id)
+    }
+
+    fn load_projects(&self) -> Result<Vec<Project>> {
+        self.load_documents("projects")
+    }
+
+    fn save_projects(&self, projects: &[Project]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM projects", [])?;
+        for project in projects {
+            put_project(&tx, project)?;
+        }
+        tx.commit()
+            .with_context(|| "Failed to save projects to SQLite")
+    }
+
+    fn load_project(&self, id: Uuid) -> Result<Option<Project>> {
+        self.load_document("projects", id)
+    }
+
+    fn save_project(&self, project: &Project) -> Result<()> {
+        put_project(&self.conn, project).with_context(|| "Failed to save project to SQLite")
+    }
+
+    fn delete_project(&self, id: Uuid) -> Result<bool> {
+        self.delete_document("projects", id)
+    }
+
+    fn load_tasks(&self) -> Result<Vec<Task>> {
+        self.load_documents("tasks")
+    }
+
+    fn save_tasks(&self, tasks: &[Task]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM tasks", [])?;
+        for task in tasks {
+            put_task(&tx, task)?;
+        }
+        tx.commit()
+            .with_context(|| "Failed to save tasks to SQLite")
+    }
+
+    fn load_task(&self, id: Uuid) -> Result<Option<Task>> {
+        self.load_document("tasks", id)
+    }
+
+    fn save_task(&self, task: &Task) -> Result<()> {
+        put_task(&self.conn, task).with_context(|| "Failed to save task to SQLite")
+    }
+
+    fn delete_task(&self, id: Uuid) -> Result<bool> {
+        self.delete_document("tasks", id)
+    }
+
+    fn load_tags(&self) -> Result<Vec<Tag>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, color FROM tags ORDER BY rowid")?;
+        let tags = stmt
+            .query_map([], |row| {
+                Ok(Tag {
+                    name: row.get(0)?,
+                    color: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<Tag>>>()
+            .with_context(|| "Failed to load tags from SQLite")?;
+        Ok(tags)
+    }
+
+    fn save_tags(&self, tags: &[Tag]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM tags", [])?;
+        {
+            let mut stmt =
+                tx.prepare("INSERT OR REPLACE INTO tags (name, color) VALUES (?1, ?2)")?;
+            for tag in tags {
+                stmt.execute(params![tag.name, tag.color])?;
+            }
+        }
+        tx.commit().with_context(|| "Failed to save tags to SQLite")
+    }
+
+    fn data_files(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
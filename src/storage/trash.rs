@@ -0,0 +1,62 @@
+//! Soft-deleted entities.
+//!
+//! Deleting an idea, project, or task moves it into `<data_dir>/trash.json`
+//! together with the time it was deleted, from where it can be restored until
+//! the trash is emptied or the entry is purged for being too old.
+
+use crate::models::idea::Idea;
+use crate::models::project::Project;
+use crate::models::task::Task;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An entity sitting in the trash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub deleted_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub item: TrashedItem,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "lowercase")]
+pub enum TrashedItem {
+    Idea(Idea),
+    Project(Project),
+    Task(Task),
+}
+
+impl TrashEntry {
+    pub fn new(item: TrashedItem) -> Self {
+        Self {
+            deleted_at: Utc::now(),
+            item,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match &self.item {
+            TrashedItem::Idea(idea) => idea.id,
+            TrashedItem::Project(project) => project.id,
+            TrashedItem::Task(task) => task.id,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match &self.item {
+            TrashedItem::Idea(idea) => &idea.title,
+            TrashedItem::Project(project) => &project.title,
+            TrashedItem::Task(task) => &task.title,
+        }
+    }
+
+    /// "idea", "project", or "task"
+    pub fn kind(&self) -> &'static str {
+        match &self.item {
+            TrashedItem::Idea(_) => "idea",
+            TrashedItem::Project(_) => "project",
+            TrashedItem::Task(_) => "task",
+        }
+    }
+}
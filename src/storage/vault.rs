@@ -0,0 +1,173 @@
+//! Named vaults.
+//!
+//! Each vault is an independent data directory. The registry in the platform
+//! config directory (`vaults.json`) maps names to directories and remembers
+//! which vault is current. The `default` vault is always available and lives in
+//! the configured `data_dir`, the platform data directory unless changed.
+
+use super::json::write_atomic;
+use crate::models::config::Config;
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The vault used when no other has been created or selected.
+pub const DEFAULT_VAULT: &str = "default";
+
+const REGISTRY_FILE: &str = "vaults.json";
+
+static SELECTED: OnceLock<String> = OnceLock::new();
+
+/// Use the vault `name` for this process instead of the current one (`--vault`).
+pub fn select(name: impl Into<String>) {
+    let _ = SELECTED.set(name.into());
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultRegistry {
+    /// The vault commands use unless another is selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+
+    /// Data directory of every vault other than the default one
+    #[serde(default)]
+    pub vaults: BTreeMap<String, PathBuf>,
+
+    #[serde(skip)]
+    path: PathBuf,
+
+    #[serde(skip)]
+    default_dir: PathBuf,
+}
+
+impl VaultRegistry {
+    /// Load the registry from the platform config directory.
+    pub fn open() -> Result<Self> {
+        let config = Config::load().context("Failed to load config")?;
+        Self::open_with(&config)
+    }
+
+    /// Load the registry from the platform config directory, with the default
+    /// vault in `config`'s data directory.
+    pub fn open_with(config: &Config) -> Result<Self> {
+        let proj_dirs = ProjectDirs::from("com", "ideavault", "ideavault")
+            .context("Failed to get project directories")?;
+        Self::open_at(
+            proj_dirs.config_dir().join(REGISTRY_FILE),
+            config.resolved_data_dir(),
+        )
+    }
+
+    /// Load the registry stored at `path`, with the default vault in `default_dir`.
+    /// A missing file is an empty registry.
+    pub fn open_at(path: PathBuf, default_dir: PathBuf) -> Result<Self> {
+        let mut registry: VaultRegistry = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read vault registry: {:?}", path))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse vault registry: {:?}", path))?
+        } else {
+            VaultRegistry::default()
+        };
+        registry.path = path;
+        registry.default_dir = default_dir;
+        Ok(registry)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create config directory: {:?}", parent))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&self.path, content.as_bytes())
+    }
+
+    /// The vault used when none is selected on the command line.
+    pub fn current_name(&self) -> &str {
+        self.current.as_deref().unwrap_or(DEFAULT_VAULT)
+    }
+
+    /// The vault this process should use: the one picked with [`select`], or
+    /// the current one.
+    pub fn active_name(&self) -> &str {
+        SELECTED
+            .get()
+            .map(String::as_str)
+            .unwrap_or_else(|| self.current_name())
+    }
+
+    /// The data directory of the vault `name`.
+    pub fn data_dir(&self, name: &str) -> Result<PathBuf> {
+        if name == DEFAULT_VAULT {
+            return Ok(self.default_dir.clone());
+        }
+        self.vaults.get(name).cloned().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Vault '{}' does not exist. Create it with: ideavault vault new {}",
+                name,
+                name
+            )
+        })
+    }
+
+    /// The data directory of the active vault.
+    pub fn active_data_dir(&self) -> Result<PathBuf> {
+        self.data_dir(self.active_name())
+    }
+
+    /// Every vault, the default one first, with its data directory.
+    pub fn list(&self) -> Vec<(&str, &Path)> {
+        let mut vaults = vec![(DEFAULT_VAULT, self.default_dir.as_path())];
+        vaults.extend(
+            self.vaults
+                .iter()
+                .map(|(name, dir)| (name.as_str(), dir.as_path())),
+        );
+        vaults
+    }
+
+    /// Register a new vault. Without `data_dir` it is kept under the default
+    /// vault's directory in `vaults/<name>`. Returns the vault's directory.
+    pub fn create(&mut self, name: &str, data_dir: Option<PathBuf>) -> Result<PathBuf> {
+        validate_name(name)?;
+        if name == DEFAULT_VAULT || self.vaults.contains_key(name) {
+            anyhow::bail!("Vault '{}' already exists", name);
+        }
+
+        let data_dir = data_dir.unwrap_or_else(|| self.default_dir.join("vaults").join(name));
+        if let Some((existing, _)) = self.list().into_iter().find(|(_, dir)| *dir == data_dir) {
+            anyhow::bail!("{:?} is already used by vault '{}'", data_dir, existing);
+        }
+
+        fs::create_dir_all(&data_dir)
+            .with_context(|| format!("Failed to create data directory: {:?}", data_dir))?;
+        self.vaults.insert(name.to_string(), data_dir.clone());
+        Ok(data_dir)
+    }
+
+    /// Make `name` the current vault.
+    pub fn switch(&mut self, name: &str) -> Result<()> {
+        self.data_dir(name)?;
+        self.current = (name != DEFAULT_VAULT).then(|| name.to_string());
+        Ok(())
+    }
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!(
+            "Invalid vault name: '{}'. Use letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
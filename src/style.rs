@@ -0,0 +1,142 @@
+//! ANSI colors for terminal output.
+//!
+//! Colors are off until [`init`] turns them on, which happens only when the
+//! `use_colors` setting is on, `--no-color` wasn't given, `NO_COLOR` is unset,
+//! and stdout is a terminal. Every helper returns plain text otherwise.
+
+use crate::models::idea::IdeaStatus;
+use crate::models::project::ProjectStatus;
+use crate::models::task::{TaskPriority, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Decide once per process whether output is colored; `wanted` is the
+/// `use_colors` setting unless `--no-color` overrode it.
+pub fn init(wanted: bool) {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    set_enabled(wanted && !no_color && io::stdout().is_terminal());
+}
+
+/// Turn colors on or off for this process, regardless of the terminal.
+pub fn set_enabled(enabled: bool) {
+    let _ = ENABLED.set(enabled);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.get().copied().unwrap_or(false)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Blue => "34",
+            Color::Magenta => "35",
+            Color::Cyan => "36",
+            Color::Gray => "90",
+        }
+    }
+}
+
+/// Colors tags are drawn in, picked by name so a tag keeps its color.
+const TAG_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Red,
+];
+
+/// `text` in `color`.
+pub fn paint(text: impl Display, color: Color) -> String {
+    if enabled() {
+        format!("\x1b[{}m{}\x1b[0m", color.code(), text)
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn bold(text: impl Display) -> String {
+    if enabled() {
+        format!("\x1b[1m{}\x1b[0m", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Less important details, such as IDs.
+pub fn dim(text: impl Display) -> String {
+    paint(text, Color::Gray)
+}
+
+pub fn idea_status_color(status: &IdeaStatus) -> Color {
+    match status {
+        IdeaStatus::Inbox => Color::Yellow,
+        IdeaStatus::Brainstorming => Color::Magenta,
+        IdeaStatus::Active => Color::Cyan,
+        IdeaStatus::Completed => Color::Green,
+        IdeaStatus::Archived => Color::Gray,
+    }
+}
+
+pub fn project_status_color(status: &ProjectStatus) -> Color {
+    match status {
+        ProjectStatus::Planning => Color::Blue,
+        ProjectStatus::InProgress => Color::Cyan,
+        ProjectStatus::Completed => Color::Green,
+        ProjectStatus::OnHold => Color::Yellow,
+        ProjectStatus::Archived => Color::Gray,
+    }
+}
+
+/// The color configured for `status`, blue if it has none.
+pub fn task_status_color(status: &TaskStatus) -> Color {
+    status
+        .definition()
+        .and_then(|status| status.color)
+        .unwrap_or(Color::Blue)
+}
+
+/// The color configured for `priority`, blue if it has none.
+pub fn priority_color(priority: &TaskPriority) -> Color {
+    priority
+        .definition()
+        .and_then(|priority| priority.color)
+        .unwrap_or(Color::Blue)
+}
+
+/// The color of the tag `name`.
+pub fn tag_color(name: &str) -> Color {
+    let hash = name.bytes().fold(0usize, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(byte as usize)
+    });
+    TAG_COLORS[hash % TAG_COLORS.len()]
+}
+
+/// Tags joined with ", ", each in its own color.
+pub fn tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| paint(tag, tag_color(tag)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,127 @@
+//! The markers that start output lines, with a plain ASCII stand-in for each.
+//!
+//! Emoji are shown unless [`init`] turns them off, which happens when the
+//! `use_emoji` setting is off or `--no-emoji` was given. Terminals and log
+//! files that can't show emoji get the ASCII labels instead. Every marker is
+//! defined here, so output never mixes the two.
+
+use crate::models::idea::IdeaStatus;
+use crate::models::project::{MilestoneStatus, ProjectStatus};
+use std::fmt::{self, Display};
+use std::sync::OnceLock;
+
+static PLAIN: OnceLock<bool> = OnceLock::new();
+
+/// Decide once per process whether markers are emoji; `wanted` is the
+/// `use_emoji` setting unless `--no-emoji` overrode it.
+pub fn init(wanted: bool) {
+    let _ = PLAIN.set(!wanted);
+}
+
+/// Whether markers are plain ASCII.
+pub fn is_plain() -> bool {
+    PLAIN.get().copied().unwrap_or(false)
+}
+
+/// A marker, shown as its emoji or its ASCII label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    emoji: &'static str,
+    ascii: &'static str,
+}
+
+impl Symbol {
+    const fn new(emoji: &'static str, ascii: &'static str) -> Self {
+        Self { emoji, ascii }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        if is_plain() {
+            self.ascii
+        } else {
+            self.emoji
+        }
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Emoji drawn one column wide in most terminals carry a trailing space, so
+// the text after them lines up with the rest.
+
+pub const OK: Symbol = Symbol::new("✅", "[ok]");
+pub const CELEBRATE: Symbol = Symbol::new("🎉", "[done]");
+pub const ERROR: Symbol = Symbol::new("❌", "[x]");
+pub const WARNING: Symbol = Symbol::new("⚠️ ", "[!]");
+pub const LIST: Symbol = Symbol::new("📋", "[i]");
+pub const NOTES: Symbol = Symbol::new("📝", "[i]");
+pub const PREVIEW: Symbol = Symbol::new("🔍", "[i]");
+pub const PAGE: Symbol = Symbol::new("📄", "[i]");
+pub const ATTACHMENT: Symbol = Symbol::new("📎", "[i]");
+pub const HISTORY: Symbol = Symbol::new("📜", "[i]");
+pub const CHECKUP: Symbol = Symbol::new("🩺", "[i]");
+pub const BACKUP: Symbol = Symbol::new("💾", "[i]");
+pub const RECENT: Symbol = Symbol::new("🕒", "[i]");
+pub const STATUS: Symbol = Symbol::new("🚦", "[status]");
+pub const NEW: Symbol = Symbol::new("✨", "[+]");
+pub const EDITED: Symbol = Symbol::new("✏️ ", "[~]");
+pub const TRASH: Symbol = Symbol::new("🗑️ ", "[-]");
+pub const FIXED: Symbol = Symbol::new("🔧", "[fix]");
+pub const SYNC: Symbol = Symbol::new("🔄", "[sync]");
+pub const OPENED: Symbol = Symbol::new("🌐", "[open]");
+pub const GITHUB: Symbol = Symbol::new("🐙", "[github]");
+pub const FOLDER: Symbol = Symbol::new("📂", "[open]");
+pub const UNDO: Symbol = Symbol::new("⏪", "-");
+pub const INBOX: Symbol = Symbol::new("📥", "[inbox]");
+pub const IDEA: Symbol = Symbol::new("💡", "[idea]");
+pub const PROJECT: Symbol = Symbol::new("📁", "[project]");
+pub const TASK: Symbol = Symbol::new("📌", "[task]");
+pub const TAGS: Symbol = Symbol::new("🏷️ ", "tags:");
+pub const MILESTONE: Symbol = Symbol::new("🎯", "milestone:");
+pub const ID: Symbol = Symbol::new("🆔", "id:");
+pub const DATE: Symbol = Symbol::new("📅", "-");
+pub const DUE: Symbol = Symbol::new("⏰", "-");
+pub const SIZE: Symbol = Symbol::new("📦", "-");
+pub const IN_PROGRESS: Symbol = Symbol::new("🔄", "-");
+pub const COUNTS: Symbol = Symbol::new("📊", "-");
+
+pub fn idea_status(status: &IdeaStatus) -> Symbol {
+    match status {
+        IdeaStatus::Inbox => Symbol::new("📥", "[inbox]"),
+        IdeaStatus::Brainstorming => Symbol::new("🧠", "[brainstorming]"),
+        IdeaStatus::Active => Symbol::new("🚀", "[active]"),
+        IdeaStatus::Completed => Symbol::new("✅", "[completed]"),
+        IdeaStatus::Archived => Symbol::new("📦", "[archived]"),
+    }
+}
+
+pub fn project_status(status: &ProjectStatus) -> Symbol {
+    match status {
+        ProjectStatus::Planning => Symbol::new("📋", "[planning]"),
+        ProjectStatus::InProgress => Symbol::new("🚀", "[in progress]"),
+        ProjectStatus::Completed => Symbol::new("✅", "[completed]"),
+        ProjectStatus::OnHold => Symbol::new("⏸️", "[on hold]"),
+        ProjectStatus::Archived => Symbol::new("📦", "[archived]"),
+    }
+}
+
+pub fn milestone_status(status: MilestoneStatus) -> Symbol {
+    match status {
+        MilestoneStatus::Open => Symbol::new("⏳", "[open]"),
+        MilestoneStatus::Completed => Symbol::new("✅", "[completed]"),
+    }
+}
+
+/// A marker set in the config, such as a task status's emoji: `emoji`, or
+/// `name` in brackets when markers are plain.
+pub fn configured(emoji: &str, name: &str) -> String {
+    if is_plain() {
+        format!("[{}]", name.to_lowercase())
+    } else {
+        emoji.to_string()
+    }
+}
@@ -0,0 +1,60 @@
+//! Sync to a plain directory, such as a mounted network share or a folder
+//! kept in step by another tool.
+
+use super::SyncProvider;
+use crate::storage::json::write_atomic;
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+pub struct DirectoryProvider {
+    root: PathBuf,
+}
+
+impl DirectoryProvider {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        path.split('/')
+            .fold(self.root.clone(), |dir, part| dir.join(part))
+    }
+}
+
+impl SyncProvider for DirectoryProvider {
+    fn location(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let file = self.path(path);
+        match fs::read(&file) {
+            Ok(content) => Ok(Some(content)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed to read {:?}", file)),
+        }
+    }
+
+    fn put(&self, path: &str, content: &[u8]) -> Result<()> {
+        let file = self.path(path);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+        write_atomic(&file, content)
+    }
+
+    fn delete(&self, path: &str) -> Result<()> {
+        let file = self.path(path);
+        match fs::remove_file(&file) {
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                Err(err).with_context(|| format!("Failed to remove {:?}", file))
+            }
+            _ => Ok(()),
+        }
+    }
+}
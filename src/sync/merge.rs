@@ -0,0 +1,370 @@
+//! Merging a vault file that was changed on two machines.
+//!
+//! Collections are merged entity by entity and field by field against the
+//! version both sides last agreed on (the base): a field changed on one side
+//! only takes that side's value, and entities added or deleted on one side are
+//! added or deleted. Only a field changed differently on both sides, or an
+//! entity edited on one side and deleted on the other, is a [`Conflict`], which
+//! the caller's resolver settles. Without a base every differing field is one.
+
+use crate::storage::compression;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+
+const UPDATED_AT: &str = "updated_at";
+const MAX_VALUE_LEN: usize = 60;
+
+/// Which machine's version to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Local,
+    Remote,
+}
+
+/// Something both sides changed in ways that can't be combined.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub path: String,
+    /// The entity, e.g. `idea 1cc3e882 "Launch"`; `None` when the whole file conflicts
+    pub entity: Option<String>,
+    /// The field both sides changed; `None` when one side deleted the entity
+    pub field: Option<String>,
+    /// Each side's value, `None` if that side deleted it
+    pub local: Option<Value>,
+    pub remote: Option<Value>,
+    /// When each side last changed the entity or file, if known
+    pub local_updated: Option<DateTime<Utc>>,
+    pub remote_updated: Option<DateTime<Utc>>,
+}
+
+impl Conflict {
+    /// A conflict over a whole file that can't be merged. Each side's time is
+    /// when it last changed the file, or `None` if it deleted it.
+    pub fn file(
+        path: &str,
+        local_updated: Option<DateTime<Utc>>,
+        remote_updated: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            path: path.to_string(),
+            entity: None,
+            field: None,
+            local: None,
+            remote: None,
+            local_updated,
+            remote_updated,
+        }
+    }
+
+    /// The side changed most recently, by `updated_at`; local wins ties.
+    pub fn newest(&self) -> Side {
+        if self.remote_updated > self.local_updated {
+            Side::Remote
+        } else {
+            Side::Local
+        }
+    }
+
+    /// One line naming what conflicts, for prompts and reports.
+    pub fn describe(&self) -> String {
+        match (&self.entity, &self.field) {
+            (None, _) => self.path.clone(),
+            (Some(entity), Some(field)) => format!("{}, field {}", entity, field),
+            (Some(entity), None) => format!("{} (deleted on one side)", entity),
+        }
+    }
+
+    /// A short rendering of one side's value.
+    pub fn value(&self, side: Side) -> String {
+        let value = match side {
+            Side::Local => &self.local,
+            Side::Remote => &self.remote,
+        };
+        let text = match (&self.entity, &self.field, value) {
+            (None, _, _) => {
+                let updated = match side {
+                    Side::Local => self.local_updated,
+                    Side::Remote => self.remote_updated,
+                };
+                match updated {
+                    Some(_) => "(changed)".to_string(),
+                    None => "(deleted)".to_string(),
+                }
+            }
+            (Some(_), None, None) => "(deleted)".to_string(),
+            (Some(_), None, Some(_)) => "(edited)".to_string(),
+            (Some(_), Some(_), None | Some(Value::Null)) => "(none)".to_string(),
+            (Some(_), Some(_), Some(value)) => value.to_string(),
+        };
+        if text.chars().count() > MAX_VALUE_LEN {
+            let truncated: String = text.chars().take(MAX_VALUE_LEN - 3).collect();
+            format!("{}...", truncated)
+        } else {
+            text
+        }
+    }
+}
+
+/// Decides a conflict; an error aborts the merge.
+pub type Resolver<'a> = dyn FnMut(&Conflict) -> Result<Side> + 'a;
+
+/// Merge two versions of the vault file at `path` (relative to the data
+/// directory). Returns `None` for files that can't be merged, such as a
+/// SQLite database, which must be resolved as a whole.
+pub fn merge_file(
+    path: &str,
+    base: Option<&[u8]>,
+    local: &[u8],
+    remote: &[u8],
+    resolve: &mut Resolver,
+) -> Result<Option<Vec<u8>>> {
+    if path == "history.jsonl" || path == "events.jsonl" {
+        return Ok(Some(merge_lines(local, remote)));
+    }
+    let Some(kind) = FileKind::of(path) else {
+        return Ok(None);
+    };
+
+    let compression = compression::detect(local);
+    let parse = |bytes: &[u8]| -> Result<Value> {
+        let bytes = compression::decode(bytes)?;
+        serde_json::from_slice(&bytes).with_context(|| format!("Failed to parse {}", path))
+    };
+    let base = base.map(parse).transpose()?;
+    let local = parse(local)?;
+    let remote = parse(remote)?;
+
+    let single = local.is_object();
+    let items = |value: Option<Value>| match value {
+        Some(Value::Array(items)) => items,
+        Some(Value::Object(object)) => vec![Value::Object(object)],
+        _ => Vec::new(),
+    };
+    let merged = merge_items(
+        path,
+        kind,
+        &items(base),
+        &items(Some(local)),
+        &items(Some(remote)),
+        resolve,
+    )?;
+
+    let merged = match (single, merged.len()) {
+        (true, 1) => merged.into_iter().next().unwrap_or_default(),
+        _ => Value::Array(merged),
+    };
+    let content = serde_json::to_vec_pretty(&merged)?;
+    compression::encode(&content, compression).map(Some)
+}
+
+/// Keep every line of `ours`, followed by the lines only `theirs` has.
+pub fn merge_lines(ours: &[u8], theirs: &[u8]) -> Vec<u8> {
+    let ours = String::from_utf8_lossy(ours);
+    let theirs = String::from_utf8_lossy(theirs);
+    let known: BTreeSet<&str> = ours.lines().collect();
+
+    let mut merged = String::new();
+    for line in ours
+        .lines()
+        .chain(theirs.lines().filter(|line| !known.contains(line)))
+    {
+        if !line.trim().is_empty() {
+            merged.push_str(line);
+            merged.push('\n');
+        }
+    }
+    merged.into_bytes()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FileKind {
+    Entity(&'static str),
+    Tag,
+    Trash,
+}
+
+impl FileKind {
+    /// The kind of a collection file (`ideas.json`) or, in the indexed layout,
+    /// of a single entity's file (`ideas/<id>.json`).
+    fn of(path: &str) -> Option<Self> {
+        let (name, indexed) = match path.split_once('/') {
+            Some((dir, file)) if !file.contains('/') && file.ends_with(".json") => (dir, true),
+            Some(_) => return None,
+            None => (path.strip_suffix(".json")?, false),
+        };
+        match (name, indexed) {
+            ("ideas", _) => Some(FileKind::Entity("idea")),
+            ("projects", _) => Some(FileKind::Entity("project")),
+            ("tasks", _) => Some(FileKind::Entity("task")),
+            ("tags", false) => Some(FileKind::Tag),
+            ("trash", false) => Some(FileKind::Trash),
+            _ => None,
+        }
+    }
+
+    /// What identifies an item across versions.
+    fn key(&self, item: &Value) -> String {
+        match self {
+            FileKind::Entity(_) => string_field(item, "id"),
+            FileKind::Tag => string_field(item, "name"),
+            // Trash entries never change, so the entry itself is its identity
+            FileKind::Trash => item.to_string(),
+        }
+    }
+
+    fn label(&self, item: &Value) -> String {
+        match self {
+            FileKind::Entity(kind) => {
+                let id: String = string_field(item, "id").chars().take(8).collect();
+                format!("{} {} \"{}\"", kind, id, string_field(item, "title"))
+            }
+            FileKind::Tag => format!("tag \"{}\"", string_field(item, "name")),
+            FileKind::Trash => "trash entry".to_string(),
+        }
+    }
+}
+
+fn string_field(item: &Value, field: &str) -> String {
+    item.get(field)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn updated_at(item: &Value) -> Option<DateTime<Utc>> {
+    item.get(UPDATED_AT)?.as_str()?.parse().ok()
+}
+
+fn merge_items(
+    path: &str,
+    kind: FileKind,
+    base: &[Value],
+    local: &[Value],
+    remote: &[Value],
+    resolve: &mut Resolver,
+) -> Result<Vec<Value>> {
+    let base: HashMap<String, &Value> = base.iter().map(|item| (kind.key(item), item)).collect();
+    let remote_by_key: HashMap<String, &Value> =
+        remote.iter().map(|item| (kind.key(item), item)).collect();
+    let local_keys: BTreeSet<String> = local.iter().map(|item| kind.key(item)).collect();
+
+    let mut merged = Vec::new();
+    for item in local {
+        let key = kind.key(item);
+        let original = base.get(&key).copied();
+        match (remote_by_key.get(&key), original) {
+            (Some(theirs), _) => {
+                merged.push(merge_entity(path, kind, original, item, theirs, resolve)?)
+            }
+            // Added here
+            (None, None) => merged.push(item.clone()),
+            // Deleted there and untouched here
+            (None, Some(original)) if original == item => {}
+            (None, Some(_)) => {
+                let conflict = deletion(path, kind, Some(item), None);
+                if resolve(&conflict)? == Side::Local {
+                    merged.push(item.clone());
+                }
+            }
+        }
+    }
+
+    for item in remote {
+        let key = kind.key(item);
+        if local_keys.contains(&key) {
+            continue;
+        }
+        match base.get(&key) {
+            None => merged.push(item.clone()),
+            Some(original) if *original == item => {}
+            Some(_) => {
+                let conflict = deletion(path, kind, None, Some(item));
+                if resolve(&conflict)? == Side::Remote {
+                    merged.push(item.clone());
+                }
+            }
+        }
+    }
+    Ok(merged)
+}
+
+fn deletion(path: &str, kind: FileKind, local: Option<&Value>, remote: Option<&Value>) -> Conflict {
+    let item = local.or(remote).cloned().unwrap_or_default();
+    Conflict {
+        path: path.to_string(),
+        entity: Some(kind.label(&item)),
+        field: None,
+        local: local.cloned(),
+        remote: remote.cloned(),
+        // The surviving side was edited after the other side last saw it
+        local_updated: local.and_then(updated_at),
+        remote_updated: remote.and_then(updated_at),
+    }
+}
+
+fn merge_entity(
+    path: &str,
+    kind: FileKind,
+    base: Option<&Value>,
+    local: &Value,
+    remote: &Value,
+    resolve: &mut Resolver,
+) -> Result<Value> {
+    if local == remote {
+        return Ok(local.clone());
+    }
+    let (Some(ours), Some(theirs)) = (local.as_object(), remote.as_object()) else {
+        let conflict = Conflict {
+            field: Some("value".to_string()),
+            ..deletion(path, kind, Some(local), Some(remote))
+        };
+        return Ok(match resolve(&conflict)? {
+            Side::Local => local.clone(),
+            Side::Remote => remote.clone(),
+        });
+    };
+    let original = base.and_then(Value::as_object);
+
+    let fields: Vec<&String> = ours
+        .keys()
+        .chain(theirs.keys().filter(|field| !ours.contains_key(*field)))
+        .collect();
+    let mut merged = Map::new();
+    for field in fields {
+        let mine = ours.get(field);
+        let other = theirs.get(field);
+        let value = if field == UPDATED_AT {
+            if updated_at(remote) > updated_at(local) {
+                other
+            } else {
+                mine
+            }
+        } else if mine == other {
+            mine
+        } else if original.is_some_and(|original| original.get(field) == mine) {
+            other
+        } else if original.is_some_and(|original| original.get(field) == other) {
+            mine
+        } else {
+            let conflict = Conflict {
+                path: path.to_string(),
+                entity: Some(kind.label(local)),
+                field: Some(field.clone()),
+                local: mine.cloned(),
+                remote: other.cloned(),
+                local_updated: updated_at(local),
+                remote_updated: updated_at(remote),
+            };
+            match resolve(&conflict)? {
+                Side::Local => mine,
+                Side::Remote => other,
+            }
+        };
+        if let Some(value) = value {
+            merged.insert(field.clone(), value.clone());
+        }
+    }
+    Ok(Value::Object(merged))
+}
@@ -0,0 +1,388 @@
+//! File-based sync for people who don't want git.
+//!
+//! A [`SyncProvider`] stores the vault's files somewhere else: a WebDAV server,
+//! or a directory such as a mounted network share. Next to the files the remote
+//! holds a manifest recording when each one was last uploaded, and the vault
+//! remembers in `.sync-state.json` what both sides looked like after the last
+//! sync. Comparing the three tells which side changed a file since then; a file
+//! changed on both sides is merged (see [`merge`]) against the copy kept from
+//! the last sync.
+
+use crate::storage::json::write_atomic;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub mod directory;
+pub mod merge;
+pub mod webdav;
+
+pub use directory::DirectoryProvider;
+pub use merge::{Conflict, Resolver, Side};
+pub use webdav::WebDavProvider;
+
+/// Where a vault's sync settings are kept, inside its data directory.
+pub const CONFIG_FILE: &str = "sync.json";
+const STATE_FILE: &str = ".sync-state.json";
+/// Copies of the files as of the last sync, used as the base when merging
+const BASE_DIR: &str = ".sync-base";
+/// Name of the manifest on the remote.
+pub const MANIFEST_FILE: &str = "ideavault-sync.json";
+/// Environment variable holding the password for providers that need one.
+pub const PASSWORD_ENV: &str = "IDEAVAULT_SYNC_PASSWORD";
+
+/// Hidden files that are part of the vault; other dotfiles are local only.
+const SYNCED_DOTFILES: [&str; 1] = [".layout"];
+const LOCAL_ONLY: [&str; 2] = [CONFIG_FILE, "backups"];
+
+/// Remote storage for the vault's files, addressed by `/`-separated paths
+/// relative to the data directory.
+pub trait SyncProvider {
+    /// Where the files are kept, for messages.
+    fn location(&self) -> String;
+
+    /// The content of `path`, or `None` if it doesn't exist.
+    fn get(&self, path: &str) -> Result<Option<Vec<u8>>>;
+
+    fn put(&self, path: &str, content: &[u8]) -> Result<()>;
+
+    /// Remove `path`; removing a missing file is not an error.
+    fn delete(&self, path: &str) -> Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    Git,
+    WebDav,
+    Directory,
+}
+
+impl FromStr for ProviderKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "git" => Ok(ProviderKind::Git),
+            "webdav" | "dav" => Ok(ProviderKind::WebDav),
+            "directory" | "dir" => Ok(ProviderKind::Directory),
+            _ => Err(anyhow::anyhow!(
+                "Invalid provider: {}. Valid options: git, webdav, directory",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ProviderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderKind::Git => write!(f, "git"),
+            ProviderKind::WebDav => write!(f, "webdav"),
+            ProviderKind::Directory => write!(f, "directory"),
+        }
+    }
+}
+
+/// The provider a vault syncs with, saved in [`CONFIG_FILE`]. Vaults without
+/// one sync through git.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub provider: ProviderKind,
+    /// WebDAV collection URL, or directory path
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+impl SyncConfig {
+    pub fn load(data_dir: &Path) -> Result<Option<Self>> {
+        let path = data_dir.join(CONFIG_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("Failed to read {:?}", path)),
+        };
+        serde_json::from_str(&content)
+            .map(Some)
+            .with_context(|| format!("Invalid sync settings in {:?}", path))
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&data_dir.join(CONFIG_FILE), content.as_bytes())
+    }
+
+    /// Forget the saved provider, so the vault syncs through git again.
+    pub fn remove(data_dir: &Path) -> Result<()> {
+        match fs::remove_file(data_dir.join(CONFIG_FILE)) {
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                Err(err).context("Failed to remove sync settings")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Connect to the configured provider. The password, if any, is read from
+    /// [`PASSWORD_ENV`] rather than stored.
+    pub fn provider(&self) -> Result<Box<dyn SyncProvider>> {
+        match self.provider {
+            ProviderKind::Git => anyhow::bail!("Git remotes are synced with git itself"),
+            ProviderKind::WebDav => {
+                let mut provider = WebDavProvider::new(&self.url);
+                if let Some(username) = &self.username {
+                    let password = std::env::var(PASSWORD_ENV).unwrap_or_default();
+                    provider = provider.with_credentials(username, &password);
+                }
+                Ok(Box::new(provider))
+            }
+            ProviderKind::Directory => Ok(Box::new(DirectoryProvider::new(&self.url))),
+        }
+    }
+}
+
+/// What one sync exchanged with the remote: commits for git, files otherwise.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub pulled: usize,
+    pub pushed: usize,
+    /// Files changed on both sides, which were merged
+    pub conflicts: Vec<String>,
+}
+
+/// When each file was last uploaded, as recorded on the remote.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: BTreeMap<String, DateTime<Utc>>,
+}
+
+/// Both sides of a file as of the last sync.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct SyncedFile {
+    local: DateTime<Utc>,
+    remote: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    files: BTreeMap<String, SyncedFile>,
+}
+
+/// How one file is brought up to date.
+enum Transfer {
+    Upload,
+    Download,
+    DeleteLocal,
+    DeleteRemote,
+    /// Both sides changed it; write the merged content to both
+    Merge(Vec<u8>),
+    /// Both sides already match
+    Keep,
+}
+
+/// Bring the files in `data_dir` and on `provider` up to date with each other.
+/// Files changed on both sides are merged, with `resolve` deciding conflicts.
+pub fn sync_files(
+    data_dir: &Path,
+    provider: &dyn SyncProvider,
+    resolve: &mut Resolver,
+) -> Result<SyncSummary> {
+    let mut manifest: Manifest = match provider.get(MANIFEST_FILE)? {
+        Some(content) => serde_json::from_slice(&content).context("Invalid remote manifest")?,
+        None => Manifest::default(),
+    };
+    let state_path = data_dir.join(STATE_FILE);
+    let mut state: SyncState = match fs::read(&state_path) {
+        Ok(content) => serde_json::from_slice(&content).context("Invalid sync state")?,
+        Err(err) if err.kind() == ErrorKind::NotFound => SyncState::default(),
+        Err(err) => return Err(err).context("Failed to read sync state"),
+    };
+    let local = local_files(data_dir)?;
+
+    let paths: BTreeSet<String> = local
+        .keys()
+        .chain(manifest.files.keys())
+        .chain(state.files.keys())
+        .cloned()
+        .collect();
+
+    let mut summary = SyncSummary::default();
+    let mut manifest_changed = false;
+    for path in paths {
+        let file = data_dir.join(&path);
+        let local_modified = local.get(&path).copied();
+        let remote_modified = manifest.files.get(&path).copied();
+        let last = state.files.get(&path).copied();
+        let local_changed = local_modified != last.map(|last| last.local);
+        let remote_changed = remote_modified != last.map(|last| last.remote);
+
+        let transfer = match (local_changed, remote_changed) {
+            (false, false) => continue,
+            (true, false) if local_modified.is_some() => Transfer::Upload,
+            (true, false) => Transfer::DeleteRemote,
+            (false, true) if remote_modified.is_some() => Transfer::Download,
+            (false, true) => Transfer::DeleteLocal,
+            (true, true) => {
+                let ours = local_modified.map(|_| read(&file)).transpose()?;
+                let theirs = match remote_modified {
+                    Some(_) => provider.get(&path)?,
+                    None => None,
+                };
+                if ours == theirs {
+                    // The same change, or deletion, was made on both sides
+                    Transfer::Keep
+                } else {
+                    summary.conflicts.push(path.clone());
+                    let merged = match (&ours, &theirs) {
+                        (Some(ours), Some(theirs)) => {
+                            let base = read_base(data_dir, &path)?;
+                            merge::merge_file(&path, base.as_deref(), ours, theirs, resolve)?
+                        }
+                        _ => None,
+                    };
+                    match merged {
+                        Some(content) => Transfer::Merge(content),
+                        None => {
+                            let conflict = Conflict::file(&path, local_modified, remote_modified);
+                            match (resolve(&conflict)?, ours.is_some(), theirs.is_some()) {
+                                (Side::Local, true, _) => Transfer::Upload,
+                                (Side::Local, false, _) => Transfer::DeleteRemote,
+                                (Side::Remote, _, true) => Transfer::Download,
+                                (Side::Remote, _, false) => Transfer::DeleteLocal,
+                            }
+                        }
+                    }
+                }
+            }
+        };
+
+        match transfer {
+            Transfer::Upload => {
+                provider.put(&path, &read(&file)?)?;
+                manifest.files.insert(path.clone(), Utc::now());
+                manifest_changed = true;
+                summary.pushed += 1;
+            }
+            Transfer::DeleteRemote => {
+                provider.delete(&path)?;
+                manifest.files.remove(&path);
+                manifest_changed = true;
+                summary.pushed += 1;
+            }
+            Transfer::Download => {
+                let content = provider
+                    .get(&path)?
+                    .with_context(|| format!("{} is missing from the remote", path))?;
+                write_file(&file, &content)?;
+                summary.pulled += 1;
+            }
+            Transfer::DeleteLocal => {
+                fs::remove_file(&file).with_context(|| format!("Failed to remove {:?}", file))?;
+                summary.pulled += 1;
+            }
+            Transfer::Keep => {}
+            Transfer::Merge(content) => {
+                write_file(&file, &content)?;
+                provider.put(&path, &content)?;
+                manifest.files.insert(path.clone(), Utc::now());
+                manifest_changed = true;
+                summary.pulled += 1;
+                summary.pushed += 1;
+            }
+        }
+
+        // Remember what both sides now hold, as the base for future merges
+        let base = base_path(data_dir, &path);
+        match (modified(&file)?, manifest.files.get(&path)) {
+            (Some(local), Some(&remote)) => {
+                state.files.insert(path, SyncedFile { local, remote });
+                write_file(&base, &read(&file)?)?;
+            }
+            _ => {
+                state.files.remove(&path);
+                if base.exists() {
+                    fs::remove_file(&base)
+                        .with_context(|| format!("Failed to remove {:?}", base))?;
+                }
+            }
+        }
+    }
+
+    if manifest_changed {
+        provider.put(MANIFEST_FILE, &serde_json::to_vec_pretty(&manifest)?)?;
+    }
+    write_atomic(&state_path, &serde_json::to_vec_pretty(&state)?)?;
+    Ok(summary)
+}
+
+/// Where the content of `path` as of the last sync is kept.
+fn base_path(data_dir: &Path, path: &str) -> PathBuf {
+    path.split('/')
+        .fold(data_dir.join(BASE_DIR), |dir, part| dir.join(part))
+}
+
+fn read_base(data_dir: &Path, path: &str) -> Result<Option<Vec<u8>>> {
+    let base = base_path(data_dir, path);
+    match fs::read(&base) {
+        Ok(content) => Ok(Some(content)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {:?}", base)),
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).with_context(|| format!("Failed to read {:?}", path))
+}
+
+/// Every file in the vault that is synced, with its modification time.
+fn local_files(data_dir: &Path) -> Result<BTreeMap<String, DateTime<Utc>>> {
+    let mut files = BTreeMap::new();
+    collect_files(data_dir, "", &mut files)?;
+    Ok(files)
+}
+
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    files: &mut BTreeMap<String, DateTime<Utc>>,
+) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))?;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let is_local_only = (name.starts_with('.') && !SYNCED_DOTFILES.contains(&name.as_str()))
+            || (prefix.is_empty() && LOCAL_ONLY.contains(&name.as_str()));
+        if is_local_only {
+            continue;
+        }
+
+        let path = format!("{}{}", prefix, name);
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &format!("{}/", path), files)?;
+        } else if let Some(modified) = modified(&entry.path())? {
+            files.insert(path, modified);
+        }
+    }
+    Ok(())
+}
+
+fn modified(path: &Path) -> Result<Option<DateTime<Utc>>> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(Some(metadata.modified()?.into())),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {:?}", path)),
+    }
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+    }
+    write_atomic(path, content)
+}
@@ -0,0 +1,121 @@
+//! Sync to a WebDAV server, such as Nextcloud, ownCloud, or `rclone serve webdav`.
+
+use super::SyncProvider;
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io::Read;
+
+const USER_AGENT: &str = concat!("IdeaVault/", env!("CARGO_PKG_VERSION"));
+
+pub struct WebDavProvider {
+    /// Collection URL the vault's files are stored under, without a trailing slash
+    url: String,
+    authorization: Option<String>,
+    /// Collections known to exist, so each is only created once per sync
+    collections: RefCell<HashSet<String>>,
+}
+
+impl WebDavProvider {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            authorization: None,
+            collections: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Authenticate with HTTP basic auth.
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        let token = STANDARD.encode(format!("{}:{}", username, password));
+        self.authorization = Some(format!("Basic {}", token));
+        self
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let url = if path.is_empty() {
+            format!("{}/", self.url)
+        } else {
+            format!("{}/{}", self.url, path)
+        };
+        let request = ureq::request(method, &url).set("User-Agent", USER_AGENT);
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+
+    /// Create the collections `path` lives in, including the vault's own,
+    /// since a PUT into a missing collection fails.
+    fn create_collections(&self, path: &str) -> Result<()> {
+        let parts: Vec<&str> = path.split('/').collect();
+        for depth in 0..parts.len() {
+            let collection = match depth {
+                0 => String::new(),
+                _ => format!("{}/", parts[..depth].join("/")),
+            };
+            if self.collections.borrow().contains(&collection) {
+                continue;
+            }
+            match self.request("MKCOL", &collection).call() {
+                // 405: the collection already exists
+                Ok(_) | Err(ureq::Error::Status(405, _)) => {}
+                Err(err) => return Err(error("create", &collection, err)),
+            }
+            self.collections.borrow_mut().insert(collection);
+        }
+        Ok(())
+    }
+}
+
+impl SyncProvider for WebDavProvider {
+    fn location(&self) -> String {
+        self.url.clone()
+    }
+
+    fn get(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        match self.request("GET", path).call() {
+            Ok(response) => {
+                let mut content = Vec::new();
+                response
+                    .into_reader()
+                    .read_to_end(&mut content)
+                    .with_context(|| format!("Failed to download {}", path))?;
+                Ok(Some(content))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(err) => Err(error("download", path, err)),
+        }
+    }
+
+    fn put(&self, path: &str, content: &[u8]) -> Result<()> {
+        self.create_collections(path)?;
+        self.request("PUT", path)
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(content)
+            .map_err(|err| error("upload", path, err))?;
+        Ok(())
+    }
+
+    fn delete(&self, path: &str) -> Result<()> {
+        match self.request("DELETE", path).call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            Err(err) => Err(error("delete", path, err)),
+        }
+    }
+}
+
+fn error(action: &str, path: &str, err: ureq::Error) -> anyhow::Error {
+    match err {
+        ureq::Error::Status(status, response) => anyhow::anyhow!(
+            "Failed to {} {}: server responded {} {}",
+            action,
+            path,
+            status,
+            response.status_text()
+        ),
+        err => anyhow::Error::new(err).context(format!("Failed to {} {}", action, path)),
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use ideavault::models::BackupConfig;
+use ideavault::storage::backup;
+use ideavault::{Idea, Storage};
+
+fn every_save(max_backups: usize) -> BackupConfig {
+    BackupConfig {
+        enabled: true,
+        max_backups,
+        interval_hours: 0,
+    }
+}
+
+#[test]
+fn backup_taken_before_save_contains_previous_state() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage =
+        Storage::new_with_path(temp_dir.path().to_path_buf())?.with_backup_config(every_save(5));
+
+    // First save has nothing to back up yet
+    storage.save_ideas(&[Idea::new("First".to_string())])?;
+    assert!(backup::list_backups(&storage.backup_dir())?.is_empty());
+
+    storage.save_ideas(&[Idea::new("Second".to_string())])?;
+    let backups = backup::list_backups(&storage.backup_dir())?;
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].files, vec!["ideas.json"]);
+
+    let snapshot = std::fs::read_to_string(backups[0].path.join("ideas.json"))?;
+    assert!(snapshot.contains("First"));
+    assert!(!snapshot.contains("Second"));
+    assert!(backup::last_backup_time(&storage.backup_dir()).is_some());
+
+    Ok(())
+}
+
+#[test]
+fn backups_rotate_beyond_max() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage =
+        Storage::new_with_path(temp_dir.path().to_path_buf())?.with_backup_config(every_save(2));
+
+    for i in 0..5 {
+        storage.save_ideas(&[Idea::new(format!("Idea {}", i))])?;
+    }
+
+    let backups = backup::list_backups(&storage.backup_dir())?;
+    assert_eq!(backups.len(), 2);
+    // The newest snapshot holds the state before the last save
+    let newest = std::fs::read_to_string(backups[1].path.join("ideas.json"))?;
+    assert!(newest.contains("Idea 3"));
+
+    Ok(())
+}
+
+#[test]
+fn backups_respect_interval_and_enabled_flag() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    // Default config: one snapshot per 24 hours
+    for i in 0..3 {
+        storage.save_ideas(&[Idea::new(format!("Idea {}", i))])?;
+    }
+    assert_eq!(backup::list_backups(&storage.backup_dir())?.len(), 1);
+
+    let other_dir = tempfile::tempdir()?;
+    let disabled =
+        Storage::new_with_path(other_dir.path().to_path_buf())?.with_backup_config(BackupConfig {
+            enabled: false,
+            ..every_save(5)
+        });
+    for i in 0..3 {
+        disabled.save_ideas(&[Idea::new(format!("Idea {}", i))])?;
+    }
+    assert!(!disabled.backup_dir().exists());
+
+    Ok(())
+}
+
+#[test]
+fn backup_command_restore_and_prune() -> Result<()> {
+    use ideavault::commands::backup::{BackupCommands, PruneBackupArgs, RestoreBackupArgs};
+
+    let temp_dir = tempfile::tempdir()?;
+    let storage =
+        Storage::new_with_path(temp_dir.path().to_path_buf())?.with_backup_config(BackupConfig {
+            enabled: false,
+            ..every_save(5)
+        });
+
+    storage.save_ideas(&[Idea::new("Keep me".to_string())])?;
+    let snapshot = BackupCommands::create_backup(&storage)?;
+
+    storage.save_ideas(&[Idea::new("Oops".to_string())])?;
+    BackupCommands::restore_backup(
+        &storage,
+        &RestoreBackupArgs {
+            name: snapshot.name.clone(),
+            force: true,
+        },
+    )?;
+
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].title, "Keep me");
+
+    // Restoring made a safety snapshot of the "Oops" state
+    assert_eq!(backup::list_backups(&storage.backup_dir())?.len(), 2);
+
+    BackupCommands::prune_backups(&storage, &PruneBackupArgs { keep: Some(1) })?;
+    let remaining = backup::list_backups(&storage.backup_dir())?;
+    assert_eq!(remaining.len(), 1);
+    assert_ne!(remaining[0].name, snapshot.name);
+
+    Ok(())
+}
+
+#[test]
+fn restore_into_indexed_vault_drops_entities_created_since() -> Result<()> {
+    use ideavault::commands::backup::{BackupCommands, RestoreBackupArgs};
+    use ideavault::storage::Layout;
+
+    let temp_dir = tempfile::tempdir()?;
+    let mut storage =
+        Storage::new_with_path(temp_dir.path().to_path_buf())?.with_backup_config(BackupConfig {
+            enabled: false,
+            ..BackupConfig::default()
+        });
+    storage.migrate(Layout::Indexed)?;
+
+    let kept = Idea::new("Kept".to_string());
+    storage.save_idea(&kept)?;
+    let snapshot = BackupCommands::create_backup(&storage)?;
+    assert!(snapshot.files.contains(&format!("ideas/{}.json", kept.id)));
+
+    storage.save_idea(&Idea::new("Later".to_string()))?;
+    BackupCommands::restore_backup(
+        &storage,
+        &RestoreBackupArgs {
+            name: snapshot.name,
+            force: true,
+        },
+    )?;
+
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].id, kept.id);
+
+    Ok(())
+}
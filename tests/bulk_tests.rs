@@ -0,0 +1,79 @@
+use anyhow::Result;
+use ideavault::commands::bulk::{self, Assignment, BulkArgs};
+use ideavault::models::idea::IdeaStatus;
+use ideavault::models::task::{TaskPriority, TaskStatus};
+use ideavault::{Idea, Storage, Task};
+
+fn set(assignment: &str) -> Assignment {
+    assignment.parse().unwrap()
+}
+
+#[test]
+fn edit_many_changes_only_matching_items() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let mut tasks = vec![
+        Task::new("Blocked".to_string())
+            .with_status(TaskStatus::Blocked)
+            .with_tags(vec!["old".to_string()]),
+        Task::new("Open".to_string()),
+        // Already has everything, so matches without changing
+        Task::new("Done already".to_string())
+            .with_status(TaskStatus::Blocked)
+            .with_priority(TaskPriority::High)
+            .with_tags(vec!["review".to_string()]),
+    ];
+    let args = BulkArgs {
+        set: vec![set("priority=high"), set("due=2030-01-02")],
+        add_tags: vec!["review".to_string()],
+        remove_tags: vec!["old".to_string()],
+        ..Default::default()
+    };
+
+    let edit = bulk::apply(
+        &storage,
+        &mut tasks,
+        |task| task.status == TaskStatus::Blocked,
+        &args,
+    )?;
+    assert_eq!(edit.matched, 2);
+    assert_eq!(edit.changes.len(), 2);
+    assert_eq!(tasks[0].priority, TaskPriority::High);
+    assert_eq!(tasks[0].tags, vec!["review"]);
+    assert_eq!(
+        tasks[0].due_date.unwrap().to_string(),
+        "2030-01-02 00:00:00 UTC"
+    );
+    assert_eq!(tasks[1].priority, TaskPriority::Medium);
+    assert!(tasks[1].due_date.is_none());
+    Ok(())
+}
+
+#[test]
+fn edit_many_rejects_unknown_fields_and_bad_values() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let mut ideas = vec![Idea::new("Idea".to_string())];
+    let apply = |ideas: &mut Vec<Idea>, assignment: &str| {
+        let args = BulkArgs {
+            set: vec![set(assignment)],
+            ..Default::default()
+        };
+        bulk::apply(&storage, ideas, |_| true, &args)
+    };
+
+    let err = apply(&mut ideas, "due=2030-01-01").unwrap_err().to_string();
+    assert!(err.contains("Set one of: status, priority"), "{}", err);
+    let err = apply(&mut ideas, "status=someday").unwrap_err().to_string();
+    assert!(err.starts_with("status: "), "{}", err);
+    assert_eq!(ideas[0].status, IdeaStatus::Brainstorming);
+
+    apply(&mut ideas, "Status = archived")?;
+    assert_eq!(ideas[0].status, IdeaStatus::Archived);
+    apply(&mut ideas, "priority=high")?;
+    assert_eq!(ideas[0].priority, TaskPriority::High);
+
+    assert!("priority".parse::<Assignment>().is_err());
+    assert!(BulkArgs::default().check(true).is_err());
+    Ok(())
+}
@@ -0,0 +1,1127 @@
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use ideavault::models::task::TaskStatus;
+use ideavault::{Idea, Storage, Task};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn ideavault(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_ideavault"));
+    command.args(args).env_remove("IDEAVAULT_DATA_DIR");
+    command
+}
+
+fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "ideavault failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn data_dir_flag_and_env_var_choose_storage() -> Result<()> {
+    let flag_dir = tempfile::tempdir()?;
+    let env_dir = tempfile::tempdir()?;
+    let flag_path = flag_dir.path().to_str().unwrap();
+
+    let output = ideavault(&["--data-dir", flag_path, "idea", "new", "From flag"]).output()?;
+    assert_success(&output);
+
+    let output = ideavault(&["idea", "new", "From env"])
+        .env("IDEAVAULT_DATA_DIR", env_dir.path())
+        .output()?;
+    assert_success(&output);
+
+    // The flag wins over the environment variable, and works after the subcommand
+    let output = ideavault(&["idea", "list", "--data-dir", flag_path])
+        .env("IDEAVAULT_DATA_DIR", env_dir.path())
+        .output()?;
+    assert_success(&output);
+    let listing = String::from_utf8_lossy(&output.stdout);
+    assert!(listing.contains("From flag"));
+    assert!(!listing.contains("From env"));
+
+    let ideas = Storage::new_with_path(env_dir.path().to_path_buf())?.load_ideas()?;
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].title, "From env");
+
+    Ok(())
+}
+
+#[test]
+fn list_commands_page_by_max_list_items() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = home.path().join(".config").join("ideavault");
+    std::fs::create_dir_all(&config_dir)?;
+    std::fs::write(config_dir.join("config.json"), r#"{ "max_list_items": 2 }"#)?;
+
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let ideas: Vec<Idea> = (1..=5)
+        .map(|n| Idea::new(format!("Idea number {}", n)))
+        .collect();
+    storage.save_ideas(&ideas)?;
+    drop(storage);
+
+    let list = |extra: &[&str]| -> Result<String> {
+        let mut args = vec!["idea", "list"];
+        args.extend_from_slice(extra);
+        let output = ideavault(&args)
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let first = list(&[])?;
+    assert_eq!(first.matches("Idea number").count(), 2);
+    assert!(first.contains("Showing 2 of 5 idea(s) (page 1 of 3)"));
+    assert!(first.contains("--page 2"));
+
+    let last = list(&["--page", "3"])?;
+    assert_eq!(last.matches("Idea number").count(), 1);
+    assert!(last.contains("Idea number 5"));
+
+    let all = list(&["--all"])?;
+    assert_eq!(all.matches("Idea number").count(), 5);
+    assert!(!all.contains("Showing"));
+
+    // --limit replaces the page size, --offset skips ahead
+    let limited = list(&["--limit", "3", "--page", "2"])?;
+    assert_eq!(limited.matches("Idea number").count(), 2);
+    assert!(limited.contains("Idea number 4"));
+
+    let skipped = list(&["--offset", "1"])?;
+    assert_eq!(skipped.matches("Idea number").count(), 2);
+    assert!(skipped.contains("Idea number 2") && skipped.contains("Idea number 3"));
+    assert!(skipped.contains("--offset 3 for the next ones"));
+
+    let ids = list(&["--ids", "--offset", "3"])?;
+    assert_eq!(ids, format!("{}\n{}\n", ideas[3].id, ideas[4].id));
+
+    let output = ideavault(&["idea", "list", "--page", "4"])
+        .env("HOME", home.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env("IDEAVAULT_DATA_DIR", data_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[test]
+fn format_flag_prints_json_and_yaml() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let idea = Idea::new("Structured".to_string()).with_tags(vec!["cli".to_string()]);
+    storage.save_ideas(std::slice::from_ref(&idea))?;
+    drop(storage);
+
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let listed: Vec<Idea> = serde_json::from_str(&run(&["--format", "json", "idea", "list"])?)?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, idea.id);
+
+    let id = idea.id.to_string();
+    let shown: Idea = serde_json::from_str(&run(&["--format", "json", "idea", "show", &id])?)?;
+    assert_eq!(shown.tags, vec!["cli"]);
+
+    let yaml = run(&["--format", "yaml", "idea", "show", &id])?;
+    assert!(yaml.starts_with(&format!("id: \"{}\"\n", id)));
+    assert!(yaml.contains("title: \"Structured\""));
+
+    let results: serde_json::Value =
+        serde_json::from_str(&run(&["--format", "json", "search", "struct"])?)?;
+    assert_eq!(results[0]["entity_type"], "idea");
+    assert_eq!(results[0]["id"], id.as_str());
+
+    Ok(())
+}
+
+#[test]
+fn format_flag_beats_environment_beats_config() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = home.path().join(".config").join("ideavault");
+    std::fs::create_dir_all(&config_dir)?;
+    std::fs::write(
+        config_dir.join("config.json"),
+        r#"{ "default_format": "Yaml" }"#,
+    )?;
+    Storage::new_with_path(data_dir.path().to_path_buf())?
+        .save_ideas(&[Idea::new("Layered".to_string())])?;
+
+    let run = |args: &[&str], format_env: Option<&str>| -> Result<String> {
+        let mut command = ideavault(args);
+        command
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("IDEAVAULT_FORMAT")
+            .env("IDEAVAULT_DATA_DIR", data_dir.path());
+        if let Some(format) = format_env {
+            command.env("IDEAVAULT_FORMAT", format);
+        }
+        let output = command.output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    assert!(run(&["idea", "list"], None)?.starts_with("- id: "));
+    assert!(run(&["idea", "list"], Some("json"))?.starts_with('['));
+    assert!(run(&["--format", "table", "idea", "list"], Some("json"))?.contains("Found 1 idea(s)"));
+
+    Ok(())
+}
+
+#[test]
+fn commands_accept_id_prefixes() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let task = Task::new("Prefixed".to_string());
+    Storage::new_with_path(data_dir.path().to_path_buf())?.add_task(&task)?;
+    let prefix = &task.id.to_string()[..8];
+
+    let output = ideavault(&["task", "status", prefix, "done"])
+        .env("IDEAVAULT_DATA_DIR", data_dir.path())
+        .output()?;
+    assert_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains(&task.id.to_string()));
+
+    let tasks = Storage::new_with_path(data_dir.path().to_path_buf())?.load_tasks()?;
+    assert_eq!(tasks[0].status, TaskStatus::Done);
+
+    Ok(())
+}
+
+#[test]
+fn json_flag_prints_the_models() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let task = Task::new("Pipe me".to_string()).with_tags(vec!["jq".to_string()]);
+    storage.add_task(&task)?;
+    drop(storage);
+
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let listed: Vec<Task> = serde_json::from_str(&run(&["task", "list", "--json"])?)?;
+    assert_eq!(listed[0].id, task.id);
+
+    let id = task.id.to_string();
+    let shown: Task = serde_json::from_str(&run(&["task", "show", &id, "--json"])?)?;
+    assert_eq!(shown.tags, vec!["jq"]);
+
+    // The command's own flag wins over --format
+    let shown = run(&["--format", "yaml", "task", "show", &id, "--json"])?;
+    assert!(shown.starts_with('{'));
+
+    let projects: Vec<serde_json::Value> =
+        serde_json::from_str(&run(&["project", "list", "--json"])?)?;
+    assert!(projects.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn quiet_flag_prints_only_ids() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let created = run(&["task", "new", "Scripted", "--quiet"])?;
+    let id: uuid::Uuid = created.trim_end().parse()?;
+    assert_eq!(created, format!("{}\n", id));
+
+    assert_eq!(
+        run(&["--porcelain", "task", "status", &id.to_string(), "done"])?,
+        ""
+    );
+    assert_eq!(run(&["task", "list", "-q"])?, created);
+
+    let tasks = Storage::new_with_path(data_dir.path().to_path_buf())?.load_tasks()?;
+    assert_eq!(tasks[0].status, TaskStatus::Done);
+
+    Ok(())
+}
+
+#[test]
+fn new_with_edit_creates_from_the_editor() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let home = tempfile::tempdir()?;
+    // An "editor" that checks the template was pre-filled and then replaces it
+    let editor = home.path().join("editor.sh");
+    std::fs::write(
+        &editor,
+        "#!/bin/sh\ngrep -q '^- home$' \"$1\" || exit 1\n\
+         printf -- '---\\ntitle: Fix the sink\\nstatus: InProgress\\npriority: High\\ntags: [home]\\n---\\n\\nIt drips.\\n' > \"$1\"\n",
+    )?;
+    std::fs::set_permissions(&editor, std::os::unix::fs::PermissionsExt::from_mode(0o755))?;
+    let run = |args: &[&str], editor: &str| {
+        ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env("IDEAVAULT_EDITOR", editor)
+            .output()
+    };
+
+    let output = run(
+        &["task", "new", "--edit", "-t", "home", "-q"],
+        editor.to_str().unwrap(),
+    )?;
+    assert_success(&output);
+    let tasks = Storage::new_with_path(data_dir.path().to_path_buf())?.load_tasks()?;
+    assert_eq!(tasks[0].title, "Fix the sink");
+    assert_eq!(tasks[0].description.as_deref(), Some("It drips."));
+    assert_eq!(tasks[0].status, TaskStatus::InProgress);
+
+    // Saving without a title creates nothing, like an empty commit message
+    let output = run(&["idea", "new", "--edit"], "true")?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("title can't be empty"));
+    assert!(Storage::new_with_path(data_dir.path().to_path_buf())?
+        .load_ideas()?
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn verbose_flag_logs_storage_to_stderr() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let run = |args: &[&str]| -> Result<(String, String)> {
+        let output = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .env_remove("RUST_LOG")
+            .output()?;
+        assert_success(&output);
+        Ok((
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ))
+    };
+
+    let (created, quiet_log) = run(&["idea", "new", "Logged", "-q"])?;
+    assert_eq!(quiet_log, "");
+
+    // The log goes to stderr, so scripted output is the same with or without it
+    let (listed, log) = run(&["idea", "list", "-q", "--verbose"])?;
+    assert_eq!(listed, created);
+    assert!(log.contains("ideas.json"), "{}", log);
+    assert!(log.contains("load ideas elapsed="), "{}", log);
+    Ok(())
+}
+
+#[test]
+fn description_dash_reads_stdin() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let run = |args: &[&str], stdin: &str| -> Result<()> {
+        let mut child = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child.stdin.take().unwrap().write_all(stdin.as_bytes())?;
+        assert_success(&child.wait_with_output()?);
+        Ok(())
+    };
+
+    let notes = "# Notes\n\n- first\n- second\n";
+    run(&["idea", "new", "Piped", "--description", "-"], notes)?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let idea = storage.load_ideas()?.remove(0);
+    assert_eq!(
+        idea.description.as_deref(),
+        Some("# Notes\n\n- first\n- second")
+    );
+    drop(storage);
+
+    run(
+        &["task", "new", "Piped task", "-d", "-", "--priority", "high"],
+        "Step one",
+    )?;
+    let id = idea.id.to_string();
+    run(
+        &["idea", "update", &id, "--description", "-"],
+        "Rewritten\n",
+    )?;
+
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    assert_eq!(
+        storage.load_tasks()?[0].description.as_deref(),
+        Some("Step one")
+    );
+    assert_eq!(
+        storage.get_idea(idea.id)?.description.as_deref(),
+        Some("Rewritten")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ids_flag_prints_every_id() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let config_dir = home.path().join(".config").join("ideavault");
+    std::fs::create_dir_all(&config_dir)?;
+    std::fs::write(
+        config_dir.join("config.json"),
+        r#"{ "max_list_items": 2, "default_format": "Json" }"#,
+    )?;
+
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let tasks: Vec<Task> = (1..=3).map(|n| Task::new(format!("Task {}", n))).collect();
+    storage.save_tasks(&tasks)?;
+    storage.trash_task(tasks[2].id)?;
+    drop(storage);
+
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("IDEAVAULT_FORMAT")
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    // Not paged, and not JSON even though that's the default format
+    let listed = run(&["task", "list", "--ids"])?;
+    assert_eq!(listed, format!("{}\n{}\n", tasks[0].id, tasks[1].id));
+
+    assert_eq!(
+        run(&["trash", "list", "--ids"])?,
+        format!("{}\n", tasks[2].id)
+    );
+
+    let output = ideavault(&["task", "list", "--ids", "--json"])
+        .env("IDEAVAULT_DATA_DIR", data_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[test]
+fn no_command_shows_the_dashboard() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    run(&["task", "new", "Pay rent", "--due", "2020-01-01"])?;
+    let dashboard = run(&[])?;
+    assert!(dashboard.contains("Overdue (1)"), "{}", dashboard);
+    assert!(dashboard.contains("Pay rent"), "{}", dashboard);
+    assert!(dashboard.contains("Tasks:    1 Todo"), "{}", dashboard);
+
+    run(&["config", "set", "dashboard.sections", r#"["recent_ideas"]"#])?;
+    let dashboard = run(&["--no-color"])?;
+    assert!(dashboard.starts_with("💡 Recent ideas"), "{}", dashboard);
+    assert!(!dashboard.contains("Pay rent"), "{}", dashboard);
+    Ok(())
+}
+
+#[test]
+fn no_emoji_flag_prints_only_ascii() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let created = run(&["idea", "new", "Plain", "--tags", "logs"])?;
+    assert!(created.starts_with("✅ Created new idea:"), "{}", created);
+
+    let created = run(&["--no-emoji", "task", "new", "Plain", "--priority", "high"])?;
+    assert!(created.is_ascii(), "{}", created);
+    assert!(created.starts_with("[ok] Created new task:"), "{}", created);
+    assert!(created.contains("[todo] [high] Plain"), "{}", created);
+
+    let listed = run(&["idea", "list", "--no-emoji"])?;
+    assert!(listed.is_ascii(), "{}", listed);
+    Ok(())
+}
+
+#[test]
+fn picking_without_a_terminal_asks_for_an_id() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let run = |args: &[&str]| {
+        ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .stdin(Stdio::null())
+            .output()
+    };
+
+    for args in [&["pick", "task", "parser"][..], &["task", "show"]] {
+        let output = run(args)?;
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("No task ID given. Pass an ID or title"),
+            "{}",
+            stderr
+        );
+    }
+
+    let output = run(&["pick", "tag"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Use idea, project, or task"));
+    Ok(())
+}
+
+#[test]
+fn path_prints_the_directories_in_use() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let data = data_dir.path().display().to_string();
+    assert_eq!(run(&["path", "--data"])?, format!("{}\n", data));
+    assert_eq!(
+        run(&["path", "--backups"])?,
+        format!("{}\n", data_dir.path().join("backups").display())
+    );
+    let config = run(&["path", "--config"])?;
+    assert!(config.starts_with(&home.path().display().to_string()));
+    assert!(config.trim_end().ends_with("config.json"), "{}", config);
+
+    let all = run(&["path"])?;
+    assert!(all.contains(&format!("Data:    {}", data)), "{}", all);
+    assert!(
+        all.contains(&format!("Config:  {}", config.trim_end())),
+        "{}",
+        all
+    );
+
+    let output = ideavault(&["path", "--data", "--config"]).output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[test]
+fn show_takes_several_ids_and_piped_ones() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let alpha = Task::new("Alpha".to_string());
+    let beta = Task::new("Beta".to_string()).with_status(TaskStatus::Blocked);
+    let gamma = Task::new("Gamma".to_string());
+    for task in [&alpha, &beta, &gamma] {
+        storage.add_task(task)?;
+    }
+    drop(storage);
+
+    let run = |args: &[&str], stdin: &str| -> Result<String> {
+        let mut child = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child.stdin.take().unwrap().write_all(stdin.as_bytes())?;
+        let output = child.wait_with_output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let shown = run(&["task", "show", "alpha", "gamma"], "")?;
+    assert_eq!(shown.matches("ID: ").count(), 2);
+    assert!(shown.find("Alpha") < shown.find("Gamma"), "{}", shown);
+
+    // As `task list --status blocked --ids` prints them
+    let piped = format!("{}\n\n{}\n", beta.id, alpha.id);
+    let shown = run(&["--format", "json", "task", "show", "-", "gamma"], &piped)?;
+    let tasks: Vec<Task> = serde_json::from_str(&shown)?;
+    let titles: Vec<&str> = tasks.iter().map(|task| task.title.as_str()).collect();
+    assert_eq!(titles, ["Beta", "Alpha", "Gamma"]);
+
+    // One task is still printed as an object, not a list
+    let shown = run(&["task", "show", "--json", "-"], &format!("{}\n", beta.id))?;
+    assert_eq!(serde_json::from_str::<Task>(&shown)?.id, beta.id);
+    Ok(())
+}
+
+#[test]
+fn idea_list_filters_by_dates_text_and_tags() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let tagged = |title: &str, tags: &[&str]| {
+        Idea::new(title.to_string()).with_tags(tags.iter().map(|t| t.to_string()).collect())
+    };
+    let mut old = tagged("Old garden plan", &["garden"]);
+    old.created_at = Utc.with_ymd_and_hms(2020, 1, 1, 9, 0, 0).unwrap();
+    old.updated_at = old.created_at;
+    let sensors = tagged("Sensors", &["garden", "iot"])
+        .with_description("Soil moisture for the GARDEN beds".to_string());
+    let lamp = tagged("Lamp", &["iot"]);
+    storage.save_ideas(&[old, sensors, lamp])?;
+    drop(storage);
+
+    let titles = |args: &[&str]| -> Result<Vec<String>> {
+        let output = ideavault(&[&["idea", "list", "--json"], args].concat())
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        let ideas: Vec<Idea> = serde_json::from_slice(&output.stdout)?;
+        let mut titles: Vec<String> = ideas.into_iter().map(|idea| idea.title).collect();
+        titles.sort();
+        Ok(titles)
+    };
+
+    assert_eq!(
+        titles(&["--created-before", "2021-01-01"])?,
+        ["Old garden plan"]
+    );
+    assert_eq!(
+        titles(&["--created-after", "2021-01-01"])?,
+        ["Lamp", "Sensors"]
+    );
+    assert_eq!(titles(&["--updated-within", "7d"])?, ["Lamp", "Sensors"]);
+    assert_eq!(
+        titles(&["--contains", "garden"])?,
+        ["Old garden plan", "Sensors"]
+    );
+    assert_eq!(titles(&["-t", "garden", "-t", "iot"])?, ["Sensors"]);
+    assert_eq!(
+        titles(&["-t", "garden", "-t", "iot", "--any-tag"])?,
+        ["Lamp", "Old garden plan", "Sensors"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn idea_list_filters_untagged_and_undescribed_ideas() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let tagged = Idea::new("Tagged".to_string()).with_tags(vec!["iot".to_string()]);
+    let bare = Idea::new("Bare".to_string());
+    let described = Idea::new("Described".to_string()).with_description("Has words".to_string());
+    storage.save_ideas(&[tagged, bare, described])?;
+    drop(storage);
+
+    let titles = |args: &[&str]| -> Result<Vec<String>> {
+        let output = ideavault(&[&["idea", "list", "--json"], args].concat())
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        let ideas: Vec<Idea> = serde_json::from_slice(&output.stdout)?;
+        let mut titles: Vec<String> = ideas.into_iter().map(|idea| idea.title).collect();
+        titles.sort();
+        Ok(titles)
+    };
+
+    assert_eq!(titles(&["--untagged"])?, ["Bare", "Described"]);
+    assert_eq!(titles(&["--no-description"])?, ["Bare", "Tagged"]);
+    assert_eq!(titles(&["--untagged", "--no-description"])?, ["Bare"]);
+
+    Ok(())
+}
+
+#[test]
+fn idea_new_refuses_a_similar_title_unless_allowed() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    storage.add_idea(&Idea::new("Dark mode for the app".to_string()))?;
+    drop(storage);
+    let new = |args: &[&str]| {
+        ideavault(&[&["idea", "new"], args].concat())
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()
+    };
+
+    let output = new(&["dark-mode for the App"])?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Dark mode for the app"), "{}", stderr);
+    assert!(stderr.contains("--allow-duplicate"), "{}", stderr);
+
+    assert_success(&new(&["dark-mode for the App", "--allow-duplicate"])?);
+    assert_success(&new(&["Light mode for the app"])?);
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    assert_eq!(storage.load_ideas()?.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn project_list_hides_archived_projects_unless_asked() -> Result<()> {
+    use ideavault::models::project::ProjectStatus;
+    use ideavault::Project;
+
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    storage.add_project(&Project::new("Current".to_string()))?;
+    storage
+        .add_project(&Project::new("Shelved".to_string()).with_status(ProjectStatus::Archived))?;
+    drop(storage);
+    let list = |args: &[&str]| -> Result<Vec<String>> {
+        let output = ideavault(&[&["project", "list", "--json"], args].concat())
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        let projects: Vec<Project> = serde_json::from_slice(&output.stdout)?;
+        Ok(projects.into_iter().map(|project| project.title).collect())
+    };
+
+    assert_eq!(list(&[])?, ["Current"]);
+    assert_eq!(list(&["--status", "archived"])?, ["Shelved"]);
+    assert_eq!(list(&["--archived"])?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn project_new_starts_from_a_saved_template() -> Result<()> {
+    use chrono::Duration;
+    use ideavault::models::task::TaskPriority;
+    use ideavault::models::Milestone;
+    use ideavault::Project;
+
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let mut talk = Project::new("RustConf talk".to_string())
+        .with_description("## Abstract\n\n## Outline".to_string())
+        .with_tags(vec!["talk".to_string()]);
+    let due = talk.created_at + Duration::days(30);
+    talk.add_milestone(
+        Milestone::new("Submit CFP".to_string()).with_due_date(due),
+        None,
+    );
+    storage.add_project(&talk)?;
+    storage.add_task(
+        &Task::new("Write slides".to_string())
+            .with_project(talk.id)
+            .with_priority(TaskPriority::named("High"))
+            .with_status(TaskStatus::Done),
+    )?;
+    storage.add_task(
+        &Task::new("Book hotel".to_string())
+            .with_project(talk.id)
+            .with_status(TaskStatus::Cancelled),
+    )?;
+    drop(storage);
+    let run = |args: &[&str]| {
+        ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()
+    };
+
+    assert_success(&run(&["template", "save", "RustConf talk", "talk"])?);
+    assert!(!run(&["template", "save", "RustConf talk", "Talk"])?
+        .status
+        .success());
+    assert!(!run(&["project", "new", "Other", "--template", "missing"])?
+        .status
+        .success());
+    assert_success(&run(&[
+        "project",
+        "new",
+        "GopherCon talk",
+        "--template",
+        "talk",
+        "--tags",
+        "go",
+    ])?);
+
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let project = storage
+        .load_projects()?
+        .into_iter()
+        .find(|project| project.title == "GopherCon talk")
+        .unwrap();
+    assert_eq!(
+        project.description.as_deref(),
+        Some("## Abstract\n\n## Outline")
+    );
+    assert_eq!(project.tags, ["talk", "go"]);
+    assert_eq!(project.milestones.len(), 1);
+    assert_eq!(
+        project.milestones[0].due_date,
+        Some(project.created_at + Duration::days(30))
+    );
+    let tasks: Vec<Task> = storage
+        .load_tasks()?
+        .into_iter()
+        .filter(|task| task.project_id == Some(project.id))
+        .collect();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].title, "Write slides");
+    assert_eq!(tasks[0].status, TaskStatus::Todo);
+    assert_eq!(tasks[0].priority, TaskPriority::named("High"));
+
+    Ok(())
+}
+
+#[test]
+fn idea_random_picks_distinct_matching_ideas() -> Result<()> {
+    use ideavault::models::idea::IdeaStatus;
+
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let brainstorming: Vec<Idea> = (1..=4)
+        .map(|n| Idea::new(format!("Idea {}", n)).with_status(IdeaStatus::Brainstorming))
+        .collect();
+    let active = Idea::new("Busy".to_string()).with_status(IdeaStatus::Active);
+    storage.save_ideas(&[brainstorming.clone(), vec![active]].concat())?;
+    drop(storage);
+
+    let output = ideavault(&[
+        "idea",
+        "random",
+        "--status",
+        "brainstorming",
+        "-n",
+        "3",
+        "--json",
+    ])
+    .env("IDEAVAULT_DATA_DIR", data_dir.path())
+    .output()?;
+    assert_success(&output);
+    let mut picked: Vec<Idea> = serde_json::from_slice(&output.stdout)?;
+    picked.sort_by_key(|idea| idea.id);
+    picked.dedup_by_key(|idea| idea.id);
+    assert_eq!(picked.len(), 3);
+    assert!(picked
+        .iter()
+        .all(|idea| idea.status == IdeaStatus::Brainstorming));
+
+    Ok(())
+}
+
+#[test]
+fn idea_delete_with_filters_trashes_every_match() -> Result<()> {
+    use ideavault::models::idea::IdeaStatus;
+
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let long_ago = chrono::Utc::now() - chrono::Duration::days(400);
+    let mut old_archived = Idea::new("Old archived".to_string()).with_status(IdeaStatus::Archived);
+    old_archived.updated_at = long_ago;
+    let mut old_open = Idea::new("Old open".to_string());
+    old_open.updated_at = long_ago;
+    let new_archived = Idea::new("New archived".to_string()).with_status(IdeaStatus::Archived);
+    storage.save_ideas(&[old_archived, old_open, new_archived])?;
+    drop(storage);
+
+    let before = (chrono::Utc::now() - chrono::Duration::days(30))
+        .format("%Y-%m-%d")
+        .to_string();
+    let delete = |extra: &str| -> Result<String> {
+        let output = ideavault(&[
+            "idea", "delete", "--status", "archived", "--before", &before, extra,
+        ])
+        .env("IDEAVAULT_DATA_DIR", data_dir.path())
+        .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let stdout = delete("--dry-run")?;
+    assert!(stdout.contains("Would delete 1 idea(s)"), "{}", stdout);
+    assert_eq!(
+        Storage::new_with_path(data_dir.path().to_path_buf())?
+            .load_ideas()?
+            .len(),
+        3
+    );
+
+    let stdout = delete("--force")?;
+    assert!(stdout.contains("Moved 1 idea(s) to trash"), "{}", stdout);
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let mut left: Vec<String> = storage
+        .load_ideas()?
+        .into_iter()
+        .map(|idea| idea.title)
+        .collect();
+    left.sort();
+    assert_eq!(left, ["New archived", "Old open"]);
+    assert_eq!(storage.load_trash()?[0].title(), "Old archived");
+
+    // An ID and filters together are refused
+    let output = ideavault(&["idea", "delete", "Old open", "--status", "active"])
+        .env("IDEAVAULT_DATA_DIR", data_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[test]
+fn idea_archive_archives_only_matching_ideas() -> Result<()> {
+    use ideavault::models::idea::IdeaStatus;
+
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let long_ago = chrono::Utc::now() - chrono::Duration::days(400);
+    let mut old_done = Idea::new("Old and done".to_string()).with_status(IdeaStatus::Completed);
+    old_done.updated_at = long_ago;
+    let mut old_open = Idea::new("Old and open".to_string());
+    old_open.updated_at = long_ago;
+    let new_done = Idea::new("New and done".to_string()).with_status(IdeaStatus::Completed);
+    storage.save_ideas(&[old_done.clone(), old_open, new_done])?;
+    drop(storage);
+
+    let before = (chrono::Utc::now() - chrono::Duration::days(30))
+        .format("%Y-%m-%d")
+        .to_string();
+    let output = ideavault(&[
+        "idea",
+        "archive",
+        "--status",
+        "completed",
+        "--before",
+        &before,
+    ])
+    .env("IDEAVAULT_DATA_DIR", data_dir.path())
+    .output()?;
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Archived 1 idea(s)"), "{}", stdout);
+
+    let ideas = Storage::new_with_path(data_dir.path().to_path_buf())?.load_ideas()?;
+    let archived: Vec<&str> = ideas
+        .iter()
+        .filter(|idea| idea.status == IdeaStatus::Archived)
+        .map(|idea| idea.title.as_str())
+        .collect();
+    assert_eq!(archived, ["Old and done"]);
+
+    // Without a filter, nothing is archived unless --all is given
+    let output = ideavault(&["idea", "archive"])
+        .env("IDEAVAULT_DATA_DIR", data_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[test]
+fn idea_tag_add_and_remove_keep_other_tags() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let idea = Idea::new("Garden".to_string()).with_tags(vec!["iot".to_string()]);
+    storage.save_ideas(std::slice::from_ref(&idea))?;
+    drop(storage);
+    let id = idea.id.to_string();
+
+    let tags = || -> Result<Vec<String>> {
+        let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+        Ok(storage.get_idea(idea.id)?.tags)
+    };
+    let run = |args: &[&str]| -> Result<()> {
+        let output = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(())
+    };
+
+    run(&["idea", "tag", "add", &id, "hardware", "iot"])?;
+    assert_eq!(tags()?, ["iot", "hardware"]);
+    run(&["idea", "tag", "remove", &id, "iot", "missing"])?;
+    assert_eq!(tags()?, ["hardware"]);
+    // Without add or remove, the tags are replaced
+    run(&["idea", "tag", &id, "garden"])?;
+    assert_eq!(tags()?, ["garden"]);
+
+    Ok(())
+}
+
+#[test]
+fn assignee_is_set_updated_and_filtered_on() -> Result<()> {
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let mine = Task::new("Write the docs".to_string()).with_assignee("Alice".to_string());
+    let theirs = Task::new("Fix the build".to_string()).with_assignee("Bob".to_string());
+    let nobody = Task::new("Tidy up".to_string());
+    storage.save_tasks(&[mine.clone(), theirs.clone(), nobody])?;
+    drop(storage);
+
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    // Names match whatever their case
+    let listed = run(&["task", "list", "--assignee", "alice", "--ids"])?;
+    assert_eq!(listed.lines().collect::<Vec<_>>(), [mine.id.to_string()]);
+
+    run(&[
+        "task",
+        "update",
+        &theirs.id.to_string(),
+        "--assignee",
+        "Alice",
+    ])?;
+    let listed = run(&["task", "list", "--assignee", "Alice", "--ids"])?;
+    assert_eq!(listed.lines().count(), 2);
+    run(&[
+        "task",
+        "update",
+        &mine.id.to_string(),
+        "--clear",
+        "assignee",
+    ])?;
+    let listed = run(&["task", "list", "--assignee", "Alice", "--ids"])?;
+    assert_eq!(listed.lines().collect::<Vec<_>>(), [theirs.id.to_string()]);
+
+    // Names are trimmed, and a blank one clears the assignee
+    run(&[
+        "task",
+        "update",
+        &mine.id.to_string(),
+        "--assignee",
+        " Carol ",
+    ])?;
+    let listed = run(&["task", "list", "--assignee", "carol", "--ids"])?;
+    assert_eq!(listed.lines().collect::<Vec<_>>(), [mine.id.to_string()]);
+    run(&["task", "update", &mine.id.to_string(), "--assignee", ""])?;
+    let shown: Task =
+        serde_json::from_str(&run(&["task", "show", &mine.id.to_string(), "--json"])?)?;
+    assert_eq!(shown.assignee, None);
+
+    run(&["project", "new", "Launch", "--assignee", "Bob"])?;
+    run(&["project", "new", "Rebrand"])?;
+    let listed = run(&[
+        "project",
+        "list",
+        "--assignee",
+        "bob",
+        "--columns",
+        "title,assignee",
+    ])?;
+    assert!(listed.contains("Launch"));
+    assert!(listed.contains("Bob"));
+    assert!(!listed.contains("Rebrand"));
+
+    Ok(())
+}
+
+#[test]
+fn project_link_takes_several_ideas_or_a_filter() -> Result<()> {
+    use ideavault::Project;
+
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let tagged = |title: &str| Idea::new(title.to_string()).with_tags(vec!["session".to_string()]);
+    let (first, second, third) = (
+        tagged("Dark mode"),
+        tagged("Offline sync"),
+        Idea::new("Other".to_string()),
+    );
+    storage.save_ideas(&[first.clone(), second.clone(), third.clone()])?;
+    let project = Project::new("App".to_string());
+    storage.add_project(&project)?;
+    drop(storage);
+
+    let run = |args: &[&str]| -> Result<String> {
+        let output = ideavault(args)
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .output()?;
+        assert_success(&output);
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    let linked = || -> Result<Vec<uuid::Uuid>> {
+        let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+        Ok(storage.get_project(project.id)?.idea_ids)
+    };
+
+    let id = project.id.to_string();
+    run(&[
+        "project",
+        "link",
+        &id,
+        &first.id.to_string(),
+        &third.id.to_string(),
+    ])?;
+    assert_eq!(linked()?, [first.id, third.id]);
+
+    let out = run(&["project", "link", &id, "--from-filter", "--tag", "session"])?;
+    assert!(out.contains("Linked 1 idea(s)"));
+    assert!(out.contains("1 idea(s) already linked"));
+    assert_eq!(linked()?, [first.id, third.id, second.id]);
+
+    // Filters without --from-filter are refused rather than ignored
+    let output = ideavault(&["project", "link", &id, &first.id.to_string(), "--tag", "x"])
+        .env("IDEAVAULT_DATA_DIR", data_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn piping_into_a_reader_that_quits_early_does_not_panic() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let data_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(data_dir.path().to_path_buf())?;
+    let ideas: Vec<Idea> = (1..=50)
+        .map(|n| Idea::new(format!("Idea number {}", n)))
+        .collect();
+    storage.save_ideas(&ideas)?;
+    drop(storage);
+
+    for flag in ["--ids", "--json"] {
+        let mut child = ideavault(&["idea", "list", "--all", flag])
+            .env("HOME", home.path())
+            .env_remove("XDG_CONFIG_HOME")
+            .env("IDEAVAULT_DATA_DIR", data_dir.path())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // Close the reading end before anything is printed, as `head` does
+        // once it has its lines
+        drop(child.stdout.take());
+        let output = child.wait_with_output()?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!stderr.contains("panicked"), "{}: {}", flag, stderr);
+    }
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use anyhow::Result;
+use ideavault::commands::config::{ConfigCommands, GetConfigArgs, SetConfigArgs};
+use ideavault::editor::{editor_command, split_command};
+use ideavault::models::config::{Compression, Config, DashboardSection, OutputFormat};
+
+#[test]
+fn first_load_writes_the_defaults() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("ideavault").join("config.json");
+
+    let config = Config::load_from(&path)?;
+    assert!(path.exists());
+    assert_eq!(config.max_list_items, Config::new().max_list_items);
+
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(saved["use_colors"], true);
+    assert_eq!(saved["backup"]["max_backups"], 10);
+
+    Ok(())
+}
+
+#[test]
+fn settings_round_trip_and_missing_ones_default() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("config.json");
+
+    std::fs::write(
+        &path,
+        r#"{ "data_dir": "~/notes", "backup": { "enabled": false } }"#,
+    )?;
+    let config = Config::load_from(&path)?;
+    assert!(!config.backup.enabled);
+    assert_eq!(config.backup.max_backups, 10);
+    assert_eq!(config.default_format, OutputFormat::Table);
+    assert!(!config.resolved_data_dir().starts_with("~"));
+    assert!(config.resolved_data_dir().ends_with("notes"));
+
+    config
+        .with_format(OutputFormat::Json)
+        .with_compression(Compression::Zstd)
+        .with_editor("nano")
+        .save_to(&path)?;
+    let reloaded = Config::load_from(&path)?;
+    assert_eq!(reloaded.default_format, OutputFormat::Json);
+    assert_eq!(reloaded.compression, Compression::Zstd);
+    assert_eq!(reloaded.default_editor.as_deref(), Some("nano"));
+    assert!(!reloaded.backup.enabled);
+
+    std::fs::write(&path, "{ not json")?;
+    assert!(Config::load_from(&path).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn config_set_validates_and_saves() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("config.json");
+
+    let set = |key: &str, value: &str| {
+        ConfigCommands::set_setting(
+            &path,
+            &SetConfigArgs {
+                key: key.to_string(),
+                value: value.to_string(),
+            },
+        )
+    };
+    set("default_format", "json")?;
+    set("backup.max_backups", "3")?;
+    set("default_editor", "code --wait")?;
+    set("max_list_items", "null")?;
+    set("dashboard.sections", r#"["counts", "overdue"]"#)?;
+    assert!(set("dashboard.sections", r#"["weather"]"#).is_err());
+    assert!(set("backup.max_backups", "many").is_err());
+    assert!(set("show_timestamps", "maybe").is_err());
+    assert!(set("backup", "false").is_err());
+    assert!(set("colour", "true").is_err());
+
+    let config = Config::load_from(&path)?;
+    assert_eq!(config.default_format, OutputFormat::Json);
+    assert_eq!(config.backup.max_backups, 3);
+    assert_eq!(config.default_editor.as_deref(), Some("code --wait"));
+    assert_eq!(config.max_list_items, None);
+    assert_eq!(
+        config.dashboard.sections,
+        vec![DashboardSection::Counts, DashboardSection::Overdue]
+    );
+
+    let value = ConfigCommands::get_setting(
+        &path,
+        &GetConfigArgs {
+            key: "backup.max_backups".to_string(),
+        },
+    )?;
+    assert_eq!(value, serde_json::json!(3));
+
+    Ok(())
+}
+
+#[test]
+fn editor_commands_keep_their_arguments() -> Result<()> {
+    assert_eq!(editor_command(Some("nano -w")), "nano -w");
+    assert_eq!(split_command("code --wait")?, vec!["code", "--wait"]);
+    assert_eq!(
+        split_command(r#""/Applications/Sublime Text/subl" -w  'a b' c\ d"#)?,
+        vec!["/Applications/Sublime Text/subl", "-w", "a b", "c d"]
+    );
+    assert!(split_command("vim \"unclosed").is_err());
+    assert!(split_command("   ").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn config_init_asks_and_keeps_defaults_for_empty_answers() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("config.json");
+
+    // Data directory, editor, colors (twice: a bad answer is asked again),
+    // backups, hours between backups (kept), backups to keep
+    let answers = "~/ideas\nnano -w\nmaybe\nn\n\n\n5\n";
+    let config = ConfigCommands::init_config(&path, &mut answers.as_bytes())?;
+    assert_eq!(config.data_dir, std::path::PathBuf::from("~/ideas"));
+    assert_eq!(config.default_editor.as_deref(), Some("nano -w"));
+    assert!(!config.use_colors);
+    assert!(config.backup.enabled);
+    assert_eq!(config.backup.interval_hours, 24);
+    assert_eq!(config.backup.max_backups, 5);
+
+    let saved = Config::load_from(&path)?;
+    assert_eq!(saved.backup.max_backups, 5);
+    assert!(!saved.use_colors);
+
+    // Running out of answers cancels without saving
+    let result = ConfigCommands::init_config(&path, &mut "~/other\n".as_bytes());
+    assert!(result.is_err());
+    assert_eq!(
+        Config::load_from(&path)?.data_dir,
+        std::path::PathBuf::from("~/ideas")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn environment_overrides_the_file() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("config.json");
+    std::fs::write(
+        &path,
+        r#"{ "default_format": "Yaml", "default_editor": "vim", "backup": { "max_backups": 3 } }"#,
+    )?;
+
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    };
+
+    // Nothing set: the file's values
+    let config = Config::load_from(&path)?.with_env(env(&[]))?;
+    assert_eq!(config.default_format, OutputFormat::Yaml);
+    assert_eq!(config.backup.max_backups, 3);
+
+    let config = Config::load_from(&path)?.with_env(env(&[
+        ("IDEAVAULT_DEFAULT_FORMAT", "table"),
+        ("IDEAVAULT_FORMAT", "json"),
+        ("IDEAVAULT_EDITOR", "nano"),
+        ("IDEAVAULT_NO_COLOR", "1"),
+        ("IDEAVAULT_BACKUP_MAX_BACKUPS", "7"),
+        ("IDEAVAULT_MAX_LIST_ITEMS", "null"),
+        ("IDEAVAULT_TRASH_AUTO_PURGE_DAYS", ""),
+    ]))?;
+    // The shorthand wins over the long name
+    assert_eq!(config.default_format, OutputFormat::Json);
+    assert_eq!(config.default_editor.as_deref(), Some("nano"));
+    assert!(!config.use_colors);
+    assert_eq!(config.backup.max_backups, 7);
+    assert_eq!(config.max_list_items, None);
+    // Empty variables are ignored
+    assert_eq!(config.trash.auto_purge_days, Some(30));
+
+    let config = Config::load_from(&path)?.with_env(env(&[("IDEAVAULT_NO_COLOR", "0")]))?;
+    assert!(config.use_colors);
+
+    let invalid =
+        Config::load_from(&path)?.with_env(env(&[("IDEAVAULT_BACKUP_ENABLED", "sometimes")]));
+    assert!(invalid.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use chrono::{NaiveDate, TimeZone, Utc};
+use ideavault::commands::dashboard::{Dashboard, StatusCount};
+use ideavault::models::config::{DashboardConfig, DashboardSection};
+use ideavault::models::idea::IdeaStatus;
+use ideavault::models::project::ProjectStatus;
+use ideavault::models::task::TaskStatus;
+use ideavault::{Idea, Project, Storage, Task};
+
+fn titles(tasks: &[Task]) -> Vec<&str> {
+    tasks.iter().map(|task| task.title.as_str()).collect()
+}
+
+#[test]
+fn dashboard_gathers_what_needs_attention() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let today = NaiveDate::from_ymd_opt(2030, 6, 15).unwrap();
+    let day = |d| Utc.with_ymd_and_hms(2030, 6, d, 0, 0, 0).unwrap();
+
+    for task in [
+        Task::new("Yesterday".to_string()).with_due_date(day(14)),
+        Task::new("Last week".to_string()).with_due_date(day(8)),
+        Task::new("Today".to_string()).with_due_date(day(15)),
+        Task::new("Tomorrow".to_string()).with_due_date(day(16)),
+        Task::new("Done late".to_string())
+            .with_due_date(day(1))
+            .with_status(TaskStatus::Done),
+        Task::new("Working".to_string()).with_status(TaskStatus::InProgress),
+    ] {
+        storage.add_task(&task)?;
+    }
+    storage
+        .add_project(&Project::new("Live".to_string()).with_status(ProjectStatus::InProgress))?;
+    storage.add_project(&Project::new("Someday".to_string()))?;
+    storage.add_idea(&Idea::new("Active".to_string()).with_status(IdeaStatus::Active))?;
+
+    let dashboard = Dashboard::build(&storage, &DashboardConfig::default(), today)?;
+    assert_eq!(
+        titles(dashboard.overdue.as_deref().unwrap()),
+        ["Last week", "Yesterday"]
+    );
+    assert_eq!(titles(dashboard.due_today.as_deref().unwrap()), ["Today"]);
+
+    let in_progress = dashboard.in_progress.unwrap();
+    assert_eq!(titles(&in_progress.tasks), ["Working"]);
+    assert_eq!(in_progress.projects.len(), 1);
+    assert_eq!(in_progress.projects[0].title, "Live");
+    assert_eq!(in_progress.ideas[0].title, "Active");
+
+    let counts = dashboard.counts.unwrap();
+    assert_eq!(
+        counts.tasks,
+        vec![
+            StatusCount {
+                status: "Todo".to_string(),
+                count: 4
+            },
+            StatusCount {
+                status: "Done".to_string(),
+                count: 1
+            },
+            StatusCount {
+                status: "InProgress".to_string(),
+                count: 1
+            },
+        ]
+    );
+    assert_eq!(counts.projects.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn dashboard_leaves_out_sections_turned_off() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let config = DashboardConfig {
+        sections: vec![DashboardSection::RecentIdeas],
+        max_items: 1,
+    };
+
+    let dashboard = Dashboard::build(&storage, &config, Utc::now().date_naive())?;
+    assert!(dashboard.overdue.is_none());
+    assert!(dashboard.counts.is_none());
+    assert_eq!(
+        dashboard.recent_ideas.as_deref().map(<[Idea]>::len),
+        Some(0)
+    );
+    assert_eq!(
+        serde_json::to_value(&dashboard)?,
+        serde_json::json!({ "recent_ideas": [] })
+    );
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use chrono::{Duration, NaiveDate, Utc};
+use ideavault::dates::{parse_span, relative, relative_day};
+
+#[test]
+fn times_read_relative_to_now() {
+    let now = Utc::now();
+    assert_eq!(relative(now - Duration::seconds(20), now), "just now");
+    assert_eq!(relative(now - Duration::minutes(1), now), "1 minute ago");
+    assert_eq!(relative(now - Duration::minutes(200), now), "3 hours ago");
+    assert_eq!(relative(now + Duration::days(2), now), "in 2 days");
+    assert_eq!(relative(now - Duration::days(16), now), "2 weeks ago");
+    assert_eq!(relative(now - Duration::days(100), now), "3 months ago");
+    assert_eq!(relative(now + Duration::days(800), now), "in 2 years");
+}
+
+#[test]
+fn days_read_relative_to_today() {
+    let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let day = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+    assert_eq!(relative_day(day(10), today), "today");
+    assert_eq!(relative_day(day(11), today), "tomorrow");
+    assert_eq!(relative_day(day(9), today), "yesterday");
+    assert_eq!(relative_day(day(12), today), "in 2 days");
+    assert_eq!(relative_day(day(1), today), "1 week ago");
+}
+
+#[test]
+fn spans_read_hours_days_and_weeks() {
+    assert_eq!(parse_span("12h").unwrap(), Duration::hours(12));
+    assert_eq!(parse_span("7d").unwrap(), Duration::days(7));
+    assert_eq!(parse_span("2W").unwrap(), Duration::weeks(2));
+    for invalid in ["", "d", "7", "7y", "-3d", "seven days"] {
+        assert!(parse_span(invalid).is_err(), "{}", invalid);
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use ideavault::commands::doctor::{self, DoctorArgs, IssueKind};
+use ideavault::models::BackupConfig;
+use ideavault::{Idea, Project, Storage, Tag, Task};
+use uuid::Uuid;
+
+fn storage_in(dir: &tempfile::TempDir) -> Result<Storage> {
+    Ok(
+        Storage::new_with_path(dir.path().to_path_buf())?.with_backup_config(BackupConfig {
+            enabled: false,
+            ..BackupConfig::default()
+        }),
+    )
+}
+
+#[test]
+fn doctor_reports_and_fixes_broken_links() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = storage_in(&temp_dir)?;
+
+    let idea = Idea::new("Real".to_string()).with_tags(vec!["rust".to_string()]);
+    let missing_idea = Uuid::new_v4();
+    let missing_project = Uuid::new_v4();
+
+    let project = Project::new("Project".to_string()).with_ideas(vec![idea.id, missing_idea]);
+    let task = Task::new("Task".to_string())
+        .with_project(missing_project)
+        .with_idea(idea.id);
+
+    storage.save_ideas(&[idea.clone(), idea.clone()])?;
+    storage.save_projects(&[project])?;
+    storage.save_tasks(&[task])?;
+    storage.save_tags(&[Tag::new("cli".to_string())])?;
+
+    let issues = doctor::check_vault(&storage)?;
+    let kinds: Vec<IssueKind> = issues.iter().map(|i| i.kind.clone()).collect();
+    assert!(kinds.contains(&IssueKind::DuplicateId));
+    assert!(kinds.contains(&IssueKind::DanglingProjectIdea));
+    assert!(kinds.contains(&IssueKind::DanglingTaskProject));
+    assert!(kinds.contains(&IssueKind::MissingTag));
+    assert!(!kinds.contains(&IssueKind::DanglingTaskIdea));
+
+    let remaining = doctor::run(&storage, &DoctorArgs { fix: true })?;
+    assert!(remaining.is_empty());
+
+    assert_eq!(storage.load_ideas()?.len(), 1);
+    assert_eq!(storage.load_projects()?[0].idea_ids, vec![idea.id]);
+    let task = &storage.load_tasks()?[0];
+    assert_eq!(task.project_id, None);
+    assert_eq!(task.idea_id, Some(idea.id));
+    assert!(storage.load_tags()?.iter().any(|t| t.name == "rust"));
+
+    Ok(())
+}
+
+#[test]
+fn doctor_removes_relations_to_missing_ideas() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = storage_in(&temp_dir)?;
+
+    let other = Idea::new("Other".to_string());
+    let mut idea = Idea::new("Related".to_string());
+    idea.related_ids = vec![other.id, Uuid::new_v4()];
+    storage.save_ideas(&[idea, other.clone()])?;
+
+    let issues = doctor::check_vault(&storage)?;
+    let kinds: Vec<IssueKind> = issues.iter().map(|i| i.kind.clone()).collect();
+    assert_eq!(kinds, [IssueKind::DanglingRelatedIdea]);
+
+    let remaining = doctor::run(&storage, &DoctorArgs { fix: true })?;
+    assert!(remaining.is_empty());
+    assert_eq!(storage.load_ideas()?[0].related_ids, vec![other.id]);
+
+    Ok(())
+}
+
+#[test]
+fn doctor_detects_and_resets_bad_timestamps() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = storage_in(&temp_dir)?;
+
+    let idea = Idea::new("Dated".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea))?;
+
+    let path = temp_dir.path().join("ideas.json");
+    let corrupted = std::fs::read_to_string(&path)?.replacen(
+        &serde_json::to_string(&idea.created_at)?,
+        "\"last tuesday\"",
+        1,
+    );
+    std::fs::write(&path, corrupted)?;
+    assert!(storage.load_ideas().is_err());
+
+    let issues = doctor::check_vault(&storage)?;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, IssueKind::InvalidDate);
+
+    doctor::run(&storage, &DoctorArgs { fix: true })?;
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas[0].id, idea.id);
+
+    Ok(())
+}
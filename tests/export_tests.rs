@@ -0,0 +1,270 @@
+use anyhow::Result;
+use ideavault::commands::export::{
+    export, export_obsidian, export_org, ExportArgs, ObsidianArgs, OrgArgs,
+};
+use ideavault::commands::idea::{ExportIdeaArgs, IdeaCommands};
+use ideavault::commands::import::{import_archive, ImportArgs};
+use ideavault::export::{EntityType, ExportFormat};
+use ideavault::models::config::Config;
+use ideavault::models::idea::IdeaStatus;
+use ideavault::models::project::ProjectStatus;
+use ideavault::models::task::{TaskPriority, TaskStatus};
+use ideavault::storage::{archive, backup};
+use ideavault::{Idea, Project, Storage, Task};
+
+fn args(format: ExportFormat, out: &std::path::Path) -> ExportArgs {
+    ExportArgs {
+        format,
+        out: out.to_path_buf(),
+        types: vec![],
+        status: None,
+        tag: None,
+        archive: None,
+        encrypt: false,
+    }
+}
+
+fn seeded_storage(dir: &std::path::Path) -> Result<(Storage, Idea, Project)> {
+    let storage = Storage::new_with_path(dir.to_path_buf())?;
+    let idea = Idea::new("Comma, \"quoted\"".to_string())
+        .with_description("Line one\nLine two".to_string())
+        .with_tags(vec!["rust".to_string(), "cli".to_string()])
+        .with_status(IdeaStatus::Active);
+    let other = Idea::new("Other".to_string());
+    let project = Project::new("Launch".to_string())
+        .with_ideas(vec![idea.id])
+        .with_status(ProjectStatus::InProgress);
+    let task = Task::new("Write docs".to_string())
+        .with_project(project.id)
+        .with_idea(idea.id);
+
+    storage.save_ideas(&[idea.clone(), other])?;
+    storage.save_projects(std::slice::from_ref(&project))?;
+    storage.save_tasks(&[task])?;
+    Ok((storage, idea, project))
+}
+
+#[test]
+fn json_export_round_trips_with_links() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (storage, idea, project) = seeded_storage(&temp_dir.path().join("vault"))?;
+    let out = temp_dir.path().join("out");
+
+    let files = export(&storage, &args(ExportFormat::Json, &out))?;
+    assert_eq!(files.len(), 3);
+
+    let projects: Vec<Project> =
+        serde_json::from_str(&std::fs::read_to_string(out.join("projects.json"))?)?;
+    assert_eq!(projects[0].idea_ids, vec![idea.id]);
+    let tasks: Vec<Task> = serde_json::from_str(&std::fs::read_to_string(out.join("tasks.json"))?)?;
+    assert_eq!(tasks[0].project_id, Some(project.id));
+
+    Ok(())
+}
+
+#[test]
+fn csv_export_quotes_fields_and_joins_lists() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (storage, idea, _) = seeded_storage(&temp_dir.path().join("vault"))?;
+    let out = temp_dir.path().join("out");
+
+    export(&storage, &args(ExportFormat::Csv, &out))?;
+    let csv = std::fs::read_to_string(out.join("ideas.csv"))?;
+    let mut lines = csv.split("\r\n");
+    assert_eq!(
+        lines.next(),
+        Some("id,title,description,source,status,priority,effort,impact,tags,related_ids,created_at,updated_at,attachments,notes,status_history")
+    );
+    let row = lines.next().unwrap_or_default();
+    assert!(row.starts_with(&format!(
+        "{},\"Comma, \"\"quoted\"\"\",\"Line one\nLine two\",,Active,Medium,,,rust;cli,,",
+        idea.id
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn export_filters_by_type_status_and_tag() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (storage, _, _) = seeded_storage(&temp_dir.path().join("vault"))?;
+
+    let out = temp_dir.path().join("by-status");
+    let mut filtered = args(ExportFormat::Yaml, &out);
+    filtered.status = Some("in-progress".to_string());
+    let files = export(&storage, &filtered)?;
+    assert_eq!(files, vec![out.join("projects.yaml")]);
+    let yaml = std::fs::read_to_string(&files[0])?;
+    assert!(yaml.starts_with("- id: "));
+    assert!(yaml.contains("  title: \"Launch\"\n"));
+
+    let out = temp_dir.path().join("by-tag");
+    let mut filtered = args(ExportFormat::Markdown, &out);
+    filtered.types = vec![EntityType::Idea];
+    filtered.tag = Some("rust".to_string());
+    let files = export(&storage, &filtered)?;
+    assert_eq!(files, vec![out.join("ideas.md")]);
+    let markdown = std::fs::read_to_string(&files[0])?;
+    assert!(markdown.contains("## Comma, \"quoted\""));
+    assert!(markdown.contains("- **Projects:** Launch"));
+    assert!(markdown.contains("- **Tasks:** Write docs"));
+    assert!(!markdown.contains("## Other"));
+
+    Ok(())
+}
+
+#[test]
+fn obsidian_notes_link_ideas_projects_and_tasks() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (storage, idea, project) = seeded_storage(&temp_dir.path().join("vault"))?;
+    let dir = temp_dir.path().join("obsidian");
+
+    let notes = export_obsidian(&storage, &ObsidianArgs { dir: dir.clone() })?;
+    assert_eq!(notes.len(), 4);
+
+    // Characters Obsidian can't use in note names are dropped, but the title is kept
+    let idea_note = std::fs::read_to_string(dir.join("Ideas/Comma, quoted.md"))?;
+    assert!(idea_note.starts_with(&format!("---\nid: \"{}\"\ntype: \"idea\"\n", idea.id)));
+    assert!(idea_note.contains("aliases:\n  - \"Comma, \\\"quoted\\\"\"\n"));
+    assert!(idea_note.contains("tags:\n  - \"rust\"\n  - \"cli\"\n"));
+    assert!(idea_note.contains("- [[Projects/Launch|Launch]]"));
+    assert!(idea_note.contains("- [[Tasks/Write docs|Write docs]]"));
+    assert!(idea_note.contains("Line one\nLine two"));
+
+    let project_note = std::fs::read_to_string(dir.join("Projects/Launch.md"))?;
+    assert!(project_note.contains(&format!("id: \"{}\"", project.id)));
+    assert!(project_note.contains("- [[Ideas/Comma, quoted|Comma, \"quoted\"]]"));
+
+    let task_note = std::fs::read_to_string(dir.join("Tasks/Write docs.md"))?;
+    assert!(task_note.contains("project: \"[[Projects/Launch|Launch]]\"\n"));
+
+    Ok(())
+}
+
+#[test]
+fn idea_export_writes_one_markdown_document() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (storage, idea, _) = seeded_storage(&temp_dir.path().join("vault"))?;
+    let out = temp_dir.path().join("docs/idea.md");
+
+    IdeaCommands::export_idea(
+        &storage,
+        &ExportIdeaArgs {
+            id: idea.id.into(),
+            out: Some(out.clone()),
+        },
+    )?;
+
+    let document = std::fs::read_to_string(&out)?;
+    assert!(document.starts_with(&format!("---\nid: \"{}\"\n", idea.id)));
+    assert!(document.contains("status: \"Active\"\n"));
+    assert!(document.contains("tags:\n  - \"rust\"\n  - \"cli\"\n"));
+    assert!(document.contains("---\n\n# Comma, \"quoted\"\n\nLine one\nLine two\n"));
+    assert!(document.contains("## Projects\n\n- **Launch** (InProgress)\n"));
+    assert!(document.contains("## Tasks\n\n- [ ] Write docs\n"));
+    assert!(!document.contains("Other"));
+
+    Ok(())
+}
+
+#[test]
+fn org_export_nests_tasks_under_projects() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (storage, _, project) = seeded_storage(&temp_dir.path().join("vault"))?;
+    let mut tasks = storage.load_tasks()?;
+    tasks.push(
+        Task::new("Loose end".to_string())
+            .with_priority(TaskPriority::Urgent)
+            .with_tags(vec!["web dev".to_string()])
+            .with_due_date(chrono::DateTime::parse_from_rfc3339("2030-01-02T00:00:00Z")?.into()),
+    );
+    tasks.push(Task::new("Finished".to_string()).with_status(TaskStatus::Done));
+    storage.save_tasks(&tasks)?;
+
+    let out = temp_dir.path().join("agenda.org");
+    let mut args = OrgArgs {
+        out: out.clone(),
+        all: false,
+    };
+    export_org(&storage, &args)?;
+    let org = std::fs::read_to_string(&out)?;
+
+    assert!(org.contains("#+TODO: TODO IN-PROGRESS BLOCKED | DONE CANCELLED\n"));
+    assert!(org.contains(&format!(
+        "\n* Launch\n  :PROPERTIES:\n  :ID: {}\n",
+        project.id
+    )));
+    assert!(org.contains("** TODO [#C] Write docs\n"));
+    assert!(org.contains(
+        "* Tasks without a project\n** TODO [#A] Loose end :web_dev:\n   DEADLINE: <2030-01-02 Wed>\n"
+    ));
+    assert!(!org.contains("Finished"));
+
+    args.all = true;
+    export_org(&storage, &args)?;
+    assert!(std::fs::read_to_string(&out)?.contains("** DONE [#C] Finished\n"));
+
+    Ok(())
+}
+
+#[test]
+fn encrypted_archive_restores_the_whole_vault() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (storage, idea, _) = seeded_storage(&temp_dir.path().join("vault"))?;
+    storage.create_backup()?;
+    let out = temp_dir.path().join("vault.tar.zst");
+    // Keep the config file this archives and restores out of the real one
+    let config_home = temp_dir.path().join("config");
+    std::env::set_var("XDG_CONFIG_HOME", &config_home);
+    let config = Config::path()?;
+    std::fs::create_dir_all(config.parent().unwrap())?;
+    std::fs::write(&config, "{\"relative_dates\": true}")?;
+
+    std::env::set_var(archive::PASSPHRASE_ENV, "correct horse");
+    let files = export(
+        &storage,
+        &ExportArgs {
+            archive: Some(out.clone()),
+            encrypt: true,
+            ..args(ExportFormat::Json, temp_dir.path())
+        },
+    )?;
+    assert_eq!(files, vec![out.clone()]);
+    assert!(archive::is_encrypted(&out)?);
+    assert!(archive::list(&out, Some("wrong")).is_err());
+    assert!(archive::list(&out, None).is_err());
+
+    let target = Storage::new_with_path(temp_dir.path().join("restored"))?;
+    target.save_ideas(&[Idea::new("Replaced".to_string())])?;
+    std::fs::remove_file(&config)?;
+    let restored = import_archive(
+        &target,
+        &ImportArgs {
+            file: out,
+            kind: None,
+            mappings: vec![],
+            dry_run: false,
+            archive: true,
+            force: true,
+        },
+    )?;
+    assert!(restored.iter().any(|file| file == "ideas.json"));
+    assert!(restored.iter().any(|file| file.starts_with("backups/")));
+    assert!(restored.iter().any(|file| file == archive::CONFIG_ENTRY));
+    assert_eq!(
+        std::fs::read_to_string(&config)?,
+        "{\"relative_dates\": true}"
+    );
+    assert!(!target.data_dir().join("config").exists());
+
+    let ideas = target.load_ideas()?;
+    assert_eq!(ideas.len(), 2);
+    assert!(ideas.iter().any(|restored| restored.id == idea.id));
+    assert_eq!(target.load_tasks()?.len(), 1);
+    assert_eq!(
+        backup::list_backups(&target.backup_dir())?.len(),
+        backup::list_backups(&storage.backup_dir())?.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use ideavault::front_matter;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Fields {
+    title: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn front_matter_round_trips_fields_and_body() -> Result<()> {
+    let fields = Fields {
+        title: "Fix: the \"sink\"".to_string(),
+        tags: vec!["home".to_string()],
+    };
+    let document = front_matter::render(
+        &fields,
+        &["Comments are ignored".to_string()],
+        Some("# Notes\n\nIt drips."),
+    )?;
+    assert!(document.starts_with("---\n# Comments are ignored\n"));
+
+    let (parsed, body): (Fields, _) = front_matter::parse(&document)?;
+    assert_eq!(parsed, fields);
+    assert_eq!(body.as_deref(), Some("# Notes\n\nIt drips."));
+
+    let (_, body): (Fields, _) = front_matter::parse("---\ntitle: x\n---\n\n")?;
+    assert_eq!(body, None);
+    Ok(())
+}
+
+#[test]
+fn front_matter_reports_what_is_wrong() {
+    let error = |content: &str| {
+        front_matter::parse::<Fields>(content)
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert!(error("title: x\n").contains("must start with a ---"));
+    assert!(error("---\ntitle: x\n").contains("no closing ---"));
+    // Line numbers count from the top of the file
+    let unknown = error("---\ntitle: x\ndue: 2024-01-01\n---\n");
+    assert!(
+        unknown.starts_with("Line 3: unknown field `due`"),
+        "{}",
+        unknown
+    );
+    assert!(error("---\ntags: [home\n---\n").starts_with("Line "));
+}
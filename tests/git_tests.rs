@@ -0,0 +1,166 @@
+use anyhow::Result;
+use ideavault::commands::sync::{sync, SyncArgs, SyncSummary};
+use ideavault::models::task::TaskStatus;
+use ideavault::storage::git;
+use ideavault::{Idea, Storage, Task};
+
+#[test]
+fn changes_are_committed_once_git_is_enabled() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().to_path_buf();
+    let storage = Storage::new_with_path(path.clone())?;
+
+    // Nothing is committed until the vault opts in
+    let mut task = Task::new("Before git".to_string());
+    storage.save_task(&task)?;
+    assert!(!git::is_enabled(&path));
+
+    assert!(git::init(&path)?);
+    assert!(!git::init(&path)?);
+    assert!(std::fs::read_to_string(path.join(".gitignore"))?.contains(".ideavault.lock"));
+
+    task.status = TaskStatus::Done;
+    storage.save_task(&task)?;
+    storage.delete_task(task.id)?;
+
+    let log = git::log(&path, 10)?;
+    let subjects: Vec<&str> = log
+        .lines()
+        .map(|line| line.splitn(3, "  ").nth(2).unwrap())
+        .collect();
+    let id = &task.id.to_string()[..8];
+    assert_eq!(
+        subjects,
+        vec![
+            format!("task delete {}: deleted \"Before git\"", id),
+            format!("task update {}: Todo → Done", id),
+            "Initialize IdeaVault history".to_string(),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sync_exchanges_changes_through_a_remote() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let remote = temp_dir.path().join("remote.git");
+    let status = std::process::Command::new("git")
+        .args(["init", "--quiet", "--bare"])
+        .arg(&remote)
+        .status()?;
+    assert!(status.success());
+    let remote_args = SyncArgs {
+        remote: Some(remote.to_string_lossy().into_owned()),
+        provider: None,
+        username: None,
+        prefer_local: false,
+        prefer_remote: false,
+    };
+
+    let laptop = Storage::new_with_path(temp_dir.path().join("laptop"))?;
+    let desktop = Storage::new_with_path(temp_dir.path().join("desktop"))?;
+    assert!(sync(&laptop, &remote_args).is_err());
+    git::init(laptop.data_dir())?;
+    git::init(desktop.data_dir())?;
+    assert!(sync(
+        &laptop,
+        &SyncArgs {
+            remote: None,
+            provider: None,
+            username: None,
+            prefer_local: false,
+            prefer_remote: false,
+        }
+    )
+    .is_err());
+
+    laptop.save_idea(&Idea::new("From the laptop".to_string()))?;
+    sync(&laptop, &remote_args)?;
+
+    // Both vaults append to the history log; those edits merge cleanly
+    desktop.save_task(&Task::new("From the desktop".to_string()))?;
+    let summary = sync(&desktop, &remote_args)?;
+    assert!(summary.pulled > 0);
+    assert_eq!(desktop.load_ideas()?[0].title, "From the laptop");
+
+    sync(
+        &laptop,
+        &SyncArgs {
+            remote: None,
+            provider: None,
+            username: None,
+            prefer_local: false,
+            prefer_remote: false,
+        },
+    )?;
+    assert_eq!(laptop.load_tasks()?[0].title, "From the desktop");
+    assert_eq!(laptop.load_history()?.len(), 2);
+    assert_eq!(
+        sync(
+            &laptop,
+            &SyncArgs {
+                remote: None,
+                provider: None,
+                username: None,
+                prefer_local: false,
+                prefer_remote: false,
+            }
+        )?,
+        SyncSummary::default()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sync_merges_conflicting_edits_field_by_field() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let remote = temp_dir.path().join("remote.git");
+    let status = std::process::Command::new("git")
+        .args(["init", "--quiet", "--bare"])
+        .arg(&remote)
+        .status()?;
+    assert!(status.success());
+    let args = |prefer_local: bool| SyncArgs {
+        remote: Some(remote.to_string_lossy().into_owned()),
+        provider: None,
+        username: None,
+        prefer_local,
+        prefer_remote: !prefer_local,
+    };
+
+    let laptop = Storage::new_with_path(temp_dir.path().join("laptop"))?;
+    let desktop = Storage::new_with_path(temp_dir.path().join("desktop"))?;
+    git::init(laptop.data_dir())?;
+    git::init(desktop.data_dir())?;
+    let idea = Idea::new("Original".to_string());
+    laptop.save_idea(&idea)?;
+    sync(&laptop, &args(true))?;
+    sync(&desktop, &args(true))?;
+
+    let mut on_laptop = idea.clone();
+    on_laptop.title = "Laptop title".to_string();
+    laptop.save_idea(&on_laptop)?;
+    sync(&laptop, &args(true))?;
+
+    let mut on_desktop = idea.clone();
+    on_desktop.title = "Desktop title".to_string();
+    on_desktop.description = Some("Added on the desktop".to_string());
+    desktop.save_idea(&on_desktop)?;
+    let summary = sync(&desktop, &args(false))?;
+    assert!(summary.conflicts.contains(&"ideas.json".to_string()));
+
+    let merged = desktop.load_idea(idea.id)?.unwrap();
+    assert_eq!(merged.title, "Laptop title");
+    assert_eq!(merged.description.as_deref(), Some("Added on the desktop"));
+
+    sync(&laptop, &args(true))?;
+    assert_eq!(laptop.load_idea(idea.id)?.unwrap().title, "Laptop title");
+    assert_eq!(
+        laptop.load_idea(idea.id)?.unwrap().description.as_deref(),
+        Some("Added on the desktop")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use ideavault::commands::idea::{DeleteIdeaArgs, IdeaCommands, IdeaUpdateArgs};
+use ideavault::storage::history::{Action, EntityKind};
+use ideavault::{Idea, Storage, Tag};
+use serde_json::json;
+
+#[test]
+fn history_records_create_update_and_delete() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let idea = Idea::new("Original".to_string());
+    let id = idea.id;
+    storage.save_idea(&idea)?;
+
+    let args = IdeaUpdateArgs {
+        id: id.into(),
+        title: Some("Renamed".to_string()),
+        description: None,
+        source: None,
+        status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
+        clear: vec![],
+    };
+    IdeaCommands::update_idea(&storage, &args)?;
+    IdeaCommands::delete_idea(
+        &storage,
+        &DeleteIdeaArgs {
+            id: Some(id.into()),
+            matching: Default::default(),
+            force: true,
+            keep_links: false,
+        },
+    )?;
+
+    let entries: Vec<_> = storage
+        .load_history()?
+        .into_iter()
+        .filter(|entry| entry.entity_id == id.to_string())
+        .collect();
+    let actions: Vec<Action> = entries.iter().map(|entry| entry.action).collect();
+    assert_eq!(
+        actions,
+        vec![Action::Create, Action::Update, Action::Delete]
+    );
+    assert!(entries.iter().all(|entry| entry.entity == EntityKind::Idea));
+
+    let title = entries[1]
+        .changes
+        .iter()
+        .find(|change| change.field == "title")
+        .expect("title change recorded");
+    assert_eq!(title.before, json!("Original"));
+    assert_eq!(title.after, json!("Renamed"));
+
+    // A delete keeps the full record so it can be recovered
+    assert!(entries[2]
+        .changes
+        .iter()
+        .any(|change| change.field == "title" && change.before == json!("Renamed")));
+
+    Ok(())
+}
+
+#[test]
+fn history_ignores_unchanged_entities_and_keys_tags_by_name() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let untouched = Idea::new("Untouched".to_string());
+    storage.save_ideas(std::slice::from_ref(&untouched))?;
+    storage.save_ideas(&[untouched.clone(), Idea::new("Added".to_string())])?;
+    let ideas_logged = storage
+        .load_history()?
+        .iter()
+        .filter(|entry| entry.entity_id == untouched.id.to_string())
+        .count();
+    assert_eq!(ideas_logged, 1);
+
+    storage.save_tags(&[Tag::new("rust".to_string())])?;
+    storage.save_tags(&[Tag::new("rust".to_string()).with_color("#ff0000".to_string())])?;
+
+    let tag_entries: Vec<_> = storage
+        .load_history()?
+        .into_iter()
+        .filter(|entry| entry.entity == EntityKind::Tag)
+        .collect();
+    assert_eq!(tag_entries.len(), 2);
+    assert!(tag_entries.iter().all(|entry| entry.entity_id == "rust"));
+    assert_eq!(tag_entries[1].changes[0].field, "color");
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use ideavault::commands::id::IdArg;
+use ideavault::models::task::Task;
+use ideavault::storage::Storage;
+use uuid::Uuid;
+
+fn id(text: &str) -> Uuid {
+    Uuid::parse_str(text).unwrap()
+}
+
+#[test]
+fn prefixes_pick_out_one_item() -> Result<()> {
+    let candidates = vec![
+        (
+            id("a1b2c3d4-0000-4000-8000-000000000001"),
+            "First".to_string(),
+        ),
+        (
+            id("a1b2ffff-0000-4000-8000-000000000002"),
+            "Second".to_string(),
+        ),
+        (
+            id("0badc0de-0000-4000-8000-000000000003"),
+            "Third".to_string(),
+        ),
+    ];
+    let resolve = |text: &str| text.parse::<IdArg>()?.resolve("task", &candidates);
+
+    assert_eq!(resolve("0b")?, candidates[2].0);
+    assert_eq!(resolve("A1B2C")?, candidates[0].0);
+    // Hyphens are optional
+    assert_eq!(resolve("a1b2ffff-00")?, candidates[1].0);
+    assert_eq!(resolve("a1b2ffff00")?, candidates[1].0);
+    // Whole UUIDs are passed through, even when unknown
+    let unknown = Uuid::new_v4();
+    assert_eq!(resolve(&unknown.to_string())?, unknown);
+
+    let err = resolve("a1b2").unwrap_err().to_string();
+    assert!(err.starts_with("ID prefix 'a1b2' matches 2 tasks"));
+    assert!(err.contains("a1b2c3d4-0000-4000-8000-000000000001  First"));
+    assert!(err.contains("Second"));
+    assert!(!err.contains("Third"));
+
+    assert_eq!(
+        resolve("ff").unwrap_err().to_string(),
+        "No task found with an ID or title matching 'ff'"
+    );
+    assert!("  ".parse::<IdArg>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn titles_match_exactly_then_loosely() -> Result<()> {
+    let candidates: Vec<(Uuid, String)> = [
+        "Rewrite parser",
+        "Rewrite parser tests",
+        "Parse dates",
+        "Write docs",
+    ]
+    .iter()
+    .map(|title| (Uuid::new_v4(), title.to_string()))
+    .collect();
+    let resolve = |text: &str| text.parse::<IdArg>()?.resolve("task", &candidates);
+
+    // An exact title wins over longer titles containing it
+    assert_eq!(resolve("rewrite PARSER")?, candidates[0].0);
+    assert_eq!(resolve("tests")?, candidates[1].0);
+    // Every word, in any order
+    assert_eq!(resolve("dates parse")?, candidates[2].0);
+    // Letters in order
+    assert_eq!(resolve("wrtdcs")?, candidates[3].0);
+
+    // Several equally good matches can't be told apart without a terminal
+    let err = resolve("rewrite").unwrap_err().to_string();
+    assert!(err.starts_with("'rewrite' matches 2 tasks:"));
+    assert!(err.contains("Rewrite parser tests"));
+    assert!(!err.contains("Write docs"));
+
+    assert!(resolve("nothing like it").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn exact_titles_win_over_id_prefixes() -> Result<()> {
+    let candidates = vec![
+        (
+            id("cafe0000-0000-4000-8000-000000000001"),
+            "Espresso machine".to_string(),
+        ),
+        (
+            id("0badc0de-0000-4000-8000-000000000002"),
+            "Cafe".to_string(),
+        ),
+        (
+            id("addadd00-0000-4000-8000-000000000003"),
+            "Adder".to_string(),
+        ),
+        (
+            id("add00000-0000-4000-8000-000000000004"),
+            "Subtract".to_string(),
+        ),
+    ];
+    let resolve = |text: &str| text.parse::<IdArg>()?.resolve("task", &candidates);
+
+    assert_eq!(resolve("cafe")?, candidates[1].0);
+    assert_eq!(resolve("CAFE")?, candidates[1].0);
+    // Without an exact title, a prefix is still an ID
+    assert_eq!(resolve("caf")?, candidates[0].0);
+    // "add" prefixes two IDs, and the title "Adder" isn't an exact match
+    assert!(resolve("add").is_err());
+    Ok(())
+}
+
+#[test]
+fn prefixes_resolve_against_the_vault() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let task = Task::new("Find me".to_string());
+    storage.add_task(&task)?;
+
+    let prefix: IdArg = task.id.to_string()[..6].parse()?;
+    assert_eq!(prefix.task(&storage)?, task.id);
+    assert!(prefix.idea(&storage).is_err());
+
+    let title: IdArg = "find".parse()?;
+    assert_eq!(title.task(&storage)?, task.id);
+
+    Ok(())
+}
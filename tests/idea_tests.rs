@@ -1,6 +1,11 @@
-use ideavault::commands::idea::{IdeaCommands, IdeaUpdateArgs};
-use ideavault::models::idea::{Idea, IdeaStatus};
+use ideavault::commands::idea::{
+    AddNoteArgs, AttachIdeaArgs, CloneIdeaArgs, DeleteIdeaArgs, IdeaCommands, IdeaToTaskArgs,
+    IdeaUpdateArgs, Matrix, MergeIdeaArgs, RelateIdeaArgs,
+};
+use ideavault::models::idea::{AttachmentKind, Idea, IdeaStatus, Rating};
+use ideavault::models::task::TaskPriority;
 use ideavault::storage::Storage;
+use ideavault::{Project, Task};
 use uuid::Uuid;
 
 #[test]
@@ -13,10 +18,17 @@ fn idea_update_title() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: None,
+        source: None,
         status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -37,10 +49,17 @@ fn idea_update_description() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: Some("New description".to_string()),
+        source: None,
         status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -61,10 +80,17 @@ fn idea_update_multiple_fields() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: Some("New description".to_string()),
+        source: None,
         status: Some(IdeaStatus::Active),
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -87,10 +113,17 @@ fn idea_update_clear_description() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
+        source: None,
         status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec!["description".to_string()],
     };
 
@@ -111,10 +144,17 @@ fn idea_update_no_changes() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
+        source: None,
         status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -128,10 +168,17 @@ fn idea_update_invalid_id() {
     let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
 
     let args = IdeaUpdateArgs {
-        id: Uuid::new_v4(),
+        id: Uuid::new_v4().into(),
         title: Some("New Title".to_string()),
         description: None,
+        source: None,
         status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -149,10 +196,17 @@ fn idea_update_invalid_clear_field() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
+        source: None,
         status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec!["invalid_field".to_string()],
     };
 
@@ -170,10 +224,17 @@ fn idea_update_status() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
+        source: None,
         status: Some(IdeaStatus::Completed),
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -194,10 +255,17 @@ fn idea_update_mix_set_and_clear() {
     storage.save_ideas(&[idea]).unwrap();
 
     let args = IdeaUpdateArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: None,
+        source: None,
         status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec!["description".to_string()],
     };
 
@@ -208,3 +276,479 @@ fn idea_update_mix_set_and_clear() {
     assert_eq!(updated.title, "New Title");
     assert_eq!(updated.description, None);
 }
+
+#[test]
+fn idea_update_replaces_adds_and_removes_tags() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let idea = Idea::new("Test".to_string()).with_tags(vec!["old".to_string()]);
+    let id = idea.id;
+    storage.save_ideas(&[idea]).unwrap();
+
+    let mut args = IdeaUpdateArgs {
+        id: id.into(),
+        title: None,
+        description: None,
+        source: None,
+        status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: Some(vec![
+            "rust".to_string(),
+            "cli".to_string(),
+            "rust".to_string(),
+        ]),
+        add_tags: vec!["web".to_string()],
+        remove_tags: vec!["cli".to_string()],
+        clear: vec![],
+    };
+    IdeaCommands::update_idea(&storage, &args).unwrap();
+    assert_eq!(storage.get_idea(id).unwrap().tags, ["rust", "web"]);
+
+    args.tags = None;
+    args.add_tags = vec![];
+    args.remove_tags = vec![];
+    args.clear = vec!["tags".to_string()];
+    IdeaCommands::update_idea(&storage, &args).unwrap();
+    assert!(storage.get_idea(id).unwrap().tags.is_empty());
+}
+
+#[test]
+fn idea_update_priority() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let idea = Idea::new("Test".to_string());
+    assert_eq!(idea.priority, TaskPriority::Medium);
+    let id = idea.id;
+    storage.save_ideas(&[idea]).unwrap();
+
+    let args = IdeaUpdateArgs {
+        id: id.into(),
+        title: None,
+        description: None,
+        source: None,
+        status: None,
+        priority: Some(TaskPriority::High),
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
+        clear: vec![],
+    };
+
+    IdeaCommands::update_idea(&storage, &args).unwrap();
+
+    let ideas = storage.load_ideas().unwrap();
+    let updated = ideas.iter().find(|i| i.id == id).unwrap();
+    assert_eq!(updated.priority, TaskPriority::High);
+}
+
+#[test]
+fn ideas_saved_without_a_priority_read_as_medium() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let id = Uuid::new_v4();
+    let saved = format!(
+        r#"[{{"id": "{}", "title": "Old", "description": null, "tags": [],
+             "status": "Active", "created_at": "2024-01-01T00:00:00Z",
+             "updated_at": "2024-01-01T00:00:00Z"}}]"#,
+        id
+    );
+    std::fs::write(temp_dir.path().join("ideas.json"), saved).unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let ideas = storage.load_ideas().unwrap();
+    assert_eq!(ideas[0].id, id);
+    assert_eq!(ideas[0].status, IdeaStatus::Active);
+    assert_eq!(ideas[0].priority, TaskPriority::Medium);
+}
+
+#[test]
+fn idea_to_task_carries_the_idea_over() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let idea = Idea::new("Garden sensors".to_string())
+        .with_description("Soil moisture".to_string())
+        .with_tags(vec!["iot".to_string()])
+        .with_priority(TaskPriority::High);
+    let project = Project::new("Garden".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea)).unwrap();
+    storage.add_project(&project).unwrap();
+
+    let args = IdeaToTaskArgs {
+        id: idea.id.into(),
+        project_id: Some(project.id.into()),
+        archive: true,
+    };
+    IdeaCommands::idea_to_task(&storage, &args).unwrap();
+
+    let tasks = storage.load_tasks().unwrap();
+    assert_eq!(tasks.len(), 1);
+    let task = &tasks[0];
+    assert_eq!(task.title, "Garden sensors");
+    assert_eq!(task.description.as_deref(), Some("Soil moisture"));
+    assert_eq!(task.tags, ["iot"]);
+    assert_eq!(task.priority, TaskPriority::High);
+    assert_eq!(task.idea_id, Some(idea.id));
+    assert_eq!(task.project_id, Some(project.id));
+    assert_eq!(
+        storage.get_idea(idea.id).unwrap().status,
+        IdeaStatus::Archived
+    );
+}
+
+#[test]
+fn idea_to_task_keeps_the_idea_unless_asked() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let idea = Idea::new("Test".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea)).unwrap();
+
+    let args = IdeaToTaskArgs {
+        id: idea.id.into(),
+        project_id: None,
+        archive: false,
+    };
+    IdeaCommands::idea_to_task(&storage, &args).unwrap();
+
+    assert_eq!(storage.load_tasks().unwrap()[0].project_id, None);
+    assert_eq!(
+        storage.get_idea(idea.id).unwrap().status,
+        IdeaStatus::Brainstorming
+    );
+}
+
+#[test]
+fn idea_clone_copies_under_a_new_id() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let mut idea = Idea::new("Garden".to_string())
+        .with_description("Sensors".to_string())
+        .with_tags(vec!["iot".to_string()])
+        .with_status(IdeaStatus::Active);
+    idea.created_at -= chrono::Duration::days(30);
+    storage.save_ideas(std::slice::from_ref(&idea)).unwrap();
+
+    let args = |title: Option<&str>| CloneIdeaArgs {
+        id: idea.id.into(),
+        title: title.map(str::to_string),
+    };
+    IdeaCommands::clone_idea(&storage, &args(None)).unwrap();
+    IdeaCommands::clone_idea(&storage, &args(Some("Balcony garden"))).unwrap();
+
+    let ideas = storage.load_ideas().unwrap();
+    assert_eq!(ideas.len(), 3);
+    let copy = ideas.iter().find(|i| i.title == "Garden (copy)").unwrap();
+    assert_ne!(copy.id, idea.id);
+    assert!(copy.created_at > idea.created_at);
+    assert_eq!(copy.description.as_deref(), Some("Sensors"));
+    assert_eq!(copy.tags, ["iot"]);
+    assert_eq!(copy.status, IdeaStatus::Active);
+    assert!(ideas.iter().any(|i| i.title == "Balcony garden"));
+}
+
+#[test]
+fn idea_relate_links_both_ways_until_unrelated() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let garden = Idea::new("Garden".to_string());
+    let sensors = Idea::new("Sensors".to_string());
+    let bees = Idea::new("Bees".to_string());
+    storage
+        .save_ideas(&[garden.clone(), sensors.clone(), bees.clone()])
+        .unwrap();
+    let args = |a: &Idea, b: &Idea| RelateIdeaArgs {
+        id: a.id.into(),
+        other_id: b.id.into(),
+    };
+    let related = |idea: &Idea| storage.get_idea(idea.id).unwrap().related_ids;
+
+    IdeaCommands::relate_ideas(&storage, &args(&garden, &sensors)).unwrap();
+    IdeaCommands::relate_ideas(&storage, &args(&sensors, &garden)).unwrap();
+    IdeaCommands::relate_ideas(&storage, &args(&bees, &garden)).unwrap();
+    assert_eq!(related(&garden), [sensors.id, bees.id]);
+    assert_eq!(related(&sensors), [garden.id]);
+    assert!(IdeaCommands::relate_ideas(&storage, &args(&bees, &bees)).is_err());
+
+    IdeaCommands::unrelate_ideas(&storage, &args(&sensors, &garden)).unwrap();
+    assert_eq!(related(&garden), [bees.id]);
+    assert!(related(&sensors).is_empty());
+
+    // Deleting an idea removes it from the ideas related to it
+    let delete = DeleteIdeaArgs {
+        id: Some(bees.id.into()),
+        matching: Default::default(),
+        force: true,
+        keep_links: false,
+    };
+    IdeaCommands::delete_idea(&storage, &delete).unwrap();
+    assert!(related(&garden).is_empty());
+}
+
+#[test]
+fn idea_merge_folds_the_duplicate_into_the_keeper() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let keep = Idea::new("Garden".to_string())
+        .with_description("Raised beds".to_string())
+        .with_tags(vec!["home".to_string()]);
+    let mut duplicate = Idea::new("Garden again".to_string())
+        .with_description("Drip irrigation".to_string())
+        .with_tags(vec!["home".to_string(), "iot".to_string()]);
+    let mut other = Idea::new("Bees".to_string());
+    duplicate.related_ids = vec![other.id];
+    other.related_ids = vec![duplicate.id];
+    storage
+        .save_ideas(&[keep.clone(), duplicate.clone(), other.clone()])
+        .unwrap();
+    let project = Project::new("Yard".to_string()).with_ideas(vec![duplicate.id, keep.id]);
+    storage.add_project(&project).unwrap();
+    let task = Task::new("Buy hoses".to_string()).with_idea(duplicate.id);
+    storage.add_task(&task).unwrap();
+
+    let args = MergeIdeaArgs {
+        keep_id: keep.id.into(),
+        duplicate_id: duplicate.id.into(),
+        force: true,
+    };
+    IdeaCommands::merge_ideas(&storage, &args).unwrap();
+
+    let ideas = storage.load_ideas().unwrap();
+    assert_eq!(ideas.len(), 2);
+    let merged = storage.get_idea(keep.id).unwrap();
+    assert_eq!(
+        merged.description.as_deref(),
+        Some("Raised beds\n\nDrip irrigation")
+    );
+    assert_eq!(merged.tags, ["home", "iot"]);
+    assert_eq!(merged.related_ids, [other.id]);
+    assert_eq!(storage.get_idea(other.id).unwrap().related_ids, [keep.id]);
+    assert_eq!(storage.load_projects().unwrap()[0].idea_ids, [keep.id]);
+    assert_eq!(storage.load_tasks().unwrap()[0].idea_id, Some(keep.id));
+    assert!(storage.get_idea(duplicate.id).is_err());
+}
+
+#[test]
+fn idea_notes_are_appended_in_order() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let idea = Idea::new("Garden".to_string()).with_description("Raised beds".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea)).unwrap();
+    let add = |text: &str| {
+        let args = AddNoteArgs {
+            id: idea.id.into(),
+            text: text.to_string(),
+        };
+        IdeaCommands::add_note(&storage, &args)
+    };
+
+    add("Beds need full sun").unwrap();
+    add("  Maybe a greenhouse instead  ").unwrap();
+    assert!(add("   ").is_err());
+
+    let saved = storage.get_idea(idea.id).unwrap();
+    let bodies: Vec<&str> = saved.notes.iter().map(|n| n.body.as_str()).collect();
+    assert_eq!(bodies, ["Beds need full sun", "Maybe a greenhouse instead"]);
+    assert!(saved.notes[0].created_at <= saved.notes[1].created_at);
+    assert_eq!(saved.description.as_deref(), Some("Raised beds"));
+}
+
+#[test]
+fn idea_attach_copies_files_and_records_urls() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().join("vault")).unwrap();
+
+    let idea = Idea::new("Garden".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea)).unwrap();
+    let sketch = temp_dir.path().join("beds.txt");
+    std::fs::write(&sketch, "three beds").unwrap();
+    let attach = |target: &str| {
+        let args = AttachIdeaArgs {
+            id: idea.id.into(),
+            target: target.to_string(),
+        };
+        IdeaCommands::attach(&storage, &args)
+    };
+
+    attach(sketch.to_str().unwrap()).unwrap();
+    attach(sketch.to_str().unwrap()).unwrap();
+    attach("https://example.com/raised-beds").unwrap();
+    assert!(attach(temp_dir.path().join("missing.txt").to_str().unwrap()).is_err());
+
+    let saved = storage.get_idea(idea.id).unwrap();
+    let kinds: Vec<AttachmentKind> = saved.attachments.iter().map(|a| a.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            AttachmentKind::File,
+            AttachmentKind::File,
+            AttachmentKind::Url
+        ]
+    );
+    assert_eq!(saved.attachments[0].name, "beds.txt");
+    assert_ne!(saved.attachments[0].location, saved.attachments[1].location);
+    for file in &saved.attachments[..2] {
+        let copy = storage.attachments_dir().join(&file.location);
+        assert_eq!(std::fs::read_to_string(copy).unwrap(), "three beds");
+    }
+    assert_eq!(
+        saved.attachments[2].location,
+        "https://example.com/raised-beds"
+    );
+}
+
+#[test]
+fn idea_status_changes_are_recorded_with_time_in_status() {
+    use chrono::{Duration, Utc};
+
+    let mut idea = Idea::new("Garden".to_string());
+    idea.set_status(IdeaStatus::Brainstorming);
+    assert!(idea.status_history.is_empty());
+
+    idea.set_status(IdeaStatus::Active);
+    idea.set_status(IdeaStatus::Completed);
+    let moves: Vec<(IdeaStatus, IdeaStatus)> = idea
+        .status_history
+        .iter()
+        .map(|change| (change.from.clone(), change.to.clone()))
+        .collect();
+    assert_eq!(
+        moves,
+        [
+            (IdeaStatus::Brainstorming, IdeaStatus::Active),
+            (IdeaStatus::Active, IdeaStatus::Completed)
+        ]
+    );
+
+    // Two days brainstorming, a day active, back to brainstorming for a
+    // day, then three days completed
+    let start = Utc::now() - Duration::days(7);
+    let day = |n| start + Duration::days(n);
+    idea.created_at = start;
+    for (change, (from, to, at)) in idea.status_history.iter_mut().zip([
+        (IdeaStatus::Brainstorming, IdeaStatus::Active, day(2)),
+        (IdeaStatus::Active, IdeaStatus::Brainstorming, day(3)),
+    ]) {
+        change.from = from;
+        change.to = to;
+        change.changed_at = at;
+    }
+    idea.status = IdeaStatus::Brainstorming;
+    idea.set_status(IdeaStatus::Completed);
+    idea.status_history[2].changed_at = day(4);
+
+    assert_eq!(
+        idea.time_in_status(day(7)),
+        [
+            (IdeaStatus::Brainstorming, Duration::days(3)),
+            (IdeaStatus::Active, Duration::days(1)),
+            (IdeaStatus::Completed, Duration::days(3)),
+        ]
+    );
+}
+
+#[test]
+fn idea_source_is_set_cleared_and_searchable() {
+    use ideavault::commands::search::{SearchEngine, SearchFilters};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let book = Idea::new("Anchoring".to_string());
+    let talk = Idea::new("Priming".to_string()).with_source("talk at work".to_string());
+    storage.save_ideas(&[book.clone(), talk.clone()]).unwrap();
+    let update = |id: Uuid, source: Option<&str>, clear: Vec<String>| {
+        let args = IdeaUpdateArgs {
+            id: id.into(),
+            title: None,
+            description: None,
+            source: source.map(str::to_string),
+            status: None,
+            priority: None,
+            effort: None,
+            impact: None,
+            tags: None,
+            add_tags: vec![],
+            remove_tags: vec![],
+            clear,
+        };
+        IdeaCommands::update_idea(&storage, &args).unwrap();
+    };
+
+    update(book.id, Some("book: Thinking, Fast and Slow"), vec![]);
+    update(talk.id, None, vec!["source".to_string()]);
+    let book = storage.get_idea(book.id).unwrap();
+    assert_eq!(
+        book.source.as_deref(),
+        Some("book: Thinking, Fast and Slow")
+    );
+    assert_eq!(storage.get_idea(talk.id).unwrap().source, None);
+
+    let results = SearchEngine::new(storage)
+        .search("fast and slow", SearchFilters::default())
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, book.id.to_string());
+}
+
+#[test]
+fn idea_ratings_run_from_one_to_five() {
+    assert_eq!("3".parse::<Rating>().unwrap().value(), 3);
+    assert_eq!(Rating::new(5).unwrap().to_string(), "5/5");
+    for invalid in ["0", "6", "two", ""] {
+        assert!(invalid.parse::<Rating>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn idea_titles_match_when_case_punctuation_order_or_a_typo_differ() {
+    let idea = Idea::new("Dark mode for the app".to_string());
+    for similar in [
+        "dark-mode for the App!",
+        "app: dark mode for the",
+        "Drak mode for the app",
+    ] {
+        assert!(idea.has_similar_title(similar), "{}", similar);
+    }
+    for different in ["Light mode for the app", "Dark mode", "", "!!!"] {
+        assert!(!idea.has_similar_title(different), "{}", different);
+    }
+
+    let numbered = Idea::new("Blog post part 1".to_string());
+    assert!(!numbered.has_similar_title("Blog post part 2"));
+    assert!(numbered.has_similar_title("blog post, part 1"));
+}
+
+#[test]
+fn idea_matrix_sorts_rated_ideas_into_quadrants() {
+    let rated = |title: &str, effort: u8, impact: u8| {
+        let mut idea = Idea::new(title.to_string());
+        idea.effort = Rating::new(effort);
+        idea.impact = Rating::new(impact);
+        idea
+    };
+    let titles =
+        |ideas: &[Idea]| -> Vec<String> { ideas.iter().map(|i| i.title.clone()).collect() };
+
+    let matrix = Matrix::of(vec![
+        rated("Rewrite", 5, 5),
+        rated("Typo fix", 1, 3),
+        rated("Dark mode", 2, 5),
+        rated("Emoji picker", 2, 1),
+        rated("Custom DSL", 4, 2),
+        Idea::new("Unscored".to_string()),
+    ]);
+    assert_eq!(titles(&matrix.quick_wins), ["Dark mode", "Typo fix"]);
+    assert_eq!(titles(&matrix.big_bets), ["Rewrite"]);
+    assert_eq!(titles(&matrix.fill_ins), ["Emoji picker"]);
+    assert_eq!(titles(&matrix.money_pits), ["Custom DSL"]);
+    assert_eq!(titles(&matrix.unrated), ["Unscored"]);
+}
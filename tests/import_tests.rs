@@ -0,0 +1,187 @@
+use anyhow::Result;
+use ideavault::commands::export::{export, ExportArgs};
+use ideavault::commands::import::{import, ImportArgs, ImportSummary};
+use ideavault::export::{EntityType, ExportFormat};
+use ideavault::models::idea::IdeaStatus;
+use ideavault::models::task::TaskPriority;
+use ideavault::{Idea, Project, Storage, Task};
+use std::path::PathBuf;
+
+fn import_args(file: PathBuf) -> ImportArgs {
+    ImportArgs {
+        file,
+        kind: None,
+        mappings: vec![],
+        dry_run: false,
+        archive: false,
+        force: false,
+    }
+}
+
+#[test]
+fn spreadsheet_columns_are_matched_and_duplicates_skipped() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().join("vault"))?;
+    storage.save_ideas(&[Idea::new("Already here".to_string())])?;
+
+    let file = temp_dir.path().join("brainstorm.csv");
+    std::fs::write(
+        &file,
+        "Name,Notes,Labels,State,Score\n\
+         Solar kiln,\"Dry wood, fast\nwith sun\",\"energy; diy\",active,5\n\
+         already here,Same title,,,1\n\
+         Solar Kiln,Repeated in file,,,2\n\
+         ,No title,,,3\n",
+    )?;
+
+    let mut args = import_args(file);
+    args.kind = Some(EntityType::Idea);
+    args.dry_run = true;
+    let summary = import(&storage, &args)?;
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 1,
+            skipped: 3
+        }
+    );
+    assert_eq!(storage.load_ideas()?.len(), 1);
+
+    args.dry_run = false;
+    import(&storage, &args)?;
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 2);
+    let kiln = &ideas[1];
+    assert_eq!(kiln.title, "Solar kiln");
+    assert_eq!(
+        kiln.description.as_deref(),
+        Some("Dry wood, fast\nwith sun")
+    );
+    assert_eq!(kiln.tags, vec!["energy", "diy"]);
+    assert_eq!(kiln.status, IdeaStatus::Active);
+
+    Ok(())
+}
+
+#[test]
+fn explicit_mappings_override_column_names() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().join("vault"))?;
+
+    let file = temp_dir.path().join("todo.csv");
+    std::fs::write(&file, "What,Urgency,When\nCall back,high,2030-01-31\n")?;
+
+    let mut args = import_args(file);
+    args.kind = Some(EntityType::Task);
+    args.mappings = vec![
+        "What=title".to_string(),
+        "Urgency=priority".to_string(),
+        "When=due_date".to_string(),
+    ];
+    import(&storage, &args)?;
+
+    let tasks = storage.load_tasks()?;
+    assert_eq!(tasks[0].title, "Call back");
+    assert_eq!(tasks[0].priority, TaskPriority::High);
+    assert_eq!(
+        tasks[0].due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+        Some("2030-01-31".to_string())
+    );
+
+    args.mappings = vec!["What=nonsense".to_string()];
+    assert!(import(&storage, &args).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn exports_import_into_a_new_vault_with_links() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = Storage::new_with_path(temp_dir.path().join("source"))?;
+    let idea = Idea::new("Linked idea".to_string()).with_tags(vec!["a".to_string()]);
+    let project = Project::new("Project".to_string()).with_ideas(vec![idea.id]);
+    let task = Task::new("Task".to_string()).with_project(project.id);
+    source.save_ideas(std::slice::from_ref(&idea))?;
+    source.save_projects(std::slice::from_ref(&project))?;
+    source.save_tasks(std::slice::from_ref(&task))?;
+
+    let out = temp_dir.path().join("out");
+    export(
+        &source,
+        &ExportArgs {
+            format: ExportFormat::Csv,
+            out: out.clone(),
+            types: vec![],
+            status: None,
+            tag: None,
+            archive: None,
+            encrypt: false,
+        },
+    )?;
+
+    let target = Storage::new_with_path(temp_dir.path().join("target"))?;
+    for file in ["ideas.csv", "projects.csv", "tasks.csv"] {
+        import(&target, &import_args(out.join(file)))?;
+    }
+
+    let ideas = target.load_ideas()?;
+    assert_eq!(ideas[0].id, idea.id);
+    assert_eq!(ideas[0].created_at, idea.created_at);
+    assert_eq!(target.load_projects()?[0].idea_ids, vec![idea.id]);
+    assert_eq!(target.load_tasks()?[0].project_id, Some(project.id));
+
+    // Importing the same file again finds only duplicates
+    let summary = import(&target, &import_args(out.join("ideas.csv")))?;
+    assert_eq!(summary.created, 0);
+    assert_eq!(summary.skipped, 1);
+
+    Ok(())
+}
+
+#[test]
+fn legacy_ideas_are_upgraded_in_place_and_on_import() -> Result<()> {
+    let legacy = r#"[
+  {
+    "id": "6f1c2b7e-1d2a-4c3b-9e8f-0a1b2c3d4e5f",
+    "title": "From the old days",
+    "description": "Written before tags existed",
+    "created_at": "2023-04-01T10:00:00Z",
+    "updated_at": "2023-04-02T10:00:00Z"
+  }
+]"#;
+    let temp_dir = tempfile::tempdir()?;
+
+    // An old vault is read as it is and written in the current format on the next save
+    let vault = temp_dir.path().join("vault");
+    std::fs::create_dir_all(&vault)?;
+    std::fs::write(vault.join("ideas.json"), legacy)?;
+    let storage = Storage::new_with_path(vault.clone())?;
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].status, IdeaStatus::Brainstorming);
+    assert!(ideas[0].tags.is_empty());
+    assert_eq!(
+        ideas[0].created_at.to_rfc3339(),
+        "2023-04-01T10:00:00+00:00"
+    );
+
+    storage.save_idea(&Idea::new("New".to_string()))?;
+    let on_disk = std::fs::read_to_string(vault.join("ideas.json"))?;
+    assert!(on_disk.contains("\"status\""));
+    assert_eq!(storage.load_ideas()?.len(), 2);
+
+    // A legacy file is recognised as ideas whatever it's called
+    let other = Storage::new_with_path(temp_dir.path().join("other"))?;
+    let file = temp_dir.path().join("backup-2023.json");
+    std::fs::write(&file, legacy)?;
+    let summary = import(&other, &import_args(file))?;
+    assert_eq!(summary.created, 1);
+    let imported = other.load_ideas()?;
+    assert_eq!(imported[0].title, "From the old days");
+    assert_eq!(
+        imported[0].description.as_deref(),
+        Some("Written before tags existed")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use ideavault::commands::inbox::{capture, CaptureArgs, InboxCommands, Processed};
+use ideavault::models::idea::IdeaStatus;
+use ideavault::Storage;
+
+fn capture_text(storage: &Storage, text: &str) -> Result<()> {
+    let args = CaptureArgs {
+        text: text.split(' ').map(String::from).collect(),
+        tags: vec!["home".to_string()],
+    };
+    capture(storage, &args)?;
+    // Captures made in the same instant still process in order
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    Ok(())
+}
+
+#[test]
+fn captured_items_wait_in_the_inbox() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    capture_text(&storage, "call dentist")?;
+
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].title, "call dentist");
+    assert_eq!(ideas[0].status, IdeaStatus::Inbox);
+    assert_eq!(ideas[0].tags, vec!["home"]);
+    Ok(())
+}
+
+#[test]
+fn processing_turns_captures_into_ideas_and_tasks() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    for text in ["app for plants", "call dentist", "old note", "later"] {
+        capture_text(&storage, text)?;
+    }
+
+    // An unknown answer is asked again; input running out stops early
+    let processed = InboxCommands::process_inbox(&storage, &mut "i\nx\nt\nd\n".as_bytes())?;
+    assert_eq!(
+        processed,
+        Processed {
+            ideas: 1,
+            tasks: 1,
+            deleted: 1,
+            skipped: 0,
+        }
+    );
+
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 2);
+    let plants = ideas.iter().find(|i| i.title == "app for plants").unwrap();
+    assert_eq!(plants.status, IdeaStatus::Brainstorming);
+    let later = ideas.iter().find(|i| i.title == "later").unwrap();
+    assert_eq!(later.status, IdeaStatus::Inbox);
+
+    let tasks = storage.load_tasks()?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].title, "call dentist");
+    assert_eq!(tasks[0].tags, vec!["home"]);
+
+    assert_eq!(storage.load_trash()?[0].title(), "old note");
+
+    // Skipping leaves the item for next time
+    let processed = InboxCommands::process_inbox(&storage, &mut "s\n".as_bytes())?;
+    assert_eq!(processed.skipped, 1);
+    assert_eq!(storage.load_ideas()?.len(), 2);
+    Ok(())
+}
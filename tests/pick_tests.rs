@@ -0,0 +1,27 @@
+use anyhow::Result;
+use ideavault::commands::id::IdArg;
+use ideavault::commands::pick::PickKind;
+use ideavault::models::task::TaskStatus;
+use ideavault::{Storage, Task};
+
+#[test]
+fn choices_list_newest_first_and_ids_skip_the_picker() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let older = Task::new("Write docs".to_string());
+    storage.add_task(&older)?;
+    let mut newer = Task::new("Fix parser".to_string()).with_status(TaskStatus::Blocked);
+    newer.updated_at = older.updated_at + chrono::Duration::seconds(1);
+    storage.add_task(&newer)?;
+
+    let choices = PickKind::Task.choices(&storage)?;
+    assert_eq!(choices.len(), 2);
+    assert_eq!(choices[0].id, newer.id);
+    assert!(choices[0].label.starts_with("Fix parser  (Blocked)  ["));
+    assert!(PickKind::Idea.choices(&storage)?.is_empty());
+
+    let id: IdArg = "docs".parse()?;
+    assert_eq!(PickKind::Task.resolve(&storage, Some(&id))?, older.id);
+    assert_eq!("Tasks".parse::<PickKind>()?, PickKind::Task);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use ideavault::models::config::Config;
+use ideavault::models::task::{self, Task, TaskPriority};
+use ideavault::style::{self, Color};
+
+// Priorities are set once per process, so everything that depends on the
+// configured scale lives in this one test.
+#[test]
+fn configured_priorities_drive_parsing_and_ordering() -> Result<()> {
+    let mut config = Config::new();
+    config.set(
+        "task_priorities",
+        r#"[
+            { "name": "P0", "emoji": "🔥", "color": "red" },
+            { "name": "P1" },
+            { "name": "P2" },
+            { "name": "P3" },
+            { "name": "P4", "color": "gray" }
+        ]"#,
+    )?;
+    task::set_priorities(config.task_priorities);
+
+    let p = |name: &str| TaskPriority::Custom(name.to_string());
+    assert_eq!("p0".parse::<TaskPriority>()?, p("P0"));
+    let err = "urgent".parse::<TaskPriority>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid priority. Must be one of: p0, p1, p2, p3, p4"
+    );
+
+    // New tasks start in the middle of the scale
+    assert_eq!(Task::new("Triage".to_string()).priority, p("P2"));
+
+    // The most urgent level is the greatest
+    let mut levels = vec![p("P3"), p("P0"), p("P4"), p("P1")];
+    levels.sort_by(|a, b| b.cmp(a));
+    assert_eq!(levels, vec![p("P0"), p("P1"), p("P3"), p("P4")]);
+    assert!(
+        p("P4") > TaskPriority::Urgent,
+        "unconfigured levels rank last"
+    );
+
+    assert_eq!(p("P0").emoji(), "🔥");
+    assert_eq!(TaskPriority::High.emoji(), "⬆️");
+    assert_eq!(style::priority_color(&p("P4")), Color::Gray);
+
+    let json = serde_json::to_value(Task::new("Fire".to_string()).with_priority(p("P0")))?;
+    assert_eq!(json["priority"], "P0");
+    let task: Task = serde_json::from_value(json)?;
+    assert_eq!(task.priority, p("P0"));
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use ideavault::commands::project::UpdateProjectArgs;
+use ideavault::commands::project::{
+    project_link, project_stats, task_progress, AddMilestoneArgs, ArchiveProjectArgs,
+    CloneProjectArgs, CompleteMilestoneArgs, DependArgs, UpdateProjectArgs,
+};
 use ideavault::commands::ProjectCommands;
-use ideavault::models::project::ProjectStatus;
-use ideavault::models::Project;
+use ideavault::models::project::{descendants, Milestone, MilestoneStatus, ProjectStatus};
+use ideavault::models::task::TaskStatus;
+use ideavault::models::{Project, Task};
 use ideavault::storage::Storage;
 use uuid::Uuid;
 
@@ -18,6 +22,33 @@ fn project_url_and_repo_roundtrip() {
     );
 }
 
+#[test]
+fn project_open_picks_url_then_repo() {
+    let project = Project::new("Site".to_string())
+        .with_url("https://example.com".to_string())
+        .with_repo("git@github.com:user/site.git".to_string());
+    assert_eq!(
+        project_link(&project, false).unwrap(),
+        "https://example.com"
+    );
+    assert_eq!(
+        project_link(&project, true).unwrap(),
+        "https://github.com/user/site"
+    );
+
+    let repo_only =
+        Project::new("Tool".to_string()).with_repo("https://github.com/user/tool".to_string());
+    assert_eq!(
+        project_link(&repo_only, false).unwrap(),
+        "https://github.com/user/tool"
+    );
+
+    let bare = Project::new("Bare".to_string());
+    let err = project_link(&bare, false).unwrap_err().to_string();
+    assert!(err.contains("no URL or repository"), "{}", err);
+    assert!(project_link(&bare, true).is_err());
+}
+
 #[test]
 fn project_new_without_url_repo() {
     let project = Project::new("Test".to_string());
@@ -74,13 +105,19 @@ fn project_update_title() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: None,
         milestone: None,
         url: None,
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -101,13 +138,19 @@ fn project_update_url() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         milestone: None,
         url: Some("https://example.com".to_string()),
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -128,13 +171,19 @@ fn project_update_multiple_fields() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: Some("New description".to_string()),
         milestone: Some("v1.0".to_string()),
         url: Some("https://example.com".to_string()),
         repo: Some("https://github.com/user/repo".to_string()),
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -162,13 +211,19 @@ fn project_update_clear_url() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         milestone: None,
         url: None,
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec!["url".to_string()],
     };
 
@@ -193,13 +248,19 @@ fn project_update_clear_multiple() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         milestone: None,
         url: None,
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec!["url".to_string(), "repo".to_string()],
     };
 
@@ -222,13 +283,19 @@ fn project_update_no_changes() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         milestone: None,
         url: None,
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -242,13 +309,19 @@ fn project_update_invalid_id() {
     let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
 
     let args = UpdateProjectArgs {
-        id: Uuid::new_v4(),
+        id: Uuid::new_v4().into(),
         title: Some("New Title".to_string()),
         description: None,
         milestone: None,
         url: None,
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -266,13 +339,19 @@ fn project_update_invalid_clear_field() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         milestone: None,
         url: None,
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec!["invalid_field".to_string()],
     };
 
@@ -290,13 +369,19 @@ fn project_update_status() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         milestone: None,
         url: None,
         repo: None,
         status: Some(ProjectStatus::InProgress),
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec![],
     };
 
@@ -319,13 +404,19 @@ fn project_update_mix_set_and_clear() {
     storage.save_projects(&[project]).unwrap();
 
     let args = UpdateProjectArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: None,
         milestone: None,
         url: Some("https://new.com".to_string()),
         repo: None,
         status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
         clear: vec!["description".to_string()],
     };
 
@@ -337,3 +428,712 @@ fn project_update_mix_set_and_clear() {
     assert_eq!(updated.url, Some("https://new.com".to_string()));
     assert_eq!(updated.description, None);
 }
+
+#[test]
+fn project_milestones_keep_their_order_and_complete_by_number_or_title() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let project = Project::new("Launch".to_string());
+    let id = project.id;
+    storage.save_projects(&[project]).unwrap();
+
+    let add = |title: &str, due: Option<&str>, position: Option<usize>| {
+        ProjectCommands::add_milestone(
+            &storage,
+            &AddMilestoneArgs {
+                project: id.into(),
+                title: title.to_string(),
+                due: due.map(str::to_string),
+                position,
+            },
+        )
+    };
+    add("Beta", Some("2030-03-01"), None).unwrap();
+    add("GA", None, None).unwrap();
+    add("Alpha", None, Some(1)).unwrap();
+    assert!(add("Late", Some("March"), None).is_err());
+
+    let complete = |milestone: &str| {
+        ProjectCommands::complete_milestone(
+            &storage,
+            &CompleteMilestoneArgs {
+                project: id.into(),
+                milestone: milestone.to_string(),
+            },
+        )
+    };
+    complete("alpha").unwrap();
+    complete("2").unwrap();
+    assert!(complete("4").is_err());
+    assert!(complete("Launch party").is_err());
+
+    let project = storage.get_project(id).unwrap();
+    let titles: Vec<&str> = project
+        .milestones
+        .iter()
+        .map(|m| m.title.as_str())
+        .collect();
+    assert_eq!(titles, ["Alpha", "Beta", "GA"]);
+    assert_eq!(
+        project.milestones[1].due_date.unwrap().to_rfc3339(),
+        "2030-03-01T00:00:00+00:00"
+    );
+    assert_eq!(project.milestones[1].status, MilestoneStatus::Completed);
+    assert!(project.milestones[1].completed_at.is_some());
+    assert_eq!(project.milestones[2].status, MilestoneStatus::Open);
+    assert_eq!(project.milestone_progress(), (2, 3));
+}
+
+#[test]
+fn project_tags_are_updated_and_searchable() {
+    use ideavault::commands::search::{SearchEngine, SearchFilters};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let site = Project::new("Site".to_string()).with_tags(vec!["web".to_string()]);
+    let tool = Project::new("Tool".to_string());
+    storage
+        .save_projects(&[site.clone(), tool.clone()])
+        .unwrap();
+
+    let args = UpdateProjectArgs {
+        id: tool.id.into(),
+        title: None,
+        description: None,
+        milestone: None,
+        url: None,
+        repo: None,
+        status: None,
+        priority: None,
+        due_date: None,
+        assignee: None,
+        tags: Some(vec!["cli".to_string(), "draft".to_string()]),
+        add_tags: vec!["rust".to_string()],
+        remove_tags: vec!["draft".to_string()],
+        clear: vec![],
+    };
+    ProjectCommands::update_project(&storage, &args).unwrap();
+    assert_eq!(storage.get_project(tool.id).unwrap().tags, ["cli", "rust"]);
+
+    let engine = SearchEngine::new(storage);
+    let results = engine.search("rust", SearchFilters::default()).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, tool.id.to_string());
+    assert_eq!(results[0].tags, ["cli", "rust"]);
+
+    let filters = SearchFilters {
+        tags_filter: vec!["web".to_string()],
+        ..SearchFilters::default()
+    };
+    let results = engine.search("t", filters).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, site.id.to_string());
+}
+
+#[test]
+fn project_due_dates_are_set_cleared_and_make_projects_overdue() {
+    use chrono::{Duration, Utc};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let project = Project::new("Launch".to_string());
+    let id = project.id;
+    storage.save_projects(&[project]).unwrap();
+    let update = |due_date: Option<&str>, status: Option<ProjectStatus>| {
+        let args = UpdateProjectArgs {
+            id: id.into(),
+            title: None,
+            description: None,
+            milestone: None,
+            url: None,
+            repo: None,
+            status,
+            priority: None,
+            due_date: due_date.map(str::to_string),
+            assignee: None,
+            tags: None,
+            add_tags: vec![],
+            remove_tags: vec![],
+            clear: vec![],
+        };
+        ProjectCommands::update_project(&storage, &args)
+    };
+
+    update(Some("2030-03-01"), None).unwrap();
+    let project = storage.get_project(id).unwrap();
+    let due = project.due_date.unwrap();
+    assert_eq!(due.to_rfc3339(), "2030-03-01T00:00:00+00:00");
+    assert!(!project.is_overdue(due - Duration::days(1)));
+    assert!(project.is_overdue(due + Duration::days(1)));
+
+    update(None, Some(ProjectStatus::Completed)).unwrap();
+    let project = storage.get_project(id).unwrap();
+    assert!(!project.is_overdue(due + Duration::days(1)));
+
+    assert!(update(Some("soon"), None).is_err());
+    update(Some("clear"), None).unwrap();
+    let project = storage.get_project(id).unwrap();
+    assert_eq!(project.due_date, None);
+    assert!(!project.is_overdue(Utc::now()));
+}
+
+#[test]
+fn archiving_a_project_can_cancel_its_open_tasks() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let project = Project::new("Old site".to_string());
+    let id = project.id;
+    storage.save_projects(&[project]).unwrap();
+    let open = Task::new("Fix footer".to_string()).with_project(id);
+    let done = Task::new("Pick fonts".to_string())
+        .with_project(id)
+        .with_status(TaskStatus::Done);
+    let elsewhere = Task::new("Unrelated".to_string());
+    storage
+        .save_tasks(&[open.clone(), done.clone(), elsewhere.clone()])
+        .unwrap();
+    let archive = |cancel_tasks| {
+        let args = ArchiveProjectArgs {
+            id: id.into(),
+            cancel_tasks,
+        };
+        ProjectCommands::archive_project(&storage, &args)
+    };
+
+    archive(true).unwrap();
+    assert_eq!(
+        storage.get_project(id).unwrap().status,
+        ProjectStatus::Archived
+    );
+    let status = |task: &Task| storage.get_task(task.id).unwrap().status;
+    assert_eq!(status(&open), TaskStatus::Cancelled);
+    assert_eq!(status(&done), TaskStatus::Done);
+    assert_eq!(status(&elsewhere), TaskStatus::Todo);
+
+    let err = archive(false).unwrap_err();
+    assert!(err.to_string().contains("already archived"));
+}
+
+#[test]
+fn cloning_a_project_copies_it_and_optionally_its_tasks_as_todo() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let mut release = Project::new("Release 1.0".to_string())
+        .with_tags(vec!["release".to_string()])
+        .with_status(ProjectStatus::Completed);
+    release.add_milestone(Milestone::new("Freeze".to_string()), None);
+    release.complete_milestone(0);
+    storage.save_projects(&[release.clone()]).unwrap();
+    storage
+        .save_tasks(&[Task::new("Tag the release".to_string())
+            .with_project(release.id)
+            .with_status(TaskStatus::Done)])
+        .unwrap();
+    let clone = |title: &str, with_tasks| {
+        let args = CloneProjectArgs {
+            id: release.id.into(),
+            title: Some(title.to_string()),
+            with_tasks,
+        };
+        ProjectCommands::clone_project(&storage, &args).unwrap();
+        storage
+            .load_projects()
+            .unwrap()
+            .into_iter()
+            .find(|project| project.title == title)
+            .unwrap()
+    };
+    let tasks_of = |project: &Project| -> Vec<Task> {
+        storage
+            .load_tasks()
+            .unwrap()
+            .into_iter()
+            .filter(|task| task.project_id == Some(project.id))
+            .collect()
+    };
+
+    let copy = clone("Release 2.0", true);
+    assert_ne!(copy.id, release.id);
+    assert_eq!(copy.status, ProjectStatus::Planning);
+    assert_eq!(copy.tags, ["release"]);
+    assert_eq!(copy.milestones.len(), 1);
+    assert_ne!(copy.milestones[0].id, release.milestones[0].id);
+    assert_eq!(copy.milestones[0].status, MilestoneStatus::Open);
+    let tasks = tasks_of(&copy);
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].title, "Tag the release");
+    assert_eq!(tasks[0].status, TaskStatus::Todo);
+    assert_eq!(tasks_of(&release).len(), 1);
+
+    let bare = clone("Release 3.0", false);
+    assert!(tasks_of(&bare).is_empty());
+}
+
+#[test]
+fn project_dependencies_block_until_completed_and_refuse_cycles() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let design = Project::new("Design".to_string());
+    let build = Project::new("Build".to_string());
+    let launch = Project::new("Launch".to_string());
+    storage
+        .save_projects(&[design.clone(), build.clone(), launch.clone()])
+        .unwrap();
+    let depend = |id: Uuid, on: Uuid| {
+        let args = DependArgs {
+            id: id.into(),
+            on: on.into(),
+        };
+        ProjectCommands::add_dependency(&storage, &args)
+    };
+
+    depend(launch.id, build.id).unwrap();
+    depend(build.id, design.id).unwrap();
+    depend(build.id, design.id).unwrap();
+    assert_eq!(
+        storage.get_project(build.id).unwrap().depends_on,
+        [design.id]
+    );
+
+    let err = depend(design.id, launch.id).unwrap_err().to_string();
+    assert!(err.contains("Design → Launch → Build → Design"), "{}", err);
+    assert!(depend(design.id, design.id).is_err());
+
+    let blocked_by = |id: Uuid| -> Vec<String> {
+        let projects = storage.load_projects().unwrap();
+        let project = projects.iter().find(|project| project.id == id).unwrap();
+        project
+            .blocked_by(&projects)
+            .into_iter()
+            .map(|project| project.title.clone())
+            .collect()
+    };
+    assert_eq!(blocked_by(launch.id), ["Build"]);
+    storage
+        .update_project(build.id, |project| {
+            project.set_status(ProjectStatus::Completed)
+        })
+        .unwrap();
+    assert!(blocked_by(launch.id).is_empty());
+
+    let args = DependArgs {
+        id: launch.id.into(),
+        on: build.id.into(),
+    };
+    ProjectCommands::remove_dependency(&storage, &args).unwrap();
+    assert!(storage
+        .get_project(launch.id)
+        .unwrap()
+        .depends_on
+        .is_empty());
+}
+
+#[test]
+fn sub_project_tasks_roll_up_into_their_parents() {
+    let site = Project::new("Site".to_string());
+    let backend = Project::new("Backend".to_string()).with_parent(site.id);
+    let api = Project::new("API".to_string()).with_parent(backend.id);
+    let other = Project::new("Other".to_string());
+    let projects = vec![site.clone(), backend.clone(), api.clone(), other.clone()];
+    let tasks = vec![
+        Task::new("Homepage".to_string()).with_project(site.id),
+        Task::new("Schema".to_string())
+            .with_project(api.id)
+            .with_status(TaskStatus::Done),
+        Task::new("Endpoints".to_string()).with_project(api.id),
+        Task::new("Elsewhere".to_string()).with_project(other.id),
+    ];
+
+    assert_eq!(descendants(&projects, site.id), [backend.id, api.id]);
+    assert!(descendants(&projects, api.id).is_empty());
+    assert_eq!(task_progress(site.id, &projects, &tasks), (1, 3));
+    assert_eq!(task_progress(backend.id, &projects, &tasks), (1, 2));
+    assert_eq!(task_progress(other.id, &projects, &tasks), (0, 1));
+}
+
+#[test]
+fn project_stats_sum_up_ideas_tasks_and_recent_changes() {
+    use chrono::{Duration, Utc};
+    use ideavault::commands::dashboard::StatusCount;
+    use ideavault::models::idea::IdeaStatus;
+    use ideavault::models::task::TaskPriority;
+    use ideavault::Idea;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let now = Utc::now();
+    let idea = Idea::new("Dark mode".to_string()).with_status(IdeaStatus::Active);
+    let mut project = Project::new("App".to_string());
+    project.add_idea(idea.id);
+    storage.add_idea(&idea).unwrap();
+    storage.add_project(&project).unwrap();
+
+    let mut oldest = Task::new("Design".to_string()).with_project(project.id);
+    oldest.created_at = now - Duration::days(30);
+    let late = Task::new("Ship".to_string())
+        .with_project(project.id)
+        .with_priority(TaskPriority::named("High"))
+        .with_due_date(now - Duration::days(1));
+    let done = Task::new("Sketch".to_string())
+        .with_project(project.id)
+        .with_status(TaskStatus::Done)
+        .with_due_date(now - Duration::days(2));
+    for task in [&oldest, &late, &done, &Task::new("Elsewhere".to_string())] {
+        storage.add_task(task).unwrap();
+    }
+
+    let stats = project_stats(&storage, project.id, now).unwrap();
+    let counts = |counts: &[StatusCount]| -> Vec<String> {
+        counts
+            .iter()
+            .map(|count| format!("{} {}", count.count, count.status))
+            .collect()
+    };
+    assert_eq!(counts(&stats.ideas), ["1 Active"]);
+    assert_eq!(counts(&stats.tasks), ["2 Todo", "1 Done"]);
+    assert_eq!(counts(&stats.task_priorities), ["2 Medium", "1 High"]);
+    assert_eq!(stats.overdue_tasks, 1);
+    assert_eq!(stats.oldest_open_task.unwrap().id, oldest.id);
+    assert_eq!(stats.recent_activity.len(), 4);
+    assert_eq!(stats.recent_activity[0].entity_id, done.id.to_string());
+}
+
+#[test]
+fn board_puts_each_task_in_its_status_column() {
+    use ideavault::commands::board::render_board;
+    use ideavault::models::task::TaskPriority;
+
+    let urgent =
+        Task::new("Fix the login page".to_string()).with_priority(TaskPriority::named("Urgent"));
+    let todo = Task::new("Docs".to_string());
+    let doing = Task::new("Deploy".to_string()).with_status(TaskStatus::InProgress);
+    let tasks = [todo.clone(), urgent.clone(), doing.clone()];
+
+    let board = render_board(&tasks, 12, true);
+    let lines: Vec<&str> = board.lines().collect();
+    assert_eq!(lines[0], "Todo (2)      InProgress …");
+    assert_eq!(lines[2], "Fix the log…  Deploy");
+    assert!(lines[3].starts_with(&urgent.id.simple().to_string()[..8]));
+    assert_eq!(lines[4], "Docs");
+    assert_eq!(lines.len(), 6);
+
+    let board = render_board(&tasks, 12, false);
+    assert!(board.lines().next().unwrap().contains("Blocked (0)"));
+}
+
+#[test]
+fn report_covers_the_period_and_what_blocks_the_project() {
+    use chrono::{Duration, Utc};
+    use ideavault::commands::project::project_report;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let now = Utc::now();
+    let api = Project::new("API".to_string());
+    let mut site = Project::new("Site".to_string());
+    site.add_dependency(api.id);
+    site.add_milestone(Milestone::new("Beta".to_string()), None);
+    site.add_milestone(
+        Milestone::new("Launch".to_string()).with_due_date(now + Duration::days(10)),
+        None,
+    );
+    site.complete_milestone(0);
+    storage.add_project(&api).unwrap();
+    storage.add_project(&site).unwrap();
+
+    let shipped = Task::new("Landing page".to_string())
+        .with_project(site.id)
+        .with_status(TaskStatus::Done);
+    let mut earlier = Task::new("Wireframes".to_string())
+        .with_project(site.id)
+        .with_status(TaskStatus::Done);
+    earlier.completed_at = Some(now - Duration::days(30));
+    earlier.updated_at = now - Duration::days(30);
+    let later = Task::new("Polish".to_string()).with_project(site.id);
+    let sooner = Task::new("Pricing".to_string())
+        .with_project(site.id)
+        .with_due_date(now + Duration::days(3));
+    let stuck = Task::new("Payments".to_string())
+        .with_project(site.id)
+        .with_status(TaskStatus::Blocked);
+    for task in [&shipped, &earlier, &later, &sooner, &stuck] {
+        storage.add_task(task).unwrap();
+    }
+
+    let report = project_report(&storage, site.id, now - Duration::days(7), now).unwrap();
+    assert!(report.starts_with("# Site — status report\n"));
+    assert!(report.contains("- **Tasks:** 2 of 5 done (40%)\n"));
+    assert!(report.contains("- **Milestones:** 1 of 2 reached\n"));
+    assert!(report.contains("- [x] Beta (reached "));
+    let section = |heading: &str| -> Vec<&str> {
+        report
+            .split(&format!("## {}\n\n", heading))
+            .nth(1)
+            .unwrap()
+            .lines()
+            .take_while(|line| !line.is_empty())
+            .collect()
+    };
+    assert_eq!(section("Completed").len(), 1);
+    assert!(section("Completed")[0].starts_with("- Landing page ("));
+    let upcoming = section("Upcoming");
+    assert!(upcoming[0].starts_with("- Pricing (Todo, Medium) — due "));
+    assert_eq!(upcoming[1], "- Polish (Todo, Medium)");
+    assert_eq!(
+        section("Blockers"),
+        [
+            "- Waiting on project API (Planning)",
+            "- Payments (blocked task)"
+        ]
+    );
+}
+
+#[test]
+fn timeline_draws_each_project_from_creation_to_its_due_date() {
+    use chrono::{Duration, TimeZone, Utc};
+    use ideavault::commands::timeline::render_timeline;
+
+    let from = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+    let day = |days: i64| from + Duration::days(days);
+    let mut api = Project::new("API".to_string()).with_due_date(day(9));
+    api.created_at = day(-30);
+    let mut site = Project::new("Site".to_string()).with_due_date(day(60));
+    site.created_at = day(4);
+    site.add_milestone(
+        Milestone::new("Beta".to_string()).with_due_date(day(6)),
+        None,
+    );
+    let undated = Project::new("Someday".to_string());
+
+    // 20 days over 20 columns, so a column is a day
+    let chart = render_timeline(&[site, undated, api], from, 20, 20, from);
+    let lines: Vec<&str> = chart.lines().collect();
+    assert_eq!(lines[0], "       Oct 2026");
+    assert_eq!(lines[1], format!("       ┬{}", "─".repeat(19)));
+    assert_eq!(
+        lines[2],
+        format!(
+            "API    {}┫{}  due 2026-10-10",
+            "━".repeat(9),
+            " ".repeat(10)
+        )
+    );
+    assert_eq!(
+        lines[3],
+        format!("Site       ━━◆{}▶  due 2026-11-30", "━".repeat(12))
+    );
+    assert!(chart.ends_with("1 project(s) without a due date or dated milestones not shown\n"));
+}
+
+#[test]
+fn activity_follows_the_project_its_ideas_and_its_tasks() {
+    use ideavault::commands::activity::{project_activity, ActivityKind};
+    use ideavault::models::idea::Note;
+    use ideavault::Idea;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let idea = Idea::new("Dark mode".to_string());
+    let mut project = Project::new("App".to_string());
+    project.add_idea(idea.id);
+    storage.add_idea(&idea).unwrap();
+    storage.add_project(&project).unwrap();
+    let task = Task::new("Ship it".to_string()).with_project(project.id);
+    let gone = Task::new("Spike".to_string()).with_project(project.id);
+    storage.add_task(&task).unwrap();
+    storage.add_task(&gone).unwrap();
+    storage
+        .add_task(&Task::new("Elsewhere".to_string()))
+        .unwrap();
+
+    storage
+        .update_task(task.id, |task| task.set_status(TaskStatus::Done))
+        .unwrap();
+    storage
+        .update_idea(idea.id, |idea| {
+            idea.add_note(Note::new("Follow the system setting".to_string()))
+        })
+        .unwrap();
+    storage.delete_task(gone.id).unwrap();
+
+    let events: Vec<(ActivityKind, String, Option<String>)> =
+        project_activity(&storage, project.id)
+            .unwrap()
+            .into_iter()
+            .map(|event| (event.kind, event.title, event.detail))
+            .collect();
+    assert_eq!(
+        events,
+        [
+            (ActivityKind::Created, "Dark mode".to_string(), None),
+            (ActivityKind::Created, "App".to_string(), None),
+            (ActivityKind::Created, "Ship it".to_string(), None),
+            (ActivityKind::Created, "Spike".to_string(), None),
+            (
+                ActivityKind::Status,
+                "Ship it".to_string(),
+                Some("Todo → Done".to_string())
+            ),
+            (
+                ActivityKind::Note,
+                "Dark mode".to_string(),
+                Some("Follow the system setting".to_string())
+            ),
+            (ActivityKind::Deleted, "Spike".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn activity_keeps_idea_status_changes_once_and_after_unlinking() {
+    use ideavault::commands::activity::{project_activity, ActivityKind};
+    use ideavault::models::idea::IdeaStatus;
+    use ideavault::Idea;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let idea = Idea::new("Dark mode".to_string());
+    let mut project = Project::new("App".to_string());
+    project.add_idea(idea.id);
+    storage.add_idea(&idea).unwrap();
+    storage.add_project(&project).unwrap();
+
+    storage
+        .update_idea(idea.id, |idea| idea.set_status(IdeaStatus::Active))
+        .unwrap();
+    let kinds = |storage: &Storage| -> Vec<(ActivityKind, String)> {
+        project_activity(storage, project.id)
+            .unwrap()
+            .into_iter()
+            .filter(|event| event.title == "Dark mode")
+            .map(|event| (event.kind, event.title))
+            .collect()
+    };
+    let expected = [
+        (ActivityKind::Created, "Dark mode".to_string()),
+        (ActivityKind::Status, "Dark mode".to_string()),
+    ];
+    assert_eq!(kinds(&storage), expected);
+
+    // Unlinking the idea keeps what happened to it while it was linked
+    storage
+        .update_project(project.id, |project| project.remove_idea(&idea.id))
+        .unwrap();
+    assert_eq!(kinds(&storage), expected);
+}
+
+#[test]
+fn activity_names_items_created_before_the_history_log() {
+    use ideavault::commands::activity::{project_activity, ActivityKind};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+    let project = Project::new("App".to_string());
+    let task = Task::new("Ship it".to_string()).with_project(project.id);
+    storage.add_project(&project).unwrap();
+    storage.add_task(&task).unwrap();
+    // As in a vault from before changes were logged
+    std::fs::remove_file(temp_dir.path().join("history.jsonl")).unwrap();
+
+    storage
+        .update_task(task.id, |task| task.set_status(TaskStatus::Done))
+        .unwrap();
+
+    let events = project_activity(&storage, project.id).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, ActivityKind::Status);
+    assert_eq!(events[0].title, "Ship it");
+}
+
+#[test]
+fn github_repos_are_read_from_urls_and_shorthand() {
+    use ideavault::commands::github::parse_repo;
+
+    let parsed = |repo: &str| parse_repo(repo).map(|(owner, name)| format!("{}/{}", owner, name));
+    for repo in [
+        "bigknoxy/ideavault",
+        "https://github.com/bigknoxy/ideavault",
+        "https://github.com/bigknoxy/ideavault.git",
+        "http://github.com/bigknoxy/ideavault/",
+        "git@github.com:bigknoxy/ideavault.git",
+        "github.com/bigknoxy/ideavault",
+    ] {
+        assert_eq!(
+            parsed(repo).as_deref(),
+            Some("bigknoxy/ideavault"),
+            "{}",
+            repo
+        );
+    }
+    assert_eq!(parsed("https://gitlab.com/bigknoxy/ideavault"), None);
+    assert_eq!(parsed("git@gitlab.com:bigknoxy/ideavault.git"), None);
+    assert_eq!(parsed("ideavault"), None);
+}
+
+#[test]
+fn github_issues_become_tasks_once() {
+    use ideavault::commands::github::{issue_tasks, Issue};
+
+    let issues: Vec<Issue> = serde_json::from_value(serde_json::json!([
+        {
+            "number": 7,
+            "title": "Crash on empty vault",
+            "body": "Steps to reproduce",
+            "html_url": "https://github.com/o/r/issues/7",
+            "labels": [{ "name": "bug" }]
+        },
+        {
+            "number": 8,
+            "title": "Add dark mode",
+            "body": null,
+            "html_url": "https://github.com/o/r/issues/8",
+            "labels": []
+        },
+        {
+            "number": 9,
+            "title": "Fix typo",
+            "html_url": "https://github.com/o/r/pull/9",
+            "pull_request": { "url": "https://api.github.com/repos/o/r/pulls/9" }
+        }
+    ]))
+    .unwrap();
+    let project_id = Uuid::new_v4();
+
+    let tasks = issue_tasks(project_id, &issues, &[]);
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].title, "Crash on empty vault");
+    assert_eq!(tasks[0].tags, ["bug"]);
+    assert_eq!(tasks[0].project_id, Some(project_id));
+    assert_eq!(
+        tasks[0].description.as_deref(),
+        Some("GitHub issue #7: https://github.com/o/r/issues/7\n\nSteps to reproduce")
+    );
+
+    // Importing again only brings in issues not imported yet
+    let again = issue_tasks(project_id, &issues, &tasks[..1]);
+    assert_eq!(again.len(), 1);
+    assert_eq!(again[0].title, "Add dark mode");
+}
+
+#[test]
+fn github_issue_imported_before_is_matched_exactly() {
+    use ideavault::commands::github::{issue_tasks, Issue};
+
+    let issues: Vec<Issue> = serde_json::from_value(serde_json::json!([
+        { "number": 7, "title": "Seven", "html_url": "https://github.com/o/r/issues/7" },
+        { "number": 70, "title": "Seventy", "html_url": "https://github.com/o/r/issues/70" }
+    ]))
+    .unwrap();
+    let project_id = Uuid::new_v4();
+
+    // Issue #70's URL starts with issue #7's, but #7 is still new
+    let seventy = issue_tasks(project_id, &issues[1..], &[]);
+    let again = issue_tasks(project_id, &issues, &seventy);
+    assert_eq!(again.len(), 1);
+    assert_eq!(again[0].title, "Seven");
+
+    let seven = issue_tasks(project_id, &issues[..1], &[]);
+    let again = issue_tasks(project_id, &issues, &seven);
+    assert_eq!(again.len(), 1);
+    assert_eq!(again[0].title, "Seventy");
+}
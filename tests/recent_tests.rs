@@ -0,0 +1,66 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ideavault::commands::recent::{recent, Change, RecentItem};
+use ideavault::storage::history::EntityKind;
+use ideavault::{Idea, Project, Storage, Task};
+
+fn titles(items: &[RecentItem]) -> Vec<&str> {
+    items.iter().map(|item| item.title.as_str()).collect()
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2030, 6, 15, 12, 0, 0).unwrap()
+}
+
+#[test]
+fn recent_merges_every_kind_newest_first() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let ago = |hours| now() - Duration::hours(hours);
+
+    let mut idea = Idea::new("Fresh idea".to_string());
+    idea.created_at = ago(1);
+    idea.updated_at = ago(1);
+    storage.add_idea(&idea)?;
+
+    let mut project = Project::new("Edited project".to_string());
+    project.created_at = ago(24 * 30);
+    project.updated_at = ago(3);
+    storage.add_project(&project)?;
+
+    let mut task = Task::new("Yesterday's task".to_string());
+    task.created_at = ago(24);
+    task.updated_at = ago(24);
+    storage.add_task(&task)?;
+
+    let mut old = Task::new("Old task".to_string());
+    old.created_at = ago(24 * 10);
+    old.updated_at = ago(24 * 10);
+    storage.add_task(&old)?;
+
+    let items = recent(&storage, 7, 20, now())?;
+    assert_eq!(
+        titles(&items),
+        ["Fresh idea", "Edited project", "Yesterday's task"]
+    );
+    assert_eq!(items[0].kind, EntityKind::Idea);
+    assert_eq!(items[0].change, Change::Created);
+    assert_eq!(items[1].kind, EntityKind::Project);
+    assert_eq!(items[1].change, Change::Updated);
+    assert_eq!(items[2].id, task.id);
+
+    let items = recent(&storage, 30, 2, now())?;
+    assert_eq!(titles(&items), ["Fresh idea", "Edited project"]);
+
+    Ok(())
+}
+
+#[test]
+fn recent_is_empty_for_an_empty_vault() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    assert!(recent(&storage, 7, 20, now())?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ideavault::commands::review::{agenda, review, Reviewed};
+use ideavault::models::idea::IdeaStatus;
+use ideavault::models::project::ProjectStatus;
+use ideavault::models::task::TaskStatus;
+use ideavault::{Idea, Project, Storage, Task};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2030, 6, 15, 12, 0, 0).unwrap()
+}
+
+/// A vault with one item of each kind that needs reviewing, and some that
+/// don't.
+fn seeded_storage(dir: &std::path::Path) -> Result<Storage> {
+    let storage = Storage::new_with_path(dir.to_path_buf())?;
+    let days_ago = |days| now() - Duration::days(days);
+
+    let mut stale = Idea::new("Forgotten".to_string()).with_status(IdeaStatus::Active);
+    stale.updated_at = days_ago(60);
+    let mut done = Idea::new("Finished".to_string()).with_status(IdeaStatus::Completed);
+    done.updated_at = days_ago(60);
+    let mut fresh = Idea::new("Fresh".to_string()).with_status(IdeaStatus::Active);
+    fresh.updated_at = days_ago(1);
+    storage.save_ideas(&[stale, done, fresh])?;
+
+    let idle = Project::new("Idle".to_string());
+    let busy = Project::new("Busy".to_string());
+    let paused = Project::new("Paused".to_string()).with_status(ProjectStatus::OnHold);
+    storage.save_projects(&[idle, busy.clone(), paused])?;
+
+    let overdue = Task::new("Late".to_string())
+        .with_due_date(days_ago(3))
+        .with_project(busy.id);
+    let blocked = Task::new("Stuck".to_string()).with_status(TaskStatus::Blocked);
+    let closed = Task::new("Done late".to_string())
+        .with_status(TaskStatus::Done)
+        .with_due_date(days_ago(3));
+    storage.save_tasks(&[overdue, blocked, closed])?;
+    Ok(storage)
+}
+
+#[test]
+fn agenda_lists_what_needs_a_decision() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = seeded_storage(temp_dir.path())?;
+
+    let agenda = agenda(&storage, 30, now())?;
+    assert_eq!(agenda.stale_ideas.len(), 1);
+    assert_eq!(agenda.stale_ideas[0].title, "Forgotten");
+    assert_eq!(agenda.overdue_tasks.len(), 1);
+    assert_eq!(agenda.overdue_tasks[0].title, "Late");
+    assert_eq!(agenda.blocked_tasks.len(), 1);
+    assert_eq!(agenda.blocked_tasks[0].title, "Stuck");
+    let idle: Vec<&str> = agenda
+        .idle_projects
+        .iter()
+        .map(|p| p.title.as_str())
+        .collect();
+    assert_eq!(idle, ["Idle"]);
+
+    Ok(())
+}
+
+#[test]
+fn review_acts_on_each_item() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = seeded_storage(temp_dir.path())?;
+
+    // Archive the idea, reschedule the overdue task after a bad date,
+    // unblock the blocked one, and give the idle project a next task
+    let answers = "a\nr\nsoon\n2030-07-01\nc\ntodo\nt\nCall the plumber\n";
+    let reviewed = review(&storage, 30, now(), &mut answers.as_bytes())?;
+    assert_eq!(
+        reviewed,
+        Reviewed {
+            archived: 1,
+            rescheduled: 1,
+            status_changed: 1,
+            tasks_added: 1,
+            skipped: 0,
+        }
+    );
+
+    let ideas = storage.load_ideas()?;
+    let forgotten = ideas.iter().find(|i| i.title == "Forgotten").unwrap();
+    assert_eq!(forgotten.status, IdeaStatus::Archived);
+
+    let tasks = storage.load_tasks()?;
+    let late = tasks.iter().find(|t| t.title == "Late").unwrap();
+    assert_eq!(
+        late.due_date,
+        Some(Utc.with_ymd_and_hms(2030, 7, 1, 0, 0, 0).unwrap())
+    );
+    let stuck = tasks.iter().find(|t| t.title == "Stuck").unwrap();
+    assert_eq!(stuck.status, TaskStatus::Todo);
+    let next = tasks
+        .iter()
+        .find(|t| t.title == "Call the plumber")
+        .unwrap();
+    let idle = storage
+        .load_projects()?
+        .into_iter()
+        .find(|p| p.title == "Idle");
+    assert_eq!(next.project_id, idle.map(|p| p.id));
+
+    Ok(())
+}
+
+#[test]
+fn review_stops_when_input_runs_out_or_on_quit() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = seeded_storage(temp_dir.path())?;
+
+    let reviewed = review(&storage, 30, now(), &mut "s\nq\n".as_bytes())?;
+    assert_eq!(reviewed.skipped, 1);
+    let reviewed = review(&storage, 30, now(), &mut "".as_bytes())?;
+    assert_eq!(reviewed, Reviewed::default());
+    assert_eq!(agenda(&storage, 30, now())?.len(), 4);
+
+    Ok(())
+}
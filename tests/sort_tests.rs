@@ -0,0 +1,107 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use ideavault::commands::sort::{sort, SortArgs, SortKey};
+use ideavault::models::task::TaskPriority;
+use ideavault::{Idea, Project, Task};
+
+fn titles(tasks: &[Task]) -> Vec<&str> {
+    tasks.iter().map(|task| task.title.as_str()).collect()
+}
+
+fn by(key: SortKey, reverse: bool) -> SortArgs {
+    SortArgs {
+        sort: Some(key),
+        reverse,
+    }
+}
+
+#[test]
+fn tasks_sort_by_each_key() -> Result<()> {
+    let now = Utc::now();
+    let mut tasks = vec![
+        Task::new("beta".to_string())
+            .with_priority(TaskPriority::Low)
+            .with_due_date(now + Duration::days(2)),
+        Task::new("Alpha".to_string()).with_priority(TaskPriority::Urgent),
+        Task::new("gamma".to_string())
+            .with_priority(TaskPriority::High)
+            .with_due_date(now + Duration::days(1)),
+    ];
+
+    sort(&mut tasks, &by(SortKey::Title, false))?;
+    assert_eq!(titles(&tasks), ["Alpha", "beta", "gamma"]);
+
+    sort(&mut tasks, &by(SortKey::Priority, false))?;
+    assert_eq!(titles(&tasks), ["Alpha", "gamma", "beta"]);
+
+    sort(&mut tasks, &by(SortKey::Priority, true))?;
+    assert_eq!(titles(&tasks), ["beta", "gamma", "Alpha"]);
+
+    // Tasks without a due date stay last, whichever way round
+    sort(&mut tasks, &by(SortKey::Due, false))?;
+    assert_eq!(titles(&tasks), ["gamma", "beta", "Alpha"]);
+    sort(&mut tasks, &by(SortKey::Due, true))?;
+    assert_eq!(titles(&tasks), ["beta", "gamma", "Alpha"]);
+
+    // Without --sort, --reverse flips the stored order
+    sort(
+        &mut tasks,
+        &SortArgs {
+            sort: None,
+            reverse: true,
+        },
+    )?;
+    assert_eq!(titles(&tasks), ["Alpha", "gamma", "beta"]);
+
+    Ok(())
+}
+
+#[test]
+fn ideas_cannot_sort_by_task_fields() {
+    let mut ideas = vec![Idea::new("One".to_string())];
+    let err = sort(&mut ideas, &by(SortKey::Due, false)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Can't sort ideas by due. Sort by one of: created, updated, title, priority"
+    );
+}
+
+#[test]
+fn ideas_sort_by_priority() -> Result<()> {
+    let mut ideas = vec![
+        Idea::new("Low".to_string()).with_priority(TaskPriority::Low),
+        Idea::new("Urgent".to_string()).with_priority(TaskPriority::Urgent),
+        Idea::new("Medium".to_string()),
+    ];
+    sort(&mut ideas, &by(SortKey::Priority, false))?;
+    let titles: Vec<&str> = ideas.iter().map(|idea| idea.title.as_str()).collect();
+    assert_eq!(titles, ["Urgent", "Medium", "Low"]);
+
+    Ok(())
+}
+
+#[test]
+fn projects_sort_by_priority_and_due_date() -> Result<()> {
+    let now = Utc::now();
+    let mut projects = vec![
+        Project::new("Low".to_string())
+            .with_priority(TaskPriority::Low)
+            .with_due_date(now + Duration::days(1)),
+        Project::new("Urgent".to_string()).with_priority(TaskPriority::Urgent),
+        Project::new("Medium".to_string()).with_due_date(now + Duration::days(2)),
+    ];
+    let titles = |projects: &[Project]| -> Vec<String> {
+        projects
+            .iter()
+            .map(|project| project.title.clone())
+            .collect()
+    };
+
+    sort(&mut projects, &by(SortKey::Priority, false))?;
+    assert_eq!(titles(&projects), ["Urgent", "Medium", "Low"]);
+
+    sort(&mut projects, &by(SortKey::Due, false))?;
+    assert_eq!(titles(&projects), ["Low", "Medium", "Urgent"]);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use ideavault::models::config::Config;
+use ideavault::models::task::{self, StatusCategory, StatusDefinition, Task, TaskStatus};
+use ideavault::style::Color;
+
+// Statuses are set once per process, so everything that depends on the
+// configured set lives in this one test.
+#[test]
+fn configured_statuses_drive_parsing_and_display() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("config.json");
+    std::fs::write(
+        &path,
+        r#"{ "task_statuses": [
+            { "name": "Todo" },
+            { "name": "InProgress", "emoji": "🔄" },
+            { "name": "Review", "emoji": "👀", "color": "magenta" },
+            { "name": "Done", "category": "closed" },
+            { "name": "Won't Do", "category": "closed" }
+        ] }"#,
+    )?;
+    let config = Config::load_from(&path)?;
+    assert_eq!(
+        config.task_statuses[2],
+        StatusDefinition::new("Review", StatusCategory::Open)
+            .with_emoji("👀")
+            .with_color(Color::Magenta)
+    );
+    task::set_statuses(config.task_statuses);
+
+    assert_eq!(
+        "review".parse::<TaskStatus>()?,
+        TaskStatus::Custom("Review".to_string())
+    );
+    assert_eq!(
+        "won't do".parse::<TaskStatus>()?,
+        TaskStatus::Custom("Won't Do".to_string())
+    );
+    assert_eq!("in-progress".parse::<TaskStatus>()?, TaskStatus::InProgress);
+    assert_eq!("x".parse::<TaskStatus>()?, TaskStatus::Done);
+
+    // Built-ins left out of the setting can't be chosen
+    let err = "blocked".parse::<TaskStatus>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid status. Must be one of: todo, inprogress, review, done, won't do"
+    );
+
+    let review = TaskStatus::Custom("Review".to_string());
+    assert_eq!(review.emoji(), "👀");
+    assert!(!review.is_closed());
+    assert!(TaskStatus::Custom("Won't Do".to_string()).is_closed());
+    assert!(TaskStatus::Done.is_closed());
+    // ...but tasks that already have them keep their look
+    assert!(TaskStatus::Cancelled.is_closed());
+    assert_eq!(TaskStatus::Blocked.emoji(), "🚫");
+    assert!(!TaskStatus::Custom("Gone".to_string()).is_closed());
+
+    // Stored by name, like the built-ins
+    let task = Task::new("Check it".to_string()).with_status(review.clone());
+    let json = serde_json::to_value(&task)?;
+    assert_eq!(json["status"], "Review");
+    let loaded: Task = serde_json::from_value(json)?;
+    assert_eq!(loaded.status, review);
+    assert_eq!(
+        serde_json::to_value(TaskStatus::InProgress)?,
+        serde_json::json!("InProgress")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn status_settings_are_checked() -> Result<()> {
+    let mut config = Config::new();
+    assert!(config.set("task_statuses", "[]").is_err());
+    assert!(config
+        .set(
+            "task_statuses",
+            r#"[{ "name": "Todo" }, { "name": "todo" }]"#
+        )
+        .is_err());
+    assert!(config
+        .set("task_statuses", r#"[{ "name": "Todo", "color": "plaid" }]"#)
+        .is_err());
+
+    config.set(
+        "task_statuses",
+        r#"[{ "name": "Todo" }, { "name": "Done", "category": "closed" }]"#,
+    )?;
+    assert_eq!(config.task_statuses.len(), 2);
+    assert_eq!(config.task_statuses[1].category, StatusCategory::Closed);
+    Ok(())
+}
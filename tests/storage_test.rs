@@ -1,5 +1,5 @@
 use anyhow::Result;
-use ideavault::{Project, Storage, Tag};
+use ideavault::{Idea, Project, Storage, Tag, Task};
 
 #[test]
 fn test_projects_and_tags_storage() -> Result<()> {
@@ -35,3 +35,434 @@ fn test_projects_and_tags_storage() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_storage_roundtrip() -> Result<()> {
+    use ideavault::models::task::TaskStatus;
+
+    let temp_dir = tempfile::tempdir()?;
+    let db_path = temp_dir.path().join("vault.db");
+    let storage = Storage::new_sqlite(db_path.clone())?;
+
+    let idea = Idea::new("SQLite idea".to_string()).with_tags(vec!["db".to_string()]);
+    let project = Project::new("SQLite project".to_string()).with_ideas(vec![idea.id]);
+    let task = Task::new("SQLite task".to_string())
+        .with_project(project.id)
+        .with_status(TaskStatus::InProgress);
+
+    storage.save_ideas(std::slice::from_ref(&idea))?;
+    storage.save_projects(std::slice::from_ref(&project))?;
+    storage.save_tasks(std::slice::from_ref(&task))?;
+    storage.save_tags(&[Tag::new("db".to_string())])?;
+
+    // Reopen to make sure data survives a new connection
+    drop(storage);
+    let storage = Storage::new_sqlite(db_path)?;
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].id, idea.id);
+    assert_eq!(ideas[0].tags, vec!["db"]);
+
+    let projects = storage.load_projects()?;
+    assert_eq!(projects[0].idea_ids, vec![idea.id]);
+
+    let tasks = storage.load_tasks()?;
+    assert_eq!(tasks[0].project_id, Some(project.id));
+    assert_eq!(tasks[0].status, TaskStatus::InProgress);
+
+    assert_eq!(storage.load_tags()?[0].name, "db");
+
+    // Saving a smaller set replaces the previous contents
+    storage.save_ideas(&[])?;
+    assert!(storage.load_ideas()?.is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_single_entity_writes() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_sqlite(temp_dir.path().join("vault.db"))?;
+
+    let first = Task::new("First".to_string());
+    let mut second = Task::new("Second".to_string());
+    let third = Task::new("Third".to_string());
+    for task in [&first, &second, &third] {
+        storage.save_task(task)?;
+    }
+
+    // Updating a task replaces it where it was
+    second.title = "Second, renamed".to_string();
+    storage.save_task(&second)?;
+    let titles: Vec<String> = storage.load_tasks()?.into_iter().map(|t| t.title).collect();
+    assert_eq!(titles, ["First", "Second, renamed", "Third"]);
+    assert_eq!(
+        storage.load_task(second.id)?.map(|t| t.title).as_deref(),
+        Some("Second, renamed")
+    );
+
+    assert!(storage.delete_task(first.id)?);
+    assert!(!storage.delete_task(first.id)?);
+    assert!(storage.load_task(first.id)?.is_none());
+    assert_eq!(storage.load_tasks()?.len(), 2);
+
+    let idea = Idea::new("Idea".to_string());
+    storage.save_idea(&idea)?;
+    assert_eq!(storage.load_idea(idea.id)?.map(|i| i.id), Some(idea.id));
+    assert!(storage.delete_idea(idea.id)?);
+    assert!(storage.load_ideas()?.is_empty());
+
+    let project = Project::new("Project".to_string());
+    storage.save_project(&project)?;
+    assert_eq!(
+        storage.load_project(project.id)?.map(|p| p.id),
+        Some(project.id)
+    );
+    assert!(storage.delete_project(project.id)?);
+    assert!(storage.load_projects()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_save_leaves_no_temp_files() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    storage.save_ideas(&[Idea::new("Atomic".to_string())])?;
+    storage.save_projects(&[Project::new("Atomic".to_string())])?;
+    storage.save_tags(&[Tag::new("atomic".to_string())])?;
+    storage.save_tasks(&[Task::new("Atomic".to_string())])?;
+
+    let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path())?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_interrupted_write_keeps_previous_data() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let idea = Idea::new("Survivor".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea))?;
+
+    // Simulate a crash halfway through the next save: the temp file holds
+    // truncated JSON and the rename never happened.
+    std::fs::write(
+        temp_dir.path().join(".ideas.json.tmp"),
+        "[{\"id\": \"00000000-",
+    )?;
+
+    let ideas = storage.load_ideas()?;
+    assert_eq!(ideas.len(), 1);
+    assert_eq!(ideas[0].id, idea.id);
+
+    // The next successful save replaces the stale temp file
+    let second = Idea::new("Second".to_string());
+    storage.save_ideas(&[idea, second])?;
+    assert_eq!(storage.load_ideas()?.len(), 2);
+    assert!(!temp_dir.path().join(".ideas.json.tmp").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_blocks_second_storage_until_released() -> Result<()> {
+    use std::time::Duration;
+
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().to_path_buf();
+
+    let first = Storage::new_with_lock_timeout(path.clone(), Duration::from_millis(100))?;
+
+    let err = match Storage::new_with_lock_timeout(path.clone(), Duration::from_millis(100)) {
+        Ok(_) => panic!("second storage acquired a held lock"),
+        Err(err) => err,
+    };
+    assert!(err
+        .to_string()
+        .contains("locked by another ideavault process"));
+
+    drop(first);
+    let second = Storage::new_with_lock_timeout(path, Duration::from_millis(100))?;
+    assert!(second.load_ideas()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_commands_run_against_custom_backend() -> Result<()> {
+    use ideavault::commands::idea::{IdeaCommands, IdeaUpdateArgs};
+    use ideavault::storage::MemoryBackend;
+
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::with_backend(
+        temp_dir.path().to_path_buf(),
+        Box::new(MemoryBackend::new()),
+    )?;
+
+    let idea = Idea::new("In memory".to_string());
+    let id = idea.id;
+    storage.save_ideas(&[idea])?;
+
+    let args = IdeaUpdateArgs {
+        id: id.into(),
+        title: Some("Still in memory".to_string()),
+        description: None,
+        source: None,
+        status: None,
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
+        clear: vec![],
+    };
+    IdeaCommands::update_idea(&storage, &args)?;
+
+    assert_eq!(storage.load_ideas()?[0].title, "Still in memory");
+    // Nothing was written to the data directory by the backend
+    assert!(!temp_dir.path().join("ideas.json").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_compressed_storage_reads_existing_plain_files() -> Result<()> {
+    use ideavault::models::Compression;
+
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().to_path_buf();
+
+    let plain = Idea::new("Plain".to_string());
+    let storage = Storage::new_with_path(path.clone())?;
+    storage.save_ideas(std::slice::from_ref(&plain))?;
+    drop(storage);
+
+    for compression in [Compression::Gzip, Compression::Zstd] {
+        let storage = Storage::new_compressed(path.clone(), compression)?;
+        let mut ideas = storage.load_ideas()?;
+        assert_eq!(ideas[0].id, plain.id);
+
+        ideas.push(Idea::new(format!("{:?}", compression)));
+        storage.save_ideas(&ideas)?;
+        let raw = std::fs::read(path.join("ideas.json"))?;
+        assert_eq!(ideavault::storage::compression::detect(&raw), compression);
+        drop(storage);
+    }
+
+    // Going back to plain JSON still reads the compressed file
+    let storage = Storage::new_with_path(path.clone())?;
+    assert_eq!(storage.load_ideas()?.len(), 3);
+    storage.save_ideas(&storage.load_ideas()?)?;
+    assert!(std::fs::read_to_string(path.join("ideas.json"))?.contains("Zstd"));
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_to_indexed_and_back() -> Result<()> {
+    use ideavault::storage::Layout;
+
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().to_path_buf();
+    let mut storage = Storage::new_with_path(path.clone())?;
+
+    let ideas = vec![
+        Idea::new("First".to_string()),
+        Idea::new("Second".to_string()),
+    ];
+    let task = Task::new("Task".to_string());
+    storage.save_ideas(&ideas)?;
+    storage.save_tasks(std::slice::from_ref(&task))?;
+    storage.save_tags(&[Tag::new("tag".to_string())])?;
+
+    assert!(storage.migrate(Layout::Indexed)?);
+    assert!(!path.join("ideas.json").exists());
+    assert!(path
+        .join("tasks")
+        .join(format!("{}.json", task.id))
+        .exists());
+    assert_eq!(storage.load_ideas()?.len(), 2);
+    assert!(!storage.migrate(Layout::Indexed)?);
+
+    // The layout is remembered when the vault is reopened
+    drop(storage);
+    let mut storage = Storage::new_with_path(path.clone())?;
+    assert_eq!(storage.layout(), Some(Layout::Indexed));
+    let loaded: Vec<String> = storage.load_ideas()?.into_iter().map(|i| i.title).collect();
+    assert_eq!(loaded, vec!["First", "Second"]);
+
+    assert!(storage.migrate(Layout::Json)?);
+    assert!(!path.join("tasks").exists());
+    assert_eq!(storage.load_tasks()?[0].id, task.id);
+    assert_eq!(storage.load_tags()?.len(), 1);
+    drop(storage);
+    assert_eq!(Layout::detect(&path), Layout::Json);
+
+    Ok(())
+}
+
+#[test]
+fn test_indexed_single_entity_update_touches_one_file() -> Result<()> {
+    use ideavault::storage::IndexedBackend;
+    use std::time::Duration;
+
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().to_path_buf();
+    let storage = Storage::with_backend(path.clone(), Box::new(IndexedBackend::new(&path)))?;
+
+    let mut first = Task::new("First".to_string());
+    let second = Task::new("Second".to_string());
+    storage.save_tasks(&[first.clone(), second.clone()])?;
+
+    let second_file = path.join("tasks").join(format!("{}.json", second.id));
+    let untouched = std::fs::metadata(&second_file)?.modified()?;
+    std::thread::sleep(Duration::from_millis(20));
+
+    first.title = "First, renamed".to_string();
+    storage.save_task(&first)?;
+    assert_eq!(std::fs::metadata(&second_file)?.modified()?, untouched);
+    assert_eq!(
+        storage.load_task(first.id)?.map(|t| t.title),
+        Some("First, renamed".to_string())
+    );
+
+    assert!(storage.delete_task(second.id)?);
+    assert!(!second_file.exists());
+    assert!(!storage.delete_task(second.id)?);
+    assert_eq!(storage.load_tasks()?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_session_caches_and_flushes_only_dirty_collections() -> Result<()> {
+    use ideavault::Session;
+
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    storage.save_ideas(&[Idea::new("Cached".to_string())])?;
+
+    let mut session = Session::new(&storage);
+    assert_eq!(session.ideas()?.len(), 1);
+
+    // Collections are loaded once; later writes behind the session's back aren't seen
+    storage.save_ideas(&[])?;
+    assert_eq!(session.ideas()?.len(), 1);
+    assert!(session.projects()?.is_empty());
+    assert!(!session.is_dirty());
+
+    session.tasks_mut()?.push(Task::new("Batched".to_string()));
+    session
+        .tasks_mut()?
+        .push(Task::new("Also batched".to_string()));
+    assert!(session.is_dirty());
+    assert!(storage.load_tasks()?.is_empty());
+
+    session.flush()?;
+    assert!(!session.is_dirty());
+    assert_eq!(storage.load_tasks()?.len(), 2);
+    // Only collections that were borrowed mutably are written
+    assert!(storage.load_ideas()?.is_empty());
+    assert!(!temp_dir.path().join("projects.json").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_event_log_appends_changes_and_compacts() -> Result<()> {
+    use ideavault::storage::{EventLogBackend, Layout};
+
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().to_path_buf();
+    let mut storage = Storage::new_with_path(path.clone())?;
+    let idea = Idea::new("Before".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea))?;
+
+    // Migrating starts the log from a snapshot
+    assert!(storage.migrate(Layout::Events)?);
+    assert!(path.join("snapshot.json").exists());
+    assert!(!path.join("events.jsonl").exists());
+    assert!(!path.join("ideas.json").exists());
+
+    let mut renamed = idea.clone();
+    renamed.title = "After".to_string();
+    storage.save_idea(&renamed)?;
+    let task = Task::new("Task".to_string());
+    storage.save_task(&task)?;
+    assert!(storage.delete_task(task.id)?);
+    assert!(!storage.delete_task(task.id)?);
+    let log = std::fs::read_to_string(path.join("events.jsonl"))?;
+    assert_eq!(log.lines().count(), 3);
+
+    // A line half-written by a crash is ignored and then replaced
+    std::fs::write(path.join("events.jsonl"), format!("{}{{\"at\":", log))?;
+    drop(storage);
+    let storage = Storage::new_with_path(path.clone())?;
+    assert_eq!(storage.layout(), Some(Layout::Events));
+    assert_eq!(storage.load_ideas()?[0].title, "After");
+    assert!(storage.load_tasks()?.is_empty());
+    storage.save_tags(&[Tag::new("tag".to_string())])?;
+    let log = std::fs::read_to_string(path.join("events.jsonl"))?;
+    assert_eq!(log.lines().count(), 4);
+
+    assert_eq!(storage.compact()?, Some(4));
+    assert!(!path.join("events.jsonl").exists());
+    assert_eq!(storage.compact()?, Some(0));
+    assert_eq!(storage.load_ideas()?[0].title, "After");
+    assert_eq!(storage.load_tags()?.len(), 1);
+    drop(storage);
+
+    // The log is compacted automatically once it grows long enough
+    let backend = EventLogBackend::new(&path).with_compact_after(2);
+    let storage = Storage::with_backend(path.clone(), Box::new(backend))?;
+    storage.save_task(&Task::new("One".to_string()))?;
+    assert!(path.join("events.jsonl").exists());
+    storage.save_task(&Task::new("Two".to_string()))?;
+    assert!(!path.join("events.jsonl").exists());
+    assert_eq!(storage.load_tasks()?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_per_entity_crud() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let task = Task::new("Write docs".to_string());
+    storage.add_task(&task)?;
+    assert!(storage.add_task(&task).is_err());
+    assert_eq!(storage.get_task(task.id)?.title, "Write docs");
+
+    let old_title = storage.update_task(task.id, |task| {
+        std::mem::replace(&mut task.title, "Write more docs".to_string())
+    })?;
+    assert_eq!(old_title, "Write docs");
+    let updated = storage.get_task(task.id)?;
+    assert_eq!(updated.title, "Write more docs");
+    assert!(updated.updated_at > task.updated_at);
+
+    // Nothing is saved, or touched, when the closure changes nothing
+    let history = storage.load_history()?.len();
+    storage.update_task(task.id, |_| ())?;
+    assert_eq!(storage.load_history()?.len(), history);
+    assert_eq!(storage.get_task(task.id)?.updated_at, updated.updated_at);
+
+    let trashed = storage.trash_task(task.id)?;
+    assert_eq!(trashed.title, "Write more docs");
+    assert!(storage.get_task(task.id).is_err());
+    assert!(storage.update_task(task.id, |_| ()).is_err());
+    assert_eq!(storage.load_trash()?.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use ideavault::models::task::TaskStatus;
+use ideavault::style::{self, Color};
+
+#[test]
+fn colors_wrap_text_once_enabled() {
+    style::set_enabled(true);
+    assert!(style::enabled());
+
+    assert_eq!(style::paint("late", Color::Red), "\x1b[31mlate\x1b[0m");
+    assert_eq!(
+        style::paint("blocked", style::task_status_color(&TaskStatus::Blocked)),
+        "\x1b[31mblocked\x1b[0m"
+    );
+
+    // A tag keeps its color everywhere it appears
+    assert_eq!(style::tag_color("rust"), style::tag_color("rust"));
+    let tags = style::tags(&["rust".to_string(), "cli".to_string()]);
+    assert!(tags.contains(&style::paint("rust", style::tag_color("rust"))));
+    assert!(tags.contains(&style::paint("cli", style::tag_color("cli"))));
+}
@@ -0,0 +1,20 @@
+use ideavault::models::idea::IdeaStatus;
+use ideavault::models::task::{TaskPriority, TaskStatus};
+use ideavault::symbols;
+
+#[test]
+fn plain_markers_are_ascii_labels() {
+    symbols::init(false);
+    assert!(symbols::is_plain());
+
+    assert_eq!(format!("{} Saved", symbols::OK), "[ok] Saved");
+    assert_eq!(symbols::WARNING.as_str(), "[!]");
+    assert_eq!(
+        symbols::idea_status(&IdeaStatus::Brainstorming).as_str(),
+        "[brainstorming]"
+    );
+    assert_eq!(TaskStatus::Blocked.marker(), "[blocked]");
+    assert_eq!(TaskPriority::High.marker(), "[high]");
+    // The emoji a status or priority was given is kept for when emoji are on
+    assert_eq!(TaskStatus::Blocked.emoji(), "🚫");
+}
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use ideavault::commands::sync::{sync, SyncArgs};
+use ideavault::models::idea::IdeaStatus;
+use ideavault::sync::{sync_files, Conflict, DirectoryProvider, ProviderKind, Side, SyncConfig};
+use ideavault::{Idea, Storage, Task};
+
+/// A resolver that always keeps `side`, recording what it was asked.
+fn keep(side: Side, asked: &mut Vec<String>) -> impl FnMut(&Conflict) -> anyhow::Result<Side> + '_ {
+    move |conflict| {
+        asked.push(conflict.describe());
+        Ok(side)
+    }
+}
+
+#[test]
+fn directory_sync_copies_and_merges_changes() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let remote = DirectoryProvider::new(temp_dir.path().join("remote"));
+    let mut asked = Vec::new();
+    let mut resolve = keep(Side::Local, &mut asked);
+    let laptop = Storage::new_with_path(temp_dir.path().join("laptop"))?;
+    let desktop = Storage::new_with_path(temp_dir.path().join("desktop"))?;
+
+    let idea = Idea::new("From the laptop".to_string());
+    laptop.save_idea(&idea)?;
+    let summary = sync_files(laptop.data_dir(), &remote, &mut resolve)?;
+    assert_eq!(summary.pushed, 2);
+    assert!(temp_dir.path().join("remote/ideas.json").exists());
+
+    // Both sides wrote to the history log; its lines are combined
+    desktop.save_task(&Task::new("From the desktop".to_string()))?;
+    let summary = sync_files(desktop.data_dir(), &remote, &mut resolve)?;
+    assert_eq!(summary.conflicts, vec!["history.jsonl"]);
+    assert_eq!(desktop.load_ideas()?[0].id, idea.id);
+    assert_eq!(desktop.load_history()?.len(), 2);
+
+    sync_files(laptop.data_dir(), &remote, &mut resolve)?;
+    assert_eq!(laptop.load_tasks()?.len(), 1);
+    assert_eq!(laptop.load_history()?.len(), 2);
+    assert_eq!(
+        sync_files(laptop.data_dir(), &remote, &mut resolve)?.pulled,
+        0
+    );
+
+    // Deleting a file on one side deletes it on the other
+    laptop.save_tasks(&[])?;
+    std::fs::remove_file(laptop.data_dir().join("tasks.json"))?;
+    sync_files(laptop.data_dir(), &remote, &mut resolve)?;
+    sync_files(desktop.data_dir(), &remote, &mut resolve)?;
+    assert!(!desktop.data_dir().join("tasks.json").exists());
+
+    // Additions to the same file on both sides are combined
+    laptop.save_idea(&Idea::new("Laptop edit".to_string()))?;
+    desktop.save_idea(&Idea::new("Desktop edit".to_string()))?;
+    sync_files(laptop.data_dir(), &remote, &mut resolve)?;
+    let summary = sync_files(desktop.data_dir(), &remote, &mut resolve)?;
+    assert_eq!(summary.conflicts, vec!["history.jsonl", "ideas.json"]);
+    let titles: Vec<String> = desktop.load_ideas()?.into_iter().map(|i| i.title).collect();
+    assert_eq!(
+        titles,
+        vec!["From the laptop", "Desktop edit", "Laptop edit"]
+    );
+    drop(resolve);
+    assert!(asked.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn conflicting_fields_are_settled_by_the_resolver() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let remote = DirectoryProvider::new(temp_dir.path().join("remote"));
+    let laptop = Storage::new_with_path(temp_dir.path().join("laptop"))?;
+    let desktop = Storage::new_with_path(temp_dir.path().join("desktop"))?;
+    let mut asked = Vec::new();
+    let mut resolve = keep(Side::Remote, &mut asked);
+
+    let idea = Idea::new("Original".to_string());
+    laptop.save_idea(&idea)?;
+    sync_files(laptop.data_dir(), &remote, &mut resolve)?;
+    sync_files(desktop.data_dir(), &remote, &mut resolve)?;
+
+    // Different fields merge; the same field edited on both sides is a conflict
+    let mut on_laptop = idea.clone();
+    on_laptop.title = "Laptop title".to_string();
+    on_laptop.set_status(IdeaStatus::Active);
+    laptop.save_idea(&on_laptop)?;
+    let mut on_desktop = idea.clone();
+    on_desktop.title = "Desktop title".to_string();
+    on_desktop.description = Some("Added on the desktop".to_string());
+    desktop.save_idea(&on_desktop)?;
+
+    sync_files(desktop.data_dir(), &remote, &mut resolve)?;
+    sync_files(laptop.data_dir(), &remote, &mut resolve)?;
+    let merged = laptop.load_idea(idea.id)?.unwrap();
+    assert_eq!(merged.title, "Desktop title");
+    assert_eq!(merged.status, IdeaStatus::Active);
+    assert_eq!(merged.description.as_deref(), Some("Added on the desktop"));
+
+    // The merged version went back to the remote as well
+    sync_files(desktop.data_dir(), &remote, &mut resolve)?;
+    assert_eq!(
+        desktop.load_idea(idea.id)?.unwrap().status,
+        IdeaStatus::Active
+    );
+    drop(resolve);
+    let id = &idea.id.to_string()[..8];
+    assert_eq!(
+        asked,
+        vec![format!("idea {} \"Laptop title\", field title", id)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sync_remembers_the_provider() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().join("vault"))?;
+    storage.save_idea(&Idea::new("Synced".to_string()))?;
+
+    let remote = temp_dir.path().join("share");
+    let args = SyncArgs {
+        remote: Some(remote.to_string_lossy().into_owned()),
+        provider: Some(ProviderKind::Directory),
+        username: None,
+        prefer_local: false,
+        prefer_remote: false,
+    };
+    sync(&storage, &args)?;
+    assert!(remote.join("ideas.json").exists());
+
+    let config = SyncConfig::load(storage.data_dir())?.unwrap();
+    assert_eq!(config.provider, ProviderKind::Directory);
+
+    // Later syncs reuse the saved provider and remote
+    storage.save_idea(&Idea::new("Also synced".to_string()))?;
+    let args = SyncArgs {
+        remote: None,
+        provider: None,
+        username: None,
+        prefer_local: false,
+        prefer_remote: false,
+    };
+    assert!(sync(&storage, &args)?.pushed > 0);
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use ideavault::commands::table::{truncate, Cell, Column, Table, TableArgs};
+
+struct Row {
+    name: &'static str,
+    size: u32,
+}
+
+fn columns<'a>() -> Vec<Column<'a, Row>> {
+    vec![
+        Column::new("name", 10, |row: &Row| Cell::new(row.name)),
+        Column::new("size", 4, |row: &Row| Cell::new(row.size.to_string())),
+    ]
+}
+
+#[test]
+fn tables_line_up_and_truncate() -> Result<()> {
+    let rows = [
+        Row {
+            name: "short",
+            size: 1,
+        },
+        Row {
+            name: "a name far too long",
+            size: 12345,
+        },
+    ];
+
+    let table = Table::new(columns(), &["name", "size"], &TableArgs::default())?;
+    assert_eq!(
+        table.render(&rows),
+        "NAME        SIZE\nshort       1\na name fa…  123…\n"
+    );
+
+    let args = TableArgs {
+        columns: vec!["size".to_string(), "NAME".to_string()],
+        no_headers: true,
+        ..Default::default()
+    };
+    let table = Table::new(columns(), &["name"], &args)?;
+    assert_eq!(table.render(&rows[..1]), "1  short\n");
+
+    let args = TableArgs {
+        columns: vec!["colour".to_string()],
+        ..Default::default()
+    };
+    let err = Table::new(columns(), &["name"], &args).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Unknown column 'colour'. Available columns: name, size"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn truncation_counts_display_width() {
+    assert_eq!(truncate("multi\nline", 20), "multi line");
+    assert_eq!(truncate("日本語のタイトル", 7), "日本語…");
+    assert_eq!(truncate("exact", 5), "exact");
+}
@@ -12,12 +12,13 @@ fn task_update_title() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -39,12 +40,13 @@ fn task_update_description() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: Some("New description".to_string()),
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -66,12 +68,13 @@ fn task_update_priority() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: Some(TaskPriority::High),
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -93,12 +96,13 @@ fn task_update_due_date() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: Some("2024-12-31".to_string()),
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -124,12 +128,13 @@ fn task_update_status() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: None,
         status: Some(TaskStatus::InProgress),
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -152,12 +157,13 @@ fn task_update_tags() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: Some(vec!["new1".to_string(), "new2".to_string()]),
         clear: vec![],
     };
@@ -179,12 +185,13 @@ fn task_update_multiple_fields() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: Some("New Title".to_string()),
         description: Some("New description".to_string()),
         priority: Some(TaskPriority::Urgent),
         due_date: None,
         status: Some(TaskStatus::Done),
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -210,12 +217,13 @@ fn task_update_clear_due_date() {
 
     // First set a due date
     let args_set_due = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: Some("2024-12-31".to_string()),
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -223,12 +231,13 @@ fn task_update_clear_due_date() {
 
     // Now clear it
     let args_clear_due = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec!["due_date".to_string()],
     };
@@ -249,12 +258,13 @@ fn task_update_clear_description() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec!["description".to_string()],
     };
@@ -277,12 +287,13 @@ fn task_update_clear_tags() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec!["tags".to_string()],
     };
@@ -304,12 +315,13 @@ fn task_update_clear_due_date_via_value() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: Some("clear".to_string()),
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -331,12 +343,13 @@ fn task_update_no_changes() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -351,12 +364,13 @@ fn task_update_invalid_id() {
     let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
 
     let args = TaskUpdateArgs {
-        id: uuid::Uuid::new_v4(),
+        id: uuid::Uuid::new_v4().into(),
         title: Some("New Title".to_string()),
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -375,12 +389,13 @@ fn task_update_invalid_clear_field() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: None,
         status: None,
+        assignee: None,
         tags: None,
         clear: vec!["invalid_field".to_string()],
     };
@@ -399,12 +414,13 @@ fn task_update_invalid_date_format() {
     storage.save_tasks(&[task]).unwrap();
 
     let args = TaskUpdateArgs {
-        id,
+        id: id.into(),
         title: None,
         description: None,
         priority: None,
         due_date: Some("invalid-date".to_string()),
         status: None,
+        assignee: None,
         tags: None,
         clear: vec![],
     };
@@ -412,3 +428,74 @@ fn task_update_invalid_date_format() {
     let result = TaskCommands::update_task(&storage, &args);
     assert!(result.is_err());
 }
+
+#[test]
+fn built_in_priorities_order_by_urgency() {
+    let mut priorities = vec![
+        TaskPriority::Medium,
+        TaskPriority::Urgent,
+        TaskPriority::Low,
+        TaskPriority::High,
+    ];
+    priorities.sort();
+    assert_eq!(
+        priorities,
+        vec![
+            TaskPriority::Low,
+            TaskPriority::Medium,
+            TaskPriority::High,
+            TaskPriority::Urgent
+        ]
+    );
+    assert_eq!(
+        Task::new("Plain".to_string()).priority,
+        TaskPriority::Medium
+    );
+}
+
+#[test]
+fn finishing_a_task_stamps_completed_at_until_reopened() {
+    let mut task = Task::new("Ship it".to_string());
+    assert!(task.completed_at.is_none());
+
+    task.set_status(TaskStatus::Done);
+    let completed_at = task.completed_at.expect("stamped when done");
+    task.set_status(TaskStatus::Done);
+    assert_eq!(task.completed_at, Some(completed_at));
+
+    task.set_status(TaskStatus::Todo);
+    assert!(task.completed_at.is_none());
+    task.set_status(TaskStatus::Cancelled);
+    assert!(task.completed_at.is_none());
+}
+
+#[test]
+fn task_done_marks_several_tasks_done() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf()).unwrap();
+
+    let first = Task::new("First".to_string());
+    let second = Task::new("Second".to_string()).with_status(TaskStatus::Blocked);
+    let finished = Task::new("Finished".to_string()).with_status(TaskStatus::Done);
+    let stamped = finished.completed_at;
+    let untouched = Task::new("Untouched".to_string());
+    let ids = [first.id, second.id, finished.id];
+    storage
+        .save_tasks(&[first, second, finished, untouched])
+        .unwrap();
+
+    TaskCommands::done_tasks(&storage, &ids.map(Into::into)).unwrap();
+
+    let tasks = storage.load_tasks().unwrap();
+    for task in &tasks {
+        if task.title == "Untouched" {
+            assert_eq!(task.status, TaskStatus::Todo);
+            assert!(task.completed_at.is_none());
+        } else {
+            assert_eq!(task.status, TaskStatus::Done);
+            assert!(task.completed_at.is_some());
+        }
+    }
+    let finished = tasks.iter().find(|t| t.title == "Finished").unwrap();
+    assert_eq!(finished.completed_at, stamped);
+}
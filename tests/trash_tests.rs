@@ -0,0 +1,180 @@
+use anyhow::Result;
+use ideavault::commands::idea::{DeleteIdeaArgs, IdeaCommands};
+use ideavault::commands::task::{DeleteTaskArgs, TaskCommands};
+use ideavault::commands::trash::{EmptyTrashArgs, RestoreTrashArgs, TrashCommands};
+use ideavault::models::TrashConfig;
+use ideavault::storage::TrashedItem;
+use ideavault::{Idea, Storage, Task};
+
+#[test]
+fn deleted_idea_can_be_restored_from_trash() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let idea = Idea::new("Second thoughts".to_string());
+    storage.save_ideas(std::slice::from_ref(&idea))?;
+
+    IdeaCommands::delete_idea(
+        &storage,
+        &DeleteIdeaArgs {
+            id: Some(idea.id.into()),
+            matching: Default::default(),
+            force: true,
+            keep_links: false,
+        },
+    )?;
+    assert!(storage.load_ideas()?.is_empty());
+
+    let trash = storage.load_trash()?;
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id(), idea.id);
+    assert!(matches!(trash[0].item, TrashedItem::Idea(_)));
+
+    TrashCommands::restore_item(&storage, &RestoreTrashArgs { id: idea.id.into() })?;
+    assert_eq!(storage.load_ideas()?[0].title, "Second thoughts");
+    assert!(storage.load_trash()?.is_empty());
+
+    // It's no longer in the trash, so a second restore fails
+    assert!(
+        TrashCommands::restore_item(&storage, &RestoreTrashArgs { id: idea.id.into() }).is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn restore_refuses_to_overwrite_existing_entity() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let task = Task::new("Duplicate".to_string());
+    storage.move_to_trash(TrashedItem::Task(task.clone()))?;
+    storage.save_tasks(std::slice::from_ref(&task))?;
+
+    assert!(storage.restore_from_trash(task.id).is_err());
+    assert_eq!(storage.load_trash()?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn trash_empty_and_auto_purge() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let task = Task::new("Gone".to_string());
+    storage.save_tasks(std::slice::from_ref(&task))?;
+    TaskCommands::delete_task(
+        &storage,
+        &DeleteTaskArgs {
+            id: task.id.into(),
+            force: true,
+        },
+    )?;
+
+    // The default keeps recent deletions
+    assert!(storage.purge_trash()?.is_empty());
+    TrashCommands::empty_trash(&storage, &EmptyTrashArgs { force: true })?;
+    assert!(storage.load_trash()?.is_empty());
+
+    let storage = storage.with_trash_config(TrashConfig {
+        auto_purge_days: Some(0),
+    });
+    storage.move_to_trash(TrashedItem::Task(task))?;
+    assert_eq!(storage.purge_trash()?.len(), 1);
+    assert!(storage.load_trash()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn deleting_removes_links_unless_kept() -> Result<()> {
+    use ideavault::commands::project::{DeleteProjectArgs, ProjectCommands};
+    use ideavault::Project;
+
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let idea = Idea::new("Linked idea".to_string());
+    let other = Idea::new("Other idea".to_string());
+    let project = Project::new("Project".to_string()).with_ideas(vec![idea.id, other.id]);
+    let task = Task::new("Task".to_string())
+        .with_idea(idea.id)
+        .with_project(project.id);
+    storage.save_ideas(&[idea.clone(), other.clone()])?;
+    storage.save_projects(std::slice::from_ref(&project))?;
+    storage.save_tasks(std::slice::from_ref(&task))?;
+
+    IdeaCommands::delete_idea(
+        &storage,
+        &DeleteIdeaArgs {
+            id: Some(idea.id.into()),
+            matching: Default::default(),
+            force: true,
+            keep_links: false,
+        },
+    )?;
+    assert_eq!(storage.get_project(project.id)?.idea_ids, vec![other.id]);
+    assert_eq!(storage.get_task(task.id)?.idea_id, None);
+
+    IdeaCommands::delete_idea(
+        &storage,
+        &DeleteIdeaArgs {
+            id: Some(other.id.into()),
+            matching: Default::default(),
+            force: true,
+            keep_links: true,
+        },
+    )?;
+    assert_eq!(storage.get_project(project.id)?.idea_ids, vec![other.id]);
+
+    ProjectCommands::delete_project(
+        &storage,
+        &DeleteProjectArgs {
+            id: project.id.into(),
+            force: true,
+            keep_links: false,
+            with_tasks: false,
+            orphan_tasks: false,
+        },
+    )?;
+    assert_eq!(storage.get_task(task.id)?.project_id, None);
+
+    Ok(())
+}
+
+#[test]
+fn deleting_a_project_with_tasks_trashes_them_too() -> Result<()> {
+    use ideavault::commands::project::{DeleteProjectArgs, ProjectCommands};
+    use ideavault::Project;
+
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+    let project = Project::new("Project".to_string());
+    let task = Task::new("Task".to_string()).with_project(project.id);
+    let other = Task::new("Other".to_string());
+    storage.save_projects(std::slice::from_ref(&project))?;
+    storage.save_tasks(&[task.clone(), other.clone()])?;
+
+    ProjectCommands::delete_project(
+        &storage,
+        &DeleteProjectArgs {
+            id: project.id.into(),
+            force: true,
+            keep_links: false,
+            with_tasks: true,
+            orphan_tasks: false,
+        },
+    )?;
+    let tasks = storage.load_tasks()?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].id, other.id);
+    let trashed: Vec<&str> = storage
+        .load_trash()?
+        .iter()
+        .map(|entry| entry.kind())
+        .collect();
+    assert_eq!(trashed, ["task", "project"]);
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use ideavault::commands::idea::{DeleteIdeaArgs, IdeaCommands, IdeaUpdateArgs};
+use ideavault::models::idea::IdeaStatus;
+use ideavault::{Idea, Storage, Tag};
+
+#[test]
+fn undo_reverts_status_change() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let idea = Idea::new("Undoable".to_string());
+    let id = idea.id;
+    storage.save_idea(&idea)?;
+
+    storage.begin_operation();
+    let args = IdeaUpdateArgs {
+        id: id.into(),
+        title: None,
+        description: None,
+        source: None,
+        status: Some(IdeaStatus::Active),
+        priority: None,
+        effort: None,
+        impact: None,
+        tags: None,
+        add_tags: vec![],
+        remove_tags: vec![],
+        clear: vec![],
+    };
+    IdeaCommands::update_idea(&storage, &args)?;
+    assert_eq!(storage.undo_stack()?.len(), 2);
+
+    let undone = storage.undo()?.expect("an operation to undo");
+    assert_eq!(undone.entries.len(), 1);
+    assert_eq!(
+        storage.load_idea(id)?.map(|i| i.status),
+        Some(IdeaStatus::Brainstorming)
+    );
+
+    // The undo itself isn't undoable, and the reverted operation is gone
+    assert_eq!(storage.undo_stack()?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn undo_restores_deleted_idea_and_clears_trash() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let idea = Idea::new("Deleted".to_string()).with_description("Keep me".to_string());
+    let id = idea.id;
+    storage.save_idea(&idea)?;
+
+    storage.begin_operation();
+    IdeaCommands::delete_idea(
+        &storage,
+        &DeleteIdeaArgs {
+            id: Some(id.into()),
+            matching: Default::default(),
+            force: true,
+            keep_links: false,
+        },
+    )?;
+    assert!(storage.load_idea(id)?.is_none());
+    assert_eq!(storage.load_trash()?.len(), 1);
+
+    storage.undo()?;
+    let restored = storage.load_idea(id)?.expect("idea restored");
+    assert_eq!(restored.description, Some("Keep me".to_string()));
+    assert_eq!(restored.created_at, idea.created_at);
+    assert!(storage.load_trash()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn undo_reverts_bulk_edit_as_one_operation() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let storage = Storage::new_with_path(temp_dir.path().to_path_buf())?;
+
+    let ideas = vec![Idea::new("One".to_string()), Idea::new("Two".to_string())];
+    storage.save_ideas(&ideas)?;
+    storage.save_tags(&[Tag::new("bulk".to_string())])?;
+
+    storage.begin_operation();
+    let edited: Vec<Idea> = ideas
+        .iter()
+        .cloned()
+        .map(|idea| idea.with_status(IdeaStatus::Archived))
+        .collect();
+    storage.save_ideas(&edited)?;
+
+    storage.undo()?;
+    assert!(storage
+        .load_ideas()?
+        .iter()
+        .all(|idea| idea.status == IdeaStatus::Brainstorming));
+
+    // Undoing the creation moves the ideas to the trash and drops the tag
+    storage.undo()?;
+    assert!(storage.load_ideas()?.is_empty());
+    assert!(storage.load_tags()?.is_empty());
+    assert_eq!(storage.load_trash()?.len(), 2);
+    assert!(storage.undo()?.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use anyhow::Result;
+use ideavault::commands::vault::{NewVaultArgs, SwitchVaultArgs, VaultCommands};
+use ideavault::storage::vault::DEFAULT_VAULT;
+use ideavault::storage::VaultRegistry;
+use ideavault::{Idea, Storage};
+
+fn open_registry(root: &std::path::Path) -> Result<VaultRegistry> {
+    VaultRegistry::open_at(root.join("config/vaults.json"), root.join("data"))
+}
+
+#[test]
+fn vaults_are_created_switched_and_persisted() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let mut registry = open_registry(temp_dir.path())?;
+    assert_eq!(registry.current_name(), DEFAULT_VAULT);
+    assert_eq!(registry.list().len(), 1);
+
+    let args = NewVaultArgs {
+        name: "work".to_string(),
+        path: None,
+        switch: false,
+    };
+    VaultCommands::new_vault(&mut registry, &args)?;
+    let work_dir = temp_dir.path().join("data/vaults/work");
+    assert!(work_dir.is_dir());
+    assert_eq!(registry.current_name(), DEFAULT_VAULT);
+
+    VaultCommands::switch_vault(
+        &mut registry,
+        &SwitchVaultArgs {
+            name: "work".to_string(),
+        },
+    )?;
+
+    let registry = open_registry(temp_dir.path())?;
+    assert_eq!(registry.current_name(), "work");
+    assert_eq!(registry.active_data_dir()?, work_dir);
+    assert_eq!(
+        registry.data_dir(DEFAULT_VAULT)?,
+        temp_dir.path().join("data")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn vault_names_must_be_valid_and_unique() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let mut registry = open_registry(temp_dir.path())?;
+
+    registry.create("personal", Some(temp_dir.path().join("elsewhere")))?;
+    assert!(registry.create("personal", None).is_err());
+    assert!(registry.create(DEFAULT_VAULT, None).is_err());
+    assert!(registry.create("../escape", None).is_err());
+    assert!(registry
+        .create("again", Some(temp_dir.path().join("elsewhere")))
+        .is_err());
+    assert!(registry.switch("missing").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn vaults_keep_their_data_separate() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let mut registry = open_registry(temp_dir.path())?;
+    let work_dir = registry.create("work", None)?;
+
+    let personal = Storage::new_with_path(registry.data_dir(DEFAULT_VAULT)?)?;
+    personal.save_ideas(&[Idea::new("Personal".to_string())])?;
+    let work = Storage::new_with_path(work_dir)?;
+    work.save_ideas(&[Idea::new("Work".to_string())])?;
+
+    assert_eq!(personal.load_ideas()?[0].title, "Personal");
+    assert_eq!(work.load_ideas()?.len(), 1);
+    assert_eq!(work.load_ideas()?[0].title, "Work");
+
+    Ok(())
+}
+
+#[test]
+fn merging_vaults_combines_entities_by_id() -> Result<()> {
+    use ideavault::commands::vault::{merge, MergeVaultArgs};
+    use ideavault::{Project, Task};
+
+    let temp_dir = tempfile::tempdir()?;
+    let laptop = Storage::new_with_path(temp_dir.path().join("laptop"))?;
+    let desktop = Storage::new_with_path(temp_dir.path().join("desktop"))?;
+
+    // Both started from the same vault
+    let shared = Idea::new("Shared".to_string());
+    let edited = Idea::new("Edited on the desktop".to_string());
+    let kept = Idea::new("Edited on the laptop".to_string());
+    laptop.save_ideas(&[shared.clone(), edited.clone(), kept.clone()])?;
+    desktop.save_ideas(&[shared.clone(), edited.clone(), kept.clone()])?;
+
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    desktop.update_idea(edited.id, |idea| {
+        idea.update_title("Renamed on the desktop".to_string())
+    })?;
+    desktop.update_idea(kept.id, |idea| {
+        idea.update_title("Older desktop rename".to_string())
+    })?;
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    laptop.update_idea(kept.id, |idea| {
+        idea.update_title("Renamed on the laptop".to_string())
+    })?;
+
+    // Created separately on both machines, so only the content matches
+    let twin_here = Idea::new("Same idea twice".to_string());
+    let twin_there = Idea::new("Same idea twice".to_string());
+    laptop.save_idea(&twin_here)?;
+    desktop.save_idea(&twin_there)?;
+    let project = Project::new("Desktop project".to_string()).with_ideas(vec![twin_there.id]);
+    desktop.save_project(&project)?;
+    desktop.save_task(&Task::new("Desktop task".to_string()).with_idea(twin_there.id))?;
+
+    let args = MergeVaultArgs {
+        source: String::new(),
+        dry_run: true,
+    };
+    let preview = merge(&laptop, &desktop, &args)?;
+    assert_eq!(preview.ideas.updated, vec!["Renamed on the desktop"]);
+    assert_eq!(laptop.get_idea(edited.id)?.title, "Edited on the desktop");
+
+    let args = MergeVaultArgs {
+        source: String::new(),
+        dry_run: false,
+    };
+    let summary = merge(&laptop, &desktop, &args)?;
+    assert_eq!(summary, preview);
+    assert!(summary.ideas.added.is_empty());
+    assert_eq!(summary.ideas.identical, 1);
+    assert_eq!(summary.ideas.kept, 1);
+    assert_eq!(summary.ideas.duplicates, 1);
+    assert_eq!(summary.projects.added, vec!["Desktop project"]);
+    assert_eq!(summary.tasks.added, vec!["Desktop task"]);
+
+    assert_eq!(laptop.load_ideas()?.len(), 4);
+    assert_eq!(laptop.get_idea(edited.id)?.title, "Renamed on the desktop");
+    assert_eq!(laptop.get_idea(kept.id)?.title, "Renamed on the laptop");
+    // Links to the duplicate now point at this vault's copy
+    assert_eq!(laptop.get_project(project.id)?.idea_ids, vec![twin_here.id]);
+    assert_eq!(laptop.load_tasks()?[0].idea_id, Some(twin_here.id));
+
+    // Merging again changes nothing
+    let again = merge(&laptop, &desktop, &args)?;
+    assert!(again.ideas.added.is_empty() && again.ideas.updated.is_empty());
+    assert!(again.projects.added.is_empty() && again.tasks.added.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn merging_vaults_relinks_duplicates_everywhere() -> Result<()> {
+    use ideavault::commands::vault::{merge, MergeVaultArgs};
+    use ideavault::Project;
+
+    let temp_dir = tempfile::tempdir()?;
+    let laptop = Storage::new_with_path(temp_dir.path().join("laptop"))?;
+    let desktop = Storage::new_with_path(temp_dir.path().join("desktop"))?;
+
+    // The same idea and project made separately on both machines
+    let idea_here = Idea::new("Shared idea".to_string());
+    let idea_there = Idea::new("Shared idea".to_string());
+    let parent_here = Project::new("Shared project".to_string());
+    let parent_there = Project::new("Shared project".to_string());
+    laptop.save_idea(&idea_here)?;
+    laptop.save_project(&parent_here)?;
+    desktop.save_idea(&idea_there)?;
+    desktop.save_project(&parent_there)?;
+
+    let mut related = Idea::new("Related idea".to_string());
+    related.add_related(idea_there.id);
+    desktop.save_idea(&related)?;
+    let mut child = Project::new("Child project".to_string()).with_parent(parent_there.id);
+    child.add_dependency(parent_there.id);
+    desktop.save_project(&child)?;
+
+    let args = MergeVaultArgs {
+        source: String::new(),
+        dry_run: false,
+    };
+    let summary = merge(&laptop, &desktop, &args)?;
+    assert_eq!(summary.ideas.duplicates, 1);
+    assert_eq!(summary.projects.duplicates, 1);
+
+    assert_eq!(laptop.get_idea(related.id)?.related_ids, vec![idea_here.id]);
+    let child = laptop.get_project(child.id)?;
+    assert_eq!(child.parent_id, Some(parent_here.id));
+    assert_eq!(child.depends_on, vec![parent_here.id]);
+
+    Ok(())
+}